@@ -0,0 +1,17 @@
+// build.rs
+
+// Solo genera código cuando se compila con las features `protobuf` o `grpc`: en ese caso
+// compila los `.proto` con un `protoc` empaquetado, sin depender del sistema.
+
+fn main() {
+    #[cfg(feature = "protobuf")]
+    {
+        let protoc = protoc_bin_vendored::protoc_bin_path().expect("protoc empaquetado no disponible");
+        std::env::set_var("PROTOC", protoc);
+        prost_build::compile_protos(&["proto/instantanea.proto"], &["proto"]).expect("no se pudo compilar instantanea.proto");
+    }
+    #[cfg(feature = "grpc")]
+    {
+        tonic_build::compile_protos("proto/simulacion.proto").expect("no se pudo compilar simulacion.proto");
+    }
+}
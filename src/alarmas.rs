@@ -0,0 +1,110 @@
+// src/alarmas.rs
+
+// Alarmas configurables: condiciones sobre las cifras de la simulación ("conejos < 10",
+// "reserva del depredador < 50 kg") que el motor comprueba al final de cada día. Cuando una
+// se cumple, la simulación anuncia un evento `AlarmaDisparada` y el visualizador se pausa.
+// Se definen en el escenario:
+//
+//   [[alarmas]]
+//   magnitud = "conejos"
+//   comparacion = "<"
+//   umbral = 10
+//
+// o en una línea con `--alarma "reserva_depredador_kg < 50"`. Una alarma solo vuelve a
+// dispararse después de que su condición haya dejado de cumplirse.
+
+use std::fmt;
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::simulacion::Simulacion;
+
+/// Cifra de la simulación que vigila una alarma.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Magnitud {
+    Conejos,
+    Cabras,
+    /// Presas de todas las especies.
+    Poblacion,
+    ReservaDepredadorKg,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub enum Comparacion {
+    #[serde(rename = "<")]
+    Menor,
+    #[serde(rename = ">")]
+    Mayor,
+}
+
+/// Una condición que, al cumplirse, dispara la alarma.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct Alarma {
+    pub magnitud: Magnitud,
+    pub comparacion: Comparacion,
+    pub umbral: f64,
+}
+
+impl Magnitud {
+    fn nombre(self) -> &'static str {
+        match self {
+            Magnitud::Conejos => "conejos",
+            Magnitud::Cabras => "cabras",
+            Magnitud::Poblacion => "poblacion",
+            Magnitud::ReservaDepredadorKg => "reserva_depredador_kg",
+        }
+    }
+
+    fn valor(self, sim: &Simulacion) -> f64 {
+        let (conejos, cabras) = sim.contar_especies();
+        match self {
+            Magnitud::Conejos => conejos as f64,
+            Magnitud::Cabras => cabras as f64,
+            Magnitud::Poblacion => (conejos + cabras) as f64,
+            Magnitud::ReservaDepredadorKg => sim.depredador.reserva_comida_kg,
+        }
+    }
+}
+
+impl Alarma {
+    /// Interpreta una alarma escrita en una línea, p. ej. `conejos < 10`.
+    pub fn desde_texto(texto: &str) -> Result<Self, String> {
+        let partes: Vec<&str> = texto.split_whitespace().collect();
+        let [magnitud, comparacion, umbral] = partes[..] else {
+            return Err(format!("alarma inválida: '{}' (se esperaba p. ej. 'conejos < 10')", texto));
+        };
+        let magnitud = [Magnitud::Conejos, Magnitud::Cabras, Magnitud::Poblacion, Magnitud::ReservaDepredadorKg]
+            .into_iter()
+            .find(|m| m.nombre() == magnitud.to_lowercase())
+            .ok_or_else(|| format!("magnitud de alarma desconocida: {} (use conejos, cabras, poblacion o reserva_depredador_kg)", magnitud))?;
+        let comparacion = match comparacion {
+            "<" => Comparacion::Menor,
+            ">" => Comparacion::Mayor,
+            otra => return Err(format!("comparación de alarma desconocida: {} (use < o >)", otra)),
+        };
+        let umbral = umbral.parse().map_err(|_| format!("umbral de alarma inválido: {}", umbral))?;
+        Ok(Self { magnitud, comparacion, umbral })
+    }
+
+    /// La condición se cumple con el estado actual de la simulación.
+    pub fn cumplida(&self, sim: &Simulacion) -> bool {
+        let valor = self.magnitud.valor(sim);
+        match self.comparacion {
+            Comparacion::Menor => valor < self.umbral,
+            Comparacion::Mayor => valor > self.umbral,
+        }
+    }
+}
+
+impl fmt::Display for Alarma {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let comparacion = match self.comparacion {
+            Comparacion::Menor => "<",
+            Comparacion::Mayor => ">",
+        };
+        write!(f, "{} {} {}", self.magnitud.nombre(), comparacion, self.umbral)
+    }
+}
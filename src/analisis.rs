@@ -0,0 +1,444 @@
+// src/analisis.rs
+
+// Análisis de campo medio: una aproximación determinista del modelo individual para poder
+// contrastar lo que se simula con la teoría. A partir de los parámetros se arma un sistema de
+// ecuaciones diferenciales para las poblaciones de conejos y cabras (y el pasto, en el modo de
+// cadena trófica), se buscan sus equilibrios con el método de Newton desde varios puntos de
+// partida y se clasifica la estabilidad local de cada uno con los autovalores del jacobiano.
+//
+// Supuestos de la aproximación:
+// - Edades repartidas de manera uniforme entre 0 y la edad máxima: de ahí salen la fracción
+//   de hembras reproductoras, el peso medio y la mortalidad media por clase de edad.
+// - El depredador, siempre vivo, caza como mucho una presa al día, de la especie más pesada
+//   que entre en su dieta; con la respuesta funcional, las que da la curva de Holling para
+//   todas las presas de su dieta, repartidas en proporción a cada especie. Su reserva no forma
+//   parte del sistema, solo se informa del balance entre lo que caza y lo que consume en cada
+//   equilibrio.
+// - El pasto está bien mezclado: la cuadrícula del pastizal se trata como un único recurso.
+
+use std::fmt;
+use std::ops::{Add, Div, Mul, Sub};
+
+use crate::entidades::Especie;
+use crate::parametros::{Parametros, RangoCrias};
+
+/// Distancia máxima (relativa) entre dos soluciones para considerarlas el mismo equilibrio.
+const TOLERANCIA_EQUILIBRIO: f64 = 1e-4;
+/// Parte real por debajo de la cual un autovalor se considera nulo.
+const TOLERANCIA_AUTOVALOR: f64 = 1e-9;
+
+/// Tasas diarias por individuo de una especie, promediadas sobre las edades.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TasasEspecie {
+    /// Crías por individuo y día (hembras reproductoras × tasa de parto × crías por parto).
+    pub natalidad: f64,
+    /// Muertes por individuo y día (vejez, enfermedad y mortalidad de su clase de edad).
+    pub mortalidad: f64,
+    /// Peso medio de un individuo (kg).
+    pub peso_medio_kg: f64,
+    /// Si el depredador la incluye en su dieta.
+    pub en_dieta: bool,
+}
+
+impl TasasEspecie {
+    fn calcular(especie: Especie, p: &Parametros) -> Self {
+        let rasgos = especie.rasgos(p);
+        let (edad_maxima, edad_reproductiva, tasa_parto, crias) = (rasgos.edad_maxima_dias, rasgos.edad_reproductiva_dias, rasgos.tasa_reproduccion_diaria, rasgos.crias_por_parto);
+        let en_dieta = match especie {
+            Especie::Conejo => p.dieta_depredador.conejos,
+            Especie::Cabra => p.dieta_depredador.cabras,
+        };
+        let vida = edad_maxima.max(1) as f64;
+        let adultas = (1.0 - edad_reproductiva as f64 / vida).clamp(0.0, 1.0);
+        let crias_medias = |r: RangoCrias| (r.min + r.max) as f64 / 2.0;
+        // Con gestación, una hembra pasa los días de preñez sin poder concebir: pare una vez cada
+        // `1/tasa + gestacion` días en promedio.
+        let tasa_parto = tasa_parto / (1.0 + tasa_parto * rasgos.gestacion_dias as f64);
+        // Mortalidad de cada clase, ponderada por la fracción de la vida que se pasa en ella.
+        let mortalidad_clases: f64 = (0..edad_maxima.max(1))
+            .map(|edad| p.clases_edad.de(especie.clase_edad(edad, p)).mortalidad_diaria)
+            .sum::<f64>() / vida;
+        let peso_medio_kg = (0..edad_maxima.max(1)).map(|edad| especie.peso_a_edad(edad, p)).sum::<f64>() / vida;
+        Self {
+            natalidad: adultas * (1.0 - p.probabilidad_nacer_macho) * tasa_parto * crias_medias(crias),
+            mortalidad: 1.0 / vida + p.probabilidad_enfermar + mortalidad_clases,
+            peso_medio_kg,
+            en_dieta,
+        }
+    }
+}
+
+/// El sistema de ecuaciones de campo medio. Variables: conejos, cabras y, en el modo de
+/// cadena trófica, el pasto (kg).
+#[derive(Debug, Clone, PartialEq)]
+pub struct ModeloCampoMedio {
+    pub conejos: TasasEspecie,
+    pub cabras: TasasEspecie,
+    parametros: Parametros,
+}
+
+impl ModeloCampoMedio {
+    /// Deriva el modelo de los parámetros.
+    pub fn new(parametros: &Parametros) -> Self {
+        Self {
+            conejos: TasasEspecie::calcular(Especie::Conejo, parametros),
+            cabras: TasasEspecie::calcular(Especie::Cabra, parametros),
+            parametros: parametros.clone(),
+        }
+    }
+
+    /// Número de variables del sistema.
+    pub fn dimension(&self) -> usize {
+        if self.parametros.cadena_trofica.activa { 3 } else { 2 }
+    }
+
+    /// Nombres de las variables, en el orden del vector de estado.
+    pub fn variables(&self) -> &'static [&'static str] {
+        &["conejos", "cabras", "pasto_kg"][..self.dimension()]
+    }
+
+    pub fn parametros(&self) -> &Parametros {
+        &self.parametros
+    }
+
+    /// Presas al día que caza el depredador de cada especie: una como mucho, de la más pesada
+    /// de su dieta. La presencia se suaviza con N/(N+1) para que el sistema sea derivable.
+    /// Con la respuesta funcional, cada presa de su dieta cae con la probabilidad `a / (1 + a·h·N)`.
+    pub fn capturas(&self, conejos: f64, cabras: f64) -> (f64, f64) {
+        let respuesta = &self.parametros.respuesta_funcional;
+        if respuesta.activa {
+            let disponibles = |n: f64, en_dieta: bool| if en_dieta { n.max(0.0) } else { 0.0 };
+            let (n_conejos, n_cabras) = (disponibles(conejos, self.conejos.en_dieta), disponibles(cabras, self.cabras.en_dieta));
+            let por_presa = respuesta.por_presa(n_conejos + n_cabras);
+            return (por_presa * n_conejos, por_presa * n_cabras);
+        }
+        let presencia = |n: f64, en_dieta: bool| if en_dieta { n.max(0.0) / (n.max(0.0) + 1.0) } else { 0.0 };
+        let (p_conejos, p_cabras) = (presencia(conejos, self.conejos.en_dieta), presencia(cabras, self.cabras.en_dieta));
+        if self.cabras.peso_medio_kg >= self.conejos.peso_medio_kg {
+            (p_conejos * (1.0 - p_cabras), p_cabras)
+        } else {
+            (p_conejos, p_cabras * (1.0 - p_conejos))
+        }
+    }
+
+    /// Saciedad de las presas (fracción de su ración que encuentran) y pasto que comen.
+    fn pastoreo(&self, x: &[f64]) -> (f64, f64) {
+        if self.dimension() < 3 {
+            return (1.0, 0.0);
+        }
+        let cadena = &self.parametros.cadena_trofica;
+        let racion = cadena.consumo_relativo * (self.conejos.peso_medio_kg * x[0].max(0.0) + self.cabras.peso_medio_kg * x[1].max(0.0));
+        if racion <= 0.0 {
+            return (1.0, 0.0);
+        }
+        let comido = racion.min((x[2] - cadena.refugio_kg).max(0.0));
+        (comido / racion, comido)
+    }
+
+    /// Derivadas del estado `x` (por día).
+    pub fn derivadas(&self, x: &[f64]) -> Vec<f64> {
+        let (saciedad, comido) = self.pastoreo(x);
+        let (caza_conejos, caza_cabras) = self.capturas(x[0], x[1]);
+        let hambre = (1.0 - saciedad) * self.parametros.cadena_trofica.mortalidad_hambre;
+        let crecimiento = |t: &TasasEspecie| saciedad * t.natalidad - t.mortalidad - hambre;
+        let mut dx = vec![
+            x[0] * crecimiento(&self.conejos) - caza_conejos,
+            x[1] * crecimiento(&self.cabras) - caza_cabras,
+        ];
+        if self.dimension() == 3 {
+            let cadena = &self.parametros.cadena_trofica;
+            let rebrote = if cadena.capacidad_kg > 0.0 { cadena.crecimiento_diario * x[2] * (1.0 - x[2] / cadena.capacidad_kg) } else { 0.0 };
+            dx.push(rebrote - comido);
+        }
+        dx
+    }
+
+    /// Jacobiano en `x` por diferencias centradas.
+    pub fn jacobiano(&self, x: &[f64]) -> Vec<Vec<f64>> {
+        let n = self.dimension();
+        let mut j = vec![vec![0.0; n]; n];
+        for (col, &valor) in x.iter().enumerate().take(n) {
+            let h = 1e-6 * valor.abs().max(1.0);
+            let (mut mas, mut menos) = (x.to_vec(), x.to_vec());
+            mas[col] += h;
+            menos[col] -= h;
+            let (f_mas, f_menos) = (self.derivadas(&mas), self.derivadas(&menos));
+            for (fila_j, (a, b)) in j.iter_mut().zip(f_mas.iter().zip(&f_menos)) {
+                fila_j[col] = (a - b) / (2.0 * h);
+            }
+        }
+        j
+    }
+
+    /// Kilos al día que el depredador asimila en el equilibrio menos los que consume: si es
+    /// negativo, con esas poblaciones acabaría muriendo de hambre.
+    pub fn balance_depredador_kg(&self, x: &[f64]) -> f64 {
+        let (caza_conejos, caza_cabras) = self.capturas(x[0], x[1]);
+        let cazado = caza_conejos * self.conejos.peso_medio_kg + caza_cabras * self.cabras.peso_medio_kg;
+        cazado * self.parametros.energia.eficiencia_asimilacion - self.parametros.consumo_depredador_kg().0 - self.parametros.coste_caza_kg()
+    }
+
+    /// Busca los equilibrios (con todas las variables no negativas) y clasifica su estabilidad.
+    pub fn equilibrios(&self) -> Vec<Equilibrio> {
+        let poblaciones = [0.0, 10.0, 100.0, 1_000.0, 10_000.0];
+        let capacidad = self.parametros.cadena_trofica.capacidad_kg;
+        let pastos: Vec<f64> = if self.dimension() == 3 { vec![capacidad * 0.1, capacidad * 0.5, capacidad] } else { vec![0.0] };
+        let mut encontrados: Vec<Vec<f64>> = Vec::new();
+        for &conejos in &poblaciones {
+            for &cabras in &poblaciones {
+                for &pasto in &pastos {
+                    let mut inicio = vec![conejos, cabras];
+                    if self.dimension() == 3 {
+                        inicio.push(pasto);
+                    }
+                    let Some(solucion) = self.newton(inicio) else { continue };
+                    let repetido = encontrados.iter().any(|e| {
+                        e.iter().zip(&solucion).all(|(a, b)| (a - b).abs() <= TOLERANCIA_EQUILIBRIO * a.abs().max(b.abs()).max(1.0))
+                    });
+                    if !repetido {
+                        encontrados.push(solucion);
+                    }
+                }
+            }
+        }
+        encontrados.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+        encontrados.into_iter().map(|estado| {
+            let autovalores = autovalores(&self.jacobiano(&estado));
+            Equilibrio {
+                balance_depredador_kg: self.balance_depredador_kg(&estado),
+                estabilidad: Estabilidad::clasificar(&autovalores),
+                estado,
+                autovalores,
+            }
+        }).collect()
+    }
+
+    /// Newton-Raphson desde `x`; `None` si no converge o sale de la región con sentido.
+    fn newton(&self, mut x: Vec<f64>) -> Option<Vec<f64>> {
+        for _ in 0..100 {
+            let f = self.derivadas(&x);
+            let escala = x.iter().map(|v| v.abs()).fold(1.0, f64::max);
+            if f.iter().all(|v| v.abs() < 1e-10 * escala) {
+                return x.iter().all(|&v| v > -1e-6).then(|| x.iter().map(|&v| v.max(0.0)).collect());
+            }
+            let paso = resolver(self.jacobiano(&x), f.iter().map(|v| -v).collect())?;
+            for (xi, di) in x.iter_mut().zip(&paso) {
+                *xi += di;
+            }
+            if x.iter().any(|v| !v.is_finite() || *v < -escala) {
+                return None;
+            }
+        }
+        None
+    }
+}
+
+/// Carácter de un equilibrio según los autovalores de su jacobiano.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Estabilidad {
+    /// Todas las partes reales negativas: las perturbaciones pequeñas se amortiguan.
+    Estable,
+    /// Alguna parte real positiva: las perturbaciones crecen.
+    Inestable,
+    /// La mayor parte real es nula: la linealización no decide.
+    Indeterminada,
+}
+
+impl Estabilidad {
+    fn clasificar(autovalores: &[Complejo]) -> Self {
+        let maxima = autovalores.iter().map(|l| l.re).fold(f64::NEG_INFINITY, f64::max);
+        if maxima < -TOLERANCIA_AUTOVALOR {
+            Estabilidad::Estable
+        } else if maxima > TOLERANCIA_AUTOVALOR {
+            Estabilidad::Inestable
+        } else {
+            Estabilidad::Indeterminada
+        }
+    }
+
+    pub fn nombre(self) -> &'static str {
+        match self {
+            Estabilidad::Estable => "estable",
+            Estabilidad::Inestable => "inestable",
+            Estabilidad::Indeterminada => "indeterminada",
+        }
+    }
+}
+
+/// Un punto de equilibrio del modelo de campo medio.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Equilibrio {
+    /// Valor de cada variable, en el orden de `ModeloCampoMedio::variables`.
+    pub estado: Vec<f64>,
+    pub autovalores: Vec<Complejo>,
+    pub estabilidad: Estabilidad,
+    /// Ver `ModeloCampoMedio::balance_depredador_kg`.
+    pub balance_depredador_kg: f64,
+}
+
+impl Equilibrio {
+    /// Si alguna perturbación oscila al volver (o al alejarse) del equilibrio.
+    pub fn oscilatorio(&self) -> bool {
+        self.autovalores.iter().any(|l| l.im.abs() > TOLERANCIA_AUTOVALOR)
+    }
+}
+
+/// Resultado completo del análisis, listo para imprimir.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Analisis {
+    pub modelo: ModeloCampoMedio,
+    pub equilibrios: Vec<Equilibrio>,
+}
+
+impl Analisis {
+    pub fn new(parametros: &Parametros) -> Self {
+        let modelo = ModeloCampoMedio::new(parametros);
+        let equilibrios = modelo.equilibrios();
+        Self { modelo, equilibrios }
+    }
+}
+
+impl fmt::Display for Analisis {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Modelo de campo medio ({} variables: {})", self.modelo.dimension(), self.modelo.variables().join(", "))?;
+        for (nombre, t) in [("conejos", &self.modelo.conejos), ("cabras", &self.modelo.cabras)] {
+            writeln!(
+                f, "  {:<8} natalidad {:.5}/día, mortalidad {:.5}/día, peso medio {:.2} kg{}",
+                nombre, t.natalidad, t.mortalidad, t.peso_medio_kg, if t.en_dieta { "" } else { " (fuera de la dieta)" }
+            )?;
+        }
+        writeln!(f, "Equilibrios:")?;
+        if self.equilibrios.is_empty() {
+            writeln!(f, "  (no se encontró ninguno)")?;
+        }
+        for e in &self.equilibrios {
+            let estado: Vec<String> = self.modelo.variables().iter().zip(&e.estado).map(|(n, v)| format!("{}={:.2}", n, v)).collect();
+            writeln!(
+                f, "  {} -> {}{}; balance del depredador {:+.2} kg/día",
+                estado.join(", "), e.estabilidad.nombre(), if e.oscilatorio() { ", oscilatorio" } else { "" }, e.balance_depredador_kg
+            )?;
+            let autovalores: Vec<String> = e.autovalores.iter().map(|l| l.to_string()).collect();
+            writeln!(f, "      autovalores: {}", autovalores.join(", "))?;
+        }
+        Ok(())
+    }
+}
+
+// --- Álgebra lineal mínima (sistemas de 2 o 3 variables) ---
+
+/// Número complejo, solo con lo necesario para los autovalores.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Complejo {
+    pub re: f64,
+    pub im: f64,
+}
+
+impl Complejo {
+    const fn new(re: f64, im: f64) -> Self {
+        Self { re, im }
+    }
+
+    fn modulo(self) -> f64 {
+        self.re.hypot(self.im)
+    }
+}
+
+impl Add for Complejo {
+    type Output = Self;
+    fn add(self, o: Self) -> Self { Self::new(self.re + o.re, self.im + o.im) }
+}
+
+impl Sub for Complejo {
+    type Output = Self;
+    fn sub(self, o: Self) -> Self { Self::new(self.re - o.re, self.im - o.im) }
+}
+
+impl Mul for Complejo {
+    type Output = Self;
+    fn mul(self, o: Self) -> Self { Self::new(self.re * o.re - self.im * o.im, self.re * o.im + self.im * o.re) }
+}
+
+impl Div for Complejo {
+    type Output = Self;
+    fn div(self, o: Self) -> Self {
+        let d = o.re * o.re + o.im * o.im;
+        Self::new((self.re * o.re + self.im * o.im) / d, (self.im * o.re - self.re * o.im) / d)
+    }
+}
+
+impl fmt::Display for Complejo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.im.abs() <= TOLERANCIA_AUTOVALOR {
+            write!(f, "{:.6}", self.re)
+        } else {
+            write!(f, "{:.6}{:+.6}i", self.re, self.im)
+        }
+    }
+}
+
+/// Resuelve `a·x = b` por eliminación gaussiana con pivoteo parcial; `None` si es singular.
+fn resolver(mut a: Vec<Vec<f64>>, mut b: Vec<f64>) -> Option<Vec<f64>> {
+    let n = b.len();
+    for col in 0..n {
+        let pivote = (col..n).max_by(|&i, &j| a[i][col].abs().total_cmp(&a[j][col].abs()))?;
+        if a[pivote][col].abs() < 1e-14 {
+            return None;
+        }
+        a.swap(col, pivote);
+        b.swap(col, pivote);
+        let (arriba, abajo) = a.split_at_mut(col + 1);
+        let fila_pivote = &arriba[col];
+        let b_pivote = b[col];
+        for (fila, b_fila) in abajo.iter_mut().zip(&mut b[col + 1..]) {
+            let factor = fila[col] / fila_pivote[col];
+            for (v, p) in fila.iter_mut().zip(fila_pivote).skip(col) {
+                *v -= factor * p;
+            }
+            *b_fila -= factor * b_pivote;
+        }
+    }
+    let mut x = vec![0.0; n];
+    for fila in (0..n).rev() {
+        let suma: f64 = (fila + 1..n).map(|k| a[fila][k] * x[k]).sum();
+        x[fila] = (b[fila] - suma) / a[fila][fila];
+    }
+    Some(x)
+}
+
+/// Autovalores de una matriz pequeña: coeficientes del polinomio característico (Faddeev-LeVerrier)
+/// y sus raíces por el método de Durand-Kerner.
+fn autovalores(a: &[Vec<f64>]) -> Vec<Complejo> {
+    let n = a.len();
+    // coeficientes[k] acompaña a λ^k; el polinomio es mónico.
+    let mut coeficientes = vec![0.0; n + 1];
+    coeficientes[n] = 1.0;
+    let mut m = vec![vec![0.0; n]; n];
+    for k in 1..=n {
+        // M_k = A·M_{k-1} + c_{n-k+1}·I
+        let c = coeficientes[n - k + 1];
+        m = (0..n).map(|i| (0..n).map(|j| (0..n).map(|l| a[i][l] * m[l][j]).sum::<f64>() + if i == j { c } else { 0.0 }).collect()).collect();
+        let traza: f64 = (0..n).map(|i| (0..n).map(|l| a[i][l] * m[l][i]).sum::<f64>()).sum();
+        coeficientes[n - k] = -traza / k as f64;
+    }
+    let evaluar = |z: Complejo| coeficientes.iter().rev().fold(Complejo::new(0.0, 0.0), |acc, &c| acc * z + Complejo::new(c, 0.0));
+    let semilla = Complejo::new(0.4, 0.9);
+    let mut raices: Vec<Complejo> = (0..n).scan(Complejo::new(1.0, 0.0), |z, _| { let actual = *z; *z = *z * semilla; Some(actual) }).collect();
+    for _ in 0..500 {
+        let mut cambio: f64 = 0.0;
+        let previas = raices.clone();
+        for (i, raiz) in raices.iter_mut().enumerate() {
+            let denominador = previas.iter().enumerate()
+                .filter(|&(j, _)| j != i)
+                .fold(Complejo::new(1.0, 0.0), |acc, (_, &otra)| acc * (*raiz - otra));
+            let delta = evaluar(*raiz) / denominador;
+            *raiz = *raiz - delta;
+            cambio = cambio.max(delta.modulo());
+        }
+        if cambio < 1e-14 {
+            break;
+        }
+    }
+    raices.sort_by(|a, b| b.re.total_cmp(&a.re));
+    raices
+}
+
@@ -0,0 +1,58 @@
+// src/ascii.rs
+
+// Representación del estado en texto plano, para logs, CI y depuración sin ventana.
+// Las etiquetas y barras solo usan caracteres ASCII (sin tildes ni bloques Unicode), así que
+// se ven igual en cualquier terminal o visor de logs.
+
+use crate::simulacion::Simulacion;
+
+/// Ancho en caracteres de las barras.
+pub(crate) const ANCHO_BARRA: usize = 40;
+
+/// Barra `[####....]` con la fracción `valor / maximo` rellena.
+pub(crate) fn barra(valor: f64, maximo: f64, ancho: usize) -> String {
+    let fraccion = if maximo > 0.0 { (valor / maximo).clamp(0.0, 1.0) } else { 0.0 };
+    let llenos = (fraccion * ancho as f64).round() as usize;
+    format!("[{}{}]", "#".repeat(llenos), ".".repeat(ancho - llenos))
+}
+
+impl Simulacion {
+    /// Dibuja el estado actual como un bloque de texto: día, poblaciones con barras
+    /// proporcionales a la mayor de ellas, estado del depredador y balance del día.
+    ///
+    /// ```text
+    /// Dia 120 (semilla 42)
+    /// Conejos        312 [########################################]
+    /// Cabras          20 [###.....................................]
+    /// Depredador    vivo [##########..............................] 300.0 kg (optimo)
+    /// Hoy: 12 nacimientos, 3 muertes, 1 cazas
+    /// ```
+    pub fn render_ascii(&self) -> String {
+        let (conejos, cabras) = self.contar_especies();
+        let mayor = conejos.max(cabras) as f64;
+        let reserva = self.depredador.reserva_comida_kg;
+        let reserva_maxima = self.parametros.depredador_reserva_inicial_kg.max(reserva);
+
+        let mut texto = format!("Dia {} (semilla {})\n", self.dia, self.semilla());
+        texto.push_str(&format!("{:<10} {:>7} {}\n", "Conejos", conejos, barra(conejos as f64, mayor, ANCHO_BARRA)));
+        texto.push_str(&format!("{:<10} {:>7} {}\n", "Cabras", cabras, barra(cabras as f64, mayor, ANCHO_BARRA)));
+        texto.push_str(&format!(
+            "{:<10} {:>7} {} {:.1} kg ({})\n",
+            "Depredador",
+            if self.depredador.vivo { "vivo" } else { "muerto" },
+            barra(reserva, reserva_maxima, ANCHO_BARRA),
+            reserva,
+            self.estado_depredador()
+        ));
+        let estadisticas = &self.estadisticas_dia;
+        texto.push_str(&format!(
+            "Hoy: {} nacimientos, {} muertes, {} cazas\n",
+            estadisticas.nacimientos, estadisticas.muertes, estadisticas.cazas
+        ));
+        // Los nacimientos y las muertes ya están en la línea anterior.
+        for evento in self.eventos_dia.iter().filter(|e| !e.es_demografico()) {
+            texto.push_str(&format!("! {}\n", evento.descripcion()));
+        }
+        texto
+    }
+}
@@ -0,0 +1,142 @@
+// src/avisos.rs
+
+// Avisos emergentes del visualizador (parte del binario, no de la biblioteca).
+// Cada evento notable de la simulación aparece unos segundos en la esquina inferior derecha,
+// así que las transiciones importantes no pasan desapercibidas en cámara rápida. Los avisos
+// repetidos se agrupan en uno con contador en lugar de llenar la pantalla.
+
+use std::collections::VecDeque;
+
+use egui_macroquad::egui;
+
+use simulador_ecosistema_presa_depredador::entidades::Especie;
+use simulador_ecosistema_presa_depredador::eventos::Evento;
+use simulador_ecosistema_presa_depredador::idioma::{Idioma, Texto};
+use simulador_ecosistema_presa_depredador::records::Logro;
+
+/// Nombre de un logro en la interfaz.
+pub fn texto_logro(logro: Logro) -> Texto {
+    match logro {
+        Logro::UnAno => Texto::LogroUnAno,
+        Logro::Decada => Texto::LogroDecada,
+        Logro::MilPresas => Texto::LogroMilPresas,
+        Logro::ColapsoRelampago => Texto::LogroColapsoRelampago,
+        Logro::DesafioSuperado => Texto::LogroDesafioSuperado,
+    }
+}
+
+/// Segundos que un aviso permanece en pantalla.
+const DURACION: f32 = 4.0;
+/// Segundos finales durante los que el aviso se desvanece.
+const DESVANECIMIENTO: f32 = 1.0;
+/// Avisos visibles a la vez; los más antiguos se descartan.
+const MAXIMO_VISIBLES: usize = 5;
+
+struct Aviso {
+    texto: String,
+    color: egui::Color32,
+    repeticiones: u32,
+    restante: f32,
+}
+
+/// Cola de avisos visibles.
+#[derive(Default)]
+pub struct Avisos {
+    cola: VecDeque<Aviso>,
+}
+
+impl Avisos {
+    /// Crea los avisos de los eventos de un día. Los cambios de parámetros y las intervenciones
+    /// no generan aviso: los hace el propio usuario y ya quedan en el registro de eventos.
+    pub fn anotar_eventos<'a>(&mut self, eventos: impl IntoIterator<Item = &'a Evento>, idioma: Idioma) {
+        for evento in eventos {
+            let (texto, color) = match evento {
+                Evento::Extincion { especie } => {
+                    let especie = match especie {
+                        Especie::Conejo => idioma.texto(Texto::Conejos),
+                        Especie::Cabra => idioma.texto(Texto::Cabras),
+                    };
+                    (format!("{}: {}", idioma.texto(Texto::AvisoExtincion), especie), egui::Color32::LIGHT_RED)
+                }
+                Evento::DepredadorEnPeligro { reserva_kg } => {
+                    (format!("{} ({:.1} kg)", idioma.texto(Texto::AvisoDepredadorPeligro), reserva_kg), egui::Color32::ORANGE)
+                }
+                Evento::MuerteDepredador => (idioma.texto(Texto::DepredadorMuerto).to_string(), egui::Color32::LIGHT_RED),
+                Evento::AlarmaDisparada { alarma } => (format!("{}: {}", idioma.texto(Texto::Alarma), alarma), egui::Color32::RED),
+                Evento::BroteEpidemia { especie } => {
+                    let especie = match especie {
+                        Especie::Conejo => idioma.texto(Texto::Conejos),
+                        Especie::Cabra => idioma.texto(Texto::Cabras),
+                    };
+                    (format!("{}: {}", idioma.texto(Texto::AvisoBrote), especie), egui::Color32::LIGHT_GREEN)
+                }
+                Evento::OlaEpidemica { especie, infectadas } => {
+                    let especie = match especie {
+                        Especie::Conejo => idioma.texto(Texto::Conejos),
+                        Especie::Cabra => idioma.texto(Texto::Cabras),
+                    };
+                    (format!("{}: {} ({})", idioma.texto(Texto::AvisoOla), especie, infectadas), egui::Color32::LIGHT_GREEN)
+                }
+                // Sin cifras, para que los días seguidos de sobrepoblación se agrupen en un aviso.
+                Evento::MortalidadEmergencia { .. } => (idioma.texto(Texto::AvisoSobrepoblacion).to_string(), egui::Color32::ORANGE),
+                Evento::ParametroCambiado { .. } | Evento::PresaAgregada { .. } | Evento::PresaRetirada { .. }
+                | Evento::DepredadorReubicado { .. } | Evento::Caza { .. } | Evento::Nacimiento { .. }
+                | Evento::MuertePresa { .. } => continue,
+            };
+            self.anadir(texto, color);
+        }
+    }
+
+    /// Anuncia un logro recién desbloqueado.
+    pub fn anotar_logro(&mut self, logro: Logro, idioma: Idioma) {
+        let texto = format!("{}: {}", idioma.texto(Texto::LogroDesbloqueado), idioma.texto(texto_logro(logro)));
+        self.anadir(texto, egui::Color32::GOLD);
+    }
+
+    /// Muestra un mensaje informativo, p. ej. la confirmación de una acción del usuario.
+    pub fn anotar_mensaje(&mut self, texto: String) {
+        self.anadir(texto, egui::Color32::LIGHT_BLUE);
+    }
+
+    fn anadir(&mut self, texto: String, color: egui::Color32) {
+        if let Some(aviso) = self.cola.iter_mut().find(|a| a.texto == texto) {
+            aviso.repeticiones += 1;
+            aviso.restante = DURACION;
+            return;
+        }
+        if self.cola.len() == MAXIMO_VISIBLES {
+            self.cola.pop_front();
+        }
+        self.cola.push_back(Aviso { texto, color, repeticiones: 1, restante: DURACION });
+    }
+
+    /// Descuenta el tiempo del fotograma y dibuja los avisos que siguen vigentes.
+    pub fn mostrar(&mut self, ctx: &egui::Context, segundos: f32) {
+        for aviso in &mut self.cola {
+            aviso.restante -= segundos;
+        }
+        self.cola.retain(|a| a.restante > 0.0);
+        if self.cola.is_empty() {
+            return;
+        }
+
+        egui::Area::new(egui::Id::new("avisos"))
+            .anchor(egui::Align2::RIGHT_BOTTOM, [-10.0, -10.0])
+            .interactable(false)
+            .show(ctx, |ui| {
+                for aviso in &self.cola {
+                    ui.scope(|ui| {
+                        ui.set_opacity((aviso.restante / DESVANECIMIENTO).min(1.0));
+                        egui::Frame::popup(ui.style()).show(ui, |ui| {
+                            let texto = if aviso.repeticiones > 1 {
+                                format!("{} (x{})", aviso.texto, aviso.repeticiones)
+                            } else {
+                                aviso.texto.clone()
+                            };
+                            ui.colored_label(aviso.color, texto);
+                        });
+                    });
+                }
+            });
+    }
+}
@@ -0,0 +1,169 @@
+// src/bin/divergencia.rs
+
+// Herramienta de diagnóstico para regresiones de determinismo.
+// Corre la misma semilla dos veces (o contra una traza exportada por otra compilación)
+// y compara la huella del estado día a día, señalando el primer día y la primera
+// entidad en la que los resultados dejan de coincidir.
+//
+// Uso:
+//   divergencia [OPCIONES]                        compara dos corridas en este binario
+//   divergencia [OPCIONES] --exportar traza.txt
+//   divergencia [OPCIONES] --comparar traza.txt
+// donde OPCIONES son [--config ESCENARIO] [--set NOMBRE=VALOR]... [--semilla N] [--dias N].
+// Los parámetros se superponen como en `simulador run`: valores por defecto < --config
+// < variables de entorno SIM_<PARAMETRO> < --set.
+
+use std::fs;
+use std::process::ExitCode;
+
+use simulador_ecosistema_presa_depredador::configuracion;
+use simulador_ecosistema_presa_depredador::parametros::Parametros;
+use simulador_ecosistema_presa_depredador::simulacion::{Retencion, Simulacion, SimulacionBuilder};
+
+/// Estado resumido de un día: la huella global y la de cada presa.
+struct EstadoDia {
+    dia: u32,
+    huella: u64,
+    presas: Vec<(u64, u64)>,
+}
+
+impl EstadoDia {
+    fn capturar(sim: &Simulacion) -> Self {
+        Self { dia: sim.dia, huella: sim.huella(), presas: sim.huellas_presas() }
+    }
+
+    /// Una línea de la traza: `dia huella id:huella id:huella ...` (huellas en hexadecimal).
+    fn a_linea(&self) -> String {
+        let mut linea = format!("{} {:016x}", self.dia, self.huella);
+        for (id, huella) in &self.presas {
+            linea.push_str(&format!(" {}:{:016x}", id, huella));
+        }
+        linea
+    }
+
+    fn desde_linea(linea: &str) -> Result<Self, String> {
+        let mut campos = linea.split_whitespace();
+        let dia = campos.next().and_then(|c| c.parse().ok()).ok_or("día inválido")?;
+        let huella = campos.next().and_then(|c| u64::from_str_radix(c, 16).ok()).ok_or("huella inválida")?;
+        let mut presas = Vec::new();
+        for campo in campos {
+            let (id, huella) = campo.split_once(':').ok_or("presa inválida")?;
+            let id = id.parse().map_err(|_| "id de presa inválido")?;
+            let huella = u64::from_str_radix(huella, 16).map_err(|_| "huella de presa inválida")?;
+            presas.push((id, huella));
+        }
+        Ok(Self { dia, huella, presas })
+    }
+}
+
+/// Describe la primera entidad distinta entre dos estados del mismo día.
+fn primera_entidad_distinta(a: &EstadoDia, b: &EstadoDia) -> String {
+    for i in 0..a.presas.len().max(b.presas.len()) {
+        match (a.presas.get(i), b.presas.get(i)) {
+            (Some(pa), Some(pb)) if pa == pb => continue,
+            (Some((id_a, _)), Some((id_b, _))) if id_a != id_b => {
+                return format!("posición {}: presa {} en A, presa {} en B", i, id_a, id_b);
+            }
+            (Some((id, _)), Some(_)) => return format!("presa {} (mismo id, estado distinto)", id),
+            (Some((id, _)), None) => return format!("presa {} existe solo en A", id),
+            (None, Some((id, _))) => return format!("presa {} existe solo en B", id),
+            (None, None) => unreachable!(),
+        }
+    }
+    "ninguna presa difiere (la diferencia está en los depredadores, los contadores, el generador, los brotes o el pasto)".to_string()
+}
+
+/// Compara dos estados; devuelve un informe si divergen.
+fn comparar(a: &EstadoDia, b: &EstadoDia) -> Option<String> {
+    if a.dia == b.dia && a.huella == b.huella {
+        return None;
+    }
+    Some(format!(
+        "Divergencia en el día {}: huella A {:016x}, huella B {:016x}\n  Primera entidad distinta: {}",
+        a.dia, a.huella, b.huella, primera_entidad_distinta(a, b)
+    ))
+}
+
+/// Ejecuta una corrida y entrega el estado de cada día (incluido el día 0).
+fn trazar(parametros: &Parametros, semilla: u64, dias: u32, mut al_capturar: impl FnMut(EstadoDia) -> Result<(), String>) -> Result<(), String> {
+    let mut sim = SimulacionBuilder::new().parametros(parametros.clone()).semilla(semilla).retencion(Retencion::DESACTIVADA).construir();
+    al_capturar(EstadoDia::capturar(&sim))?;
+    for _ in 0..dias {
+        sim.avanzar_dia();
+        al_capturar(EstadoDia::capturar(&sim))?;
+    }
+    Ok(())
+}
+
+fn ejecutar() -> Result<(), String> {
+    let mut semilla: u64 = 42;
+    let mut dias: u32 = 1000;
+    let mut exportar: Option<String> = None;
+    let mut comparar_con: Option<String> = None;
+    let mut base = Parametros::default();
+    let mut asignaciones = Vec::new();
+
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        let mut valor = || args.next().ok_or(format!("falta el valor de {}", arg));
+        match arg.as_str() {
+            "--config" => {
+                let escenario = configuracion::cargar_escenario(valor()?)?;
+                // Como en `simulador run`, los flags posteriores pueden cambiar días y semilla.
+                if let Some(d) = escenario.dias { dias = d; }
+                if let Some(s) = escenario.semilla { semilla = s; }
+                base = escenario.parametros;
+            }
+            "--set" => asignaciones.push(valor()?),
+            "--semilla" => semilla = valor()?.parse().map_err(|_| "semilla inválida")?,
+            "--dias" => dias = valor()?.parse().map_err(|_| "número de días inválido")?,
+            "--exportar" => exportar = Some(valor()?),
+            "--comparar" => comparar_con = Some(valor()?),
+            otro => return Err(format!("argumento desconocido: {}", otro)),
+        }
+    }
+    let parametros = configuracion::parametros_en_capas(base, std::env::vars(), &asignaciones)?;
+
+    if let Some(ruta) = exportar {
+        let mut lineas = Vec::new();
+        trazar(&parametros, semilla, dias, |estado| { lineas.push(estado.a_linea()); Ok(()) })?;
+        fs::write(&ruta, lineas.join("\n") + "\n").map_err(|e| format!("no se pudo escribir {}: {}", ruta, e))?;
+        println!("Traza de {} días (semilla {}) exportada a {}", dias, semilla, ruta);
+        return Ok(());
+    }
+
+    // La referencia "B" es la traza de otra compilación o una segunda corrida en este binario.
+    let referencia: Vec<EstadoDia> = match comparar_con {
+        Some(ruta) => {
+            let contenido = fs::read_to_string(&ruta).map_err(|e| format!("no se pudo leer {}: {}", ruta, e))?;
+            contenido.lines().map(EstadoDia::desde_linea).collect::<Result<_, _>>()?
+        }
+        None => {
+            let mut estados = Vec::new();
+            trazar(&parametros, semilla, dias, |estado| { estados.push(estado); Ok(()) })?;
+            estados
+        }
+    };
+
+    let mut referencia = referencia.into_iter();
+    trazar(&parametros, semilla, dias, |estado| match referencia.next() {
+        Some(otro) => match comparar(&estado, &otro) {
+            Some(informe) => Err(informe),
+            None => Ok(()),
+        },
+        None => Err(format!("la referencia termina antes del día {}", estado.dia)),
+    })?;
+
+    println!("Sin divergencias: {} días idénticos con la semilla {}", dias, semilla);
+    Ok(())
+}
+
+fn main() -> ExitCode {
+    match ejecutar() {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(mensaje) => {
+            eprintln!("{}", mensaje);
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,38 @@
+// src/bin/servidor_graphql.rs
+
+// Servidor GraphQL que expone el estado de una simulación a clientes remotos.
+//
+// Uso:
+//   servidor_graphql [--direccion 127.0.0.1:8000] [--semilla N]
+//
+// Ejemplo:
+//   curl -d '{"query":"mutation { avanzar(dias: 30) }"}' http://127.0.0.1:8000/graphql
+//   curl -d '{"query":"{ dia poblacion(especie: CONEJO) { claseEdad total } historialReserva(desde: 25) { dia reservaKg } }"}' http://127.0.0.1:8000/graphql
+
+use std::net::SocketAddr;
+
+use simulador_ecosistema_presa_depredador::graphql;
+use simulador_ecosistema_presa_depredador::simulacion::Simulacion;
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let mut direccion: SocketAddr = "127.0.0.1:8000".parse()?;
+    let mut semilla: Option<u64> = None;
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--direccion" => direccion = args.next().ok_or("falta el valor de --direccion")?.parse()?,
+            "--semilla" => semilla = Some(args.next().ok_or("falta el valor de --semilla")?.parse()?),
+            otro => return Err(format!("argumento desconocido: {}", otro).into()),
+        }
+    }
+
+    let sim = match semilla {
+        Some(semilla) => Simulacion::con_semilla(semilla),
+        None => Simulacion::new(),
+    };
+    let listener = tokio::net::TcpListener::bind(direccion).await?;
+    println!("Servidor GraphQL escuchando en http://{}/graphql (GraphiQL en http://{}/)", direccion, direccion);
+    graphql::servir(graphql::crear_esquema(sim), listener).await?;
+    Ok(())
+}
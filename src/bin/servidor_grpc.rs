@@ -0,0 +1,29 @@
+// src/bin/servidor_grpc.rs
+
+// Servidor gRPC que expone el motor de simulación a clientes remotos.
+//
+// Uso:
+//   servidor_grpc [--direccion 127.0.0.1:50051]
+
+use std::net::SocketAddr;
+
+use simulador_ecosistema_presa_depredador::grpc::{Servidor, ServicioSimulacionServer};
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let mut direccion: SocketAddr = "127.0.0.1:50051".parse()?;
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--direccion" => direccion = args.next().ok_or("falta el valor de --direccion")?.parse()?,
+            otro => return Err(format!("argumento desconocido: {}", otro).into()),
+        }
+    }
+
+    println!("Servidor gRPC escuchando en {}", direccion);
+    tonic::transport::Server::builder()
+        .add_service(ServicioSimulacionServer::new(Servidor::new()))
+        .serve(direccion)
+        .await?;
+    Ok(())
+}
@@ -0,0 +1,708 @@
+// src/bin/simulador.rs
+
+// Interfaz de línea de comandos para ejecutar la simulación sin ventana (modo "headless").
+// Pensada para corridas largas en servidores o CI, donde no hace falta el visualizador.
+//
+// Uso:
+//   simulador [run] [--config ESCENARIO] [--especie ARCHIVO.json]... [--set NOMBRE=VALOR]... [--dias N] [--semilla N] [--dificultad facil|normal|dificil] [--censo ARCHIVO | --warm-start INSTANTANEA] [--csv ARCHIVO [--csv-incremental]] [--vega ARCHIVO.json|.html] [--ascii-cada N] [--replay ARCHIVO] [--genealogia ARCHIVO.dot [--linaje ID] [--ventana DESDE:HASTA] [--cazas]] [--superposicion ARCHIVO] [--alarma "conejos < 10"]... [--checkpoint-every N --checkpoint-dir DIR]
+//                   [--determinista]   esqueleto determinista: valores esperados en lugar de sorteos
+//                   [--csv-incremental]   escribe cada fila del CSV en cuanto se registra, no al final
+//                   [--muestreo N] [--metricas conejos,cabras,...] [--retencion FILAS]   historial del CSV (ver [telemetria])
+//                   [--publicar nats://host:4222|mqtt://host:1883 [--tema simulacion] [--formato json|protobuf]]
+//   simulador aggregate resultados/*.csv [--salida ARCHIVO] [--resumen ARCHIVO]
+//   simulador experiment --variar NOMBRE=v1,v2|inicio:paso:fin... [--repeticiones N] [--dias N] [--semilla N]
+//                        [--config ESCENARIO] [--set NOMBRE=VALOR]... [--warm-start INSTANTANEA] [--nombre EXPERIMENTO] [--salida ARCHIVO] [--resumen ARCHIVO]
+//                        [--numeros-comunes]   la misma semilla para la repetición k de cada combinación
+//                        barrido con salida en la tabla ancha de BehaviorSpace (una fila por corrida)
+//   simulador sweep --param NOMBRE --from A --to B [--steps N] [--repeticiones N] [--dias N] [--semilla N]
+//                   [--config ESCENARIO] [--set NOMBRE=VALOR]... [--numeros-comunes] [--salida ARCHIVO]
+//                   N valores equiespaciados de A a B (10 por defecto); una fila por valor con la fracción
+//                   de corridas en que coexisten depredador y presas
+//   aggregate y experiment escriben además un resumen con intervalos de confianza bootstrap del 95 %
+//   (picos de población y probabilidades de extinción): en --resumen, junto a --salida como
+//   NOMBRE_resumen.csv o, si no hay archivo de salida, por stderr.
+//   simulador species export conejo|cabra [--salida ARCHIVO]   (por defecto conejo.json / cabra.json)
+//   simulador stdio [--semilla N]      protocolo de comandos JSON por stdin/stdout
+//   simulador analyze [--config ESCENARIO] [--set NOMBRE=VALOR]...
+//                     equilibrios del modelo de campo medio y su estabilidad local
+//   simulador schema [parametros|escenario]
+//   simulador play CORRIDA.replay [--dia N]
+//                  reproduce una corrida grabada con --replay; por stdin: Enter o n (siguiente día),
+//                  p (anterior), +N / -N (saltar N días), un número (ir a ese día), i / f (inicio / fin), q (salir)
+//
+// Los parámetros se superponen en capas: valores por defecto < archivo (--config)
+// < definiciones de especie (--especie) < dificultad < variables de entorno SIM_<PARAMETRO> < flags --set.
+// Con --warm-start la corrida empieza en el día 0 con la población final de una instantánea
+// guardada (p. ej. con --checkpoint-every); sin --config, también con sus parámetros.
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+use simulador_ecosistema_presa_depredador::alarmas::Alarma;
+use simulador_ecosistema_presa_depredador::analisis::Analisis;
+use simulador_ecosistema_presa_depredador::configuracion::{self, Escenario};
+use simulador_ecosistema_presa_depredador::desafio::{Desafio, Desenlace};
+use simulador_ecosistema_presa_depredador::determinista::{self, SimulacionDeterminista};
+use simulador_ecosistema_presa_depredador::dificultad::Dificultad;
+use simulador_ecosistema_presa_depredador::especies::{self, DefinicionEspecie};
+use simulador_ecosistema_presa_depredador::eventos::Evento;
+use simulador_ecosistema_presa_depredador::experimento::{Experimento, Variacion};
+use simulador_ecosistema_presa_depredador::genealogia::{FiltroGenealogia, Genealogia};
+use simulador_ecosistema_presa_depredador::parametros::Parametros;
+use simulador_ecosistema_presa_depredador::protocolo;
+use simulador_ecosistema_presa_depredador::publicacion::{Formato, Publicador};
+use simulador_ecosistema_presa_depredador::registro::{self, CsvIncremental, Registro, Telemetria};
+use simulador_ecosistema_presa_depredador::repeticion::Repeticion;
+use simulador_ecosistema_presa_depredador::simulacion::{ArranqueCaliente, Retencion, Simulacion, SimulacionBuilder};
+use simulador_ecosistema_presa_depredador::superposicion::Superposicion;
+use simulador_ecosistema_presa_depredador::vegalite;
+
+/// Opciones de una corrida headless.
+struct OpcionesCorrida {
+    escenario: Escenario,
+    especies: Vec<DefinicionEspecie>,
+    asignaciones: Vec<String>,
+    dias: u32,
+    semilla: Option<u64>,
+    dificultad: Dificultad,
+    checkpoint_cada: Option<u32>,
+    checkpoint_dir: PathBuf,
+    censo: Option<PathBuf>,
+    arranque: Option<ArranqueCaliente>,
+    /// Escenario leído con --config (sus parámetros prevalecen sobre los del arranque).
+    config: Option<String>,
+    csv: Option<PathBuf>,
+    /// Escribe el CSV durante la corrida en lugar de al final (ver `CsvIncremental`).
+    csv_incremental: bool,
+    vega: Option<PathBuf>,
+    ascii_cada: Option<u32>,
+    /// Archivo en que se graba la repetición de la corrida (ver `simulador play`).
+    repeticion: Option<PathBuf>,
+    /// Grafo DOT de la genealogía de las presas (ver `genealogia`) y qué parte se exporta.
+    genealogia: Option<PathBuf>,
+    filtro_genealogia: FiltroGenealogia,
+    superposicion: Option<PathBuf>,
+    alarmas: Vec<Alarma>,
+    publicar: Option<String>,
+    tema: String,
+    formato: Formato,
+    /// Corre el esqueleto determinista en lugar de la simulación individual.
+    determinista: bool,
+    telemetria: Telemetria,
+}
+
+impl OpcionesCorrida {
+    fn desde_args(args: impl Iterator<Item = String>) -> Result<Self, String> {
+        let mut opciones = Self { escenario: Escenario::default(), especies: Vec::new(), asignaciones: Vec::new(), dias: 3650, semilla: None, dificultad: Dificultad::Normal, checkpoint_cada: None, checkpoint_dir: PathBuf::from("checkpoints"), censo: None, arranque: None, config: None, csv: None, csv_incremental: false, vega: None, ascii_cada: None, repeticion: None, genealogia: None, filtro_genealogia: FiltroGenealogia::default(), superposicion: None, alarmas: Vec::new(), publicar: None, tema: "simulacion".to_string(), formato: Formato::Json, determinista: false, telemetria: Telemetria::default() };
+        let mut args = args;
+        while let Some(arg) = args.next() {
+            let mut valor = || args.next().ok_or(format!("falta el valor de {}", arg));
+            match arg.as_str() {
+                "--config" => {
+                    let ruta = valor()?;
+                    let escenario = configuracion::cargar_escenario(&ruta)?;
+                    // Los datos de la corrida del escenario se pueden sobrescribir con flags posteriores.
+                    if let Some(dias) = escenario.dias { opciones.dias = dias; }
+                    if let Some(semilla) = escenario.semilla { opciones.semilla = Some(semilla); }
+                    if let Some(dificultad) = escenario.dificultad { opciones.dificultad = dificultad; }
+                    if let Some(censo) = &escenario.censo { opciones.censo = Some(PathBuf::from(censo)); }
+                    if let Some(ruta) = &escenario.superposicion { opciones.superposicion = Some(PathBuf::from(ruta)); }
+                    opciones.alarmas.extend(escenario.alarmas.iter().cloned());
+                    opciones.telemetria = escenario.telemetria.clone();
+                    opciones.escenario = escenario;
+                    opciones.config = Some(ruta);
+                }
+                "--especie" => opciones.especies.push(DefinicionEspecie::cargar(valor()?)?),
+                "--set" => opciones.asignaciones.push(valor()?),
+                "--dias" => opciones.dias = valor()?.parse().map_err(|_| "número de días inválido")?,
+                "--semilla" => opciones.semilla = Some(valor()?.parse().map_err(|_| "semilla inválida")?),
+                "--dificultad" => opciones.dificultad = Dificultad::desde_nombre(&valor()?)?,
+                "--checkpoint-every" => {
+                    let cada: u32 = valor()?.parse().map_err(|_| "intervalo de checkpoint inválido")?;
+                    if cada == 0 {
+                        return Err("--checkpoint-every debe ser mayor que 0".to_string());
+                    }
+                    opciones.checkpoint_cada = Some(cada);
+                }
+                "--checkpoint-dir" => opciones.checkpoint_dir = PathBuf::from(valor()?),
+                "--censo" => opciones.censo = Some(PathBuf::from(valor()?)),
+                "--warm-start" => opciones.arranque = Some(ArranqueCaliente::cargar(valor()?)?),
+                "--csv" => opciones.csv = Some(PathBuf::from(valor()?)),
+                "--csv-incremental" => opciones.csv_incremental = true,
+                "--vega" => opciones.vega = Some(PathBuf::from(valor()?)),
+                "--ascii-cada" => {
+                    let cada: u32 = valor()?.parse().map_err(|_| "intervalo de --ascii-cada inválido")?;
+                    if cada == 0 {
+                        return Err("--ascii-cada debe ser mayor que 0".to_string());
+                    }
+                    opciones.ascii_cada = Some(cada);
+                }
+                "--replay" => opciones.repeticion = Some(PathBuf::from(valor()?)),
+                "--genealogia" => opciones.genealogia = Some(PathBuf::from(valor()?)),
+                "--linaje" => opciones.filtro_genealogia.linaje = Some(valor()?.parse().map_err(|_| "id de --linaje inválido")?),
+                "--ventana" => {
+                    let texto = valor()?;
+                    let (desde, hasta) = texto.split_once(':').ok_or(format!("ventana inválida: {} (use DESDE:HASTA)", texto))?;
+                    let dia = |t: &str| -> Result<Option<u32>, String> {
+                        if t.is_empty() { Ok(None) } else { t.parse().map(Some).map_err(|_| format!("ventana inválida: {}", texto)) }
+                    };
+                    opciones.filtro_genealogia.desde = dia(desde)?;
+                    opciones.filtro_genealogia.hasta = dia(hasta)?;
+                }
+                "--cazas" => opciones.filtro_genealogia.cazas = true,
+                "--superposicion" => opciones.superposicion = Some(PathBuf::from(valor()?)),
+                "--alarma" => opciones.alarmas.push(Alarma::desde_texto(&valor()?)?),
+                "--publicar" => opciones.publicar = Some(valor()?),
+                "--tema" => opciones.tema = valor()?,
+                "--formato" => opciones.formato = Formato::desde_nombre(&valor()?)?,
+                "--determinista" => opciones.determinista = true,
+                "--muestreo" => opciones.telemetria.cada_dias = valor()?.parse().map_err(|_| "intervalo de --muestreo inválido")?,
+                "--metricas" => opciones.telemetria.metricas = Telemetria::metricas_desde_texto(&valor()?),
+                "--retencion" => opciones.telemetria.maximo_filas = Some(valor()?.parse().map_err(|_| "número de filas de --retencion inválido")?),
+                otro => return Err(format!("argumento desconocido: {}", otro)),
+            }
+        }
+        opciones.telemetria.validar()?;
+        Ok(opciones)
+    }
+}
+
+/// Ejecuta una corrida completa sin ventana y muestra un resumen final.
+fn correr(opciones: OpcionesCorrida) -> Result<(), String> {
+    let mut constructor = match (&opciones.censo, &opciones.arranque) {
+        (Some(_), Some(_)) => return Err("--censo y --warm-start no se pueden combinar".to_string()),
+        (Some(ruta), None) => SimulacionBuilder::desde_censo(ruta)?,
+        (None, Some(arranque)) => SimulacionBuilder::new().arranque(arranque),
+        (None, None) => SimulacionBuilder::new(),
+    };
+    let mut base = match &opciones.arranque {
+        Some(arranque) if opciones.config.is_none() => arranque.parametros.clone(),
+        _ => opciones.escenario.parametros.clone(),
+    };
+    for definicion in &opciones.especies {
+        definicion.aplicar(&mut base)?;
+    }
+    opciones.dificultad.aplicar(&mut base);
+    let parametros = configuracion::parametros_en_capas(base, std::env::vars(), &opciones.asignaciones)?;
+    if opciones.determinista {
+        return correr_determinista(&opciones, &parametros);
+    }
+    // Sin ventana nadie retrocede: no hace falta retener instantáneas.
+    constructor = constructor.parametros(parametros).retencion(Retencion::DESACTIVADA);
+    if let Some(semilla) = opciones.semilla {
+        constructor = constructor.semilla(semilla);
+    }
+    let mut sim = constructor.construir();
+    sim.definir_alarmas(opciones.alarmas.clone());
+    let mut registro = Registro::con_telemetria(opciones.telemetria.clone());
+    registro.anotar("semilla", sim.semilla());
+    registro.anotar("dias", opciones.dias);
+    registro.anotar_parametros(&sim.parametros);
+    if let Some(ruta) = &opciones.config {
+        registro.anotar("config", ruta);
+    }
+    if !opciones.asignaciones.is_empty() {
+        registro.anotar("set", opciones.asignaciones.join(" "));
+    }
+    let mut csv_incremental = match (&opciones.csv, opciones.csv_incremental) {
+        (Some(ruta), true) => Some(CsvIncremental::crear(ruta, &registro).map_err(|e| format!("no se pudo escribir {}: {}", ruta.display(), e))?),
+        (None, true) => return Err("--csv-incremental necesita --csv ARCHIVO".to_string()),
+        _ => None,
+    };
+
+    let mut publicador = match &opciones.publicar {
+        Some(url) => {
+            let publicador = Publicador::desde_url(url, &opciones.tema).map_err(|e| format!("no se pudo conectar a {}: {}", url, e))?;
+            Some(publicador.con_formato(opciones.formato))
+        }
+        None => None,
+    };
+
+    let superposicion = opciones.superposicion.as_ref().map(Superposicion::new);
+    let mut repeticion = opciones.repeticion.as_ref().map(|_| Repeticion::new(&sim));
+    let mut genealogia = opciones.genealogia.as_ref().map(|_| Genealogia::default());
+    let mut desafio = opciones.escenario.desafio.clone().map(Desafio::new);
+
+    if opciones.checkpoint_cada.is_some() {
+        fs::create_dir_all(&opciones.checkpoint_dir)
+            .map_err(|e| format!("no se pudo crear {}: {}", opciones.checkpoint_dir.display(), e))?;
+    }
+
+    for _ in 0..opciones.dias {
+        if !sim.depredador.vivo {
+            break;
+        }
+        sim.avanzar_dia();
+        registro.registrar(&sim);
+        if let (Some(csv), Some(ruta)) = (&mut csv_incremental, &opciones.csv) {
+            csv.escribir_nuevas(&registro).map_err(|e| format!("no se pudo escribir {}: {}", ruta.display(), e))?;
+        }
+        for evento in &sim.eventos_dia {
+            if let Evento::AlarmaDisparada { .. } | Evento::MortalidadEmergencia { .. } | Evento::OlaEpidemica { .. } = evento {
+                println!("Día {}: {}", sim.dia, evento.descripcion());
+            }
+        }
+        if let Some(desafio) = &mut desafio {
+            desafio.dia_completado(&sim);
+        }
+        if let Some(repeticion) = &mut repeticion {
+            repeticion.grabar(&sim);
+        }
+        if let Some(genealogia) = &mut genealogia {
+            genealogia.anotar_dia(&sim);
+        }
+        if let Some(publicador) = &mut publicador {
+            publicador.publicar_dia(&sim).map_err(|e| format!("error al publicar el día {}: {}", sim.dia, e))?;
+        }
+
+        // Reescribe el archivo que lee la superposición de la transmisión.
+        if let (Some(superposicion), Some(ruta)) = (&superposicion, &opciones.superposicion) {
+            superposicion.escribir(&sim).map_err(|e| format!("no se pudo escribir {}: {}", ruta.display(), e))?;
+        }
+
+        // Dibuja el estado en texto cada N días, para seguir la corrida en el log.
+        if let Some(cada) = opciones.ascii_cada {
+            if sim.dia % cada == 0 || !sim.depredador.vivo {
+                println!("{}", sim.render_ascii());
+            }
+        }
+
+        // Guarda una instantánea completa cada N días para poder ramificar desde ahí.
+        if let Some(cada) = opciones.checkpoint_cada {
+            if sim.dia % cada == 0 {
+                let ruta = opciones.checkpoint_dir.join(format!("dia_{:06}.sim", sim.dia));
+                sim.instantanea().guardar(&ruta)
+                    .map_err(|e| format!("no se pudo escribir {}: {}", ruta.display(), e))?;
+            }
+        }
+    }
+
+    if let (Some(ruta), None) = (&opciones.csv, &csv_incremental) {
+        registro.guardar_csv(ruta).map_err(|e| format!("no se pudo escribir {}: {}", ruta.display(), e))?;
+    }
+    if let Some(ruta) = &opciones.vega {
+        let titulo = format!("Corrida con semilla {}", sim.semilla());
+        vegalite::guardar(ruta, &titulo, registro.filas.make_contiguous()).map_err(|e| format!("no se pudo escribir {}: {}", ruta.display(), e))?;
+    }
+    if let (Some(repeticion), Some(ruta)) = (&repeticion, &opciones.repeticion) {
+        repeticion.guardar(ruta).map_err(|e| format!("no se pudo escribir {}: {}", ruta.display(), e))?;
+    }
+    if let (Some(genealogia), Some(ruta)) = (&genealogia, &opciones.genealogia) {
+        fs::write(ruta, genealogia.a_dot(&opciones.filtro_genealogia)).map_err(|e| format!("no se pudo escribir {}: {}", ruta.display(), e))?;
+    }
+
+    let (conejos, cabras) = sim.contar_especies();
+    println!(
+        "Día {}: {} conejos, {} cabras, reserva del depredador {:.1} kg ({})",
+        sim.dia, conejos, cabras, sim.depredador.reserva_comida_kg,
+        if sim.depredador.vivo { "vivo" } else { "muerto" }
+    );
+    println!("Balance energético de la corrida:\n{}", sim.balance_corrida);
+    println!("Muertes de presas por causa:\n{}", sim.muertes_corrida);
+    if let Some(desafio) = &desafio {
+        let estado = match desafio.terminado {
+            Some((dia, Desenlace::Superado)) => format!("superado el día {}", dia),
+            Some((dia, Desenlace::DepredadorMuerto)) => format!("fallido el día {}", dia),
+            None => "sin terminar".to_string(),
+        };
+        println!(
+            "Desafío {}: {} puntos ({} días en equilibrio, {:.1} kg cosechados)",
+            estado, desafio.puntos(), desafio.dias_equilibrio, desafio.biomasa_cosechada_kg
+        );
+    }
+    Ok(())
+}
+
+/// Corre el esqueleto determinista con los mismos parámetros que tendría la corrida individual.
+fn correr_determinista(opciones: &OpcionesCorrida, parametros: &Parametros) -> Result<(), String> {
+    if opciones.censo.is_some() || opciones.arranque.is_some() {
+        return Err("--determinista parte de las poblaciones iniciales: no admite --censo ni --warm-start".to_string());
+    }
+    let mut sim = SimulacionDeterminista::new(parametros);
+    let mut filas = Vec::new();
+    for _ in 0..opciones.dias {
+        if !sim.depredador_vivo {
+            break;
+        }
+        sim.avanzar_dia();
+        filas.push(sim.dia_actual());
+    }
+    if let Some(ruta) = &opciones.csv {
+        let metadatos = [("modo".to_string(), "determinista".to_string()), ("dias".to_string(), opciones.dias.to_string())];
+        fs::write(ruta, determinista::a_csv(&metadatos, &filas)).map_err(|e| format!("no se pudo escribir {}: {}", ruta.display(), e))?;
+    }
+    if let Some(ruta) = &opciones.vega {
+        vegalite::guardar(ruta, "Esqueleto determinista", &filas).map_err(|e| format!("no se pudo escribir {}: {}", ruta.display(), e))?;
+    }
+    let fin = sim.dia_actual();
+    println!(
+        "Día {}: {:.2} conejos, {:.2} cabras, reserva del depredador {:.1} kg ({})",
+        fin.dia, fin.conejos, fin.cabras, fin.reserva_depredador_kg,
+        if sim.depredador_vivo { "vivo" } else { "muerto" }
+    );
+    Ok(())
+}
+
+/// Combina los CSV diarios de muchas corridas en una tabla larga con columnas de corrida y parámetros.
+fn agregar(args: impl Iterator<Item = String>) -> Result<(), String> {
+    let mut archivos = Vec::new();
+    let mut salida: Option<PathBuf> = None;
+    let mut resumen: Option<PathBuf> = None;
+    let mut args = args;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--salida" => salida = Some(PathBuf::from(args.next().ok_or("falta el valor de --salida")?)),
+            "--resumen" => resumen = Some(PathBuf::from(args.next().ok_or("falta el valor de --resumen")?)),
+            _ => archivos.push(PathBuf::from(arg)),
+        }
+    }
+    if archivos.is_empty() {
+        return Err("aggregate necesita al menos un archivo CSV".to_string());
+    }
+
+    let mut corridas = Vec::new();
+    for archivo in &archivos {
+        let contenido = fs::read_to_string(archivo).map_err(|e| format!("no se pudo leer {}: {}", archivo.display(), e))?;
+        corridas.push((id_corrida(archivo), contenido));
+    }
+    let tabla = registro::combinar_formato_largo(&corridas)?;
+    escribir_resumen(registro::resumen_bootstrap(&corridas)?, resumen, salida.as_deref())?;
+
+    match salida {
+        Some(ruta) => fs::write(&ruta, tabla).map_err(|e| format!("no se pudo escribir {}: {}", ruta.display(), e)),
+        None => {
+            print!("{}", tabla);
+            Ok(())
+        }
+    }
+}
+
+/// Escribe la tabla de intervalos bootstrap en `ruta`, o junto a `salida` como `NOMBRE_resumen.csv`;
+/// sin ninguna de las dos, por stderr, para no mezclarla con la tabla principal.
+fn escribir_resumen(resumen: String, ruta: Option<PathBuf>, salida: Option<&Path>) -> Result<(), String> {
+    let ruta = ruta.or_else(|| salida.map(|s| s.with_file_name(format!("{}_resumen.csv", id_corrida(s)))));
+    match ruta {
+        Some(ruta) => fs::write(&ruta, resumen).map_err(|e| format!("no se pudo escribir {}: {}", ruta.display(), e)),
+        None => {
+            eprint!("{}", resumen);
+            Ok(())
+        }
+    }
+}
+
+/// El identificador de una corrida es el nombre de su archivo sin extensión.
+fn id_corrida(archivo: &Path) -> String {
+    archivo.file_stem().map(|s| s.to_string_lossy().into_owned()).unwrap_or_default()
+}
+
+/// Corre un barrido de parámetros y escribe la tabla de resultados al estilo BehaviorSpace.
+fn experimentar(args: impl Iterator<Item = String>) -> Result<(), String> {
+    let mut base = Escenario::default().parametros;
+    let mut asignaciones = Vec::new();
+    let mut variaciones = Vec::new();
+    let mut nombre = "experimento".to_string();
+    let mut repeticiones = 1;
+    let mut dias = 3650;
+    let mut semilla: Option<u64> = None;
+    let mut salida: Option<PathBuf> = None;
+    let mut resumen: Option<PathBuf> = None;
+    let mut arranque: Option<ArranqueCaliente> = None;
+    let mut con_config = false;
+    let mut numeros_comunes = false;
+    let mut args = args;
+    while let Some(arg) = args.next() {
+        let mut valor = || args.next().ok_or(format!("falta el valor de {}", arg));
+        match arg.as_str() {
+            "--numeros-comunes" => numeros_comunes = true,
+            "--config" => {
+                let escenario = configuracion::cargar_escenario(valor()?)?;
+                if let Some(d) = escenario.dias { dias = d; }
+                if let Some(s) = escenario.semilla { semilla = Some(s); }
+                base = escenario.parametros;
+                con_config = true;
+            }
+            "--set" => asignaciones.push(valor()?),
+            "--warm-start" => arranque = Some(ArranqueCaliente::cargar(valor()?)?),
+            "--variar" => variaciones.push(Variacion::desde_texto(&valor()?)?),
+            "--nombre" => nombre = valor()?,
+            "--repeticiones" => repeticiones = valor()?.parse().map_err(|_| "número de repeticiones inválido")?,
+            "--dias" => dias = valor()?.parse().map_err(|_| "número de días inválido")?,
+            "--semilla" => semilla = Some(valor()?.parse().map_err(|_| "semilla inválida")?),
+            "--salida" => salida = Some(PathBuf::from(valor()?)),
+            "--resumen" => resumen = Some(PathBuf::from(valor()?)),
+            otro => return Err(format!("argumento desconocido: {}", otro)),
+        }
+    }
+    if repeticiones == 0 {
+        return Err("--repeticiones debe ser mayor que 0".to_string());
+    }
+
+    if let (Some(arranque), false) = (&arranque, con_config) {
+        base = arranque.parametros.clone();
+    }
+
+    let experimento = Experimento {
+        nombre,
+        base: configuracion::parametros_en_capas(base, std::env::vars(), &asignaciones)?,
+        variaciones,
+        repeticiones,
+        dias,
+        semilla_base: semilla.unwrap_or_else(rand::random),
+        numeros_comunes,
+        arranque,
+    };
+    let total = experimento.combinaciones().len() as u32 * repeticiones;
+    let resultados = experimento.ejecutar(|r| eprintln!("corrida {}/{} (semilla {}): día {}", r.numero, total, r.semilla, r.paso))?;
+    let tabla = experimento.tabla_behaviorspace(&resultados);
+    escribir_resumen(experimento.tabla_resumen(&resultados), resumen, salida.as_deref())?;
+
+    match salida {
+        Some(ruta) => fs::write(&ruta, tabla).map_err(|e| format!("no se pudo escribir {}: {}", ruta.display(), e)),
+        None => {
+            print!("{}", tabla);
+            Ok(())
+        }
+    }
+}
+
+/// Barre un parámetro en un rango y escribe una fila por valor con cuántas corridas acaban en coexistencia.
+fn barrer(args: impl Iterator<Item = String>) -> Result<(), String> {
+    let mut base = Escenario::default().parametros;
+    let mut asignaciones = Vec::new();
+    let mut parametro: Option<String> = None;
+    let (mut desde, mut hasta): (Option<f64>, Option<f64>) = (None, None);
+    let mut pasos = 10;
+    let mut repeticiones = 1;
+    let mut dias = 3650;
+    let mut semilla: Option<u64> = None;
+    let mut salida: Option<PathBuf> = None;
+    let mut numeros_comunes = false;
+    let mut args = args;
+    while let Some(arg) = args.next() {
+        let mut valor = || args.next().ok_or(format!("falta el valor de {}", arg));
+        match arg.as_str() {
+            "--numeros-comunes" => numeros_comunes = true,
+            "--config" => {
+                let escenario = configuracion::cargar_escenario(valor()?)?;
+                if let Some(d) = escenario.dias { dias = d; }
+                if let Some(s) = escenario.semilla { semilla = Some(s); }
+                base = escenario.parametros;
+            }
+            "--set" => asignaciones.push(valor()?),
+            "--param" => parametro = Some(valor()?),
+            "--from" => desde = Some(valor()?.parse().map_err(|_| "valor de --from inválido")?),
+            "--to" => hasta = Some(valor()?.parse().map_err(|_| "valor de --to inválido")?),
+            "--steps" => pasos = valor()?.parse().map_err(|_| "número de pasos inválido")?,
+            "--repeticiones" => repeticiones = valor()?.parse().map_err(|_| "número de repeticiones inválido")?,
+            "--dias" => dias = valor()?.parse().map_err(|_| "número de días inválido")?,
+            "--semilla" => semilla = Some(valor()?.parse().map_err(|_| "semilla inválida")?),
+            "--salida" => salida = Some(PathBuf::from(valor()?)),
+            otro => return Err(format!("argumento desconocido: {}", otro)),
+        }
+    }
+    let parametro = parametro.ok_or("sweep necesita --param")?;
+    let (desde, hasta) = desde.zip(hasta).ok_or("sweep necesita --from y --to")?;
+    if repeticiones == 0 {
+        return Err("--repeticiones debe ser mayor que 0".to_string());
+    }
+
+    let experimento = Experimento {
+        nombre: format!("sweep {}", parametro),
+        base: configuracion::parametros_en_capas(base, std::env::vars(), &asignaciones)?,
+        variaciones: vec![Variacion::lineal(&parametro, desde, hasta, pasos)?],
+        repeticiones,
+        dias,
+        semilla_base: semilla.unwrap_or_else(rand::random),
+        numeros_comunes,
+        arranque: None,
+    };
+    let total = experimento.combinaciones().len() as u32 * repeticiones;
+    let resultados = experimento.ejecutar(|r| eprintln!("corrida {}/{} (semilla {}): día {}", r.numero, total, r.semilla, r.paso))?;
+    let tabla = experimento.tabla_barrido(&resultados);
+
+    match salida {
+        Some(ruta) => fs::write(&ruta, tabla).map_err(|e| format!("no se pudo escribir {}: {}", ruta.display(), e)),
+        None => {
+            print!("{}", tabla);
+            Ok(())
+        }
+    }
+}
+
+/// Exporta el bloque de parámetros de una especie (con los valores por defecto o los de --config).
+fn exportar_especie(args: impl Iterator<Item = String>) -> Result<(), String> {
+    let mut args = args;
+    match args.next().as_deref() {
+        Some("export") => {}
+        Some(otro) => return Err(format!("acción desconocida: {} (use export)", otro)),
+        None => return Err("species necesita una acción: export".to_string()),
+    }
+    let mut especie = None;
+    let mut parametros = Escenario::default().parametros;
+    let mut salida: Option<PathBuf> = None;
+    while let Some(arg) = args.next() {
+        let mut valor = || args.next().ok_or(format!("falta el valor de {}", arg));
+        match arg.as_str() {
+            "--config" => parametros = configuracion::cargar_escenario(valor()?)?.parametros,
+            "--salida" => salida = Some(PathBuf::from(valor()?)),
+            nombre if especie.is_none() => especie = Some(especies::especie_desde_nombre(nombre)?),
+            otro => return Err(format!("argumento desconocido: {}", otro)),
+        }
+    }
+    let especie = especie.ok_or("indique la especie a exportar (conejo o cabra)")?;
+    let ruta = salida.unwrap_or_else(|| PathBuf::from(especies::nombre_archivo(especie)));
+    DefinicionEspecie::desde_parametros(&parametros, especie).guardar(&ruta)?;
+    println!("Especie exportada en {}", ruta.display());
+    Ok(())
+}
+
+/// Atiende el protocolo de líneas JSON por stdin/stdout hasta que se cierre la entrada.
+fn atender_stdio(args: impl Iterator<Item = String>) -> Result<(), String> {
+    let mut semilla: Option<u64> = None;
+    let mut args = args;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--semilla" => semilla = Some(args.next().ok_or("falta el valor de --semilla")?.parse().map_err(|_| "semilla inválida")?),
+            otro => return Err(format!("argumento desconocido: {}", otro)),
+        }
+    }
+    let mut sim = match semilla {
+        Some(semilla) => Simulacion::con_semilla(semilla),
+        None => Simulacion::new(),
+    };
+    protocolo::atender(&mut sim, io::stdin().lock(), io::stdout().lock()).map_err(|e| e.to_string())
+}
+
+/// Reproduce una repetición grabada, moviéndose por los días con órdenes de una línea por stdin.
+fn reproducir(args: impl Iterator<Item = String>) -> Result<(), String> {
+    let mut ruta: Option<PathBuf> = None;
+    let mut dia = 0;
+    let mut args = args;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--dia" => dia = args.next().ok_or("falta el valor de --dia")?.parse().map_err(|_| "día inválido")?,
+            otro if ruta.is_none() && !otro.starts_with("--") => ruta = Some(PathBuf::from(otro)),
+            otro => return Err(format!("argumento desconocido: {}", otro)),
+        }
+    }
+    let ruta = ruta.ok_or("falta el archivo de repetición")?;
+    let repeticion = Repeticion::cargar(&ruta).map_err(|e| format!("no se pudo leer {}: {}", ruta.display(), e))?;
+    let ultimo = repeticion.fotogramas.len().saturating_sub(1);
+    let mut indice = repeticion.indice_del_dia(dia);
+    print!("{}", repeticion.render_ascii(indice));
+    for linea in io::stdin().lines() {
+        let linea = linea.map_err(|e| e.to_string())?;
+        let orden = linea.trim();
+        indice = match orden {
+            "" | "n" => (indice + 1).min(ultimo),
+            "p" => indice.saturating_sub(1),
+            "i" => 0,
+            "f" => ultimo,
+            "q" => break,
+            _ => {
+                let salto = |texto: &str| texto.parse::<usize>().map_err(|_| format!("orden desconocida: {}", orden));
+                let nuevo = if let Some(n) = orden.strip_prefix('+') {
+                    salto(n).map(|n| indice.saturating_add(n).min(ultimo))
+                } else if let Some(n) = orden.strip_prefix('-') {
+                    salto(n).map(|n| indice.saturating_sub(n))
+                } else {
+                    orden.parse().map(|dia| repeticion.indice_del_dia(dia)).map_err(|_| format!("orden desconocida: {}", orden))
+                };
+                match nuevo {
+                    Ok(nuevo) => nuevo,
+                    Err(mensaje) => {
+                        eprintln!("{} (n, p, +N, -N, DIA, i, f, q)", mensaje);
+                        continue;
+                    }
+                }
+            }
+        };
+        print!("{}", repeticion.render_ascii(indice));
+    }
+    Ok(())
+}
+
+/// Imprime los equilibrios del modelo de campo medio que corresponde a los parámetros.
+fn analizar(args: impl Iterator<Item = String>) -> Result<(), String> {
+    let mut parametros = Escenario::default().parametros;
+    let mut asignaciones = Vec::new();
+    let mut args = args;
+    while let Some(arg) = args.next() {
+        let mut valor = || args.next().ok_or(format!("falta el valor de {}", arg));
+        match arg.as_str() {
+            "--config" => parametros = configuracion::cargar_escenario(valor()?)?.parametros,
+            "--set" => asignaciones.push(valor()?),
+            otro => return Err(format!("argumento desconocido: {}", otro)),
+        }
+    }
+    let parametros = configuracion::parametros_en_capas(parametros, std::env::vars(), &asignaciones)?;
+    print!("{}", Analisis::new(&parametros));
+    Ok(())
+}
+
+/// Imprime el JSON Schema del formato de configuración.
+fn imprimir_esquema(args: impl Iterator<Item = String>) -> Result<(), String> {
+    let tipos: Vec<String> = args.collect();
+    match tipos.first().map(String::as_str) {
+        None | Some("escenario") => println!("{}", configuracion::esquema_escenario()),
+        Some("parametros") => println!("{}", configuracion::esquema_parametros()),
+        Some(otro) => return Err(format!("esquema desconocido: {} (use parametros o escenario)", otro)),
+    }
+    Ok(())
+}
+
+fn ejecutar() -> Result<(), String> {
+    let mut args = std::env::args().skip(1).peekable();
+    match args.peek().map(String::as_str) {
+        Some("run") => {
+            args.next();
+            correr(OpcionesCorrida::desde_args(args)?)
+        }
+        Some("aggregate") => {
+            args.next();
+            agregar(args)
+        }
+        Some("experiment") => {
+            args.next();
+            experimentar(args)
+        }
+        Some("sweep") => {
+            args.next();
+            barrer(args)
+        }
+        Some("schema") => {
+            args.next();
+            imprimir_esquema(args)
+        }
+        Some("species") => {
+            args.next();
+            exportar_especie(args)
+        }
+        Some("stdio") => {
+            args.next();
+            atender_stdio(args)
+        }
+        Some("analyze") => {
+            args.next();
+            analizar(args)
+        }
+        Some("play") => {
+            args.next();
+            reproducir(args)
+        }
+        Some(otro) if !otro.starts_with("--") => Err(format!("subcomando desconocido: {}", otro)),
+        _ => correr(OpcionesCorrida::desde_args(args)?),
+    }
+}
+
+fn main() -> ExitCode {
+    match ejecutar() {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(mensaje) => {
+            eprintln!("{}", mensaje);
+            ExitCode::FAILURE
+        }
+    }
+}
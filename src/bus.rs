@@ -0,0 +1,161 @@
+// src/bus.rs
+
+// Bus de sucesos internos del día. Cada fase de `Simulacion::avanzar_dia` solo cambia el estado
+// que le toca (el depredador, las presas, el censo) y anuncia lo que pasó como un `Suceso`
+// tipado; los sistemas que antes se actualizaban dentro de la misma función (estadísticas del
+// día, balance energético, eventos notables para la interfaz y los exportadores) son oyentes
+// que reciben esos sucesos al terminar el día, en el orden en que se emitieron.
+//
+// Así el orden de las fases queda escrito en un solo sitio (`Fase::ORDEN`), un sistema nuevo
+// se añade como un oyente más sin tocar las fases, y lo que emitió cada fase se puede
+// consultar después con `Simulacion::sucesos_dia`.
+
+use crate::energia::FlujosKg;
+use crate::entidades::{CausaMuerte, Especie};
+use crate::eventos::Evento;
+use crate::simulacion::EstadisticasDia;
+
+/// Fases de un día, en el orden en que se ejecutan.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Fase {
+    /// Los depredadores envejecen, mueren de viejos y se reproducen (solo con la manada).
+    Manada,
+    /// Cada depredador come de su despensa, gasta su reserva e intenta cazar.
+    Depredador,
+    /// Las presas pastan (con la cadena trófica), envejecen y se reproducen.
+    Presas,
+    /// Entran las crías, actúan los brotes y se retiran las muertas y las que pasan del tope.
+    Censo,
+    /// Se comparan el principio y el final del día para anunciar extinciones, alarmas, etc.
+    Transiciones,
+}
+
+impl Fase {
+    pub const ORDEN: [Fase; 5] = [Fase::Manada, Fase::Depredador, Fase::Presas, Fase::Censo, Fase::Transiciones];
+}
+
+/// Algo que ocurrió durante una fase, con las cifras que necesitan los oyentes.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Suceso {
+    /// Estado de las presas al empezar el día.
+    Amanecer { conejos: usize, cabras: usize, biomasa_kg: f64 },
+    /// Depredadores nacidos y muertos de viejos en el día (solo con la manada).
+    Manada { crias: u32, muertes_vejez: u32 },
+    /// Los depredadores comieron de su despensa y gastaron parte de su reserva.
+    Despensa { asimilado_kg: f64, podrido_kg: f64, metabolizado_kg: f64 },
+    /// Resultado de los intentos de caza (ver `ResultadoCaza`); `cazable_kg` es el mayor de ellos
+    /// y `escapadas`, los intentos en que la presa elegida escapó.
+    Caza { cazadas: u32, escapadas: u32, cazable_kg: f64, presa_kg: f64, asimilado_kg: f64, gasto_kg: f64 },
+    /// Las presas comieron del pastizal (solo con la cadena trófica).
+    Pastoreo { disponible_kg: f64, racion_kg: f64, pastado_kg: f64 },
+    /// Peso que ganaron las presas al envejecer.
+    Crecimiento { kg: f64 },
+    /// Una hembra parió; `crias` son los ids de las recién nacidas.
+    Parto { madre: u64, especie: Especie, crias: Vec<u64> },
+    Nacimientos { crias: u32, kg: f64 },
+    /// Presas muertas por una causa; las cazadas también, aunque no cuenten como muertes del día.
+    Muertes { causa: CausaMuerte, cantidad: u32 },
+    /// Contagios del día e infectadas que quedan (solo con la enfermedad contagiosa).
+    Contagios { contagios: u32, infectadas: u32 },
+    /// Biomasa de las presas retiradas en el censo.
+    Retiradas { kg: f64 },
+    /// Suceso notable que se anuncia fuera de la simulación (ver `Evento`).
+    Notable(Evento),
+}
+
+/// Sucesos emitidos durante el día, con la fase que los emitió.
+#[derive(Debug, Clone, Default)]
+pub struct Bus {
+    sucesos: Vec<(Fase, Suceso)>,
+}
+
+impl Bus {
+    pub fn emitir(&mut self, fase: Fase, suceso: Suceso) {
+        self.sucesos.push((fase, suceso));
+    }
+
+    pub fn vaciar(&mut self) {
+        self.sucesos.clear();
+    }
+
+    pub fn sucesos(&self) -> &[(Fase, Suceso)] {
+        &self.sucesos
+    }
+
+    /// Entrega todos los sucesos a `oyente`, en el orden en que se emitieron.
+    pub fn repartir(&self, oyente: &mut impl Oyente) {
+        for (fase, suceso) in &self.sucesos {
+            oyente.recibir(*fase, suceso);
+        }
+    }
+}
+
+/// Un sistema que se actualiza a partir de los sucesos del día.
+pub trait Oyente {
+    fn recibir(&mut self, fase: Fase, suceso: &Suceso);
+}
+
+/// Las estadísticas del día cuentan nacimientos, cazas, contagios y muertes por causa.
+impl Oyente for EstadisticasDia {
+    fn recibir(&mut self, _fase: Fase, suceso: &Suceso) {
+        match *suceso {
+            Suceso::Caza { cazadas, escapadas, .. } => {
+                self.cazas += cazadas;
+                self.cazas_fallidas += escapadas;
+            }
+            Suceso::Nacimientos { crias, .. } => self.nacimientos += crias,
+            Suceso::Contagios { contagios, infectadas } => {
+                self.contagios += contagios;
+                self.infectadas = infectadas;
+            }
+            Suceso::Muertes { causa, cantidad } => {
+                self.muertes_por_causa.anotar(causa, cantidad);
+                if causa != CausaMuerte::Caza {
+                    self.muertes += cantidad;
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// La contabilidad energética suma los flujos de biomasa en kg (ver `BalanceEnergia`).
+impl Oyente for FlujosKg {
+    fn recibir(&mut self, _fase: Fase, suceso: &Suceso) {
+        match *suceso {
+            Suceso::Amanecer { biomasa_kg, .. } => self.biomasa_presas = biomasa_kg,
+            Suceso::Despensa { asimilado_kg, podrido_kg, metabolizado_kg } => {
+                self.asimilado += asimilado_kg;
+                self.podrido += podrido_kg;
+                self.metabolizado += metabolizado_kg;
+            }
+            Suceso::Caza { cazable_kg, presa_kg, asimilado_kg, gasto_kg, .. } => {
+                self.cazable = cazable_kg;
+                self.cazado = presa_kg;
+                self.asimilado += asimilado_kg;
+                self.metabolizado += gasto_kg;
+            }
+            Suceso::Pastoreo { disponible_kg, racion_kg, pastado_kg } => {
+                self.pasto_disponible = disponible_kg;
+                self.racion_pasto = racion_kg;
+                self.pastado = pastado_kg;
+                self.con_pasto = true;
+            }
+            Suceso::Crecimiento { kg } | Suceso::Nacimientos { kg, .. } => self.produccion_presas += kg,
+            Suceso::Retiradas { kg } => self.muertes_presas += kg,
+            _ => {}
+        }
+    }
+}
+
+/// Los eventos notables del día, para la interfaz, los publicadores y los exportadores. Cada
+/// parto se anuncia también como `Evento::Nacimiento`.
+impl Oyente for Vec<Evento> {
+    fn recibir(&mut self, _fase: Fase, suceso: &Suceso) {
+        match suceso {
+            Suceso::Notable(evento) => self.push(evento.clone()),
+            Suceso::Parto { madre, especie, crias } => self.push(Evento::Nacimiento { madre: *madre, especie: *especie, crias: crias.clone() }),
+            _ => {}
+        }
+    }
+}
@@ -0,0 +1,98 @@
+// src/capas.rs
+
+// Capas de dibujo propias sobre la escena del visualizador. Una capa recibe el estado de la
+// simulación (solo para leerlo) y un `Lienzo` con unas pocas primitivas, y dibuja lo que
+// quiera encima de las presas y el depredador: zonas de intervención, umbrales, marcas de
+// reglas propias... sin tocar el código del visualizador.
+//
+// Hay dos formas de añadir capas:
+//   - Declararlas en el escenario con tablas `[[capas]]` (ver `Figura`), p. ej.
+//       [[capas]]
+//       figura = "rectangulo"
+//       x = 0.0
+//       y = 0.0
+//       ancho = 0.5
+//       alto = 1.0
+//       color = [1.0, 0.0, 0.0, 0.2]
+//       etiqueta = "Zona de caza controlada"
+//   - Implementar `Capa` y llamar a `registrar` antes de abrir el visualizador, desde un
+//     módulo propio o desde una aplicación que incruste la biblioteca.
+//
+// Las posiciones de `Figura` son fracciones del área de la escena (0 a 1), para que las zonas
+// no dependan del tamaño de la ventana; las capas en código reciben ese área del lienzo.
+
+use std::sync::Mutex;
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::simulacion::Simulacion;
+
+/// Color RGBA con componentes de 0 a 1.
+pub type Rgba = [f32; 4];
+
+/// Primitivas de dibujo que el visualizador ofrece a las capas, en coordenadas de la escena
+/// (las de las presas: se desplazan y se amplían con la cámara).
+pub trait Lienzo {
+    /// Rectángulo de la escena en que se reparten las presas: `[x, y, ancho, alto]`.
+    fn area(&self) -> [f32; 4];
+    fn rectangulo(&mut self, x: f32, y: f32, ancho: f32, alto: f32, color: Rgba);
+    fn circulo(&mut self, x: f32, y: f32, radio: f32, color: Rgba);
+    fn linea(&mut self, desde: [f32; 2], hasta: [f32; 2], grosor: f32, color: Rgba);
+    fn texto(&mut self, texto: &str, x: f32, y: f32, tamano: f32, color: Rgba);
+}
+
+/// Una capa de dibujo propia. Se llama en cada fotograma.
+pub trait Capa: Send {
+    fn dibujar(&self, sim: &Simulacion, lienzo: &mut dyn Lienzo);
+}
+
+static CAPAS: Mutex<Vec<Box<dyn Capa>>> = Mutex::new(Vec::new());
+
+/// Añade una capa a las que dibuja el visualizador, por encima de las ya registradas.
+pub fn registrar(capa: Box<dyn Capa>) {
+    if let Ok(mut capas) = CAPAS.lock() {
+        capas.push(capa);
+    }
+}
+
+/// Dibuja todas las capas registradas, en el orden en que se registraron.
+pub fn dibujar_todas(sim: &Simulacion, lienzo: &mut dyn Lienzo) {
+    if let Ok(capas) = CAPAS.lock() {
+        for capa in capas.iter() {
+            capa.dibujar(sim, lienzo);
+        }
+    }
+}
+
+/// Figura fija declarada en el escenario, con posiciones en fracciones del área de la escena.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(tag = "figura", rename_all = "snake_case", deny_unknown_fields)]
+pub enum Figura {
+    /// Rectángulo con la esquina superior izquierda en (`x`, `y`).
+    Rectangulo { x: f32, y: f32, ancho: f32, alto: f32, color: Rgba, #[serde(default)] etiqueta: Option<String> },
+    /// Círculo con centro en (`x`, `y`); el radio es una fracción del ancho del área.
+    Circulo { x: f32, y: f32, radio: f32, color: Rgba, #[serde(default)] etiqueta: Option<String> },
+}
+
+impl Capa for Figura {
+    fn dibujar(&self, _sim: &Simulacion, lienzo: &mut dyn Lienzo) {
+        let [x0, y0, ancho_area, alto_area] = lienzo.area();
+        let punto = |x: f32, y: f32| (x0 + x * ancho_area, y0 + y * alto_area);
+        let (etiqueta, posicion, color) = match self {
+            Figura::Rectangulo { x, y, ancho, alto, color, etiqueta } => {
+                let (px, py) = punto(*x, *y);
+                lienzo.rectangulo(px, py, ancho * ancho_area, alto * alto_area, *color);
+                (etiqueta, (px + 4.0, py + 16.0), color)
+            }
+            Figura::Circulo { x, y, radio, color, etiqueta } => {
+                let (px, py) = punto(*x, *y);
+                lienzo.circulo(px, py, radio * ancho_area, *color);
+                (etiqueta, (px, py), color)
+            }
+        };
+        if let Some(etiqueta) = etiqueta {
+            lienzo.texto(etiqueta, posicion.0, posicion.1, 16.0, [color[0], color[1], color[2], 1.0]);
+        }
+    }
+}
@@ -0,0 +1,81 @@
+// src/cazas.rs
+
+// Animación de las cazas en la escena (parte del binario, no de la biblioteca).
+// Con cada evento `Caza` del día, el depredador se lanza hasta donde estaba la presa y vuelve,
+// y en ese punto queda una cruz roja que se desvanece en unos segundos: así se ve qué hay
+// detrás de una caída brusca de la población. Las posiciones son de la escena (las de la
+// cámara) y los tiempos, segundos reales, no días; en cámara rápida, con varias cazas por
+// fotograma, el depredador se lanza solo hacia la última, pero todas dejan su marca.
+
+use std::collections::VecDeque;
+
+use macroquad::prelude::*;
+
+/// Segundos que dura la embestida, ida y vuelta.
+const DURACION_EMBESTIDA: f32 = 0.35;
+/// Segundos que tarda en desvanecerse una marca.
+const DURACION_MARCA: f32 = 3.0;
+/// Marcas visibles a la vez; las más antiguas se descartan.
+const MAXIMO_MARCAS: usize = 64;
+/// Medio lado de la cruz.
+const TAMANO_MARCA: f32 = 6.0;
+
+struct Marca {
+    posicion: Vec2,
+    restante: f32,
+}
+
+/// Cazas recientes que todavía se están animando.
+#[derive(Default)]
+pub struct Cazas {
+    marcas: VecDeque<Marca>,
+    /// Destino y segundos restantes de la embestida en curso.
+    embestida: Option<(Vec2, f32)>,
+}
+
+impl Cazas {
+    /// Anota una caza en `posicion`: el depredador se lanza hacia ella y allí queda una marca.
+    pub fn anotar(&mut self, posicion: Vec2) {
+        if self.marcas.len() == MAXIMO_MARCAS {
+            self.marcas.pop_front();
+        }
+        self.marcas.push_back(Marca { posicion, restante: DURACION_MARCA });
+        self.embestida = Some((posicion, DURACION_EMBESTIDA));
+    }
+
+    /// Descuenta el tiempo del fotograma.
+    pub fn avanzar(&mut self, segundos: f32) {
+        for marca in &mut self.marcas {
+            marca.restante -= segundos;
+        }
+        self.marcas.retain(|m| m.restante > 0.0);
+        if let Some((_, restante)) = &mut self.embestida {
+            *restante -= segundos;
+            if *restante <= 0.0 {
+                self.embestida = None;
+            }
+        }
+    }
+
+    /// Dónde dibujar al depredador que descansa en `origen`: durante la embestida avanza hasta
+    /// la presa en la primera mitad y regresa en la segunda.
+    pub fn posicion_depredador(&self, origen: Vec2) -> Vec2 {
+        match self.embestida {
+            Some((destino, restante)) => {
+                let progreso = 1.0 - restante / DURACION_EMBESTIDA;
+                origen.lerp(destino, 1.0 - (2.0 * progreso - 1.0).abs())
+            }
+            None => origen,
+        }
+    }
+
+    /// Dibuja las marcas, más transparentes cuanto más antiguas.
+    pub fn dibujar(&self) {
+        for marca in &self.marcas {
+            let color = Color { a: marca.restante / DURACION_MARCA, ..RED };
+            let Vec2 { x, y } = marca.posicion;
+            draw_line(x - TAMANO_MARCA, y - TAMANO_MARCA, x + TAMANO_MARCA, y + TAMANO_MARCA, 3.0, color);
+            draw_line(x - TAMANO_MARCA, y + TAMANO_MARCA, x + TAMANO_MARCA, y - TAMANO_MARCA, 3.0, color);
+        }
+    }
+}
@@ -0,0 +1,82 @@
+// src/censo.rs
+
+// Lectura de un censo de población inicial en CSV (datos reales de granja o de campo).
+// Columnas: species, sex, age, weight (también se aceptan especie, sexo, edad, peso).
+// La edad se expresa en días; si el peso queda vacío se usa el de la curva de crecimiento
+// por defecto de su especie.
+
+use std::fs;
+use std::path::Path;
+
+use crate::entidades::Sexo;
+use crate::especies;
+use crate::instantanea::EstadoPresa;
+use crate::parametros::Parametros;
+
+/// Posición de cada columna requerida dentro del encabezado.
+struct Columnas {
+    especie: usize,
+    sexo: usize,
+    edad: usize,
+    peso: Option<usize>,
+}
+
+impl Columnas {
+    fn desde_encabezado(encabezado: &str) -> Result<Self, String> {
+        let nombres: Vec<String> = encabezado.split(',').map(|c| c.trim().to_lowercase()).collect();
+        let buscar = |alternativas: &[&str]| nombres.iter().position(|n| alternativas.contains(&n.as_str()));
+        Ok(Self {
+            especie: buscar(&["species", "especie"]).ok_or("falta la columna species")?,
+            sexo: buscar(&["sex", "sexo"]).ok_or("falta la columna sex")?,
+            edad: buscar(&["age", "edad"]).ok_or("falta la columna age")?,
+            peso: buscar(&["weight", "peso"]),
+        })
+    }
+}
+
+fn leer_sexo(texto: &str) -> Result<Sexo, String> {
+    match texto.to_lowercase().as_str() {
+        "macho" | "m" | "male" => Ok(Sexo::Macho),
+        "hembra" | "h" | "f" | "female" => Ok(Sexo::Hembra),
+        otro => Err(format!("sexo desconocido: {}", otro)),
+    }
+}
+
+/// Interpreta el contenido de un censo. Los ids se asignan en orden, empezando en 0.
+pub fn interpretar_censo(contenido: &str) -> Result<Vec<EstadoPresa>, String> {
+    let mut lineas = contenido.lines().enumerate()
+        .filter(|(_, l)| !l.trim().is_empty() && !l.trim_start().starts_with('#'));
+    let (_, encabezado) = lineas.next().ok_or("el censo está vacío")?;
+    let columnas = Columnas::desde_encabezado(encabezado)?;
+
+    let mut presas = Vec::new();
+    for (numero, linea) in lineas {
+        let campos: Vec<&str> = linea.split(',').map(str::trim).collect();
+        let campo = |indice: usize| campos.get(indice).copied().unwrap_or("");
+        let fila = || -> Result<EstadoPresa, String> {
+            let especie = especies::especie_desde_nombre(campo(columnas.especie))?;
+            let sexo = leer_sexo(campo(columnas.sexo))?;
+            let edad_dias: u32 = campo(columnas.edad).parse().map_err(|_| format!("edad inválida: '{}'", campo(columnas.edad)))?;
+            let peso_kg = match columnas.peso.map(campo).filter(|p| !p.is_empty()) {
+                Some(peso) => {
+                    let peso: f64 = peso.parse().map_err(|_| format!("peso inválido: '{}'", peso))?;
+                    if !peso.is_finite() || peso <= 0.0 {
+                        return Err(format!("el peso debe ser positivo (es {})", peso));
+                    }
+                    peso
+                }
+                None => especie.peso_a_edad(edad_dias, &Parametros::default()),
+            };
+            Ok(EstadoPresa { id: presas.len() as u64, especie, sexo, edad_dias, peso_kg, vivo: true, gestacion: None })
+        };
+        presas.push(fila().map_err(|e| format!("línea {}: {}", numero + 1, e))?);
+    }
+    Ok(presas)
+}
+
+/// Lee un censo desde un archivo CSV.
+pub fn leer_censo(ruta: impl AsRef<Path>) -> Result<Vec<EstadoPresa>, String> {
+    let ruta = ruta.as_ref();
+    let contenido = fs::read_to_string(ruta).map_err(|e| format!("no se pudo leer {}: {}", ruta.display(), e))?;
+    interpretar_censo(&contenido).map_err(|e| format!("{}: {}", ruta.display(), e))
+}
@@ -0,0 +1,52 @@
+// src/clima.rs
+
+// Ciclo anual de estaciones. El año tiene cuatro estaciones de la misma duración y empieza en
+// primavera; la estación del día se deduce del contador de días, así que no hay estado que
+// guardar. Cada estación multiplica la reproducción de las presas, su probabilidad de enfermar
+// y el rebrote del pasto (ver `Clima`): la simulación aplica los factores a una copia de los
+// parámetros con la que corre el día.
+
+use serde::{Deserialize, Serialize};
+
+use crate::parametros::{Clima, FactoresEstacion, Parametros};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Estacion { Primavera, Verano, Otono, Invierno }
+
+impl Estacion {
+    pub const TODAS: [Estacion; 4] = [Estacion::Primavera, Estacion::Verano, Estacion::Otono, Estacion::Invierno];
+
+    /// Estación en que cae el día indicado.
+    pub fn del_dia(dia: u32, clima: &Clima) -> Estacion {
+        let duracion = clima.duracion_estacion_dias.max(1);
+        Estacion::TODAS[(dia / duracion % 4) as usize]
+    }
+
+    /// Factores de la estación según la tabla.
+    pub fn factores(self, clima: &Clima) -> FactoresEstacion {
+        match self {
+            Estacion::Primavera => clima.primavera,
+            Estacion::Verano => clima.verano,
+            Estacion::Otono => clima.otono,
+            Estacion::Invierno => clima.invierno,
+        }
+    }
+}
+
+/// Parámetros con que corre el día: los vigentes con los factores de la estación aplicados.
+/// Sin `CLIMA.activo`, una copia sin cambios. Las probabilidades que crecen no pasan de 1.
+pub fn parametros_del_dia(parametros: &Parametros, dia: u32) -> Parametros {
+    let mut del_dia = parametros.clone();
+    let clima = parametros.clima;
+    if !clima.activo {
+        return del_dia;
+    }
+    let factores = Estacion::del_dia(dia, &clima).factores(&clima);
+    let p = &mut del_dia;
+    p.conejo_tasa_reproduccion_diaria = (p.conejo_tasa_reproduccion_diaria * factores.reproduccion).min(1.0);
+    p.cabra_tasa_reproduccion_diaria = (p.cabra_tasa_reproduccion_diaria * factores.reproduccion).min(1.0);
+    p.probabilidad_enfermar = (p.probabilidad_enfermar * factores.enfermedad).min(1.0);
+    p.enfermedad.transmision *= factores.enfermedad;
+    p.cadena_trofica.crecimiento_diario = (p.cadena_trofica.crecimiento_diario * factores.pasto).min(1.0);
+    del_dia
+}
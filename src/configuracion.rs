@@ -0,0 +1,212 @@
+// src/configuracion.rs
+
+// Carga de archivos de configuración (parámetros sueltos o escenarios completos) en TOML o JSON,
+// y superposición de capas: valores por defecto < archivo < variables de entorno < flags de CLI.
+// El formato está descrito por un JSON Schema generado con schemars a partir de los mismos tipos,
+// así que el esquema publicado y la validación nunca se desincronizan. Los errores indican el
+// campo y la línea del archivo en lugar de caer silenciosamente en valores por defecto.
+
+use std::fs;
+use std::path::Path;
+
+use schemars::{schema_for, JsonSchema};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::alarmas::Alarma;
+use crate::capas::Figura;
+use crate::desafio::Objetivos;
+use crate::dificultad::Dificultad;
+use crate::idioma::Idioma;
+use crate::parametros::{self, ErrorValidacion, Parametros};
+use crate::registro::Telemetria;
+
+/// Prefijo de las variables de entorno que sobrescriben parámetros
+/// (p. ej. `SIM_CONEJO_TASA_REPRODUCCION=0.08`; los subcampos se separan con `__`).
+pub const PREFIJO_ENTORNO: &str = "SIM_";
+
+/// Un escenario completo: parámetros más los datos de la corrida.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(default, deny_unknown_fields)]
+pub struct Escenario {
+    /// Semilla del generador aleatorio; si se omite se sortea una.
+    pub semilla: Option<u64>,
+    /// Días a simular en modo headless.
+    pub dias: Option<u32>,
+    /// Censo CSV con la población inicial (columnas species, sex, age, weight).
+    pub censo: Option<String>,
+    /// Idioma de la interfaz (`es` o `en`); si se omite se usa el del sistema.
+    pub idioma: Option<Idioma>,
+    /// Archivo que se reescribe cada día con las cifras actuales, para superposiciones de
+    /// transmisiones en directo (JSON si termina en `.json`, texto plano si no).
+    pub superposicion: Option<String>,
+    /// Nivel de dificultad (`facil`, `normal` o `dificil`); escala el consumo del depredador
+    /// y la tasa de enfermedad de los parámetros. Si se omite, `normal`.
+    pub dificultad: Option<Dificultad>,
+    /// Muestra el tutorial guiado en el visualizador.
+    pub tutorial: bool,
+    /// Objetivos del modo desafío (tabla `[desafio]`); si se omite, no hay puntuación.
+    pub desafio: Option<Objetivos>,
+    /// Alarmas que pausan el visualizador (tablas `[[alarmas]]`).
+    pub alarmas: Vec<Alarma>,
+    /// Sonido del visualizador (tabla `[audio]`).
+    pub audio: AjustesAudio,
+    /// Carpeta con las imágenes `conejo.png`, `cabra.png` y `depredador.png` del visualizador;
+    /// si se omite, `sprites`. Las que falten se dibujan como círculos.
+    pub sprites: Option<String>,
+    /// Figuras que el visualizador dibuja sobre la escena (tablas `[[capas]]`), p. ej. zonas de intervención.
+    pub capas: Vec<Figura>,
+    /// Historial diario de las corridas headless (tabla `[telemetria]`): muestreo, métricas y tope de filas.
+    pub telemetria: Telemetria,
+    /// Parámetros del ecosistema; los omitidos toman su valor por defecto.
+    pub parametros: Parametros,
+}
+
+/// Ajustes del sonido del visualizador.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(default, deny_unknown_fields)]
+pub struct AjustesAudio {
+    /// Si el sonido empieza activado (se puede alternar durante la corrida).
+    pub activado: bool,
+    /// Volumen general, de 0 a 1.
+    pub volumen: f32,
+    /// Población de una especie por debajo de la cual suena la alerta.
+    pub umbral_alerta: u32,
+}
+
+impl Default for AjustesAudio {
+    fn default() -> Self {
+        Self { activado: true, volumen: 0.5, umbral_alerta: 20 }
+    }
+}
+
+/// Interpreta el texto de un valor sobrescrito: JSON si es posible (números, booleanos),
+/// si no, una cadena literal.
+pub(crate) fn valor_desde_texto(texto: &str) -> Value {
+    serde_json::from_str(texto).unwrap_or_else(|_| Value::String(texto.to_string()))
+}
+
+/// Separa una asignación `NOMBRE=VALOR` (como la del flag `--set`).
+fn separar_asignacion(asignacion: &str) -> Result<(String, Value), String> {
+    let (nombre, valor) = asignacion.split_once('=')
+        .ok_or_else(|| format!("se esperaba NOMBRE=VALOR, se recibió '{}'", asignacion))?;
+    Ok((nombre.trim().to_string(), valor_desde_texto(valor.trim())))
+}
+
+/// Aplica una asignación `NOMBRE=VALOR` suelta, validando el resultado.
+pub fn aplicar_asignacion(parametros: &mut Parametros, asignacion: &str) -> Result<(), String> {
+    let (nombre, valor) = separar_asignacion(asignacion)?;
+    parametros.establecer(&nombre, valor)
+}
+
+/// Construye los parámetros superponiendo las capas en orden de prioridad creciente:
+/// los de `base` (valores por defecto o del archivo), las variables de entorno `SIM_*`
+/// y, por último, las asignaciones `NOMBRE=VALOR` de la línea de comandos. Solo se valida
+/// el resultado final, para que una capa posterior pueda corregir a una anterior.
+pub fn parametros_en_capas(base: Parametros, entorno: impl Iterator<Item = (String, String)>, asignaciones_cli: &[String]) -> Result<Parametros, String> {
+    let mut arbol = base.a_arbol();
+    for (variable, valor) in entorno {
+        let Some(nombre) = variable.strip_prefix(PREFIJO_ENTORNO) else { continue };
+        let nombre = nombre.replace("__", ".");
+        parametros::asignar_en_arbol(&mut arbol, &nombre, valor_desde_texto(&valor))
+            .map_err(|e| format!("variable de entorno {}: {}", variable, e))?;
+    }
+    for asignacion in asignaciones_cli {
+        let (nombre, valor) = separar_asignacion(asignacion)?;
+        parametros::asignar_en_arbol(&mut arbol, &nombre, valor).map_err(|e| format!("--set {}: {}", asignacion, e))?;
+    }
+    Parametros::desde_arbol(arbol)
+}
+
+/// Esquema JSON de un archivo de parámetros.
+pub fn esquema_parametros() -> String {
+    serde_json::to_string_pretty(&schema_for!(Parametros)).unwrap_or_default()
+}
+
+/// Esquema JSON de un archivo de escenario.
+pub fn esquema_escenario() -> String {
+    serde_json::to_string_pretty(&schema_for!(Escenario)).unwrap_or_default()
+}
+
+/// Lee un archivo de parámetros sueltos.
+pub fn cargar_parametros(ruta: impl AsRef<Path>) -> Result<Parametros, String> {
+    cargar(ruta.as_ref(), "", Parametros::validar)
+}
+
+/// Lee un archivo de escenario.
+pub fn cargar_escenario(ruta: impl AsRef<Path>) -> Result<Escenario, String> {
+    cargar(ruta.as_ref(), "parametros.", |e: &Escenario| e.parametros.validar())
+}
+
+/// Interpreta el texto de un archivo de parámetros; `es_json` elige el formato.
+pub fn interpretar_parametros(texto: &str, es_json: bool) -> Result<Parametros, String> {
+    interpretar(texto, es_json, "", Parametros::validar)
+}
+
+/// Interpreta el texto de un archivo de escenario; `es_json` elige el formato.
+pub fn interpretar_escenario(texto: &str, es_json: bool) -> Result<Escenario, String> {
+    interpretar(texto, es_json, "parametros.", |e: &Escenario| e.parametros.validar())
+}
+
+fn cargar<T: DeserializeOwned>(ruta: &Path, prefijo: &str, validar: impl Fn(&T) -> Result<(), ErrorValidacion>) -> Result<T, String> {
+    let texto = fs::read_to_string(ruta).map_err(|e| format!("no se pudo leer {}: {}", ruta.display(), e))?;
+    let es_json = ruta.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("json"));
+    interpretar(&texto, es_json, prefijo, validar).map_err(|e| format!("{}: {}", ruta.display(), e))
+}
+
+fn interpretar<T: DeserializeOwned>(texto: &str, es_json: bool, prefijo: &str, validar: impl Fn(&T) -> Result<(), ErrorValidacion>) -> Result<T, String> {
+    // 1. Sintaxis y tipos: los propios analizadores saben dónde está el error.
+    let valor: T = if es_json {
+        serde_json::from_str(texto).map_err(|e| {
+            let mensaje = e.to_string();
+            let mensaje = mensaje.split(" at line").next().unwrap_or(&mensaje).to_string();
+            format!("{} en la línea {}", mensaje, e.line())
+        })?
+    } else {
+        toml::from_str(texto).map_err(|e| {
+            let linea = e.span().map(|s| linea_de_posicion(texto, s.start));
+            match linea {
+                Some(linea) => format!("{} en la línea {}", e.message(), linea),
+                None => e.message().to_string(),
+            }
+        })?
+    };
+
+    // 2. Restricciones entre valores: se busca en el texto la línea del campo culpable.
+    validar(&valor).map_err(|e| {
+        let ruta = format!("{}{}", prefijo, e.campo);
+        match buscar_linea_campo(texto, &ruta) {
+            Some(linea) => format!("{} en la línea {}", e, linea),
+            None => e.to_string(),
+        }
+    })?;
+    Ok(valor)
+}
+
+/// Número de línea (desde 1) que contiene la posición en bytes indicada.
+fn linea_de_posicion(texto: &str, posicion: usize) -> usize {
+    texto[..posicion.min(texto.len())].matches('\n').count() + 1
+}
+
+/// Busca la línea donde aparece un campo anidado (`a.b.c`): cada segmento se busca a partir
+/// de la línea donde apareció el anterior, lo que funciona igual en TOML y en JSON.
+fn buscar_linea_campo(texto: &str, ruta: &str) -> Option<usize> {
+    let lineas: Vec<&str> = texto.lines().collect();
+    let mut desde = 0;
+    for segmento in ruta.split('.') {
+        let encontrada = (desde..lineas.len()).find(|&i| contiene_palabra(lineas[i], segmento))?;
+        desde = encontrada;
+    }
+    Some(desde + 1)
+}
+
+/// Indica si `palabra` aparece en la línea como identificador completo (no como parte de otro).
+fn contiene_palabra(linea: &str, palabra: &str) -> bool {
+    let es_parte = |c: char| c.is_alphanumeric() || c == '_';
+    linea.match_indices(palabra).any(|(i, _)| {
+        let antes = linea[..i].chars().next_back();
+        let despues = linea[i + palabra.len()..].chars().next();
+        !antes.is_some_and(es_parte) && !despues.is_some_and(es_parte)
+    })
+}
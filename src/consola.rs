@@ -0,0 +1,330 @@
+// src/consola.rs
+
+// Consola de órdenes desplegable (parte del binario, no de la biblioteca).
+// Se abre y se cierra con la tecla ` y acepta órdenes de una línea que llaman a las mismas
+// intervenciones que el modo de edición y la ventana de parámetros:
+//
+//   spawn conejo 20                 añade 20 conejos recién nacidos
+//   kill cabras viejas              retira las cabras senescentes (o jovenes, subadultas, adultas, todas)
+//   set CONEJO_TASA_REPRODUCCION 0.08
+//   set cabra TASA_REPRODUCCION 0.02   lo mismo, con el nombre sin el prefijo de la especie
+//   save corrida1.bin               guarda una instantánea
+//   help, clear
+//
+// Las flechas recorren las órdenes anteriores y el tabulador completa la palabra en curso
+// (órdenes, especies, clases de edad y nombres de parámetros). Todo lo que hacen las órdenes
+// queda como eventos del día, igual que las intervenciones con el ratón.
+
+use std::collections::VecDeque;
+
+use egui_macroquad::egui;
+use serde_json::Value;
+
+use simulador_ecosistema_presa_depredador::entidades::{ClaseEdad, Especie};
+use simulador_ecosistema_presa_depredador::eventos::Evento;
+use simulador_ecosistema_presa_depredador::idioma::{Idioma, Texto};
+use simulador_ecosistema_presa_depredador::simulacion::Simulacion;
+
+/// Líneas de salida que se conservan.
+const MAXIMO_SALIDA: usize = 200;
+/// Órdenes que recuerda el historial.
+const MAXIMO_HISTORIAL: usize = 100;
+
+const ORDENES: &[&str] = &["spawn", "kill", "set", "save", "help", "clear"];
+const ESPECIES: &[&str] = &["conejo", "cabra"];
+const CLASES: &[&str] = &["jovenes", "subadultas", "adultas", "viejas", "todas"];
+
+const AYUDA: &str = "spawn <conejo|cabra> <n> | kill <conejos|cabras|todas> [jovenes|subadultas|adultas|viejas|todas] | \
+set [conejo|cabra] <PARAMETRO> <valor> | save <archivo> | clear";
+
+/// Estado de la consola: la línea en curso, lo escrito antes y lo que respondió.
+#[derive(Default)]
+pub struct Consola {
+    pub abierta: bool,
+    linea: String,
+    historial: VecDeque<String>,
+    /// Posición en el historial mientras se recorre con las flechas.
+    navegando: Option<usize>,
+    /// Respuestas, marcadas como error o no.
+    salida: VecDeque<(String, bool)>,
+    enfocar: bool,
+}
+
+impl Consola {
+    pub fn alternar(&mut self) {
+        self.abierta = !self.abierta;
+        self.enfocar = self.abierta;
+    }
+
+    /// Dibuja la consola bajo la barra superior, si está abierta, y ejecuta la orden al pulsar
+    /// Intro. Devuelve si se ejecutó alguna (y puede haber eventos nuevos en `sim`).
+    pub fn mostrar(&mut self, ctx: &egui::Context, sim: &mut Simulacion, idioma: Idioma) -> bool {
+        if !self.abierta {
+            return false;
+        }
+        let mut ejecutada = false;
+        egui::TopBottomPanel::top("consola").resizable(false).show(ctx, |ui| {
+            egui::ScrollArea::vertical().max_height(160.0).stick_to_bottom(true).show(ui, |ui| {
+                for (texto, error) in &self.salida {
+                    let texto = egui::RichText::new(texto).monospace();
+                    if *error {
+                        ui.colored_label(egui::Color32::LIGHT_RED, texto);
+                    } else {
+                        ui.label(texto);
+                    }
+                }
+            });
+            // Las flechas y el tabulador se atienden antes que el campo de texto.
+            let (arriba, abajo, tabulador) = ui.input_mut(|i| (
+                i.consume_key(egui::Modifiers::NONE, egui::Key::ArrowUp),
+                i.consume_key(egui::Modifiers::NONE, egui::Key::ArrowDown),
+                i.consume_key(egui::Modifiers::NONE, egui::Key::Tab),
+            ));
+            let campo = ui.add(
+                egui::TextEdit::singleline(&mut self.linea)
+                    .font(egui::TextStyle::Monospace)
+                    .hint_text(idioma.texto(Texto::AyudaConsola))
+                    .desired_width(f32::INFINITY)
+                    .lock_focus(true),
+            );
+            if self.enfocar {
+                campo.request_focus();
+                self.enfocar = false;
+            }
+            // La tecla que abre y cierra la consola no se escribe.
+            self.linea.retain(|c| c != '`');
+            if campo.has_focus() {
+                if arriba {
+                    self.recorrer_historial(true);
+                }
+                if abajo {
+                    self.recorrer_historial(false);
+                }
+                if tabulador {
+                    self.completar(sim);
+                }
+            }
+            if campo.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter)) {
+                let orden = std::mem::take(&mut self.linea);
+                if !orden.trim().is_empty() {
+                    self.ejecutar(&orden, sim);
+                    ejecutada = true;
+                }
+                campo.request_focus();
+            }
+        });
+        ejecutada
+    }
+
+    fn escribir(&mut self, texto: impl Into<String>, error: bool) {
+        if self.salida.len() == MAXIMO_SALIDA {
+            self.salida.pop_front();
+        }
+        self.salida.push_back((texto.into(), error));
+    }
+
+    fn ejecutar(&mut self, orden: &str, sim: &mut Simulacion) {
+        if self.historial.back().map(String::as_str) != Some(orden) {
+            if self.historial.len() == MAXIMO_HISTORIAL {
+                self.historial.pop_front();
+            }
+            self.historial.push_back(orden.to_string());
+        }
+        self.navegando = None;
+        self.escribir(format!("> {}", orden), false);
+        let partes: Vec<&str> = orden.split_whitespace().collect();
+        if partes[0].eq_ignore_ascii_case("clear") {
+            self.salida.clear();
+            return;
+        }
+        match ejecutar_orden(&partes, sim) {
+            Ok(respuesta) => self.escribir(respuesta, false),
+            Err(error) => self.escribir(error, true),
+        }
+    }
+
+    /// Sustituye la línea por la orden anterior (`atras`) o siguiente del historial.
+    fn recorrer_historial(&mut self, atras: bool) {
+        if self.historial.is_empty() {
+            return;
+        }
+        let ultima = self.historial.len() - 1;
+        self.navegando = match (self.navegando, atras) {
+            (None, true) => Some(ultima),
+            (None, false) => None,
+            (Some(i), true) => Some(i.saturating_sub(1)),
+            (Some(i), false) if i < ultima => Some(i + 1),
+            (Some(_), false) => None,
+        };
+        self.linea = self.navegando.map(|i| self.historial[i].clone()).unwrap_or_default();
+    }
+
+    /// Completa la última palabra de la línea: del todo si solo hay una opción; si hay varias,
+    /// hasta donde coinciden, y las muestra.
+    fn completar(&mut self, sim: &Simulacion) {
+        let linea = self.linea.clone();
+        let termina_en_espacio = linea.ends_with(' ');
+        let mut partes: Vec<&str> = linea.split_whitespace().collect();
+        let palabra = if termina_en_espacio { "" } else { partes.pop().unwrap_or("") };
+        let opciones = opciones_para(&partes, sim);
+        let candidatas: Vec<&String> = opciones.iter()
+            .filter(|o| o.to_lowercase().starts_with(&palabra.to_lowercase()))
+            .collect();
+        let completada = match candidatas.as_slice() {
+            [] => return,
+            [unica] => format!("{} ", unica),
+            varias => {
+                let listado = varias.iter().map(|c| c.as_str()).collect::<Vec<_>>().join("  ");
+                self.escribir(listado, false);
+                prefijo_comun(varias)
+            }
+        };
+        self.linea = partes.iter().map(|p| format!("{} ", p)).collect();
+        self.linea.push_str(&completada);
+    }
+}
+
+/// Palabras que pueden seguir a `partes` (las ya escritas) para el tabulador.
+fn opciones_para(partes: &[&str], sim: &Simulacion) -> Vec<String> {
+    let lista = |palabras: &[&str]| palabras.iter().map(|p| p.to_string()).collect();
+    let Some(orden) = partes.first() else {
+        return lista(ORDENES);
+    };
+    match (orden.to_lowercase().as_str(), partes.len()) {
+        ("spawn", 1) => lista(ESPECIES),
+        ("kill", 1) => ["conejos", "cabras", "todas"].iter().map(|p| p.to_string()).collect(),
+        ("kill", 2) => lista(CLASES),
+        ("set", 1) => {
+            let mut opciones: Vec<String> = lista(ESPECIES);
+            opciones.extend(nombres_parametros(sim));
+            opciones
+        }
+        ("set", 2) => match especie(partes[1]) {
+            Some(especie) => {
+                let prefijo = prefijo_especie(especie);
+                nombres_parametros(sim).into_iter().filter_map(|n| n.strip_prefix(prefijo).map(str::to_string)).collect()
+            }
+            None => Vec::new(),
+        },
+        _ => Vec::new(),
+    }
+}
+
+fn nombres_parametros(sim: &Simulacion) -> Vec<String> {
+    match sim.parametros.a_arbol() {
+        Value::Object(campos) => campos.keys().cloned().collect(),
+        _ => Vec::new(),
+    }
+}
+
+fn prefijo_comun(palabras: &[&String]) -> String {
+    let primera = palabras[0];
+    let mut largo = primera.len();
+    for palabra in &palabras[1..] {
+        largo = primera.chars().zip(palabra.chars())
+            .take_while(|(a, b)| a.eq_ignore_ascii_case(b))
+            .count()
+            .min(largo);
+    }
+    primera[..largo].to_string()
+}
+
+fn prefijo_especie(especie: Especie) -> &'static str {
+    match especie {
+        Especie::Conejo => "CONEJO_",
+        Especie::Cabra => "CABRA_",
+    }
+}
+
+/// Especie nombrada en una orden, en singular o plural.
+fn especie(palabra: &str) -> Option<Especie> {
+    match palabra.to_lowercase().trim_end_matches('s') {
+        "conejo" => Some(Especie::Conejo),
+        "cabra" => Some(Especie::Cabra),
+        _ => None,
+    }
+}
+
+/// Interpreta los argumentos de `set` (y de la línea de la ventana de parámetros): especie
+/// opcional, nombre y valor en JSON. Un valor que no es JSON se toma como texto, p. ej. el
+/// nombre de una variante.
+pub fn interpretar_parametro(partes: &[&str]) -> Result<(Option<Especie>, String, Value), String> {
+    let (especie, resto) = match partes.first().and_then(|p| especie(p)) {
+        Some(especie) => (Some(especie), &partes[1..]),
+        None => (None, partes),
+    };
+    let (nombre, valor) = resto.split_first().ok_or("falta el nombre del parámetro")?;
+    if valor.is_empty() {
+        return Err(format!("falta el valor de {}", nombre));
+    }
+    let valor = valor.join(" ");
+    let valor = serde_json::from_str(&valor).unwrap_or(Value::String(valor));
+    Ok((especie, nombre.to_string(), valor))
+}
+
+/// Ejecuta una orden ya separada en palabras y devuelve la respuesta que se muestra.
+fn ejecutar_orden(partes: &[&str], sim: &mut Simulacion) -> Result<String, String> {
+    let argumentos = &partes[1..];
+    match partes[0].to_lowercase().as_str() {
+        "help" => Ok(AYUDA.to_string()),
+        "spawn" => {
+            let [nombre, cantidad] = argumentos else {
+                return Err("uso: spawn <conejo|cabra> <n>".to_string());
+            };
+            let especie = especie(nombre).ok_or_else(|| format!("especie desconocida: {}", nombre))?;
+            let cantidad: u32 = cantidad.parse().map_err(|_| format!("cantidad inválida: {}", cantidad))?;
+            if cantidad > sim.parametros.poblacion_maxima {
+                return Err(format!("como mucho {} (POBLACION_MAXIMA)", sim.parametros.poblacion_maxima));
+            }
+            let ids = sim.generar_presas(especie, cantidad);
+            sim.eventos_dia.extend(ids.iter().map(|&id| Evento::PresaAgregada { id, especie }));
+            Ok(format!("{} {:?} añadidas", ids.len(), especie))
+        }
+        "kill" => {
+            let (especies, clase) = match argumentos {
+                [nombre] => (*nombre, "todas"),
+                [nombre, clase] => (*nombre, *clase),
+                _ => return Err("uso: kill <conejos|cabras|todas> [jovenes|subadultas|adultas|viejas|todas]".to_string()),
+            };
+            let especie = match especies.to_lowercase().as_str() {
+                "todas" => None,
+                nombre => Some(especie(nombre).ok_or_else(|| format!("especie desconocida: {}", nombre))?),
+            };
+            let clase = match clase.to_lowercase().as_str() {
+                "jovenes" | "juveniles" => Some(ClaseEdad::Juvenil),
+                "subadultas" => Some(ClaseEdad::Subadulto),
+                "adultas" => Some(ClaseEdad::Adulto),
+                "viejas" | "senescentes" => Some(ClaseEdad::Senescente),
+                "todas" => None,
+                otra => return Err(format!("clase de edad desconocida: {}", otra)),
+            };
+            let ids: Vec<u64> = sim.presas.iter()
+                .filter(|p| especie.is_none_or(|e| p.especie() == e))
+                .filter(|p| clase.is_none_or(|c| p.especie().clase_edad(p.edad(), &sim.parametros) == c))
+                .map(|p| p.id())
+                .collect();
+            for &id in &ids {
+                sim.retirar_presa(id);
+            }
+            Ok(format!("{} presas retiradas", ids.len()))
+        }
+        "set" => {
+            let (especie, nombre, valor) = interpretar_parametro(argumentos)?;
+            let previos = sim.eventos_dia.len();
+            sim.establecer_parametro(especie, &nombre, valor)?;
+            let cambio = sim.eventos_dia[previos..].iter().find_map(|e| match e {
+                Evento::ParametroCambiado { nombre, nuevo, .. } => Some(format!("{} = {}", nombre, nuevo)),
+                _ => None,
+            });
+            Ok(cambio.unwrap_or_else(|| "sin cambios".to_string()))
+        }
+        "save" => {
+            let [ruta] = argumentos else {
+                return Err("uso: save <archivo>".to_string());
+            };
+            sim.instantanea().guardar(ruta).map_err(|e| format!("no se pudo guardar {}: {}", ruta, e))?;
+            Ok(format!("instantánea del día {} guardada en {}", sim.dia, ruta))
+        }
+        otra => Err(format!("orden desconocida: {} (help muestra las disponibles)", otra)),
+    }
+}
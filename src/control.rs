@@ -0,0 +1,390 @@
+// src/control.rs
+
+// Entrada del visualizador (parte del binario, no de la biblioteca): teclado, ratón, pantalla
+// táctil y mando se traducen a las mismas acciones, así que el bucle principal no necesita
+// saber de dónde vienen. También guarda la cámara de la escena (zoom y desplazamiento).
+//
+//   Acción            Teclado/ratón       Táctil               Mando
+//   Pausa             Espacio             -                    Start
+//   Avanzar un día    N (en pausa)        -                    A
+//   Saltar al evento  F                   -                    -
+//   Nueva corrida     Mayús+R             -                    Select
+//   Turbo             T                   -                    -
+//   Cámara rápida     Flecha derecha      -                    Gatillo derecho
+//   Cámara lenta      Flecha izquierda    -                    Gatillo izquierdo
+//   Inspeccionar      Clic en una presa   Toque en una presa   RB / LB (presa siguiente/anterior)
+//   Retirar (edición) Clic derecho        -                    -
+//   Zoom              Rueda               Pellizco             Stick derecho (vertical)
+//   Desplazar         Arrastrar           Arrastrar            Stick izquierdo
+//   Reubicar          Arrastrar el        Arrastrar el         -
+//   depredador        depredador          depredador
+//
+// Los mandos se leen con gilrs en escritorio y con la API Gamepad del navegador en la web
+// (plugin `web/mando.js`); ambos usan la distribución estándar de botones de esa API.
+
+use macroquad::prelude::*;
+
+/// Botones en la distribución estándar de la API Gamepad.
+const BOTON_A: u32 = 0;
+const BOTON_LB: u32 = 4;
+const BOTON_RB: u32 = 5;
+const BOTON_LT: u32 = 6;
+const BOTON_RT: u32 = 7;
+const BOTON_SELECT: u32 = 8;
+const BOTON_START: u32 = 9;
+
+/// Por debajo de este valor se ignora la inclinación de un stick.
+const ZONA_MUERTA: f32 = 0.2;
+/// Píxeles por segundo que desplaza el stick inclinado del todo.
+const VELOCIDAD_STICK: f32 = 600.0;
+/// Píxeles que debe moverse un clic o toque para contar como arrastre y no como selección.
+const UMBRAL_ARRASTRE: f32 = 8.0;
+/// Zoom máximo de la escena (el mínimo es la escena completa).
+const ZOOM_MAXIMO: f32 = 8.0;
+
+/// Velocidad de la simulación pedida en este fotograma.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Velocidad {
+    Normal,
+    Rapida,
+    Lenta,
+}
+
+/// Lo que el usuario pidió en un fotograma, venga del dispositivo que venga.
+#[derive(Debug, Clone)]
+pub struct Acciones {
+    pub pausar: bool,
+    pub paso: bool,
+    /// Archivar la corrida y empezar otra con una semilla al azar, como "Nueva corrida".
+    pub reiniciar: bool,
+    /// Empezar (o cancelar) el salto hasta el evento elegido en la barra.
+    pub saltar: bool,
+    pub turbo: bool,
+    pub velocidad: Velocidad,
+    /// Punto de la pantalla donde se hizo clic o se tocó sin arrastrar.
+    pub inspeccionar: Option<Vec2>,
+    /// Punto de la pantalla donde se hizo clic derecho.
+    pub retirar: Option<Vec2>,
+    /// Punto de la pantalla donde empezó un clic o toque en este fotograma. Si cae sobre algo
+    /// que se pueda arrastrar, se llama a [`Control::capturar_arrastre`].
+    pub pulsar: Option<Vec2>,
+    /// Punto actual de un arrastre capturado.
+    pub arrastrar: Option<Vec2>,
+    /// Punto donde terminó un arrastre capturado.
+    pub soltar: Option<Vec2>,
+    /// Presa siguiente (+1) o anterior (-1) en el inspector.
+    pub cambiar_seleccion: i32,
+    /// Factor de zoom de este fotograma (1 = sin cambio).
+    pub zoom: f32,
+    /// Desplazamiento de la escena en píxeles de pantalla.
+    pub desplazamiento: Vec2,
+}
+
+/// Estado de un mando: botones como máscara de bits y ejes de los dos sticks
+/// (izquierdo x, y; derecho x, y), en la distribución estándar.
+#[derive(Debug, Clone, Copy, Default)]
+struct EstadoMando {
+    botones: u32,
+    ejes: [f32; 4],
+}
+
+impl EstadoMando {
+    fn pulsado(&self, boton: u32) -> bool {
+        self.botones & (1 << boton) != 0
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+mod mando {
+    use gilrs::{Axis, Button, Gilrs};
+
+    use super::EstadoMando;
+
+    pub struct Mandos(Option<Gilrs>);
+
+    impl Mandos {
+        pub fn new() -> Self {
+            // Sin subsistema de mandos (p. ej. sin permisos sobre /dev/input) se sigue sin ellos.
+            Self(Gilrs::new().map_err(|e| eprintln!("mandos no disponibles: {}", e)).ok())
+        }
+
+        /// Estado del primer mando conectado.
+        pub fn leer(&mut self) -> Option<EstadoMando> {
+            let gilrs = self.0.as_mut()?;
+            while gilrs.next_event().is_some() {}
+            let (_, mando) = gilrs.gamepads().find(|(_, m)| m.is_connected())?;
+            let botones = [
+                (Button::South, super::BOTON_A),
+                (Button::LeftTrigger, super::BOTON_LB),
+                (Button::RightTrigger, super::BOTON_RB),
+                (Button::LeftTrigger2, super::BOTON_LT),
+                (Button::RightTrigger2, super::BOTON_RT),
+                (Button::Select, super::BOTON_SELECT),
+                (Button::Start, super::BOTON_START),
+            ];
+            let mut estado = EstadoMando::default();
+            for (boton, bit) in botones {
+                if mando.is_pressed(boton) {
+                    estado.botones |= 1 << bit;
+                }
+            }
+            // gilrs da el eje vertical hacia arriba; la API Gamepad, hacia abajo.
+            estado.ejes = [
+                mando.value(Axis::LeftStickX),
+                -mando.value(Axis::LeftStickY),
+                mando.value(Axis::RightStickX),
+                -mando.value(Axis::RightStickY),
+            ];
+            Some(estado)
+        }
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+mod mando {
+    use super::EstadoMando;
+
+    extern "C" {
+        fn mando_conectado() -> u32;
+        fn mando_botones() -> u32;
+        fn mando_eje(indice: u32) -> f32;
+    }
+
+    /// Versión que el plugin de JavaScript comprueba al iniciarse.
+    #[no_mangle]
+    pub extern "C" fn mando_crate_version() -> u32 {
+        1
+    }
+
+    pub struct Mandos;
+
+    impl Mandos {
+        pub fn new() -> Self {
+            Self
+        }
+
+        /// Estado del primer mando conectado.
+        pub fn leer(&mut self) -> Option<EstadoMando> {
+            if unsafe { mando_conectado() } == 0 {
+                return None;
+            }
+            let mut estado = EstadoMando { botones: unsafe { mando_botones() }, ejes: [0.0; 4] };
+            for (indice, eje) in estado.ejes.iter_mut().enumerate() {
+                *eje = unsafe { mando_eje(indice as u32) };
+            }
+            Some(estado)
+        }
+    }
+}
+
+/// Clic o toque en curso sobre la escena.
+struct Arrastre {
+    inicio: Vec2,
+    anterior: Vec2,
+    movido: bool,
+    /// El arrastre mueve un objeto de la escena en lugar de desplazarla.
+    capturado: bool,
+}
+
+/// Estado de la entrada entre fotogramas.
+pub struct Control {
+    mandos: mando::Mandos,
+    mando_anterior: EstadoMando,
+    arrastre: Option<Arrastre>,
+    /// Distancia entre los dos dedos en el fotograma anterior y su punto medio.
+    pellizco: Option<(f32, Vec2)>,
+    /// Hay o hubo más de un dedo desde que empezó el gesto; se ignora el ratón simulado.
+    multitactil: bool,
+    /// Se ha usado la pantalla táctil (o es un móvil), así que la interfaz agranda los controles.
+    tactil: bool,
+}
+
+impl Control {
+    pub fn new() -> Self {
+        Self {
+            mandos: mando::Mandos::new(),
+            mando_anterior: EstadoMando::default(),
+            arrastre: None,
+            pellizco: None,
+            multitactil: false,
+            tactil: cfg!(any(target_os = "android", target_os = "ios")),
+        }
+    }
+
+    pub fn tactil(&self) -> bool {
+        self.tactil
+    }
+
+    /// El arrastre que acaba de empezar mueve un objeto (p. ej. el depredador): hasta soltarlo
+    /// se informa con `arrastrar` y `soltar` en lugar de desplazar la escena.
+    pub fn capturar_arrastre(&mut self) {
+        if let Some(arrastre) = &mut self.arrastre {
+            arrastre.capturado = true;
+        }
+    }
+
+    /// Lee la entrada del fotograma. `raton_libre` y `teclado_libre` indican que la entrada
+    /// no es de las ventanas de la interfaz.
+    pub fn leer(&mut self, raton_libre: bool, teclado_libre: bool) -> Acciones {
+        let mut acciones = Acciones {
+            pausar: false,
+            paso: false,
+            reiniciar: false,
+            saltar: false,
+            turbo: false,
+            velocidad: Velocidad::Normal,
+            inspeccionar: None,
+            retirar: None,
+            pulsar: None,
+            arrastrar: None,
+            soltar: None,
+            cambiar_seleccion: 0,
+            zoom: 1.0,
+            desplazamiento: Vec2::ZERO,
+        };
+
+        if teclado_libre {
+            acciones.pausar = is_key_pressed(KeyCode::Space);
+            acciones.paso = is_key_pressed(KeyCode::N);
+            // La R sola copia el resumen del estado.
+            acciones.reiniciar = is_key_pressed(KeyCode::R) && (is_key_down(KeyCode::LeftShift) || is_key_down(KeyCode::RightShift));
+            acciones.saltar = is_key_pressed(KeyCode::F);
+            acciones.turbo = is_key_pressed(KeyCode::T);
+            if is_key_down(KeyCode::Right) {
+                acciones.velocidad = Velocidad::Rapida;
+            } else if is_key_down(KeyCode::Left) {
+                acciones.velocidad = Velocidad::Lenta;
+            }
+        }
+
+        self.leer_tactil(&mut acciones);
+        self.leer_raton(&mut acciones, raton_libre);
+        self.leer_mando(&mut acciones);
+        acciones
+    }
+
+    /// Pellizco con dos dedos: zoom y desplazamiento del punto medio.
+    fn leer_tactil(&mut self, acciones: &mut Acciones) {
+        let toques = touches();
+        if toques.is_empty() {
+            self.multitactil = false;
+        } else {
+            self.tactil = true;
+        }
+        if toques.len() < 2 {
+            self.pellizco = None;
+            return;
+        }
+        self.multitactil = true;
+        self.arrastre = None;
+        let (a, b) = (toques[0].position, toques[1].position);
+        let (distancia, medio) = (a.distance(b), (a + b) / 2.0);
+        if let Some((distancia_anterior, medio_anterior)) = self.pellizco {
+            if distancia_anterior > 0.0 {
+                acciones.zoom *= distancia / distancia_anterior;
+            }
+            acciones.desplazamiento += medio - medio_anterior;
+        }
+        self.pellizco = Some((distancia, medio));
+    }
+
+    /// Clic (o toque de un dedo, que macroquad simula como ratón): arrastrar desplaza
+    /// la escena (o mueve lo capturado) y soltar sin haber arrastrado inspecciona. La rueda hace zoom y el clic
+    /// derecho retira presas en el modo de edición.
+    fn leer_raton(&mut self, acciones: &mut Acciones, raton_libre: bool) {
+        let posicion: Vec2 = mouse_position().into();
+        if raton_libre && !self.multitactil && is_mouse_button_pressed(MouseButton::Left) {
+            self.arrastre = Some(Arrastre { inicio: posicion, anterior: posicion, movido: false, capturado: false });
+            acciones.pulsar = Some(posicion);
+        }
+        if let Some(arrastre) = &mut self.arrastre {
+            arrastre.movido |= posicion.distance(arrastre.inicio) > UMBRAL_ARRASTRE;
+            if arrastre.movido && arrastre.capturado {
+                acciones.arrastrar = Some(posicion);
+            } else if arrastre.movido {
+                acciones.desplazamiento += posicion - arrastre.anterior;
+            }
+            arrastre.anterior = posicion;
+        }
+        if is_mouse_button_released(MouseButton::Left) {
+            if let Some(arrastre) = self.arrastre.take() {
+                if !arrastre.movido {
+                    acciones.inspeccionar = Some(arrastre.inicio);
+                } else if arrastre.capturado {
+                    acciones.soltar = Some(posicion);
+                }
+            }
+        }
+
+        if raton_libre && is_mouse_button_pressed(MouseButton::Right) {
+            acciones.retirar = Some(posicion);
+        }
+
+        let (_, rueda) = mouse_wheel();
+        if raton_libre && rueda != 0.0 {
+            acciones.zoom *= if rueda > 0.0 { 1.1 } else { 1.0 / 1.1 };
+        }
+    }
+
+    fn leer_mando(&mut self, acciones: &mut Acciones) {
+        let Some(estado) = self.mandos.leer() else {
+            return;
+        };
+        let anterior = std::mem::replace(&mut self.mando_anterior, estado);
+        let recien_pulsado = |boton| estado.pulsado(boton) && !anterior.pulsado(boton);
+
+        acciones.pausar |= recien_pulsado(BOTON_START);
+        acciones.paso |= recien_pulsado(BOTON_A);
+        acciones.reiniciar |= recien_pulsado(BOTON_SELECT);
+        if recien_pulsado(BOTON_RB) {
+            acciones.cambiar_seleccion += 1;
+        }
+        if recien_pulsado(BOTON_LB) {
+            acciones.cambiar_seleccion -= 1;
+        }
+        if estado.pulsado(BOTON_RT) {
+            acciones.velocidad = Velocidad::Rapida;
+        } else if estado.pulsado(BOTON_LT) {
+            acciones.velocidad = Velocidad::Lenta;
+        }
+
+        let eje = |valor: f32| if valor.abs() < ZONA_MUERTA { 0.0 } else { valor };
+        let segundos = get_frame_time();
+        // El stick mueve la vista, así que la escena se desplaza en sentido contrario.
+        acciones.desplazamiento -= vec2(eje(estado.ejes[0]), eje(estado.ejes[1])) * VELOCIDAD_STICK * segundos;
+        acciones.zoom *= (-eje(estado.ejes[3]) * segundos * 2.0).exp();
+    }
+}
+
+/// Zoom y desplazamiento de la escena. A zoom 1 las coordenadas de la escena coinciden
+/// con las de la pantalla.
+pub struct Camara {
+    zoom: f32,
+    /// Desplazamiento del centro de la vista respecto al centro de la pantalla.
+    desplazamiento: Vec2,
+}
+
+impl Camara {
+    pub fn new() -> Self {
+        Self { zoom: 1.0, desplazamiento: Vec2::ZERO }
+    }
+
+    /// Aplica el zoom y el desplazamiento pedidos, sin dejar que la vista salga de la escena.
+    pub fn aplicar(&mut self, acciones: &Acciones) {
+        self.zoom = (self.zoom * acciones.zoom).clamp(1.0, ZOOM_MAXIMO);
+        self.desplazamiento -= acciones.desplazamiento / self.zoom;
+        let limite = vec2(screen_width(), screen_height()) / 2.0 * (1.0 - 1.0 / self.zoom);
+        self.desplazamiento = self.desplazamiento.clamp(-limite, limite);
+    }
+
+    pub fn camara2d(&self) -> Camera2D {
+        let (ancho, alto) = (screen_width() / self.zoom, screen_height() / self.zoom);
+        let centro = vec2(screen_width(), screen_height()) / 2.0 + self.desplazamiento;
+        Camera2D::from_display_rect(Rect::new(centro.x - ancho / 2.0, centro.y - alto / 2.0, ancho, alto))
+    }
+
+    /// Punto de la escena bajo un punto de la pantalla.
+    pub fn a_escena(&self, punto: Vec2) -> Vec2 {
+        self.camara2d().screen_to_world(punto)
+    }
+}
@@ -0,0 +1,211 @@
+// src/cuaderno.rs
+
+// API de alto nivel para explorar el modelo desde un notebook de Rust (evcxr/Jupyter).
+// Una sola llamada corre la simulación y devuelve una tabla con una fila por día, con
+// acceso por columnas y un resumen legible al imprimirla:
+//
+//   :dep simulador_ecosistema_presa_depredador = { path = "." }
+//   use simulador_ecosistema_presa_depredador::cuaderno::*;
+//   let corrida = correr(Parametros::default().con("CONEJO_TASA", 0.08)?, 365, 42);
+//   corrida                      // evcxr la muestra como tabla HTML
+//   corrida.columna("conejos")   // Vec<f64> para graficar
+
+use std::fmt;
+
+pub use crate::parametros::Parametros;
+pub use crate::registro::RegistroDiario;
+
+use crate::registro::{self, Registro};
+use crate::simulacion::{Retencion, SimulacionBuilder};
+
+/// Filas que se muestran al principio y al final al imprimir una corrida.
+const FILAS_VISTA_PREVIA: usize = 5;
+
+/// Resultado de una corrida: los parámetros usados y las métricas de cada día.
+#[derive(Debug, Clone)]
+pub struct Corrida {
+    pub parametros: Parametros,
+    pub semilla: u64,
+    pub filas: Vec<RegistroDiario>,
+    /// Si el depredador seguía vivo al terminar.
+    pub depredador_vivo: bool,
+}
+
+/// Corre la simulación `dias` días (o hasta que muera el depredador) con la semilla indicada.
+pub fn correr(parametros: Parametros, dias: u32, semilla: u64) -> Corrida {
+    let mut sim = SimulacionBuilder::new().parametros(parametros.clone()).semilla(semilla).retencion(Retencion::DESACTIVADA).construir();
+    let mut filas = Vec::with_capacity(dias as usize);
+    for _ in 0..dias {
+        if !sim.depredador.vivo {
+            break;
+        }
+        sim.avanzar_dia();
+        filas.push(RegistroDiario::capturar(&sim));
+    }
+    Corrida { parametros, semilla, filas, depredador_vivo: sim.depredador.vivo }
+}
+
+/// Corre la simulación con los parámetros por defecto.
+pub fn correr_por_defecto(dias: u32, semilla: u64) -> Corrida {
+    correr(Parametros::default(), dias, semilla)
+}
+
+impl Corrida {
+    /// Días simulados.
+    pub fn len(&self) -> usize {
+        self.filas.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.filas.is_empty()
+    }
+
+    /// Nombres de las columnas, en el mismo orden que el CSV diario.
+    pub fn columnas(&self) -> Vec<&'static str> {
+        registro::ENCABEZADO_CSV.split(',').collect()
+    }
+
+    /// Valores de una columna por su nombre (`dia`, `conejos`, `cabras`, ...).
+    pub fn columna(&self, nombre: &str) -> Option<Vec<f64>> {
+        let extraer: fn(&RegistroDiario) -> f64 = match nombre {
+            "dia" => |f| f.dia as f64,
+            "conejos" => |f| f.conejos as f64,
+            "cabras" => |f| f.cabras as f64,
+            "reserva_depredador_kg" => |f| f.reserva_depredador_kg,
+            "nacimientos" => |f| f.nacimientos as f64,
+            "muertes" => |f| f.muertes as f64,
+            "cazas" => |f| f.cazas as f64,
+            "pasto_kg" => |f| f.pasto_kg,
+            "presion_depredacion" => |f| f.presion_depredacion,
+            "presion_pastoreo" => |f| f.presion_pastoreo,
+            "indice_control" => |f| f.indice_control,
+            _ => return None,
+        };
+        Some(self.filas.iter().map(extraer).collect())
+    }
+
+    pub fn conejos(&self) -> Vec<usize> {
+        self.filas.iter().map(|f| f.conejos).collect()
+    }
+
+    pub fn cabras(&self) -> Vec<usize> {
+        self.filas.iter().map(|f| f.cabras).collect()
+    }
+
+    pub fn reserva_depredador(&self) -> Vec<f64> {
+        self.filas.iter().map(|f| f.reserva_depredador_kg).collect()
+    }
+
+    /// Métricas del último día simulado.
+    pub fn ultima(&self) -> Option<&RegistroDiario> {
+        self.filas.last()
+    }
+
+    /// Día en que murió el depredador, si ocurrió dentro de la corrida.
+    pub fn dia_muerte_depredador(&self) -> Option<u32> {
+        if self.depredador_vivo { None } else { self.ultima().map(|f| f.dia) }
+    }
+
+    /// La misma tabla en CSV, con la semilla y la huella de los parámetros como metadatos.
+    pub fn a_csv(&self) -> String {
+        let mut registro = Registro::new();
+        registro.anotar("semilla", self.semilla);
+        registro.anotar_parametros(&self.parametros);
+        registro.filas = self.filas.iter().cloned().collect();
+        registro.a_csv()
+    }
+
+    /// Representación enriquecida para evcxr: el notebook llama a este método al mostrar el valor.
+    pub fn evcxr_display(&self) {
+        let mut html = format!("<p>{}</p><table><tr>", html_escapar(&self.resumen()));
+        for columna in self.columnas() {
+            html.push_str(&format!("<th>{}</th>", columna));
+        }
+        html.push_str("</tr>");
+        for fila in self.vista_previa() {
+            html.push_str("<tr>");
+            match fila {
+                Some(fila) => {
+                    for celda in celdas(fila) {
+                        html.push_str(&format!("<td>{}</td>", celda));
+                    }
+                }
+                None => html.push_str(&format!("<td colspan=\"{}\">…</td>", self.columnas().len())),
+            }
+            html.push_str("</tr>");
+        }
+        html.push_str("</table>");
+        println!("EVCXR_BEGIN_CONTENT text/html\n{}\nEVCXR_END_CONTENT", html);
+    }
+
+    /// Una línea con lo esencial: duración, poblaciones finales y extremos.
+    pub fn resumen(&self) -> String {
+        let Some(ultima) = self.ultima() else {
+            return format!("Corrida vacía (semilla {})", self.semilla);
+        };
+        let max_conejos = self.conejos().into_iter().max().unwrap_or(0);
+        let max_cabras = self.cabras().into_iter().max().unwrap_or(0);
+        format!(
+            "Corrida de {} días (semilla {}): final {} conejos, {} cabras, reserva {:.1} kg{}; máximos {} conejos, {} cabras",
+            ultima.dia, self.semilla, ultima.conejos, ultima.cabras, ultima.reserva_depredador_kg,
+            if !self.depredador_vivo { " (depredador muerto)" } else { "" },
+            max_conejos, max_cabras
+        )
+    }
+
+    /// Primeras y últimas filas; `None` marca el corte intermedio.
+    fn vista_previa(&self) -> Vec<Option<&RegistroDiario>> {
+        if self.filas.len() <= 2 * FILAS_VISTA_PREVIA {
+            return self.filas.iter().map(Some).collect();
+        }
+        let inicio = self.filas[..FILAS_VISTA_PREVIA].iter().map(Some);
+        let fin = self.filas[self.filas.len() - FILAS_VISTA_PREVIA..].iter().map(Some);
+        inicio.chain(std::iter::once(None)).chain(fin).collect()
+    }
+}
+
+impl fmt::Display for Corrida {
+    /// Resumen seguido de una vista previa de la tabla, alineada en columnas.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{}", self.resumen())?;
+        let columnas = self.columnas();
+        let anchos: Vec<usize> = columnas.iter().map(|c| c.len().max(8)).collect();
+        for (columna, ancho) in columnas.iter().zip(&anchos) {
+            write!(f, "{:>ancho$} ", columna, ancho = ancho)?;
+        }
+        writeln!(f)?;
+        for fila in self.vista_previa() {
+            match fila {
+                Some(fila) => {
+                    for (celda, ancho) in celdas(fila).iter().zip(&anchos) {
+                        write!(f, "{:>ancho$} ", celda, ancho = ancho)?;
+                    }
+                    writeln!(f)?;
+                }
+                None => writeln!(f, "{:>ancho$}", "…", ancho = anchos[0])?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Los valores de una fila ya formateados, en el orden de las columnas.
+fn celdas(fila: &RegistroDiario) -> Vec<String> {
+    vec![
+        fila.dia.to_string(),
+        fila.conejos.to_string(),
+        fila.cabras.to_string(),
+        format!("{:.1}", fila.reserva_depredador_kg),
+        fila.nacimientos.to_string(),
+        fila.muertes.to_string(),
+        fila.cazas.to_string(),
+        format!("{:.0}", fila.pasto_kg),
+        format!("{:.3}", fila.presion_depredacion),
+        format!("{:.3}", fila.presion_pastoreo),
+        format!("{:+.2}", fila.indice_control),
+    ]
+}
+
+fn html_escapar(texto: &str) -> String {
+    texto.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;")
+}
@@ -0,0 +1,112 @@
+// src/desafio.rs
+
+// Modo desafío: una capa de puntuación sobre la simulación para que los estudiantes compitan
+// gestionando el ecosistema. Los objetivos se fijan en la tabla `[desafio]` del escenario:
+//
+//   - Sobrevivir `dias` días con el depredador vivo (el desafío termina al llegar o al morir).
+//   - Mantener ambas especies por encima de `minimo_conejos` y `minimo_cabras`: cada día en
+//     equilibrio suma puntos.
+//   - Cosechar biomasa: cada kg de presa retirada a mano (modo de edición) suma puntos.
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::eventos::Evento;
+use crate::simulacion::Simulacion;
+
+/// Puntos por cada día con ambas especies por encima de su umbral.
+pub const PUNTOS_DIA_EQUILIBRIO: u32 = 10;
+/// Puntos por cada kg de biomasa cosechada.
+pub const PUNTOS_KG_COSECHADO: f64 = 1.0;
+/// Bonificación por llegar al último día con el depredador vivo.
+pub const PUNTOS_SUPERADO: u32 = 500;
+
+/// Objetivos del desafío (tabla `[desafio]` del escenario).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(default, deny_unknown_fields)]
+pub struct Objetivos {
+    /// Días que hay que sobrevivir.
+    pub dias: u32,
+    /// Conejos por debajo de los cuales el día no cuenta como equilibrado.
+    pub minimo_conejos: usize,
+    /// Cabras por debajo de las cuales el día no cuenta como equilibrado.
+    pub minimo_cabras: usize,
+}
+
+impl Default for Objetivos {
+    fn default() -> Self {
+        Self { dias: 365, minimo_conejos: 20, minimo_cabras: 5 }
+    }
+}
+
+/// Cómo terminó el desafío.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Desenlace {
+    /// Se llegó al último día con el depredador vivo.
+    Superado,
+    /// El depredador murió antes de tiempo.
+    DepredadorMuerto,
+}
+
+/// Marcador de un desafío en curso.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Desafio {
+    pub objetivos: Objetivos,
+    /// Días completados con ambas especies por encima de su umbral.
+    pub dias_equilibrio: u32,
+    /// Peso total de las presas retiradas a mano.
+    pub biomasa_cosechada_kg: f64,
+    /// Día en que terminó y cómo; `None` mientras sigue en curso.
+    pub terminado: Option<(u32, Desenlace)>,
+}
+
+impl Desafio {
+    pub fn new(objetivos: Objetivos) -> Self {
+        Self { objetivos, dias_equilibrio: 0, biomasa_cosechada_kg: 0.0, terminado: None }
+    }
+
+    /// Anota la biomasa de las presas retiradas a mano; se llama con los eventos de cada
+    /// intervención, que ocurren entre un día y el siguiente.
+    pub fn anotar_eventos<'a>(&mut self, eventos: impl IntoIterator<Item = &'a Evento>) {
+        if self.terminado.is_some() {
+            return;
+        }
+        for evento in eventos {
+            if let Evento::PresaRetirada { peso_kg, .. } = evento {
+                self.biomasa_cosechada_kg += peso_kg;
+            }
+        }
+    }
+
+    /// Puntúa el día que la simulación acaba de completar y comprueba si el desafío terminó.
+    /// Devuelve el desenlace el día en que se produce.
+    pub fn dia_completado(&mut self, sim: &Simulacion) -> Option<Desenlace> {
+        if self.terminado.is_some() {
+            return None;
+        }
+        let (conejos, cabras) = sim.contar_especies();
+        if sim.depredador.vivo && conejos >= self.objetivos.minimo_conejos && cabras >= self.objetivos.minimo_cabras {
+            self.dias_equilibrio += 1;
+        }
+        let desenlace = if !sim.depredador.vivo {
+            Desenlace::DepredadorMuerto
+        } else if sim.dia >= self.objetivos.dias {
+            Desenlace::Superado
+        } else {
+            return None;
+        };
+        self.terminado = Some((sim.dia, desenlace));
+        Some(desenlace)
+    }
+
+    /// Puntuación acumulada (la bonificación solo cuenta si se superó).
+    pub fn puntos(&self) -> u32 {
+        let bonificacion = match self.terminado {
+            Some((_, Desenlace::Superado)) => PUNTOS_SUPERADO,
+            _ => 0,
+        };
+        self.dias_equilibrio * PUNTOS_DIA_EQUILIBRIO
+            + (self.biomasa_cosechada_kg * PUNTOS_KG_COSECHADO).round() as u32
+            + bonificacion
+    }
+}
@@ -0,0 +1,122 @@
+// src/determinista.rs
+
+// Esqueleto determinista de la simulación: las mismas reglas con cada sorteo sustituido por su
+// valor esperado, de modo que las poblaciones pasan a ser cantidades continuas (puede haber
+// 12,4 conejos). Avanza día a día el modelo de campo medio de `analisis` y lleva aparte la
+// reserva del depredador, que consume y caza como en la simulación individual.
+// Sirve para comparar, con parámetros idénticos, la tendencia de fondo con las corridas
+// estocásticas: lo que el esqueleto no muestra (extinciones por azar, oscilaciones que se
+// sostienen solas) es obra de la estocasticidad demográfica.
+//
+// Simplificaciones: la carne sobrante no pasa por la despensa (se asimila entera el mismo día)
+// y el pastizal se trata como un único recurso bien mezclado.
+
+use serde::Serialize;
+
+use crate::analisis::ModeloCampoMedio;
+use crate::parametros::Parametros;
+
+/// Encabezado del CSV del esqueleto; las columnas coinciden con las del CSV diario.
+pub const ENCABEZADO_CSV: &str = "dia,conejos,cabras,reserva_depredador_kg,pasto_kg";
+
+/// Estado del esqueleto al terminar un día.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct DiaDeterminista {
+    pub dia: u32,
+    pub conejos: f64,
+    pub cabras: f64,
+    pub reserva_depredador_kg: f64,
+    /// Pasto disponible (kg); 0 fuera del modo de cadena trófica.
+    pub pasto_kg: f64,
+}
+
+/// Corrida del esqueleto determinista.
+#[derive(Debug, Clone)]
+pub struct SimulacionDeterminista {
+    modelo: ModeloCampoMedio,
+    /// Conejos, cabras y, en el modo de cadena trófica, pasto.
+    estado: Vec<f64>,
+    pub dia: u32,
+    pub reserva_depredador_kg: f64,
+    pub depredador_vivo: bool,
+}
+
+impl SimulacionDeterminista {
+    /// Parte de las poblaciones y la reserva iniciales de los parámetros.
+    pub fn new(parametros: &Parametros) -> Self {
+        let modelo = ModeloCampoMedio::new(parametros);
+        let mut estado = vec![parametros.n_conejos_inicial as f64, parametros.n_cabras_inicial as f64];
+        if modelo.dimension() == 3 {
+            estado.push(parametros.cadena_trofica.pasto_inicial_kg);
+        }
+        Self { modelo, estado, dia: 0, reserva_depredador_kg: parametros.depredador_reserva_inicial_kg, depredador_vivo: true }
+    }
+
+    /// Avanza un día con el mismo orden que la simulación individual: el depredador consume
+    /// su reserva y caza, y después las presas (y el pasto) cambian según su valor esperado.
+    /// Como en aquella, con el depredador muerto ya no avanza.
+    pub fn avanzar_dia(&mut self) {
+        if !self.depredador_vivo {
+            return;
+        }
+        self.dia += 1;
+        let parametros = self.modelo.parametros();
+        let (minimo, optimo) = parametros.consumo_depredador_kg();
+        if self.reserva_depredador_kg >= optimo {
+            self.reserva_depredador_kg -= optimo;
+        } else if self.reserva_depredador_kg >= minimo {
+            self.reserva_depredador_kg -= minimo;
+        } else {
+            self.depredador_vivo = false;
+        }
+        if self.depredador_vivo {
+            let (caza_conejos, caza_cabras) = self.modelo.capturas(self.estado[0], self.estado[1]);
+            let cazado = caza_conejos * self.modelo.conejos.peso_medio_kg + caza_cabras * self.modelo.cabras.peso_medio_kg;
+            self.reserva_depredador_kg = (self.reserva_depredador_kg - parametros.coste_caza_kg()).max(0.0)
+                + cazado * parametros.energia.eficiencia_asimilacion;
+        }
+        let derivadas = self.modelo.derivadas(&self.estado);
+        for (x, dx) in self.estado.iter_mut().zip(derivadas) {
+            *x = (*x + dx).max(0.0);
+        }
+    }
+
+    /// Estado del último día simulado.
+    pub fn dia_actual(&self) -> DiaDeterminista {
+        DiaDeterminista {
+            dia: self.dia,
+            conejos: self.estado[0],
+            cabras: self.estado[1],
+            reserva_depredador_kg: self.reserva_depredador_kg,
+            pasto_kg: self.estado.get(2).copied().unwrap_or(0.0),
+        }
+    }
+
+    /// Corre `dias` días (o hasta que muera el depredador) y devuelve la serie diaria.
+    pub fn correr(parametros: &Parametros, dias: u32) -> Vec<DiaDeterminista> {
+        let mut sim = Self::new(parametros);
+        let mut filas = Vec::with_capacity(dias as usize);
+        for _ in 0..dias {
+            if !sim.depredador_vivo {
+                break;
+            }
+            sim.avanzar_dia();
+            filas.push(sim.dia_actual());
+        }
+        filas
+    }
+}
+
+/// La serie en CSV, con el mismo formato de metadatos que el CSV diario.
+pub fn a_csv(metadatos: &[(String, String)], filas: &[DiaDeterminista]) -> String {
+    let mut csv = String::new();
+    for (clave, valor) in metadatos {
+        csv.push_str(&format!("# {}={}\n", clave, valor));
+    }
+    csv.push_str(ENCABEZADO_CSV);
+    csv.push('\n');
+    for f in filas {
+        csv.push_str(&format!("{},{:.4},{:.4},{:.3},{:.1}\n", f.dia, f.conejos, f.cabras, f.reserva_depredador_kg, f.pasto_kg));
+    }
+    csv
+}
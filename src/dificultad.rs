@@ -0,0 +1,61 @@
+// src/dificultad.rs
+
+// Niveles de dificultad para los modos interactivos (p. ej. en clase con distintas edades).
+// Cada nivel escala a la vez el consumo del depredador y la tasa de enfermedad respecto a los
+// parámetros del escenario: en "difícil" el depredador necesita más comida y las presas
+// enferman más, así que mantener el equilibrio exige intervenir con más cuidado.
+// Todavía no hay catástrofes en el modelo; cuando las haya, su frecuencia se escalará aquí.
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::parametros::Parametros;
+
+/// Nivel de dificultad.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum Dificultad {
+    Facil,
+    #[default]
+    Normal,
+    Dificil,
+}
+
+impl Dificultad {
+    pub const TODAS: [Dificultad; 3] = [Dificultad::Facil, Dificultad::Normal, Dificultad::Dificil];
+
+    /// Interpreta el nombre de un nivel (`facil`, `normal`, `dificil`).
+    pub fn desde_nombre(nombre: &str) -> Result<Self, String> {
+        match nombre.to_lowercase().as_str() {
+            "facil" | "fácil" | "easy" => Ok(Dificultad::Facil),
+            "normal" => Ok(Dificultad::Normal),
+            "dificil" | "difícil" | "hard" => Ok(Dificultad::Dificil),
+            otro => Err(format!("dificultad desconocida: {} (use facil, normal o dificil)", otro)),
+        }
+    }
+
+    /// Factores (consumo del depredador, probabilidad de enfermar) respecto a "normal".
+    fn factores(self) -> (f64, f64) {
+        match self {
+            Dificultad::Facil => (0.8, 0.5),
+            Dificultad::Normal => (1.0, 1.0),
+            Dificultad::Dificil => (1.25, 2.0),
+        }
+    }
+
+    /// Aplica este nivel a parámetros de dificultad normal.
+    pub fn aplicar(self, parametros: &mut Parametros) {
+        Dificultad::Normal.cambiar(self, parametros);
+    }
+
+    /// Pasa unos parámetros de este nivel a otro, deshaciendo los factores del actual.
+    pub fn cambiar(self, nueva: Dificultad, parametros: &mut Parametros) {
+        let (consumo_actual, enfermar_actual) = self.factores();
+        let (consumo, enfermar) = nueva.factores();
+        let consumo = consumo / consumo_actual;
+        parametros.depredador_consumo_minimo_diario_kg *= consumo;
+        parametros.depredador_consumo_optimo_diario_kg *= consumo;
+        parametros.metabolismo.coeficiente_kg *= consumo;
+        parametros.probabilidad_enfermar = (parametros.probabilidad_enfermar * enfermar / enfermar_actual).min(1.0);
+    }
+}
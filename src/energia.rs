@@ -0,0 +1,172 @@
+// src/energia.rs
+
+// Contabilidad energética de la cadena trófica. La simulación sigue llevando la comida en kg
+// (el peso de las presas, la reserva y la despensa del depredador), pero cada día anota los
+// flujos entre niveles en megajulios, con la conversión y las eficiencias de `Energia`.
+// Así se pueden comparar los niveles en la misma unidad y calcular eficiencias tróficas.
+// En el modo de cadena trófica el forraje es el pasto que comieron las presas; sin él no se
+// simula la vegetación y se estima a partir de su producción y su eficiencia de conversión.
+// También calcula, con los mismos flujos, las métricas de cascada trófica del día: cuánto
+// aprieta el depredador a las presas, cuánto aprietan las presas al pasto y cuál de los dos
+// controles domina.
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::parametros::Energia;
+
+/// Megajulios por kg de pasto fresco, para el forraje comido en el modo de cadena trófica.
+pub const MJ_POR_KG_PASTO: f64 = 4.0;
+
+/// Flujos de energía (MJ) de un periodo: un día o el acumulado de una corrida.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct BalanceEnergia {
+    /// Pasto que comieron las presas o, si no se simula, el que hace falta para sostener su
+    /// producción (estimado).
+    pub forraje_mj: f64,
+    /// Crecimiento de las presas más el peso de las crías nacidas.
+    pub produccion_presas_mj: f64,
+    /// Presas muertas por causas distintas de la caza (vejez, enfermedad, brotes, densidad).
+    pub muertes_presas_mj: f64,
+    /// Presas cazadas por el depredador.
+    pub cazado_mj: f64,
+    /// Parte de lo comido que pasó a la reserva del depredador.
+    pub asimilado_mj: f64,
+    /// Carne de la despensa que se pudrió.
+    pub podrido_mj: f64,
+    /// Reserva que el depredador gastó en mantenerse y en cazar.
+    pub metabolizado_mj: f64,
+}
+
+/// Flujos del día en kg, tal como los mide la simulación.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct FlujosKg {
+    /// Biomasa de las presas al empezar el día.
+    pub biomasa_presas: f64,
+    /// Biomasa de las presas que el depredador podía cazar (las de su dieta y a su alcance).
+    pub cazable: f64,
+    /// Pasto disponible tras el rebrote, antes de que coman las presas.
+    pub pasto_disponible: f64,
+    /// Pasto que necesitaban las presas para comer su ración completa.
+    pub racion_pasto: f64,
+    /// Pasto que comieron.
+    pub pastado: f64,
+    /// Si hoy se simuló el pasto (modo de cadena trófica); si no, los tres campos anteriores son 0.
+    pub con_pasto: bool,
+    pub produccion_presas: f64,
+    pub muertes_presas: f64,
+    pub cazado: f64,
+    pub asimilado: f64,
+    pub podrido: f64,
+    pub metabolizado: f64,
+}
+
+impl BalanceEnergia {
+    /// Convierte los flujos de un día de kg a megajulios.
+    pub fn desde_kg(flujos: &FlujosKg, energia: &Energia) -> Self {
+        let mj = |kg: f64| kg * energia.mj_por_kg;
+        let produccion = mj(flujos.produccion_presas);
+        let forraje_mj = if flujos.con_pasto {
+            flujos.pastado * MJ_POR_KG_PASTO
+        } else if energia.eficiencia_pasto_presa > 0.0 {
+            produccion / energia.eficiencia_pasto_presa
+        } else {
+            0.0
+        };
+        Self {
+            forraje_mj,
+            produccion_presas_mj: produccion,
+            muertes_presas_mj: mj(flujos.muertes_presas),
+            cazado_mj: mj(flujos.cazado),
+            asimilado_mj: mj(flujos.asimilado),
+            podrido_mj: mj(flujos.podrido),
+            metabolizado_mj: mj(flujos.metabolizado),
+        }
+    }
+
+    /// Suma otro balance a este (para acumular los días de una corrida).
+    pub fn sumar(&mut self, otro: &BalanceEnergia) {
+        self.forraje_mj += otro.forraje_mj;
+        self.produccion_presas_mj += otro.produccion_presas_mj;
+        self.muertes_presas_mj += otro.muertes_presas_mj;
+        self.cazado_mj += otro.cazado_mj;
+        self.asimilado_mj += otro.asimilado_mj;
+        self.podrido_mj += otro.podrido_mj;
+        self.metabolizado_mj += otro.metabolizado_mj;
+    }
+
+    /// Eficiencia trófica presas → depredador: energía asimilada por producción de presas.
+    pub fn eficiencia_trofica(&self) -> Option<f64> {
+        cociente(self.asimilado_mj, self.produccion_presas_mj)
+    }
+
+    /// Fracción de la producción de presas que acaba cazada.
+    pub fn eficiencia_explotacion(&self) -> Option<f64> {
+        cociente(self.cazado_mj, self.produccion_presas_mj)
+    }
+
+    /// Fracción de lo cazado que el depredador llega a asimilar (el resto se pierde al comer o se pudre).
+    pub fn eficiencia_aprovechamiento(&self) -> Option<f64> {
+        cociente(self.asimilado_mj, self.cazado_mj)
+    }
+}
+
+/// Métricas de cascada trófica de un día; 0 cuando no hay de qué calcularlas.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize)]
+pub struct MetricasTroficas {
+    /// Fracción de la biomasa cazable que se llevó el depredador.
+    pub presion_depredacion: f64,
+    /// Fracción del pasto disponible que comieron las presas (solo en el modo de cadena trófica).
+    pub presion_pastoreo: f64,
+    /// Entre -1 (control desde abajo: a las presas las limita el pasto) y 1 (control desde
+    /// arriba: las limita el depredador). Compara la fracción de la biomasa de presas que se
+    /// cazó con la fracción de su ración de pasto que no encontraron.
+    pub indice_control: f64,
+}
+
+impl MetricasTroficas {
+    pub fn desde_kg(flujos: &FlujosKg) -> Self {
+        let arriba = cociente(flujos.cazado, flujos.biomasa_presas).unwrap_or(0.0);
+        let abajo = cociente(flujos.racion_pasto - flujos.pastado, flujos.racion_pasto).unwrap_or(0.0);
+        Self {
+            presion_depredacion: cociente(flujos.cazado, flujos.cazable).unwrap_or(0.0),
+            presion_pastoreo: cociente(flujos.pastado, flujos.pasto_disponible).unwrap_or(0.0),
+            indice_control: cociente(arriba - abajo, arriba + abajo).unwrap_or(0.0),
+        }
+    }
+}
+
+fn cociente(numerador: f64, denominador: f64) -> Option<f64> {
+    (denominador > 0.0).then(|| numerador / denominador)
+}
+
+impl fmt::Display for BalanceEnergia {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let porcentaje = |valor: Option<f64>| valor.map_or("-".to_string(), |v| format!("{:.1} %", v * 100.0));
+        writeln!(f, "  Forraje:                 {:>12.1} MJ", self.forraje_mj)?;
+        writeln!(f, "  Producción de presas:    {:>12.1} MJ", self.produccion_presas_mj)?;
+        writeln!(f, "  Muertes no depredadas:   {:>12.1} MJ", self.muertes_presas_mj)?;
+        writeln!(f, "  Cazado:                  {:>12.1} MJ", self.cazado_mj)?;
+        writeln!(f, "  Asimilado:               {:>12.1} MJ", self.asimilado_mj)?;
+        writeln!(f, "  Podrido en la despensa:  {:>12.1} MJ", self.podrido_mj)?;
+        writeln!(f, "  Metabolizado:            {:>12.1} MJ", self.metabolizado_mj)?;
+        writeln!(f, "  Eficiencia trófica:      {:>12}", porcentaje(self.eficiencia_trofica()))?;
+        writeln!(f, "  Explotación de presas:   {:>12}", porcentaje(self.eficiencia_explotacion()))?;
+        write!(f, "  Aprovechamiento:         {:>12}", porcentaje(self.eficiencia_aprovechamiento()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn el_forraje_es_el_pasto_comido_cuando_se_simula() {
+        let energia = Energia::default();
+        let estimado = FlujosKg { produccion_presas: 2.0, pastado: 30.0, ..FlujosKg::default() };
+        assert_eq!(BalanceEnergia::desde_kg(&estimado, &energia).forraje_mj, 2.0 * energia.mj_por_kg / energia.eficiencia_pasto_presa);
+        let medido = FlujosKg { con_pasto: true, ..estimado };
+        assert_eq!(BalanceEnergia::desde_kg(&medido, &energia).forraje_mj, 30.0 * MJ_POR_KG_PASTO);
+    }
+}
@@ -0,0 +1,70 @@
+// src/enfermedad.rs
+
+// Enfermedad contagiosa entre las presas de una misma especie, con un modelo SIR: cada presa
+// es susceptible, está infectada o se ha recuperado (y es inmune). Cada día, una susceptible se
+// contagia con una probabilidad que crece con la fracción de infectadas de su especie; una
+// infectada puede morir o recuperarse, y una recuperada puede volver a ser susceptible, lo que
+// produce olas sucesivas. Sustituye a la probabilidad fija de enfermar (ver `Enfermedad`).
+
+use serde::{Deserialize, Serialize};
+
+use crate::parametros::Enfermedad;
+
+/// Estado de una presa frente a la enfermedad.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Salud {
+    #[default]
+    Susceptible,
+    Infectada,
+    Recuperada,
+}
+
+/// Presas de una especie en cada estado.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RecuentoSir {
+    pub susceptibles: u32,
+    pub infectadas: u32,
+    pub recuperadas: u32,
+}
+
+impl RecuentoSir {
+    pub fn anotar(&mut self, salud: Salud) {
+        match salud {
+            Salud::Susceptible => self.susceptibles += 1,
+            Salud::Infectada => self.infectadas += 1,
+            Salud::Recuperada => self.recuperadas += 1,
+        }
+    }
+
+    pub fn total(&self) -> u32 {
+        self.susceptibles + self.infectadas + self.recuperadas
+    }
+
+    /// Fracción de la especie que está infectada (0 si no queda ninguna presa).
+    pub fn fraccion_infectada(&self) -> f64 {
+        match self.total() {
+            0 => 0.0,
+            total => self.infectadas as f64 / total as f64,
+        }
+    }
+
+    /// Probabilidad diaria de que una susceptible se contagie: `1 − e^(−transmision × I / N)`.
+    pub fn probabilidad_contagio(&self, enfermedad: &Enfermedad) -> f64 {
+        1.0 - (-enfermedad.transmision * self.fraccion_infectada()).exp()
+    }
+
+    /// Si la especie está en plena ola. Empieza cuando la fracción infectada llega al umbral de
+    /// la tabla y no termina hasta que baja de la mitad, para que una población pequeña en que
+    /// entra y sale una infectada no encadene olas día tras día.
+    pub fn en_ola(&self, enfermedad: &Enfermedad, ya_en_ola: bool) -> bool {
+        let umbral = if ya_en_ola { enfermedad.umbral_ola / 2.0 } else { enfermedad.umbral_ola };
+        self.infectadas > 0 && self.fraccion_infectada() >= umbral
+    }
+}
+
+/// Número reproductivo básico: contagios que causa una infectada en una población toda
+/// susceptible durante su enfermedad. Por encima de 1 la enfermedad se propaga.
+pub fn r0(enfermedad: &Enfermedad) -> f64 {
+    let salida = enfermedad.recuperacion + enfermedad.mortalidad;
+    if salida > 0.0 { enfermedad.transmision / salida } else { f64::INFINITY }
+}
@@ -0,0 +1,90 @@
+// src/enlace.rs
+
+// Escenarios compartibles como fragmento de URL, para la versión web.
+// La semilla y los parámetros que difieren de los valores por defecto se escriben como
+// `#semilla=42&CONEJO_TASA_REPRODUCCION_DIARIA=0.08&CABRA_CRIAS_POR_PARTO.max=3`, así que un
+// enlace reproduce exactamente la misma corrida en cualquier navegador y sigue siendo legible.
+
+use serde_json::Value;
+
+use crate::configuracion;
+use crate::parametros::Parametros;
+
+/// Clave del fragmento que lleva la semilla.
+const CLAVE_SEMILLA: &str = "semilla";
+
+/// Un escenario leído desde un fragmento de URL.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EscenarioEnlace {
+    pub semilla: Option<u64>,
+    pub parametros: Parametros,
+}
+
+/// Codifica la semilla y los parámetros distintos de los por defecto como fragmento (sin `#`).
+pub fn codificar(semilla: u64, parametros: &Parametros) -> String {
+    let mut partes = vec![format!("{}={}", CLAVE_SEMILLA, semilla)];
+    let mut diferencias = Vec::new();
+    recoger_diferencias("", &parametros.a_arbol(), &Parametros::default().a_arbol(), &mut diferencias);
+    for (nombre, valor) in diferencias {
+        partes.push(format!("{}={}", nombre, escapar(&valor.to_string())));
+    }
+    partes.join("&")
+}
+
+/// Interpreta un fragmento (con o sin `#` inicial). Los parámetros no mencionados
+/// toman su valor por defecto; el resultado se valida como cualquier configuración.
+pub fn decodificar(fragmento: &str) -> Result<EscenarioEnlace, String> {
+    let mut semilla = None;
+    let mut asignaciones = Vec::new();
+    for parte in fragmento.trim_start_matches('#').split('&').filter(|p| !p.is_empty()) {
+        let parte = desescapar(parte)?;
+        match parte.split_once('=') {
+            Some((CLAVE_SEMILLA, valor)) => semilla = Some(valor.parse().map_err(|_| format!("semilla inválida: '{}'", valor))?),
+            Some(_) => asignaciones.push(parte),
+            None => return Err(format!("se esperaba NOMBRE=VALOR en el enlace, se recibió '{}'", parte)),
+        }
+    }
+    let parametros = configuracion::parametros_en_capas(Parametros::default(), std::iter::empty(), &asignaciones)?;
+    Ok(EscenarioEnlace { semilla, parametros })
+}
+
+/// Recorre ambos árboles y anota las hojas cuyo valor difiere (`GRUPO.campo` en las anidadas).
+fn recoger_diferencias(prefijo: &str, actual: &Value, por_defecto: &Value, diferencias: &mut Vec<(String, Value)>) {
+    match (actual, por_defecto) {
+        (Value::Object(campos), Value::Object(campos_defecto)) => {
+            for (nombre, valor) in campos {
+                let ruta = if prefijo.is_empty() { nombre.clone() } else { format!("{}.{}", prefijo, nombre) };
+                recoger_diferencias(&ruta, valor, campos_defecto.get(nombre).unwrap_or(&Value::Null), diferencias);
+            }
+        }
+        _ if actual != por_defecto => diferencias.push((prefijo.to_string(), actual.clone())),
+        _ => {}
+    }
+}
+
+/// Codifica con `%XX` los caracteres que no pueden ir tal cual en un fragmento.
+fn escapar(texto: &str) -> String {
+    texto.bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'+' => (b as char).to_string(),
+            otro => format!("%{:02X}", otro),
+        })
+        .collect()
+}
+
+fn desescapar(texto: &str) -> Result<String, String> {
+    let bytes = texto.as_bytes();
+    let mut salida = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' {
+            let hex = texto.get(i + 1..i + 3).ok_or_else(|| format!("escape incompleto en '{}'", texto))?;
+            salida.push(u8::from_str_radix(hex, 16).map_err(|_| format!("escape inválido: %{}", hex))?);
+            i += 3;
+        } else {
+            salida.push(bytes[i]);
+            i += 1;
+        }
+    }
+    String::from_utf8(salida).map_err(|_| format!("texto no UTF-8 en '{}'", texto))
+}
@@ -1,243 +1,727 @@
 // src/entidades.rs
 
 // Este módulo define todas las entidades de la simulación y sus reglas.
-// Contiene las "clases base" (traits), las implementaciones concretas (structs),
-// y los parámetros que gobiernan el ecosistema.
+// Contiene las "clases base" (traits) y las implementaciones concretas (structs).
+// Los parámetros que gobiernan el ecosistema viven en el módulo `parametros`.
 
 use rand::{Rng, seq::SliceRandom};
-use rand::rngs::ThreadRng; // Se importa el tipo concreto de generador de números aleatorios.
+use serde::{Deserialize, Serialize};
+use rand_chacha::ChaCha8Rng; // Tipo concreto de generador: sembrable, portable y serializable.
 
-// =================================================
-// PARÁMETROS GLOBALES DE LA SIMULACIÓN
-// Estas constantes actúan como "perillas" para ajustar el comportamiento del ecosistema.
-// =================================================
-
-// --- Población Inicial (AJUSTADO) ---
-pub const N_CONEJOS_INICIAL: u32 = 60; 
-pub const N_CABRAS_INICIAL: u32 = 25;
-
-// --- Parámetros del Depredador ---
-pub const DEPREDADOR_RESERVA_INICIAL_KG: f64 = 900.0; 
-pub const DEPREDADOR_CONSUMO_MINIMO_DIARIO_KG: f64 = 3.0;
-pub const DEPREDADOR_CONSUMO_OPTIMO_DIARIO_KG: f64 = 5.0;
-
-// --- Parámetros de CONEJO (AJUSTADO) ---
-const CONEJO_EDAD_MAXIMA_DIAS: u32 = 1825;
-const CONEJO_EDAD_REPRODUCTIVA_DIAS: u32 = 100;
-const CONEJO_EDAD_SACRIFICIO_DIAS: u32 = 150;  
-const CONEJO_TASA_REPRODUCCION_DIARIA: f64 = 0.05;
-const CONEJO_CRIAS_POR_PARTO: (u32, u32) = (3, 6);
-
-// --- Parámetros de CABRA (AJUSTADO) ---
-const CABRA_EDAD_MAXIMA_DIAS: u32 = 5475;
-const CABRA_EDAD_REPRODUCTIVA_DIAS: u32 = 300;
-const CABRA_EDAD_SACRIFICIO_DIAS: u32 = 250;  
-const CABRA_TASA_REPRODUCCION_DIARIA: f64 = 0.01;
-const CABRA_CRIAS_POR_PARTO: (u32, u32) = (1, 2);
-
-// --- Probabilidades Comunes ---
-const PROBABILIDAD_ENFERMAR: f64 = 0.001;
-const PROBABILIDAD_NACER_MACHO: f64 = 0.5;
+use crate::espacio::Posicion;
+use crate::parametros::{CadenaTrofica, CurvaCrecimiento, EstrategiaCaza, Parametros, RangoCrias};
 
 // =================================================
 // DEFINICIONES DE TIPOS (ENUMS, STRUCTS, TRAITS)
 // =================================================
 
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub enum Sexo { Macho, Hembra }
 
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub enum Especie { Conejo, Cabra }
 
+/// De qué murió una presa. `Vejez` es llegar a la edad máxima y `MortalidadClase` la
+/// mortalidad propia de cada clase de edad (ver `ClasesEdad`); `Sobrepoblacion` es la válvula
+/// de `POBLACION_MAXIMA`, que la simulación aplica por su cuenta.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CausaMuerte { Vejez, Enfermedad, Hambre, Caza, Sobrepoblacion, MortalidadClase }
+
+impl CausaMuerte {
+    pub const TODAS: [CausaMuerte; 6] = [
+        CausaMuerte::Vejez,
+        CausaMuerte::Enfermedad,
+        CausaMuerte::Hambre,
+        CausaMuerte::Caza,
+        CausaMuerte::Sobrepoblacion,
+        CausaMuerte::MortalidadClase,
+    ];
+}
+
+/// Etapa de la vida de una presa. Los límites se derivan de las edades de cada especie:
+/// juvenil hasta la mitad de la edad reproductiva, subadulto hasta alcanzarla, adulto hasta
+/// el 80 % de la edad máxima y senescente a partir de ahí.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ClaseEdad { Juvenil, Subadulto, Adulto, Senescente }
+
+impl ClaseEdad {
+    pub const TODAS: [ClaseEdad; 4] = [ClaseEdad::Juvenil, ClaseEdad::Subadulto, ClaseEdad::Adulto, ClaseEdad::Senescente];
+}
+
+/// Preñez de una hembra: las crías que lleva y los días que faltan para el parto.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Gestacion {
+    pub dias_restantes: u32,
+    pub crias: u32,
+}
+
+/// Rasgos de vida de una especie tal como están en los parámetros vigentes. Es todo lo que
+/// distingue a un conejo de una cabra (ver `PresaGenerica` y `DefinicionEspecie`).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RasgosEspecie {
+    pub edad_maxima_dias: u32,
+    pub edad_reproductiva_dias: u32,
+    pub edad_sacrificio_dias: u32,
+    pub tasa_reproduccion_diaria: f64,
+    pub crias_por_parto: RangoCrias,
+    pub crecimiento: CurvaCrecimiento,
+    pub gestacion_dias: u32,
+}
+
+impl Especie {
+    /// Rasgos de vida de la especie según los parámetros.
+    pub fn rasgos(self, params: &Parametros) -> RasgosEspecie {
+        let p = params;
+        match self {
+            Especie::Conejo => RasgosEspecie {
+                edad_maxima_dias: p.conejo_edad_maxima_dias,
+                edad_reproductiva_dias: p.conejo_edad_reproductiva_dias,
+                edad_sacrificio_dias: p.conejo_edad_sacrificio_dias,
+                tasa_reproduccion_diaria: p.conejo_tasa_reproduccion_diaria,
+                crias_por_parto: p.conejo_crias_por_parto,
+                crecimiento: p.conejo_crecimiento,
+                gestacion_dias: p.conejo_gestacion_dias,
+            },
+            Especie::Cabra => RasgosEspecie {
+                edad_maxima_dias: p.cabra_edad_maxima_dias,
+                edad_reproductiva_dias: p.cabra_edad_reproductiva_dias,
+                edad_sacrificio_dias: p.cabra_edad_sacrificio_dias,
+                tasa_reproduccion_diaria: p.cabra_tasa_reproduccion_diaria,
+                crias_por_parto: p.cabra_crias_por_parto,
+                crecimiento: p.cabra_crecimiento,
+                gestacion_dias: p.cabra_gestacion_dias,
+            },
+        }
+    }
+
+    /// Peso esperado de un individuo de esta especie a la edad indicada.
+    pub fn peso_a_edad(self, edad_dias: u32, params: &Parametros) -> f64 {
+        peso_gompertz(self.rasgos(params).crecimiento, edad_dias)
+    }
+
+    /// Clase de edad de un individuo de esta especie.
+    pub fn clase_edad(self, edad_dias: u32, params: &Parametros) -> ClaseEdad {
+        let rasgos = self.rasgos(params);
+        let (edad_reproductiva, edad_maxima) = (rasgos.edad_reproductiva_dias, rasgos.edad_maxima_dias);
+        if edad_dias < edad_reproductiva / 2 {
+            ClaseEdad::Juvenil
+        } else if edad_dias < edad_reproductiva {
+            ClaseEdad::Subadulto
+        } else if (edad_dias as u64) * 5 < (edad_maxima as u64) * 4 {
+            ClaseEdad::Adulto
+        } else {
+            ClaseEdad::Senescente
+        }
+    }
+}
+
+/// Probabilidad diaria de que el depredador pueda alcanzar a una presa (ver `Cazabilidad`):
+/// alta en las crías, mínima en los adultos en plenitud y creciente en la vejez, y mayor
+/// cuanto peor es su condición corporal (peso por debajo del esperado para su edad).
+pub fn vulnerabilidad(presa: &dyn Presa, params: &Parametros) -> f64 {
+    let c = &params.cazabilidad;
+    let especie = presa.especie();
+    let rasgos = especie.rasgos(params);
+    let (edad_reproductiva, edad_maxima) = (rasgos.edad_reproductiva_dias, rasgos.edad_maxima_dias);
+    let edad = presa.edad() as f64;
+    let inicio_vejez = edad_maxima as f64 * 0.8;
+    let por_edad = if edad < edad_reproductiva as f64 {
+        c.cria + (c.adulto - c.cria) * edad / edad_reproductiva as f64
+    } else if edad < inicio_vejez {
+        c.adulto
+    } else {
+        let avance = ((edad - inicio_vejez) / (edad_maxima as f64 - inicio_vejez).max(1.0)).min(1.0);
+        c.adulto + (c.vejez - c.adulto) * avance
+    };
+    let condicion = presa.peso() / especie.peso_a_edad(presa.edad(), params).max(f64::EPSILON);
+    (por_edad * (1.0 + c.peso_condicion * (1.0 - condicion).max(0.0))).clamp(0.0, 1.0)
+}
+
+/// Probabilidad de que una caza de esta presa salga bien (ver `ExitoCaza`).
+pub fn probabilidad_exito(presa: &dyn Presa, params: &Parametros) -> f64 {
+    let e = &params.exito_caza;
+    let base = match presa.especie() {
+        Especie::Conejo => e.conejo,
+        Especie::Cabra => e.cabra,
+    };
+    (base + (1.0 - base) * vulnerabilidad(presa, params)).clamp(0.0, 1.0)
+}
+
+/// Sorteo de la mortalidad propia de la clase de edad (ver `ClasesEdad`). Sin mortalidad
+/// configurada no se consume el generador, para no alterar corridas ya guardadas.
+fn muere_por_clase(especie: Especie, edad_dias: u32, params: &Parametros, rng: &mut ChaCha8Rng) -> bool {
+    let mortalidad = params.clases_edad.de(especie.clase_edad(edad_dias, params)).mortalidad_diaria;
+    mortalidad > 0.0 && rng.gen_bool(mortalidad)
+}
+
+/// Sorteo de la muerte por hambre en el modo de cadena trófica (ver `CadenaTrofica`). Con la
+/// ración completa (siempre, fuera de ese modo) no se consume el generador.
+fn muere_de_hambre(saciedad: f64, params: &Parametros, rng: &mut ChaCha8Rng) -> bool {
+    saciedad < 1.0 && rng.gen_bool((params.cadena_trofica.mortalidad_hambre * (1.0 - saciedad)).min(1.0))
+}
+
+/// Peso tras un día de crecimiento, de `ayer_kg` a `objetivo_kg` en la curva de su especie: con
+/// la ración completa la sigue; con menos comida, solo recorre esa fracción del camino hacia ella
+/// y además quema reservas. Una presa rezagada no alcanza la curva de golpe: cada día gana como
+/// mucho lo que crece la curva más `recuperacion_peso_diaria` de su propio peso.
+fn crecer(peso_kg: f64, ayer_kg: f64, objetivo_kg: f64, saciedad: f64, cadena: &CadenaTrofica) -> f64 {
+    let saciedad = saciedad.min(1.0);
+    let hambre = 1.0 - saciedad;
+    let tope = (objetivo_kg - ayer_kg).max(0.0) + peso_kg * cadena.recuperacion_peso_diaria;
+    let subida = (objetivo_kg - peso_kg).max(0.0).min(tope);
+    (peso_kg + subida * saciedad - peso_kg * cadena.perdida_peso_hambre * hambre).max(0.0)
+}
+
+/// Si una presa de `peso_kg` está tan por debajo del peso de su edad que muere de inanición.
+/// Sin desnutrición (siempre, fuera del modo de cadena trófica) el peso es el de la curva y
+/// nunca se cumple.
+fn muere_de_inanicion(peso_kg: f64, objetivo_kg: f64, params: &Parametros) -> bool {
+    peso_kg < objetivo_kg * params.cadena_trofica.peso_minimo_relativo
+}
+
+/// Sorteo diario de la muerte de una presa de `edad_dias` (ya cumplidos hoy). Las causas se
+/// sortean en el orden de siempre y la primera que se cumple evita sortear las demás. Con la
+/// enfermedad contagiosa activa, la de enfermedad se decide aparte (ver `enfermedad`).
+fn sortear_muerte(especie: Especie, edad_dias: u32, edad_maxima: u32, saciedad: f64, params: &Parametros, rng: &mut ChaCha8Rng) -> Option<CausaMuerte> {
+    if edad_dias > edad_maxima {
+        Some(CausaMuerte::Vejez)
+    } else if !params.enfermedad.activa && rng.gen_bool(params.probabilidad_enfermar) {
+        Some(CausaMuerte::Enfermedad)
+    } else if muere_por_clase(especie, edad_dias, params, rng) {
+        Some(CausaMuerte::MortalidadClase)
+    } else if muere_de_hambre(saciedad, params, rng) {
+        Some(CausaMuerte::Hambre)
+    } else {
+        None
+    }
+}
+
 /// El trait `Presa` define un "contrato" de comportamiento común para todas las presas.
 /// Esto permite el polimorfismo dinámico (tratar a Conejos y Cabras de la misma manera).
-pub trait Presa {
+/// Se exige `Send + Sync` para que una simulación pueda moverse entre hilos (p. ej. en un servidor).
+pub trait Presa: Send + Sync {
     // Métodos para acceder a los datos internos de forma segura.
-    fn id(&self) -> u32;
+    fn id(&self) -> u64;
     fn especie(&self) -> Especie;
     fn sexo(&self) -> Sexo;
     fn edad(&self) -> u32;
     fn peso(&self) -> f64;
     fn esta_viva(&self) -> bool;
+    /// De qué murió en su último día; `None` si sigue viva o si se restauró ya muerta.
+    fn causa_muerte(&self) -> Option<CausaMuerte>;
+    /// La preñez en curso; `None` si no está preñada.
+    fn gestacion(&self) -> Option<Gestacion>;
 
     // Métodos que modifican el estado de la presa.
-    fn envejecer(&mut self);
-    fn reproducirse(&self, rng: &mut ThreadRng, next_id: &mut u32) -> Vec<Box<dyn Presa>>;
+    /// `saciedad` es la fracción de su ración de pasto que encontró hoy (1 fuera del modo de cadena trófica).
+    fn envejecer(&mut self, params: &Parametros, saciedad: f64, rng: &mut ChaCha8Rng);
+    /// `con_macho` indica si la hembra tiene a su alcance un macho adulto de su especie.
+    fn reproducirse(&mut self, params: &Parametros, con_macho: bool, rng: &mut ChaCha8Rng, next_id: &mut u64) -> Vec<Box<dyn Presa>>;
 }
 
-/// Función de orden superior (concepto funcional) que actúa como una "fábrica".
-/// Crea y devuelve una clausura especializada para calcular el peso según la curva de Gompertz.
-fn crear_funcion_gompertz(peso_max: f64, tasa_crecimiento: f64, punto_inflexion: f64) -> Box<dyn Fn(u32) -> f64> {
-    Box::new(move |edad_dias| {
-        let t = edad_dias as f64;
-        let exponente_interno = -tasa_crecimiento * (t - punto_inflexion);
-        let exponente_externo = -f64::exp(exponente_interno);
-        peso_max * f64::exp(exponente_externo)
-    })
+/// Toma el siguiente id de presa del contador y lo avanza. El último valor, `u64::MAX`, nunca
+/// se asigna: marca que los ids se agotaron, y desde entonces no nacen más presas en lugar de
+/// desbordar el contador o repetir un id. A un id por presa, no se alcanza en la práctica.
+pub fn asignar_id(next_id: &mut u64) -> Option<u64> {
+    let id = *next_id;
+    *next_id = id.checked_add(1)?;
+    Some(id)
 }
 
-// --- Implementación de CONEJO ---
+/// Peso según la curva de Gompertz a la edad indicada.
+fn peso_gompertz(curva: CurvaCrecimiento, edad_dias: u32) -> f64 {
+    let t = edad_dias as f64;
+    let exponente_interno = -curva.tasa * (t - curva.inflexion_dias);
+    let exponente_externo = -f64::exp(exponente_interno);
+    curva.peso_maximo_kg * f64::exp(exponente_externo)
+}
+
+// --- Implementación de las PRESAS ---
 
-/// Representa a un conejo individual en la simulación.
-pub struct Conejo {
-    id: u32,
+/// Una presa de cualquier especie. Sus edades, su reproducción y su crecimiento salen de los
+/// rasgos de la especie (ver `RasgosEspecie`), así que una especie nueva solo necesita su
+/// definición, no una struct propia.
+pub struct PresaGenerica {
+    id: u64,
+    especie: Especie,
     edad_dias: u32,
     peso_kg: f64,
     sexo: Sexo,
     vivo: bool,
-    crecimiento: Box<dyn Fn(u32) -> f64>,
-}
-
-impl Conejo {
-    /// Constructor para crear un nuevo Conejo.
-    pub fn new(id: u32, rng: &mut ThreadRng) -> Self {
-        let sexo = if rng.gen_bool(PROBABILIDAD_NACER_MACHO) { Sexo::Macho } else { Sexo::Hembra };
-        let crecimiento = crear_funcion_gompertz(5.0, 0.05, 90.0);
-        let peso_inicial = crecimiento(0);
-        Self { id, edad_dias: 0, peso_kg: peso_inicial, sexo, vivo: true, crecimiento }
-    }
+    causa_muerte: Option<CausaMuerte>,
+    gestacion: Option<Gestacion>,
 }
 
-/// Implementación del "contrato" `Presa` para la struct `Conejo`.
-impl Presa for Conejo {
-    fn id(&self) -> u32 { self.id }
-    fn especie(&self) -> Especie { Especie::Conejo }
-    fn sexo(&self) -> Sexo { self.sexo }
-    fn edad(&self) -> u32 { self.edad_dias }
-    fn peso(&self) -> f64 { self.peso_kg }
-    fn esta_viva(&self) -> bool { self.vivo }
-
-    /// Incrementa la edad, actualiza el peso y gestiona la muerte por vejez o enfermedad.
-    fn envejecer(&mut self) {
-        self.edad_dias += 1;
-        self.peso_kg = (self.crecimiento)(self.edad_dias);
-        if self.edad_dias > CONEJO_EDAD_MAXIMA_DIAS || rand::random::<f64>() < PROBABILIDAD_ENFERMAR {
-            self.vivo = false;
-        }
+impl PresaGenerica {
+    /// Constructor para crear una cría recién nacida.
+    pub fn new(especie: Especie, id: u64, params: &Parametros, rng: &mut ChaCha8Rng) -> Self {
+        let sexo = if rng.gen_bool(params.probabilidad_nacer_macho) { Sexo::Macho } else { Sexo::Hembra };
+        let peso_inicial = especie.peso_a_edad(0, params);
+        Self { id, especie, edad_dias: 0, peso_kg: peso_inicial, sexo, vivo: true, causa_muerte: None, gestacion: None }
     }
 
-    /// Gestiona la reproducción si se cumplen las condiciones de edad, sexo y probabilidad.
-    fn reproducirse(&self, rng: &mut ThreadRng, next_id: &mut u32) -> Vec<Box<dyn Presa>> {
-        let mut crias: Vec<Box<dyn Presa>> = Vec::new();
-        if self.sexo == Sexo::Hembra && self.edad_dias >= CONEJO_EDAD_REPRODUCTIVA_DIAS && rng.gen_bool(CONEJO_TASA_REPRODUCCION_DIARIA) {
-            let cantidad = rng.gen_range(CONEJO_CRIAS_POR_PARTO.0..=CONEJO_CRIAS_POR_PARTO.1);
-            for _ in 0..cantidad {
-                crias.push(Box::new(Conejo::new(*next_id, rng)));
-                *next_id += 1;
-            }
-        }
-        crias
+    /// Reconstruye una presa con un estado ya conocido (p. ej. al cargar una instantánea).
+    pub fn restaurar(especie: Especie, id: u64, edad_dias: u32, peso_kg: f64, sexo: Sexo, vivo: bool, gestacion: Option<Gestacion>) -> Self {
+        Self { id, especie, edad_dias, peso_kg, sexo, vivo, causa_muerte: None, gestacion }
     }
-}
 
-// --- Implementación de CABRA ---
-
-/// Representa a una cabra individual en la simulación.
-pub struct Cabra {
-    id: u32,
-    edad_dias: u32,
-    peso_kg: f64,
-    sexo: Sexo,
-    vivo: bool,
-    crecimiento: Box<dyn Fn(u32) -> f64>,
-}
-
-impl Cabra {
-    /// Constructor para crear una nueva Cabra.
-    pub fn new(id: u32, rng: &mut ThreadRng) -> Self {
-        let sexo = if rng.gen_bool(PROBABILIDAD_NACER_MACHO) { Sexo::Macho } else { Sexo::Hembra };
-        let crecimiento = crear_funcion_gompertz(75.0, 0.01, 180.0);
-        let peso_inicial = crecimiento(0);
-        Self { id, edad_dias: 0, peso_kg: peso_inicial, sexo, vivo: true, crecimiento }
+    /// Da a luz `cantidad` crías, o las que queden por numerar si se agotan los ids.
+    fn parir(&self, cantidad: u32, params: &Parametros, rng: &mut ChaCha8Rng, next_id: &mut u64) -> Vec<Box<dyn Presa>> {
+        (0..cantidad)
+            .map_while(|_| {
+                let id = asignar_id(next_id)?;
+                let cria: Box<dyn Presa> = Box::new(PresaGenerica::new(self.especie, id, params, rng));
+                Some(cria)
+            })
+            .collect()
     }
 }
 
-/// Implementación del "contrato" `Presa` para la struct `Cabra`.
-impl Presa for Cabra {
-    fn id(&self) -> u32 { self.id }
-    fn especie(&self) -> Especie { Especie::Cabra }
+/// Implementación del "contrato" `Presa` para cualquier especie.
+impl Presa for PresaGenerica {
+    fn id(&self) -> u64 { self.id }
+    fn especie(&self) -> Especie { self.especie }
     fn sexo(&self) -> Sexo { self.sexo }
     fn edad(&self) -> u32 { self.edad_dias }
     fn peso(&self) -> f64 { self.peso_kg }
     fn esta_viva(&self) -> bool { self.vivo }
+    fn causa_muerte(&self) -> Option<CausaMuerte> { self.causa_muerte }
+    fn gestacion(&self) -> Option<Gestacion> { self.gestacion }
 
-    fn envejecer(&mut self) {
+    /// Incrementa la edad, actualiza el peso y gestiona la muerte por vejez o enfermedad. Una
+    /// presa consumida por el hambre muere sin sortear las demás causas.
+    fn envejecer(&mut self, params: &Parametros, saciedad: f64, rng: &mut ChaCha8Rng) {
+        let rasgos = self.especie.rasgos(params);
         self.edad_dias += 1;
-        self.peso_kg = (self.crecimiento)(self.edad_dias);
-        if self.edad_dias > CABRA_EDAD_MAXIMA_DIAS || rand::random::<f64>() < PROBABILIDAD_ENFERMAR {
+        let objetivo_kg = peso_gompertz(rasgos.crecimiento, self.edad_dias);
+        self.peso_kg = if params.cadena_trofica.activa {
+            let ayer_kg = peso_gompertz(rasgos.crecimiento, self.edad_dias - 1);
+            crecer(self.peso_kg, ayer_kg, objetivo_kg, saciedad, &params.cadena_trofica)
+        } else {
+            objetivo_kg
+        };
+        let causa = if muere_de_inanicion(self.peso_kg, objetivo_kg, params) {
+            Some(CausaMuerte::Hambre)
+        } else {
+            sortear_muerte(self.especie, self.edad_dias, rasgos.edad_maxima_dias, saciedad, params, rng)
+        };
+        if let Some(causa) = causa {
             self.vivo = false;
+            self.causa_muerte = Some(causa);
         }
     }
 
-    fn reproducirse(&self, rng: &mut ThreadRng, next_id: &mut u32) -> Vec<Box<dyn Presa>> {
-        let mut crias: Vec<Box<dyn Presa>> = Vec::new();
-        if self.sexo == Sexo::Hembra && self.edad_dias >= CABRA_EDAD_REPRODUCTIVA_DIAS && rng.gen_bool(CABRA_TASA_REPRODUCCION_DIARIA) {
-            let cantidad = rng.gen_range(CABRA_CRIAS_POR_PARTO.0..=CABRA_CRIAS_POR_PARTO.1);
-            for _ in 0..cantidad {
-                crias.push(Box::new(Cabra::new(*next_id, rng)));
-                *next_id += 1;
+    /// Gestiona la reproducción si se cumplen las condiciones de edad, sexo, pareja y probabilidad.
+    /// Con gestación, la hembra concibe hoy y pare al cumplirse los días de su especie; mientras
+    /// tanto no vuelve a concebir. Sin macho no se consume el generador.
+    fn reproducirse(&mut self, params: &Parametros, con_macho: bool, rng: &mut ChaCha8Rng, next_id: &mut u64) -> Vec<Box<dyn Presa>> {
+        if let Some(gestacion) = &mut self.gestacion {
+            gestacion.dias_restantes = gestacion.dias_restantes.saturating_sub(1);
+            if gestacion.dias_restantes > 0 {
+                return Vec::new();
+            }
+            let crias = gestacion.crias;
+            self.gestacion = None;
+            return self.parir(crias, params, rng, next_id);
+        }
+        let rasgos = self.especie.rasgos(params);
+        if self.sexo == Sexo::Hembra && self.edad_dias >= rasgos.edad_reproductiva_dias && con_macho && rng.gen_bool(rasgos.tasa_reproduccion_diaria) {
+            let cantidad = rng.gen_range(rasgos.crias_por_parto.min..=rasgos.crias_por_parto.max);
+            if rasgos.gestacion_dias == 0 {
+                return self.parir(cantidad, params, rng, next_id);
             }
+            self.gestacion = Some(Gestacion { dias_restantes: rasgos.gestacion_dias, crias: cantidad });
         }
-        crias
+        Vec::new()
     }
 }
 
 
 // --- Implementación del DEPREDADOR ---
 
-/// Representa al único depredador de la simulación.
+/// Representa a un depredador. Sin el modo de manada (ver `Manada`) es el único de la
+/// simulación y no envejece: solo muere de hambre.
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Depredador {
     pub reserva_comida_kg: f64,
     pub vivo: bool,
+    /// Carne de presas grandes que todavía no ha comido; se descompone cada día.
+    #[serde(default)]
+    pub despensa_kg: f64,
+    /// Días de vida; solo cuentan en el modo de manada.
+    #[serde(default)]
+    pub edad_dias: u32,
+    #[serde(default = "sexo_depredador_por_defecto")]
+    pub sexo: Sexo,
+    /// Punto del terreno en que está (ver `espacio`); `None` mientras no se ha movido del centro.
+    #[serde(default)]
+    pub posicion: Option<Posicion>,
+}
+
+/// Sexo de los depredadores guardados antes de la manada (el valor por defecto en protobuf).
+fn sexo_depredador_por_defecto() -> Sexo {
+    Sexo::Macho
 }
 
 impl Depredador {
     pub fn new(reserva_inicial: f64) -> Self {
-        Self { reserva_comida_kg: reserva_inicial, vivo: true }
+        Self::cria(reserva_inicial, Sexo::Macho, None)
+    }
+
+    /// Un depredador recién nacido con la reserva indicada, en el punto de su madre.
+    pub fn cria(reserva_inicial: f64, sexo: Sexo, posicion: Option<Posicion>) -> Self {
+        Self { reserva_comida_kg: reserva_inicial, vivo: true, despensa_kg: 0.0, edad_dias: 0, sexo, posicion }
+    }
+
+    /// Kilos de `disponible` que puede comer de una vez, según su ingesta máxima.
+    fn ingesta(disponible: f64, params: &Parametros) -> f64 {
+        if params.depredador_ingesta_maxima_kg > 0.0 {
+            disponible.min(params.depredador_ingesta_maxima_kg)
+        } else {
+            disponible
+        }
+    }
+
+    /// Come de la despensa lo que le cabe en el día; del resto, se pudre una parte.
+    /// Devuelve los kilos asimilados (los que pasan a la reserva) y los podridos.
+    pub fn atender_despensa(&mut self, params: &Parametros) -> (f64, f64) {
+        if self.despensa_kg <= 0.0 {
+            return (0.0, 0.0);
+        }
+        let comido = Self::ingesta(self.despensa_kg, params);
+        let asimilado = comido * params.energia.eficiencia_asimilacion;
+        self.reserva_comida_kg += asimilado;
+        let restante = self.despensa_kg - comido;
+        self.despensa_kg = restante * (1.0 - params.tasa_descomposicion_diaria);
+        (asimilado, restante - self.despensa_kg)
+    }
+
+    /// Indica si la reserva ya no alcanza para el consumo mínimo del día siguiente.
+    pub fn en_peligro(&self, params: &Parametros) -> bool {
+        self.reserva_comida_kg < params.consumo_depredador_kg().0
     }
 
     /// Consume comida de la reserva para sobrevivir, gestionando la muerte por inanición.
-    pub fn consumir_reserva(&mut self) {
-        if self.reserva_comida_kg >= DEPREDADOR_CONSUMO_OPTIMO_DIARIO_KG {
-            self.reserva_comida_kg -= DEPREDADOR_CONSUMO_OPTIMO_DIARIO_KG;
-        } else if self.reserva_comida_kg >= DEPREDADOR_CONSUMO_MINIMO_DIARIO_KG {
-            self.reserva_comida_kg -= DEPREDADOR_CONSUMO_MINIMO_DIARIO_KG;
+    /// Devuelve los kilos consumidos.
+    pub fn consumir_reserva(&mut self, params: &Parametros) -> f64 {
+        let (minimo, optimo) = params.consumo_depredador_kg();
+        let consumo = if self.reserva_comida_kg >= optimo {
+            optimo
+        } else if self.reserva_comida_kg >= minimo {
+            minimo
         } else {
             // Si no puede consumir ni el mínimo, muere.
             self.vivo = false;
-        }
+            0.0
+        };
+        self.reserva_comida_kg -= consumo;
+        consumo
     }
 
-    /// Implementa la lógica de caza siguiendo las reglas especificadas.
-    pub fn cazar(&mut self, presas: &mut Vec<Box<dyn Presa>>, rng: &mut ThreadRng) {
-        // 1. Filtrar solo presas que han alcanzado la edad de sacrificio.
-        let presas_cazables: Vec<(usize, &Box<dyn Presa>)> = presas.iter().enumerate()
-            .filter(|(_, p)| {
-                let edad_sacrificio = match p.especie() {
-                    Especie::Conejo => CONEJO_EDAD_SACRIFICIO_DIAS,
-                    Especie::Cabra => CABRA_EDAD_SACRIFICIO_DIAS,
+    /// Implementa la lógica de caza siguiendo las reglas especificadas. `distancia` da los metros
+    /// hasta cada presa, o `None` si está fuera de su alcance (en el modo espacial, las lejanas);
+    /// entre las que puede cazar elige según `ESTRATEGIA_CAZA`. Caza una al día o, con
+    /// `RESPUESTA_FUNCIONAL`, tantas como dé la curva de Holling.
+    pub fn cazar(
+        &mut self,
+        presas: &mut Vec<Box<dyn Presa>>,
+        params: &Parametros,
+        rng: &mut ChaCha8Rng,
+        distancia: impl Fn(&dyn Presa) -> Option<f32>,
+    ) -> ResultadoCaza {
+        // 0. Salir a cazar cuesta energía (solo con el metabolismo activo), se cobre pieza o no.
+        let mut resultado = ResultadoCaza::default();
+        let reserva_previa = self.reserva_comida_kg;
+        self.reserva_comida_kg = (self.reserva_comida_kg - params.coste_caza_kg()).max(0.0);
+        resultado.gasto_kg = reserva_previa - self.reserva_comida_kg;
+
+        // 1. Filtrar las presas que entran en su dieta y que hoy están a su alcance: las que están
+        //    cerca y han alcanzado la edad de sacrificio o, con la cazabilidad activa, salen en el sorteo.
+        let dieta = &params.dieta_depredador;
+        let presas_cazables: Vec<(usize, &Box<dyn Presa>, f32)> = presas.iter().enumerate()
+            .filter_map(|(i, p)| {
+                let (edad_sacrificio, en_dieta, peso_minimo) = match p.especie() {
+                    Especie::Conejo => (params.conejo_edad_sacrificio_dias, dieta.conejos, dieta.conejo_peso_minimo_kg),
+                    Especie::Cabra => (params.cabra_edad_sacrificio_dias, dieta.cabras, dieta.cabra_peso_minimo_kg),
                 };
-                p.edad() >= edad_sacrificio && p.esta_viva()
+                let respetada = dieta.respetar_hembras_prenadas && p.sexo() == Sexo::Hembra && p.gestacion().is_some();
+                let alcanzable = |rng: &mut ChaCha8Rng| if params.cazabilidad.activa {
+                    rng.gen_bool(vulnerabilidad(p.as_ref(), params))
+                } else {
+                    p.edad() >= edad_sacrificio
+                };
+                if !(p.esta_viva() && en_dieta && p.peso() >= peso_minimo && !respetada) {
+                    return None;
+                }
+                let metros = distancia(p.as_ref())?;
+                alcanzable(rng).then_some((i, p, metros))
             })
             .collect();
 
-        if presas_cazables.is_empty() { return; } // Si no hay presas válidas, no caza.
-
-        // 2. Encontrar el peso máximo entre las presas cazables.
-        let peso_maximo = presas_cazables.iter()
-            .map(|(_, p)| p.peso())
-            .fold(0.0, f64::max);
-
-        // 3. Obtener los índices de todas las presas que empatan en el peso máximo.
-        let mejores_presas_indices: Vec<usize> = presas_cazables.into_iter()
-            .filter(|(_, p)| p.peso() >= peso_maximo - 0.01) // Tolerancia para flotantes
-            .map(|(i, _)| i)
+        if presas_cazables.is_empty() { return resultado; } // Si no hay presas válidas, no caza.
+        resultado.cazable_kg = presas_cazables.iter().map(|(_, p, _)| p.peso()).sum();
+
+        // 2. Encontrar el mayor atractivo según la estrategia. Las clases de edad invulnerables
+        //    no atraen con ninguna.
+        let vulnerable = |p: &dyn Presa| params.clases_edad.de(p.especie().clase_edad(p.edad(), params)).vulnerabilidad;
+        let atractivo = |p: &dyn Presa, metros: f32| match params.estrategia_caza {
+            EstrategiaCaza::Pesada => p.peso() * vulnerable(p),
+            EstrategiaCaza::Cercana => -(metros as f64),
+            EstrategiaCaza::Debil => vulnerabilidad(p, params),
+            EstrategiaCaza::Aleatoria => 0.0,
+            EstrategiaCaza::Vieja => p.edad() as f64,
+        };
+        let mut presas_cazables: Vec<(usize, f64)> = presas_cazables.into_iter()
+            .filter(|&(_, p, _)| vulnerable(p.as_ref()) > 0.0)
+            .map(|(i, p, metros)| (i, atractivo(p.as_ref(), metros)))
             .collect();
+        if presas_cazables.is_empty() {
+            return resultado; // Solo quedan presas de clases invulnerables.
+        }
 
-        // 4. Elegir una al azar de los mejores, removerla y añadir su peso a la reserva.
-        if let Some(&indice_a_cazar) = mejores_presas_indices.choose(rng) {
+        // 3. Cuántas intenta cazar hoy: una o, con la respuesta funcional, las que salen de la
+        //    curva de Holling para las presas que tiene a su alcance.
+        let respuesta = &params.respuesta_funcional;
+        let intentos = if respuesta.activa {
+            let esperadas = respuesta.capturas_esperadas(presas_cazables.len() as f64);
+            esperadas.floor() as usize + rng.gen_bool(esperadas.fract()) as usize
+        } else {
+            1
+        };
+
+        for _ in 0..intentos {
+            // 4. Entre las que empatan en el atractivo máximo, elegir una al azar.
+            let Some(atractivo_maximo) = presas_cazables.iter().map(|&(_, a)| a).reduce(f64::max) else {
+                break;
+            };
+            let mejores: Vec<usize> = (0..presas_cazables.len())
+                .filter(|&j| presas_cazables[j].1 >= atractivo_maximo - 0.01) // Tolerancia para flotantes
+                .collect();
+            let Some(&elegida) = mejores.choose(rng) else {
+                break;
+            };
+            let (indice_a_cazar, _) = presas_cazables.remove(elegida);
+
+            // 5. Con el éxito de caza activo puede escapar (y ya no se la persigue hoy); el
+            //    intento fallido cuesta reserva.
+            if params.exito_caza.activo && !rng.gen_bool(probabilidad_exito(presas[indice_a_cazar].as_ref(), params)) {
+                let reserva_previa = self.reserva_comida_kg;
+                self.reserva_comida_kg = (self.reserva_comida_kg - params.exito_caza.coste_fallo_kg).max(0.0);
+                resultado.gasto_kg += reserva_previa - self.reserva_comida_kg;
+                resultado.escapadas += 1;
+                continue;
+            }
+            // 6. Removerla y añadir su peso a la reserva; lo que no se come al momento va a la despensa.
             let presa_cazada = presas.remove(indice_a_cazar);
-            self.reserva_comida_kg += presa_cazada.peso();
+            for (indice, _) in presas_cazables.iter_mut().filter(|(i, _)| *i > indice_a_cazar) {
+                *indice -= 1;
+            }
+            let comido = Self::ingesta(presa_cazada.peso(), params);
+            resultado.presa_kg += presa_cazada.peso();
+            resultado.cazadas.push(PresaCazada {
+                id: presa_cazada.id(),
+                especie: presa_cazada.especie(),
+                edad_dias: presa_cazada.edad(),
+                peso_kg: presa_cazada.peso(),
+            });
+            let asimilado = comido * params.energia.eficiencia_asimilacion;
+            resultado.asimilado_kg += asimilado;
+            self.reserva_comida_kg += asimilado;
+            self.despensa_kg += presa_cazada.peso() - comido;
         }
+        resultado
+    }
+}
+
+/// Kilos que movió un día de caza, para la contabilidad energética.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ResultadoCaza {
+    /// Biomasa de las presas que podía cazar.
+    pub cazable_kg: f64,
+    /// Peso de las presas cazadas (0 si no cazó).
+    pub presa_kg: f64,
+    /// Parte de las presas que pasó a la reserva.
+    pub asimilado_kg: f64,
+    /// Reserva gastada en el esfuerzo de caza, también la de los intentos fallidos.
+    pub gasto_kg: f64,
+    /// Intentos en que la presa elegida escapó (solo con `EXITO_CAZA`).
+    pub escapadas: u32,
+    /// Las presas cazadas, en el orden en que cayeron: como mucho una sin `RESPUESTA_FUNCIONAL`.
+    pub cazadas: Vec<PresaCazada>,
+}
+
+/// Qué presa cayó en una caza, para anunciarla como evento.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PresaCazada {
+    pub id: u64,
+    pub especie: Especie,
+    pub edad_dias: u32,
+    pub peso_kg: f64,
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+
+    use crate::instantanea::EstadoPresa;
+
+    #[test]
+    fn asignar_id_se_detiene_antes_de_desbordar() {
+        let mut next_id = u64::MAX - 1;
+        assert_eq!(asignar_id(&mut next_id), Some(u64::MAX - 1));
+        assert_eq!(asignar_id(&mut next_id), None);
+        assert_eq!(asignar_id(&mut next_id), None);
+        assert_eq!(next_id, u64::MAX);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn un_parto_sin_ids_solo_da_las_crias_numeradas() {
+        let params = Parametros::default();
+        let mut rng = ChaCha8Rng::seed_from_u64(7);
+        let gestacion = Some(Gestacion { dias_restantes: 1, crias: 3 });
+        let mut madre = PresaGenerica::restaurar(Especie::Conejo, 0, 200, 1.5, Sexo::Hembra, true, gestacion);
+        let mut next_id = u64::MAX - 1;
+        let crias = madre.reproducirse(&params, true, &mut rng, &mut next_id);
+        assert_eq!(crias.iter().map(|c| c.id()).collect::<Vec<_>>(), vec![u64::MAX - 1]);
+        assert_eq!(next_id, u64::MAX);
+    }
+
+    fn adulta(especie: Especie, id: u64, peso_kg: f64, gestacion: Option<Gestacion>) -> Box<dyn Presa> {
+        Box::new(PresaGenerica::restaurar(especie, id, 400, peso_kg, Sexo::Hembra, true, gestacion))
+    }
+
+    /// Ids de las presas que caza hoy un depredador que las tiene a todas a su alcance.
+    fn cazadas(mut presas: Vec<Box<dyn Presa>>, params: &Parametros, semilla: u64) -> Vec<u64> {
+        let mut rng = ChaCha8Rng::seed_from_u64(semilla);
+        let caza = Depredador::new(100.0).cazar(&mut presas, params, &mut rng, |_| Some(0.0));
+        caza.cazadas.iter().map(|c| c.id).collect()
+    }
+
+    fn con_gestacion(dias: u32) -> Parametros {
+        let mut params = Parametros { conejo_tasa_reproduccion_diaria: 1.0, conejo_gestacion_dias: dias, ..Parametros::default() };
+        params.conejo_crias_por_parto = crate::parametros::RangoCrias { min: 2, max: 2 };
+        params
+    }
+
+    #[test]
+    fn la_hembra_concibe_gesta_y_pare_al_cumplirse_los_dias() {
+        let params = con_gestacion(3);
+        let mut rng = ChaCha8Rng::seed_from_u64(3);
+        let mut next_id = 10;
+        let mut madre = PresaGenerica::restaurar(Especie::Conejo, 0, 400, 4.0, Sexo::Hembra, true, None);
+        assert!(madre.reproducirse(&params, false, &mut rng, &mut next_id).is_empty());
+        assert_eq!(madre.gestacion(), None);
+
+        assert!(madre.reproducirse(&params, true, &mut rng, &mut next_id).is_empty());
+        assert_eq!(madre.gestacion(), Some(Gestacion { dias_restantes: 3, crias: 2 }));
+        for dias_restantes in [2, 1] {
+            // Mientras está preñada no vuelve a concebir, aunque la tasa sea 1.
+            assert!(madre.reproducirse(&params, true, &mut rng, &mut next_id).is_empty());
+            assert_eq!(madre.gestacion(), Some(Gestacion { dias_restantes, crias: 2 }));
+        }
+        let crias = madre.reproducirse(&params, true, &mut rng, &mut next_id);
+        assert_eq!(crias.iter().map(|c| c.id()).collect::<Vec<_>>(), vec![10, 11]);
+        assert_eq!(madre.gestacion(), None);
+    }
+
+    #[test]
+    fn sin_dias_de_gestacion_pare_el_mismo_dia() {
+        let params = con_gestacion(0);
+        let mut rng = ChaCha8Rng::seed_from_u64(3);
+        let mut next_id = 0;
+        let mut madre = PresaGenerica::restaurar(Especie::Conejo, 100, 400, 4.0, Sexo::Hembra, true, None);
+        assert_eq!(madre.reproducirse(&params, true, &mut rng, &mut next_id).len(), 2);
+        assert_eq!(madre.gestacion(), None);
+    }
+
+    #[test]
+    fn la_gestacion_se_conserva_en_el_estado_guardado() {
+        let gestacion = Some(Gestacion { dias_restantes: 5, crias: 4 });
+        let estado = EstadoPresa::desde_presa(adulta(Especie::Cabra, 1, 40.0, gestacion).as_ref());
+        let json = serde_json::to_string(&estado).unwrap();
+        let restaurada: EstadoPresa = serde_json::from_str(&json).unwrap();
+        assert_eq!(restaurada.a_presa().gestacion(), gestacion);
+        // Los archivos anteriores a la gestación no la traen.
+        let anterior = r#"{"id":1,"especie":"Cabra","sexo":"Hembra","edad_dias":400,"peso_kg":40.0,"vivo":true}"#;
+        assert_eq!(serde_json::from_str::<EstadoPresa>(anterior).unwrap().gestacion, None);
+    }
+
+    #[test]
+    fn la_dieta_descarta_las_especies_que_no_caza() {
+        let presas = || vec![adulta(Especie::Conejo, 1, 4.0, None), adulta(Especie::Cabra, 2, 40.0, None)];
+        let mut params = Parametros { estrategia_caza: EstrategiaCaza::Aleatoria, ..Parametros::default() };
+        params.dieta_depredador.cabras = false;
+        for semilla in 0..10 {
+            assert_eq!(cazadas(presas(), &params, semilla), vec![1]);
+        }
+        params.dieta_depredador = crate::parametros::Dieta { conejos: false, ..Default::default() };
+        for semilla in 0..10 {
+            assert_eq!(cazadas(presas(), &params, semilla), vec![2]);
+        }
+        params.dieta_depredador.cabras = false;
+        assert!(cazadas(presas(), &params, 0).is_empty());
+    }
+
+    #[test]
+    fn la_dieta_descarta_las_presas_por_debajo_del_peso_minimo_de_su_especie() {
+        let mut params = Parametros::default();
+        params.dieta_depredador.cabra_peso_minimo_kg = 30.0;
+        assert!(cazadas(vec![adulta(Especie::Cabra, 1, 25.0, None)], &params, 0).is_empty());
+        assert_eq!(cazadas(vec![adulta(Especie::Cabra, 1, 35.0, None)], &params, 0), vec![1]);
+        // El mínimo de las cabras no afecta a los conejos.
+        assert_eq!(cazadas(vec![adulta(Especie::Conejo, 1, 4.0, None)], &params, 0), vec![1]);
+        params.dieta_depredador.conejo_peso_minimo_kg = 4.5;
+        assert!(cazadas(vec![adulta(Especie::Conejo, 1, 4.0, None)], &params, 0).is_empty());
+    }
+
+    #[test]
+    fn la_dieta_respeta_solo_a_las_hembras_prenadas() {
+        let mut params = Parametros::default();
+        params.dieta_depredador.respetar_hembras_prenadas = true;
+        let prenada = || adulta(Especie::Cabra, 1, 40.0, Some(Gestacion { dias_restantes: 10, crias: 1 }));
+        for semilla in 0..10 {
+            assert_eq!(cazadas(vec![prenada(), adulta(Especie::Cabra, 2, 40.0, None)], &params, semilla), vec![2]);
+        }
+        assert!(cazadas(vec![prenada()], &params, 0).is_empty());
+        params.dieta_depredador.respetar_hembras_prenadas = false;
+        assert_eq!(cazadas(vec![prenada()], &params, 0), vec![1]);
+        // Las instantáneas anteriores guardan la opción con su nombre antiguo.
+        let antigua: crate::parametros::Dieta = serde_json::from_str(r#"{"respetar_hembras_reproductoras":true}"#).unwrap();
+        assert!(antigua.respetar_hembras_prenadas);
+    }
+
+    #[test]
+    fn la_mortalidad_de_clase_no_se_cuenta_como_vejez() {
+        let mut params = Parametros { probabilidad_enfermar: 0.0, ..Parametros::default() };
+        params.clases_edad.juvenil.mortalidad_diaria = 1.0;
+        let mut rng = ChaCha8Rng::seed_from_u64(1);
+        let edad_maxima = params.conejo_edad_maxima_dias;
+        assert_eq!(sortear_muerte(Especie::Conejo, 1, edad_maxima, 1.0, &params, &mut rng), Some(CausaMuerte::MortalidadClase));
+        assert_eq!(sortear_muerte(Especie::Conejo, edad_maxima + 1, edad_maxima, 1.0, &params, &mut rng), Some(CausaMuerte::Vejez));
+    }
+
+    #[test]
+    fn tras_una_hambruna_el_peso_se_recupera_poco_a_poco() {
+        let cadena = CadenaTrofica::default();
+        let curva = CurvaCrecimiento::from(crate::parametros::CONEJO_CRECIMIENTO);
+        let mut edad = 300;
+        let mut peso = peso_gompertz(curva, edad);
+        for _ in 0..10 {
+            edad += 1;
+            peso = crecer(peso, peso_gompertz(curva, edad - 1), peso_gompertz(curva, edad), 0.0, &cadena);
+        }
+        assert!(peso < peso_gompertz(curva, edad) * 0.85);
+
+        let mut dias = 0;
+        while peso < peso_gompertz(curva, edad) {
+            edad += 1;
+            dias += 1;
+            let antes = peso;
+            peso = crecer(peso, peso_gompertz(curva, edad - 1), peso_gompertz(curva, edad), 1.0, &cadena);
+            let tope = peso_gompertz(curva, edad) - peso_gompertz(curva, edad - 1) + antes * cadena.recuperacion_peso_diaria;
+            assert!(peso - antes <= tope + 1e-12);
+            assert!(peso <= peso_gompertz(curva, edad));
+        }
+        assert!(dias > 1, "recuperó el peso en {} día(s)", dias);
+    }
+}
@@ -0,0 +1,82 @@
+// src/espacio.rs
+
+// Posiciones en el terreno del modo espacial (ver `Espacio`). El terreno es un cuadrado de
+// `lado_m` metros con el origen en una esquina; cada presa tiene su punto y da un paso al azar
+// cada día, y el depredador solo alcanza a las que tiene cerca. Con la cadena trófica, la
+// cuadrícula del pastizal se extiende sobre todo el terreno y cada presa se queda dentro de
+// la celda en que pasta.
+
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::pastizal::Pastizal;
+
+/// Un punto del terreno, en metros.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct Posicion {
+    pub x: f32,
+    pub y: f32,
+}
+
+impl Posicion {
+    pub fn new(x: f32, y: f32) -> Self {
+        Self { x, y }
+    }
+
+    /// Centro del terreno, donde empieza el depredador.
+    pub fn centro(lado: f32) -> Self {
+        Self::new(lado / 2.0, lado / 2.0)
+    }
+
+    /// Punto de partida de una presa que todavía no tiene ninguno: se dispersa según su id,
+    /// como `Pastizal::celda_inicial`.
+    pub fn inicial(id: u64, lado: f32) -> Self {
+        let fraccion = |multiplicador: u64| (id.wrapping_mul(multiplicador) % 10_007) as f32 / 10_007.0;
+        Self::new(fraccion(2_654_435_761) * lado, fraccion(40_503) * lado)
+    }
+
+    pub fn distancia(self, otra: Posicion) -> f32 {
+        (self.x - otra.x).hypot(self.y - otra.y)
+    }
+
+    /// Avanza hacia `destino` como mucho `paso` metros; si está más cerca, llega.
+    pub fn hacia(self, destino: Posicion, paso: f32) -> Self {
+        let distancia = self.distancia(destino);
+        if distancia <= paso {
+            return destino;
+        }
+        let t = paso / distancia;
+        Self::new(self.x + (destino.x - self.x) * t, self.y + (destino.y - self.y) * t)
+    }
+
+    /// Un paso de hasta `paso` metros en una dirección al azar.
+    pub fn paso_aleatorio(self, paso: f32, rng: &mut impl Rng) -> Self {
+        let angulo = rng.gen_range(0.0..std::f32::consts::TAU);
+        let largo = rng.gen_range(0.0..=paso);
+        Self::new(self.x + largo * angulo.cos(), self.y + largo * angulo.sin())
+    }
+
+    /// El punto más cercano dentro del rectángulo de esquinas `minimo` y `maximo`.
+    pub fn limitar(self, (minimo, maximo): (Posicion, Posicion)) -> Self {
+        Self::new(self.x.clamp(minimo.x, maximo.x), self.y.clamp(minimo.y, maximo.y))
+    }
+}
+
+/// Esquinas (mínima, máxima) de todo el terreno.
+pub fn limites_terreno(lado: f32) -> (Posicion, Posicion) {
+    (Posicion::new(0.0, 0.0), Posicion::new(lado, lado))
+}
+
+/// Esquinas (mínima, máxima) de una celda del pastizal, que cubre todo el terreno.
+pub fn limites_celda(pastizal: &Pastizal, celda: u32, lado: f32) -> (Posicion, Posicion) {
+    let (columna, fila) = pastizal.coordenadas(celda);
+    let (ancho, alto) = (lado / pastizal.columnas as f32, lado / pastizal.filas as f32);
+    let minimo = Posicion::new(columna as f32 * ancho, fila as f32 * alto);
+    (minimo, Posicion::new(minimo.x + ancho, minimo.y + alto))
+}
+
+/// Celda del pastizal en que cae un punto del terreno.
+pub fn celda_en(pastizal: &Pastizal, posicion: Posicion, lado: f32) -> u32 {
+    let indice = |coordenada: f32, divisiones: u32| ((coordenada / lado * divisiones as f32) as u32).min(divisiones - 1);
+    indice(posicion.y, pastizal.filas) * pastizal.columnas + indice(posicion.x, pastizal.columnas)
+}
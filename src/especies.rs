@@ -0,0 +1,156 @@
+// src/especies.rs
+
+// Definiciones de especie como archivos JSON independientes (`conejo.json`, `cabra.json`).
+// Cada archivo contiene el bloque de parámetros de una especie de presa, así que se pueden
+// exportar los actuales, compartir variantes (p. ej. una raza de cabra más prolífica) e
+// importarlas al arrancar. Una definición sustituye los parámetros de la especie indicada
+// en su campo `especie`; su `nombre` y `descripcion` solo sirven para identificar el preset.
+// Como todas las presas son `PresaGenerica`, la definición (edades, reproducción, gestación y
+// curva de crecimiento) es todo lo que distingue a una especie de otra.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::entidades::Especie;
+use crate::parametros::{CurvaCrecimiento, Parametros, RangoCrias};
+
+/// Carpeta de la que el visualizador importa definiciones al arrancar.
+pub const CARPETA_ESPECIES: &str = "especies";
+
+/// Parámetros de una especie de presa.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct DefinicionEspecie {
+    /// Especie del motor cuyos parámetros se reemplazan (`Conejo` o `Cabra`).
+    #[schemars(with = "String")]
+    pub especie: Especie,
+    /// Nombre del preset (p. ej. "Conejo de monte").
+    pub nombre: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub descripcion: String,
+    /// Edad a partir de la cual muere de vieja (días).
+    pub edad_maxima_dias: u32,
+    /// Edad mínima de reproducción de las hembras (días).
+    pub edad_reproductiva_dias: u32,
+    /// Edad a partir de la cual puede ser cazada (días).
+    pub edad_sacrificio_dias: u32,
+    /// Probabilidad diaria de parto de una hembra adulta.
+    #[schemars(range(min = 0.0, max = 1.0))]
+    pub tasa_reproduccion_diaria: f64,
+    /// Crías por parto.
+    pub crias_por_parto: RangoCrias,
+    /// Curva de crecimiento de Gompertz; si falta, se conserva la actual.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub crecimiento: Option<CurvaCrecimiento>,
+    /// Días de gestación (0: pare el día en que concibe); si falta, se conservan los actuales.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gestacion_dias: Option<u32>,
+}
+
+impl DefinicionEspecie {
+    /// Extrae la definición de una especie a partir de los parámetros.
+    pub fn desde_parametros(parametros: &Parametros, especie: Especie) -> Self {
+        let rasgos = especie.rasgos(parametros);
+        Self {
+            especie,
+            nombre: format!("{:?}", especie),
+            descripcion: String::new(),
+            edad_maxima_dias: rasgos.edad_maxima_dias,
+            edad_reproductiva_dias: rasgos.edad_reproductiva_dias,
+            edad_sacrificio_dias: rasgos.edad_sacrificio_dias,
+            tasa_reproduccion_diaria: rasgos.tasa_reproduccion_diaria,
+            crias_por_parto: rasgos.crias_por_parto,
+            crecimiento: Some(rasgos.crecimiento),
+            gestacion_dias: Some(rasgos.gestacion_dias),
+        }
+    }
+
+    /// Copia la definición sobre los parámetros de su especie, validando el resultado.
+    pub fn aplicar(&self, parametros: &mut Parametros) -> Result<(), String> {
+        let mut nuevos = parametros.clone();
+        let campos = match self.especie {
+            Especie::Conejo => (
+                &mut nuevos.conejo_edad_maxima_dias, &mut nuevos.conejo_edad_reproductiva_dias, &mut nuevos.conejo_edad_sacrificio_dias,
+                &mut nuevos.conejo_tasa_reproduccion_diaria, &mut nuevos.conejo_crias_por_parto, &mut nuevos.conejo_crecimiento,
+                &mut nuevos.conejo_gestacion_dias,
+            ),
+            Especie::Cabra => (
+                &mut nuevos.cabra_edad_maxima_dias, &mut nuevos.cabra_edad_reproductiva_dias, &mut nuevos.cabra_edad_sacrificio_dias,
+                &mut nuevos.cabra_tasa_reproduccion_diaria, &mut nuevos.cabra_crias_por_parto, &mut nuevos.cabra_crecimiento,
+                &mut nuevos.cabra_gestacion_dias,
+            ),
+        };
+        *campos.0 = self.edad_maxima_dias;
+        *campos.1 = self.edad_reproductiva_dias;
+        *campos.2 = self.edad_sacrificio_dias;
+        *campos.3 = self.tasa_reproduccion_diaria;
+        *campos.4 = self.crias_por_parto;
+        if let Some(crecimiento) = self.crecimiento {
+            *campos.5 = crecimiento;
+        }
+        if let Some(gestacion_dias) = self.gestacion_dias {
+            *campos.6 = gestacion_dias;
+        }
+        nuevos.validar().map_err(|e| format!("{}: {}", self.nombre, e))?;
+        *parametros = nuevos;
+        Ok(())
+    }
+
+    /// Representación JSON legible de la definición.
+    pub fn a_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap_or_default()
+    }
+
+    /// Escribe la definición en un archivo JSON.
+    pub fn guardar(&self, ruta: impl AsRef<Path>) -> Result<(), String> {
+        let ruta = ruta.as_ref();
+        fs::write(ruta, self.a_json() + "\n").map_err(|e| format!("no se pudo escribir {}: {}", ruta.display(), e))
+    }
+
+    /// Lee una definición desde un archivo JSON.
+    pub fn cargar(ruta: impl AsRef<Path>) -> Result<Self, String> {
+        let ruta = ruta.as_ref();
+        let texto = fs::read_to_string(ruta).map_err(|e| format!("no se pudo leer {}: {}", ruta.display(), e))?;
+        Self::desde_json(&texto).map_err(|e| format!("{}: {}", ruta.display(), e))
+    }
+
+    /// Interpreta una definición ya leída (p. ej. de los recursos de una app Android).
+    pub fn desde_json(texto: &str) -> Result<Self, String> {
+        serde_json::from_str(texto).map_err(|e| e.to_string())
+    }
+}
+
+/// Nombre de archivo sugerido para exportar una especie (`conejo.json`).
+pub fn nombre_archivo(especie: Especie) -> String {
+    format!("{:?}.json", especie).to_lowercase()
+}
+
+/// Interpreta el nombre de una especie (`conejo`/`rabbit`, `cabra`/`goat`).
+pub fn especie_desde_nombre(nombre: &str) -> Result<Especie, String> {
+    match nombre.to_lowercase().as_str() {
+        "conejo" | "rabbit" => Ok(Especie::Conejo),
+        "cabra" | "goat" => Ok(Especie::Cabra),
+        otro => Err(format!("especie desconocida: {}", otro)),
+    }
+}
+
+/// Importa todas las definiciones `*.json` de una carpeta, en orden alfabético, y las aplica.
+/// Devuelve las rutas importadas; una carpeta inexistente no es un error.
+pub fn importar_carpeta(carpeta: impl AsRef<Path>, parametros: &mut Parametros) -> Result<Vec<PathBuf>, String> {
+    let carpeta = carpeta.as_ref();
+    let Ok(entradas) = fs::read_dir(carpeta) else {
+        return Ok(Vec::new());
+    };
+    let mut rutas: Vec<PathBuf> = entradas
+        .filter_map(|e| e.ok().map(|e| e.path()))
+        .filter(|r| r.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("json")))
+        .collect();
+    rutas.sort();
+    for ruta in &rutas {
+        DefinicionEspecie::cargar(ruta)?.aplicar(parametros)?;
+    }
+    Ok(rutas)
+}
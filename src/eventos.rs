@@ -0,0 +1,131 @@
+// src/eventos.rs
+
+// Este módulo define los sucesos notables que la simulación anuncia al terminar cada día.
+// Son la base para notificar a sistemas externos (publicadores, interfaz) sin que estos
+// tengan que comparar el estado día a día por su cuenta.
+
+use serde::{Deserialize, Serialize};
+
+use crate::entidades::{CausaMuerte, Especie};
+use crate::espacio::Posicion;
+
+/// Un suceso notable ocurrido durante un día de la simulación.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum Evento {
+    /// La última presa de una especie ha desaparecido.
+    Extincion { especie: Especie },
+    /// La reserva del depredador bajó del consumo mínimo diario.
+    DepredadorEnPeligro { reserva_kg: f64 },
+    /// El depredador murió de inanición.
+    MuerteDepredador,
+    /// Se cambió un parámetro en caliente (valores en su representación JSON).
+    ParametroCambiado { nombre: String, anterior: String, nuevo: String },
+    /// El usuario añadió una presa a mano (modo de edición).
+    PresaAgregada { id: u64, especie: Especie },
+    /// El usuario retiró (cosechó) una presa a mano (modo de edición).
+    PresaRetirada { id: u64, especie: Especie, peso_kg: f64 },
+    /// El usuario arrastró al depredador a otro punto del terreno (reubicación), en metros.
+    DepredadorReubicado { x: f32, y: f32 },
+    /// Empezó a cumplirse la condición de una alarma configurada (p. ej. "conejos < 10").
+    AlarmaDisparada { alarma: String },
+    /// Estalló un brote de enfermedad por hacinamiento en una especie.
+    BroteEpidemia { especie: Especie },
+    /// La fracción infectada de una especie alcanzó `ENFERMEDAD.umbral_ola`: empieza una ola.
+    OlaEpidemica { especie: Especie, infectadas: u32 },
+    /// Las presas superaron `POBLACION_MAXIMA` y murieron al azar las que sobraban.
+    MortalidadEmergencia { poblacion: u32, tope: u32, muertes: u32 },
+    /// El depredador cazó una presa; `celda` es la del pastizal en que estaba (modo de cadena
+    /// trófica) y `posicion`, su punto del terreno, para que la interfaz pueda señalar dónde cayó.
+    Caza {
+        id: u64,
+        especie: Especie,
+        edad_dias: u32,
+        peso_kg: f64,
+        celda: Option<u32>,
+        #[serde(default)]
+        posicion: Posicion,
+    },
+    /// Una hembra parió; `crias` son los ids de las recién nacidas.
+    Nacimiento { madre: u64, especie: Especie, crias: Vec<u64> },
+    /// Una presa murió de vieja, de enfermedad o de hambre. Las cazadas se anuncian con `Caza`
+    /// y las de la sobrepoblación, todas juntas, con `MortalidadEmergencia`.
+    MuertePresa { id: u64, especie: Especie, edad_dias: u32, causa: CausaMuerte },
+}
+
+/// Tipos de suceso que produce el propio modelo al avanzar un día (no las intervenciones),
+/// para esperar a uno con `Simulacion::ejecutar_hasta_evento`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TipoEvento {
+    Extincion,
+    DepredadorEnPeligro,
+    MuerteDepredador,
+    Alarma,
+    Sobrepoblacion,
+    Brote,
+    Ola,
+}
+
+impl TipoEvento {
+    pub const TODOS: [TipoEvento; 7] = [
+        TipoEvento::Extincion,
+        TipoEvento::DepredadorEnPeligro,
+        TipoEvento::MuerteDepredador,
+        TipoEvento::Alarma,
+        TipoEvento::Sobrepoblacion,
+        TipoEvento::Brote,
+        TipoEvento::Ola,
+    ];
+}
+
+impl Evento {
+    /// Tipo del evento, si es uno de los sucesos del modelo.
+    pub fn tipo(&self) -> Option<TipoEvento> {
+        match self {
+            Evento::Extincion { .. } => Some(TipoEvento::Extincion),
+            Evento::DepredadorEnPeligro { .. } => Some(TipoEvento::DepredadorEnPeligro),
+            Evento::MuerteDepredador => Some(TipoEvento::MuerteDepredador),
+            Evento::AlarmaDisparada { .. } => Some(TipoEvento::Alarma),
+            Evento::MortalidadEmergencia { .. } => Some(TipoEvento::Sobrepoblacion),
+            Evento::BroteEpidemia { .. } => Some(TipoEvento::Brote),
+            Evento::OlaEpidemica { .. } => Some(TipoEvento::Ola),
+            Evento::ParametroCambiado { .. }
+            | Evento::PresaAgregada { .. }
+            | Evento::PresaRetirada { .. }
+            | Evento::DepredadorReubicado { .. }
+            | Evento::Caza { .. }
+            | Evento::Nacimiento { .. }
+            | Evento::MuertePresa { .. } => None,
+        }
+    }
+
+    /// Si es el nacimiento o la muerte natural de una presa concreta. Hay muchos cada día, así que
+    /// los registros que se leen de un vistazo los dejan fuera, como las cazas.
+    pub fn es_demografico(&self) -> bool {
+        matches!(self, Evento::Nacimiento { .. } | Evento::MuertePresa { .. })
+    }
+
+    /// Descripción breve y legible del evento.
+    pub fn descripcion(&self) -> String {
+        match self {
+            Evento::Extincion { especie } => format!("Extinción de {:?}", especie),
+            Evento::DepredadorEnPeligro { reserva_kg } => format!("El depredador entró en peligro ({:.1} kg)", reserva_kg),
+            Evento::MuerteDepredador => "El depredador ha muerto".to_string(),
+            Evento::ParametroCambiado { nombre, anterior, nuevo } => format!("{}: {} -> {}", nombre, anterior, nuevo),
+            Evento::PresaAgregada { id, especie } => format!("Intervención: se añadió {:?} #{}", especie, id),
+            Evento::PresaRetirada { id, especie, peso_kg } => format!("Intervención: se retiró {:?} #{} ({:.1} kg)", especie, id, peso_kg),
+            Evento::DepredadorReubicado { x, y } => format!("Intervención: el depredador se reubicó en ({:.0}, {:.0}) m", x, y),
+            Evento::AlarmaDisparada { alarma } => format!("Alarma: {}", alarma),
+            Evento::BroteEpidemia { especie } => format!("Brote de enfermedad entre {:?}", especie),
+            Evento::OlaEpidemica { especie, infectadas } => format!("Ola epidémica entre {:?} ({} infectadas)", especie, infectadas),
+            Evento::MortalidadEmergencia { poblacion, tope, muertes } => {
+                format!("Sobrepoblación: {} presas superan el tope de {}; mueren {}", poblacion, tope, muertes)
+            }
+            Evento::Caza { id, especie, peso_kg, .. } => format!("El depredador cazó {:?} #{} ({:.1} kg)", especie, id, peso_kg),
+            Evento::Nacimiento { madre, especie, crias } => format!("{:?} #{} parió {} crías", especie, madre, crias.len()),
+            Evento::MuertePresa { id, especie, edad_dias, causa } => {
+                format!("{:?} #{} murió ({:?}) a los {} días", especie, id, causa, edad_dias)
+            }
+        }
+    }
+}
@@ -0,0 +1,377 @@
+// src/experimento.rs
+
+// Experimentos de barrido de parámetros al estilo de BehaviorSpace de NetLogo.
+// Se definen unas variaciones (cada una, un parámetro con una lista de valores), se corre
+// cada combinación varias veces con semillas consecutivas y los resultados se exportan en
+// la tabla "ancha" de BehaviorSpace: una fila por corrida, con las columnas de parámetros
+// seguidas de las de reporteros, de modo que los notebooks que ya leen ese formato
+// (p. ej. `pd.read_csv(ruta, skiprows=6)`) funcionen sin cambios.
+// Con números aleatorios comunes, la repetición k de cada combinación usa la misma semilla
+// y los sorteos se reparten en subflujos, de modo que las diferencias entre combinaciones
+// se deben a los parámetros y no al azar; basta con menos repeticiones para compararlas.
+// Aparte, cada combinación se resume como un lote de Monte Carlo: media, extremos e intervalo
+// de confianza bootstrap de sus resultados clave (ver `remuestreo`), o en una tabla de barrido
+// con la fracción de corridas en que sobreviven depredador y presas, para localizar los
+// regímenes de coexistencia.
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde_json::Value;
+
+use crate::configuracion;
+use crate::parametros::{self, Parametros};
+use crate::remuestreo::{self, Desenlace};
+use crate::simulacion::{ArranqueCaliente, Retencion, SimulacionBuilder};
+
+/// Nombres de las columnas de reporteros, en el orden en que se escriben.
+pub const REPORTEROS: &[&str] = &[
+    "conejos", "cabras", "reserva_depredador_kg", "depredador_vivo",
+    "nacimientos_totales", "muertes_totales", "cazas_totales",
+];
+
+/// Columnas de la tabla de barrido que siguen a las de los parámetros barridos.
+pub const ENCABEZADO_BARRIDO: &str =
+    "corridas,coexistencia,extincion_presas,muerte_depredador,vida_depredador_media,presas_finales_media";
+
+/// Un parámetro que se barre y los valores que toma.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Variacion {
+    pub nombre: String,
+    pub valores: Vec<Value>,
+}
+
+impl Variacion {
+    /// Interpreta `NOMBRE=v1,v2,v3` o `NOMBRE=inicio:paso:fin` (rango inclusivo, como
+    /// `["param" [inicio paso fin]]` en BehaviorSpace).
+    pub fn desde_texto(texto: &str) -> Result<Self, String> {
+        let (nombre, valores) = texto.split_once('=')
+            .ok_or_else(|| format!("se esperaba NOMBRE=v1,v2,... o NOMBRE=inicio:paso:fin, se recibió '{}'", texto))?;
+        let nombre = nombre.trim().to_string();
+        let partes: Vec<&str> = valores.split(':').map(str::trim).collect();
+        let valores = if let [inicio, paso, fin] = partes.as_slice() {
+            rango(inicio, paso, fin).map_err(|e| format!("{}: {}", nombre, e))?
+        } else {
+            valores.split(',').map(|v| configuracion::valor_desde_texto(v.trim())).collect()
+        };
+        if valores.is_empty() {
+            return Err(format!("{}: no hay valores que barrer", nombre));
+        }
+        Ok(Self { nombre, valores })
+    }
+
+    /// `pasos` valores equiespaciados de `desde` a `hasta`, ambos incluidos. Los valores
+    /// enteros se guardan como enteros, para que sirva con parámetros como las edades.
+    pub fn lineal(nombre: &str, desde: f64, hasta: f64, pasos: u32) -> Result<Self, String> {
+        if pasos == 0 {
+            return Err(format!("{}: hace falta al menos un paso", nombre));
+        }
+        if !desde.is_finite() || !hasta.is_finite() {
+            return Err(format!("{}: los extremos del barrido deben ser finitos", nombre));
+        }
+        let salto = if pasos > 1 { (hasta - desde) / (pasos - 1) as f64 } else { 0.0 };
+        let valores = (0..pasos)
+            .map(|i| {
+                let valor = if i + 1 == pasos && pasos > 1 { hasta } else { desde + salto * i as f64 };
+                if valor.fract() == 0.0 && valor.abs() < i64::MAX as f64 { Value::from(valor as i64) } else { Value::from(valor) }
+            })
+            .collect();
+        Ok(Self { nombre: nombre.trim().to_string(), valores })
+    }
+}
+
+/// Valores de `inicio` a `fin` (inclusive) en saltos de `paso`. Si los tres son enteros
+/// el resultado también lo es, para que sirva con parámetros como las edades.
+fn rango(inicio: &str, paso: &str, fin: &str) -> Result<Vec<Value>, String> {
+    let leer = |texto: &str| texto.parse::<f64>().map_err(|_| format!("número inválido: '{}'", texto));
+    let (a, p, b) = (leer(inicio)?, leer(paso)?, leer(fin)?);
+    if p <= 0.0 || !p.is_finite() {
+        return Err("el paso debe ser positivo".to_string());
+    }
+    if b < a {
+        return Err("el fin del rango es menor que el inicio".to_string());
+    }
+    let enteros = [inicio, paso, fin].iter().all(|t| t.parse::<i64>().is_ok());
+    let pasos = ((b - a) / p + 1e-9).floor() as u64;
+    Ok((0..=pasos)
+        .map(|i| {
+            let valor = a + p * i as f64;
+            if enteros { Value::from(valor.round() as i64) } else { Value::from(valor) }
+        })
+        .collect())
+}
+
+/// Definición de un experimento.
+#[derive(Debug, Clone)]
+pub struct Experimento {
+    pub nombre: String,
+    /// Parámetros comunes a todas las corridas (los no barridos).
+    pub base: Parametros,
+    pub variaciones: Vec<Variacion>,
+    /// Corridas por cada combinación de valores.
+    pub repeticiones: u32,
+    /// Días máximos de cada corrida (se detiene antes si muere el depredador).
+    pub dias: u32,
+    /// La corrida número `n` usa la semilla `semilla_base + n - 1`; con números comunes, la
+    /// repetición `k` (desde 0) de cada combinación usa `semilla_base + k`.
+    pub semilla_base: u64,
+    /// Números aleatorios comunes: semillas emparejadas entre combinaciones y `SUBFLUJOS_ALEATORIOS` activado.
+    pub numeros_comunes: bool,
+    /// Población de partida común a todas las corridas (arranque en caliente).
+    pub arranque: Option<ArranqueCaliente>,
+}
+
+/// Lo que se obtiene de una corrida del experimento.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResultadoCorrida {
+    /// Número de corrida, empezando en 1 como en BehaviorSpace.
+    pub numero: u32,
+    pub semilla: u64,
+    /// Valor de cada parámetro barrido, en el orden de las variaciones.
+    pub valores: Vec<Value>,
+    /// Último día simulado.
+    pub paso: u32,
+    /// Valores de los reporteros, en el orden de `REPORTEROS`.
+    pub reporteros: Vec<String>,
+    /// Picos y poblaciones finales, para el resumen bootstrap.
+    pub desenlace: Desenlace,
+}
+
+impl Experimento {
+    /// Todas las combinaciones de valores; la última variación es la que cambia más rápido.
+    pub fn combinaciones(&self) -> Vec<Vec<Value>> {
+        self.variaciones.iter().fold(vec![Vec::new()], |combinaciones, variacion| {
+            combinaciones.iter()
+                .flat_map(|previa| variacion.valores.iter().map(move |valor| {
+                    let mut combinacion = previa.clone();
+                    combinacion.push(valor.clone());
+                    combinacion
+                }))
+                .collect()
+        })
+    }
+
+    /// Parámetros de una combinación de valores: los de la base con los barridos sustituidos.
+    pub fn parametros_de(&self, combinacion: &[Value]) -> Result<Parametros, String> {
+        let mut arbol = self.base.a_arbol();
+        for (variacion, valor) in self.variaciones.iter().zip(combinacion) {
+            parametros::asignar_en_arbol(&mut arbol, &variacion.nombre, valor.clone())
+                .map_err(|e| format!("{}: {}", variacion.nombre, e))?;
+        }
+        let mut parametros = Parametros::desde_arbol(arbol)?;
+        if self.numeros_comunes {
+            parametros.subflujos_aleatorios = true;
+        }
+        Ok(parametros)
+    }
+
+    /// Ejecuta todas las corridas. `progreso` se llama al terminar cada una.
+    pub fn ejecutar(&self, mut progreso: impl FnMut(&ResultadoCorrida)) -> Result<Vec<ResultadoCorrida>, String> {
+        // Se validan todas las combinaciones antes de empezar, no a mitad del barrido.
+        let combinaciones = self.combinaciones();
+        let parametros: Vec<Parametros> = combinaciones.iter().map(|c| self.parametros_de(c)).collect::<Result<_, _>>()?;
+        let mut resultados = Vec::new();
+        let mut numero = 1;
+        for (combinacion, parametros) in combinaciones.into_iter().zip(parametros) {
+            for repeticion in 0..self.repeticiones {
+                let desplazamiento = if self.numeros_comunes { repeticion } else { numero - 1 };
+                let semilla = self.semilla_base.wrapping_add(desplazamiento as u64);
+                let mut constructor = SimulacionBuilder::new()
+                    .parametros(parametros.clone())
+                    .semilla(semilla)
+                    .retencion(Retencion::DESACTIVADA);
+                if let Some(arranque) = &self.arranque {
+                    constructor = constructor.arranque(arranque);
+                }
+                let resultado = correr(numero, constructor, combinacion.clone(), self.dias);
+                progreso(&resultado);
+                resultados.push(resultado);
+                numero += 1;
+            }
+        }
+        Ok(resultados)
+    }
+
+    /// Escribe los resultados en la tabla ancha de BehaviorSpace: seis líneas de cabecera
+    /// (título, modelo, experimento, fecha y dos líneas de ajustes), el encabezado de columnas
+    /// y una fila por corrida. Todos los campos van entre comillas, como en NetLogo.
+    pub fn tabla_behaviorspace(&self, resultados: &[ResultadoCorrida]) -> String {
+        let linea = |campos: &[String]| -> String {
+            let citados: Vec<String> = campos.iter().map(|c| format!("\"{}\"", c.replace('"', "\"\""))).collect();
+            citados.join(",") + "\n"
+        };
+
+        let mut tabla = String::new();
+        tabla.push_str(&linea(&[format!("BehaviorSpace results ({} {})", env!("CARGO_PKG_NAME"), env!("CARGO_PKG_VERSION"))]));
+        tabla.push_str(&linea(&[env!("CARGO_PKG_NAME").to_string()]));
+        tabla.push_str(&linea(std::slice::from_ref(&self.nombre)));
+        tabla.push_str(&linea(&[fecha_actual()]));
+        tabla.push_str(&linea(&["dias".to_string(), "repeticiones".to_string(), "semilla_base".to_string()]));
+        tabla.push_str(&linea(&[self.dias.to_string(), self.repeticiones.to_string(), self.semilla_base.to_string()]));
+
+        let mut encabezado = vec!["[run number]".to_string()];
+        encabezado.extend(self.variaciones.iter().map(|v| v.nombre.clone()));
+        encabezado.push("semilla".to_string());
+        encabezado.push("[step]".to_string());
+        encabezado.extend(REPORTEROS.iter().map(|r| r.to_string()));
+        tabla.push_str(&linea(&encabezado));
+
+        for resultado in resultados {
+            let mut fila = vec![resultado.numero.to_string()];
+            fila.extend(resultado.valores.iter().map(texto_valor));
+            fila.push(resultado.semilla.to_string());
+            fila.push(resultado.paso.to_string());
+            fila.extend(resultado.reporteros.iter().cloned());
+            tabla.push_str(&linea(&fila));
+        }
+        tabla
+    }
+
+    /// Resume cada combinación con intervalos de confianza bootstrap de sus resultados clave:
+    /// `<parámetros barridos...>,variable,n,media,ic95_inferior,ic95_superior,minimo,maximo`.
+    pub fn tabla_resumen(&self, resultados: &[ResultadoCorrida]) -> String {
+        let mut tabla: String = self.variaciones.iter().map(|v| format!("{},", v.nombre)).collect();
+        tabla.push_str(remuestreo::ENCABEZADO_CSV);
+        tabla.push('\n');
+        for combinacion in self.combinaciones() {
+            let desenlaces: Vec<Desenlace> = resultados.iter()
+                .filter(|r| r.valores == combinacion)
+                .map(|r| r.desenlace)
+                .collect();
+            let prefijo: String = combinacion.iter().map(|v| format!("{},", texto_valor(v))).collect();
+            tabla.push_str(&remuestreo::filas_csv(&prefijo, &desenlaces));
+        }
+        tabla
+    }
+
+    /// Resume cada combinación en una fila para buscar regímenes de coexistencia:
+    /// `<parámetros barridos...>,corridas,coexistencia,...`. `coexistencia` es la fracción de
+    /// corridas que terminan con el depredador y alguna presa vivos; las otras fracciones, las
+    /// que terminan sin presas o sin depredador.
+    pub fn tabla_barrido(&self, resultados: &[ResultadoCorrida]) -> String {
+        let mut tabla: String = self.variaciones.iter().map(|v| format!("{},", v.nombre)).collect();
+        tabla.push_str(ENCABEZADO_BARRIDO);
+        tabla.push('\n');
+        for combinacion in self.combinaciones() {
+            let desenlaces: Vec<Desenlace> = resultados.iter()
+                .filter(|r| r.valores == combinacion)
+                .map(|r| r.desenlace)
+                .collect();
+            let n = desenlaces.len().max(1) as f64;
+            let fraccion = |cumple: fn(&Desenlace) -> bool| desenlaces.iter().filter(|d| cumple(d)).count() as f64 / n;
+            let media = |valor: fn(&Desenlace) -> u32| desenlaces.iter().map(|d| valor(d) as f64).sum::<f64>() / n;
+            let prefijo: String = combinacion.iter().map(|v| format!("{},", texto_valor(v))).collect();
+            tabla.push_str(&format!(
+                "{}{},{:.4},{:.4},{:.4},{:.1},{:.1}\n",
+                prefijo,
+                desenlaces.len(),
+                fraccion(Desenlace::coexisten),
+                fraccion(|d| d.conejos_finales + d.cabras_finales == 0),
+                fraccion(|d| d.depredador_muerto),
+                media(Desenlace::vida_depredador),
+                media(|d| d.conejos_finales + d.cabras_finales),
+            ));
+        }
+        tabla
+    }
+}
+
+/// Ejecuta una corrida y calcula sus reporteros.
+fn correr(numero: u32, constructor: SimulacionBuilder, valores: Vec<Value>, dias: u32) -> ResultadoCorrida {
+    let mut sim = constructor.construir();
+    let semilla = sim.semilla();
+    let (mut nacimientos, mut muertes, mut cazas) = (0u64, 0u64, 0u64);
+    let mut desenlace = Desenlace::desde(sim.dia);
+    let (conejos, cabras) = sim.contar_especies();
+    desenlace.observar(sim.dia, conejos as u32, cabras as u32);
+    for _ in 0..dias {
+        if !sim.depredador.vivo {
+            break;
+        }
+        sim.avanzar_dia();
+        nacimientos += sim.estadisticas_dia.nacimientos as u64;
+        muertes += sim.estadisticas_dia.muertes as u64;
+        cazas += sim.estadisticas_dia.cazas as u64;
+        let (conejos, cabras) = sim.contar_especies();
+        desenlace.observar(sim.dia, conejos as u32, cabras as u32);
+    }
+    desenlace.depredador_muerto = !sim.depredador.vivo;
+    let (conejos, cabras) = sim.contar_especies();
+    let reporteros = vec![
+        conejos.to_string(),
+        cabras.to_string(),
+        format!("{:.3}", sim.depredador.reserva_comida_kg),
+        sim.depredador.vivo.to_string(),
+        nacimientos.to_string(),
+        muertes.to_string(),
+        cazas.to_string(),
+    ];
+    ResultadoCorrida { numero, semilla, valores, paso: sim.dia, reporteros, desenlace }
+}
+
+/// Las cadenas se escriben sin las comillas de JSON; el resto, tal cual.
+fn texto_valor(valor: &Value) -> String {
+    match valor {
+        Value::String(texto) => texto.clone(),
+        otro => otro.to_string(),
+    }
+}
+
+/// Fecha y hora UTC en el formato de BehaviorSpace (`MM/dd/yyyy HH:mm:ss:SSS +0000`).
+fn fecha_actual() -> String {
+    let ahora = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default();
+    let segundos = ahora.as_secs();
+    let (anio, mes, dia) = fecha_civil((segundos / 86_400) as i64);
+    let segundos_dia = segundos % 86_400;
+    format!(
+        "{:02}/{:02}/{:04} {:02}:{:02}:{:02}:{:03} +0000",
+        mes, dia, anio, segundos_dia / 3600, segundos_dia % 3600 / 60, segundos_dia % 60, ahora.subsec_millis()
+    )
+}
+
+/// Convierte días desde 1970-01-01 en (año, mes, día) del calendario gregoriano.
+fn fecha_civil(dias: i64) -> (i64, u32, u32) {
+    let z = dias + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let dia = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let mes = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let anio = yoe + era * 400 + if mes <= 2 { 1 } else { 0 };
+    (anio, mes, dia)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn barrido(pasos: u32) -> Experimento {
+        Experimento {
+            nombre: "prueba".to_string(),
+            base: Parametros::default(),
+            variaciones: vec![Variacion::lineal("CONEJO_EDAD_MAXIMA_DIAS", 1000.0, 2000.0, pasos).unwrap()],
+            repeticiones: 1,
+            dias: 2,
+            semilla_base: 1,
+            numeros_comunes: false,
+            arranque: None,
+        }
+    }
+
+    #[test]
+    fn un_barrido_entero_con_saltos_fraccionarios_falla_antes_de_empezar() {
+        let mut corridas = 0;
+        let error = barrido(4).ejecutar(|_| corridas += 1).unwrap_err();
+        assert_eq!(corridas, 0);
+        assert!(error.contains("es entero"), "{}", error);
+    }
+
+    #[test]
+    fn los_valores_enteros_escritos_como_decimales_se_aceptan() {
+        let experimento = barrido(3);
+        let combinacion = [Value::from(1500.0)];
+        assert_eq!(experimento.parametros_de(&combinacion).unwrap().conejo_edad_maxima_dias, 1500);
+        assert_eq!(experimento.ejecutar(|_| {}).unwrap().len(), 3);
+    }
+}
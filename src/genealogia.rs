@@ -0,0 +1,156 @@
+// src/genealogia.rs
+
+// Genealogía de las presas y registro de cazas, exportable como grafo DOT para Graphviz.
+// Se alimenta de los sucesos del día (ver `bus`): cada parto añade a las crías con su madre
+// y cada caza apunta el día en que cayó la presa. Con eso se dibuja quién desciende de quién
+// y, si se pide, qué linajes fueron los que alimentaron al depredador.
+//
+// Una corrida larga tiene decenas de miles de nacimientos, más de lo que Graphviz dibuja
+// con soltura, así que lo normal es exportar un solo linaje (los antepasados y descendientes
+// de una presa) o una ventana de días:
+//
+//   simulador run --dias 365 --genealogia genealogia.dot --linaje 12 --cazas
+//   dot -Tsvg genealogia.dot -o genealogia.svg
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fmt::Write;
+
+use crate::bus::Suceso;
+use crate::entidades::Especie;
+use crate::eventos::Evento;
+use crate::simulacion::Simulacion;
+
+/// Lo que se sabe de una presa.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Individuo {
+    pub especie: Especie,
+    /// `None` en la población inicial y en las presas añadidas a mano.
+    pub madre: Option<u64>,
+    /// Día en que nació; 0 en las que no nacieron durante la corrida.
+    pub nacimiento: u32,
+    /// Día en que la cazó el depredador.
+    pub cazada: Option<u32>,
+}
+
+/// Qué parte de la genealogía se exporta.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FiltroGenealogia {
+    /// Solo esta presa, sus antepasados y sus descendientes.
+    pub linaje: Option<u64>,
+    /// Solo las presas nacidas (y las cazas ocurridas) entre estos días, ambos incluidos.
+    pub desde: Option<u32>,
+    pub hasta: Option<u32>,
+    /// Añade al depredador con una arista a cada presa que cazó.
+    pub cazas: bool,
+}
+
+impl FiltroGenealogia {
+    fn en_ventana(&self, dia: u32) -> bool {
+        self.desde.is_none_or(|desde| dia >= desde) && self.hasta.is_none_or(|hasta| dia <= hasta)
+    }
+}
+
+/// Genealogía de una corrida, por id de presa.
+#[derive(Debug, Clone, Default)]
+pub struct Genealogia {
+    individuos: BTreeMap<u64, Individuo>,
+}
+
+impl Genealogia {
+    /// Anota los partos y cazas del día que acaba de simular `sim`.
+    pub fn anotar_dia(&mut self, sim: &Simulacion) {
+        for (_, suceso) in sim.sucesos_dia() {
+            match suceso {
+                Suceso::Parto { madre, especie, crias } => {
+                    self.conocer(*madre, *especie);
+                    for &cria in crias {
+                        self.individuos.insert(cria, Individuo { especie: *especie, madre: Some(*madre), nacimiento: sim.dia, cazada: None });
+                    }
+                }
+                Suceso::Notable(Evento::Caza { id, especie, .. }) => self.conocer(*id, *especie).cazada = Some(sim.dia),
+                _ => {}
+            }
+        }
+    }
+
+    /// La presa `id`, que se da de alta como fundadora si todavía no se conocía.
+    fn conocer(&mut self, id: u64, especie: Especie) -> &mut Individuo {
+        self.individuos.entry(id).or_insert(Individuo { especie, madre: None, nacimiento: 0, cazada: None })
+    }
+
+    /// Ids de la presa `id`, todos sus antepasados conocidos y todos sus descendientes.
+    fn linaje(&self, id: u64) -> BTreeSet<u64> {
+        let mut hijos: HashMap<u64, Vec<u64>> = HashMap::new();
+        for (&cria, individuo) in &self.individuos {
+            if let Some(madre) = individuo.madre {
+                hijos.entry(madre).or_default().push(cria);
+            }
+        }
+        let mut linaje = BTreeSet::new();
+        let mut ancestro = self.individuos.get(&id).and_then(|i| i.madre);
+        while let Some(madre) = ancestro {
+            linaje.insert(madre);
+            ancestro = self.individuos.get(&madre).and_then(|i| i.madre);
+        }
+        let mut pendientes = vec![id];
+        while let Some(actual) = pendientes.pop() {
+            if linaje.insert(actual) {
+                pendientes.extend(hijos.get(&actual).into_iter().flatten());
+            }
+        }
+        linaje
+    }
+
+    /// Grafo DOT de la parte de la genealogía que deja pasar `filtro`: una arista de cada madre
+    /// a cada cría y, con `cazas`, del depredador a cada presa cazada. Las madres que no pasan
+    /// el filtro pero tienen crías que sí se dibujan con borde discontinuo, para no perder el
+    /// parentesco en los límites de la ventana.
+    pub fn a_dot(&self, filtro: &FiltroGenealogia) -> String {
+        let linaje = filtro.linaje.map(|id| self.linaje(id));
+        let incluidos: BTreeSet<u64> = self.individuos.iter()
+            .filter(|(id, _)| linaje.as_ref().is_none_or(|linaje| linaje.contains(id)))
+            .filter(|(_, individuo)| filtro.en_ventana(individuo.nacimiento) || individuo.cazada.is_some_and(|dia| filtro.cazas && filtro.en_ventana(dia)))
+            .map(|(&id, _)| id)
+            .collect();
+        let contexto: BTreeSet<u64> = incluidos.iter()
+            .filter_map(|id| self.individuos[id].madre)
+            .filter(|madre| !incluidos.contains(madre))
+            .collect();
+
+        let mut dot = String::from("digraph genealogia {\n    rankdir=LR;\n    node [shape=ellipse, style=filled, fontname=\"Helvetica\"];\n");
+        for id in incluidos.iter().chain(&contexto) {
+            let Some(individuo) = self.individuos.get(id) else {
+                continue;
+            };
+            let (nombre, relleno) = match individuo.especie {
+                Especie::Conejo => ("Conejo", "#e8d5b7"),
+                Especie::Cabra => ("Cabra", "#c9d8e8"),
+            };
+            let mut etiqueta = format!("{} #{}\\nnace día {}", nombre, id, individuo.nacimiento);
+            if let Some(dia) = individuo.cazada {
+                etiqueta.push_str(&format!("\\ncazada día {}", dia));
+            }
+            let estilo = if contexto.contains(id) { "filled,dashed" } else { "filled" };
+            let borde = if individuo.cazada.is_some() { ", color=\"#c0392b\", penwidth=2" } else { "" };
+            let _ = writeln!(dot, "    p{} [label=\"{}\", fillcolor=\"{}\", style=\"{}\"{}];", id, etiqueta, relleno, estilo, borde);
+        }
+        for id in &incluidos {
+            if let Some(madre) = self.individuos[id].madre {
+                let _ = writeln!(dot, "    p{} -> p{};", madre, id);
+            }
+        }
+        if filtro.cazas {
+            let cazadas: Vec<(u64, u32)> = incluidos.iter()
+                .filter_map(|id| self.individuos[id].cazada.filter(|&dia| filtro.en_ventana(dia)).map(|dia| (*id, dia)))
+                .collect();
+            if !cazadas.is_empty() {
+                dot.push_str("    depredador [label=\"Depredador\", shape=doublecircle, fillcolor=\"#c0392b\", fontcolor=white];\n");
+                for (id, dia) in cazadas {
+                    let _ = writeln!(dot, "    depredador -> p{} [color=\"#c0392b\", style=dashed, label=\"día {}\"];", id, dia);
+                }
+            }
+        }
+        dot.push_str("}\n");
+        dot
+    }
+}
@@ -0,0 +1,278 @@
+// src/graphql.rs
+
+// Endpoint GraphQL (feature `graphql`) para consultar el estado de una simulación.
+// En lugar de descargar instantáneas completas, cada cliente pide exactamente los campos
+// que necesita: conteos por especie, sexo y clase de edad, el historial de la reserva del
+// depredador, las estadísticas del día, etc. La mutación `avanzar` hace correr los días y
+// `establecerParametro` cambia un parámetro en caliente.
+//
+// El servidor HTTP es mínimo y está escrito a mano: `POST /graphql` con el cuerpo JSON
+// habitual (`{"query": ..., "variables": ...}`) y `GET /` sirve GraphiQL para explorar.
+
+use std::sync::Mutex;
+
+use async_graphql::http::GraphiQLSource;
+use async_graphql::{Context, EmptySubscription, Enum, Object, Result, Schema, SimpleObject};
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+use tokio::net::{TcpListener, TcpStream};
+
+use crate::entidades::{self, Sexo};
+use crate::simulacion::Simulacion;
+
+/// Esquema completo del endpoint.
+pub type Esquema = Schema<Consulta, Mutacion, EmptySubscription>;
+
+/// Especie de presa tal como se expone en GraphQL.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Enum)]
+pub enum Especie {
+    Conejo,
+    Cabra,
+}
+
+impl From<entidades::Especie> for Especie {
+    fn from(especie: entidades::Especie) -> Self {
+        match especie {
+            entidades::Especie::Conejo => Especie::Conejo,
+            entidades::Especie::Cabra => Especie::Cabra,
+        }
+    }
+}
+
+impl From<Especie> for entidades::Especie {
+    fn from(especie: Especie) -> Self {
+        match especie {
+            Especie::Conejo => entidades::Especie::Conejo,
+            Especie::Cabra => entidades::Especie::Cabra,
+        }
+    }
+}
+
+/// Clase de edad: las juveniles aún no alcanzan la edad reproductiva de su especie.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Enum)]
+pub enum ClaseEdad {
+    Juvenil,
+    Adulto,
+}
+
+/// Presas vivas de una especie y clase de edad.
+#[derive(Debug, Clone, SimpleObject)]
+pub struct ConteoPoblacion {
+    pub especie: Especie,
+    pub clase_edad: ClaseEdad,
+    pub machos: u32,
+    pub hembras: u32,
+    pub total: u32,
+}
+
+/// Reserva del depredador al final de un día.
+#[derive(Debug, Clone, SimpleObject)]
+pub struct PuntoReserva {
+    pub dia: u32,
+    pub reserva_kg: f64,
+}
+
+#[derive(Debug, Clone, SimpleObject)]
+pub struct EstadoDepredador {
+    pub reserva_kg: f64,
+    pub vivo: bool,
+    pub en_peligro: bool,
+}
+
+#[derive(Debug, Clone, SimpleObject)]
+pub struct EstadisticasDia {
+    pub nacimientos: u32,
+    pub muertes: u32,
+    pub cazas: u32,
+}
+
+/// La simulación servida y el historial que se va acumulando día a día.
+struct Estado {
+    sim: Simulacion,
+    historial_reserva: Vec<PuntoReserva>,
+}
+
+impl Estado {
+    fn anotar_dia(&mut self) {
+        self.historial_reserva.push(PuntoReserva { dia: self.sim.dia, reserva_kg: self.sim.depredador.reserva_comida_kg });
+    }
+}
+
+/// Ejecuta `f` con acceso exclusivo al estado compartido.
+fn con_estado<T>(ctx: &Context<'_>, f: impl FnOnce(&mut Estado) -> T) -> Result<T> {
+    let mut estado = ctx.data::<Mutex<Estado>>()?.lock().map_err(|_| "estado envenenado")?;
+    Ok(f(&mut estado))
+}
+
+/// Raíz de las consultas.
+pub struct Consulta;
+
+#[Object]
+impl Consulta {
+    /// Último día simulado.
+    async fn dia(&self, ctx: &Context<'_>) -> Result<u32> {
+        con_estado(ctx, |e| e.sim.dia)
+    }
+
+    /// Semilla de la corrida (como texto: no cabe en un Int de GraphQL).
+    async fn semilla(&self, ctx: &Context<'_>) -> Result<String> {
+        con_estado(ctx, |e| e.sim.semilla().to_string())
+    }
+
+    /// Presas vivas agrupadas por especie y clase de edad; `especie` filtra una sola.
+    async fn poblacion(&self, ctx: &Context<'_>, especie: Option<Especie>) -> Result<Vec<ConteoPoblacion>> {
+        con_estado(ctx, |e| {
+            let params = &e.sim.parametros;
+            let mut conteos: Vec<ConteoPoblacion> = [Especie::Conejo, Especie::Cabra].into_iter()
+                .filter(|&esp| especie.is_none_or(|filtro| filtro == esp))
+                .flat_map(|especie| [ClaseEdad::Juvenil, ClaseEdad::Adulto].map(|clase_edad| ConteoPoblacion { especie, clase_edad, machos: 0, hembras: 0, total: 0 }))
+                .collect();
+            for presa in e.sim.presas.iter().filter(|p| p.esta_viva()) {
+                let especie = Especie::from(presa.especie());
+                let edad_reproductiva = match especie {
+                    Especie::Conejo => params.conejo_edad_reproductiva_dias,
+                    Especie::Cabra => params.cabra_edad_reproductiva_dias,
+                };
+                let clase_edad = if presa.edad() < edad_reproductiva { ClaseEdad::Juvenil } else { ClaseEdad::Adulto };
+                if let Some(conteo) = conteos.iter_mut().find(|c| c.especie == especie && c.clase_edad == clase_edad) {
+                    match presa.sexo() {
+                        Sexo::Macho => conteo.machos += 1,
+                        Sexo::Hembra => conteo.hembras += 1,
+                    }
+                    conteo.total += 1;
+                }
+            }
+            conteos
+        })
+    }
+
+    /// Estado actual del depredador.
+    async fn depredador(&self, ctx: &Context<'_>) -> Result<EstadoDepredador> {
+        con_estado(ctx, |e| EstadoDepredador {
+            reserva_kg: e.sim.depredador.reserva_comida_kg,
+            vivo: e.sim.depredador.vivo,
+            en_peligro: e.sim.depredador.en_peligro(&e.sim.parametros),
+        })
+    }
+
+    /// Reserva del depredador día a día, opcionalmente entre `desde` y `hasta` (inclusive).
+    async fn historial_reserva(&self, ctx: &Context<'_>, desde: Option<u32>, hasta: Option<u32>) -> Result<Vec<PuntoReserva>> {
+        con_estado(ctx, |e| {
+            e.historial_reserva.iter()
+                .filter(|p| desde.is_none_or(|d| p.dia >= d) && hasta.is_none_or(|h| p.dia <= h))
+                .cloned()
+                .collect()
+        })
+    }
+
+    /// Nacimientos, muertes y cazas del último día.
+    async fn estadisticas_dia(&self, ctx: &Context<'_>) -> Result<EstadisticasDia> {
+        con_estado(ctx, |e| {
+            let crate::simulacion::EstadisticasDia { nacimientos, muertes, cazas, .. } = e.sim.estadisticas_dia;
+            EstadisticasDia { nacimientos, muertes, cazas }
+        })
+    }
+
+    /// Descripción de los eventos notables del último día.
+    async fn eventos(&self, ctx: &Context<'_>) -> Result<Vec<String>> {
+        con_estado(ctx, |e| e.sim.eventos_dia.iter().filter(|ev| !ev.es_demografico()).map(|ev| ev.descripcion()).collect())
+    }
+
+    /// Resumen del estado en frases, para lectores de pantalla (ver `Simulacion::resumen_texto`).
+    async fn resumen(&self, ctx: &Context<'_>) -> Result<String> {
+        con_estado(ctx, |e| e.sim.resumen_texto(&[]))
+    }
+}
+
+/// Raíz de las mutaciones.
+pub struct Mutacion;
+
+#[Object]
+impl Mutacion {
+    /// Avanza la simulación `dias` días (1 por defecto) y devuelve el día alcanzado.
+    async fn avanzar(&self, ctx: &Context<'_>, #[graphql(default = 1)] dias: u32) -> Result<u32> {
+        con_estado(ctx, |e| {
+            for _ in 0..dias {
+                if !e.sim.depredador.vivo {
+                    break;
+                }
+                e.sim.avanzar_dia();
+                e.anotar_dia();
+            }
+            e.sim.dia
+        })
+    }
+
+    /// Cambia un parámetro en caliente (ver `Simulacion::establecer_parametro`). `valor` va en
+    /// JSON (`"0.02"`, `"{\"min\": 1, \"max\": 3}"`). Devuelve el día en que se aplicó.
+    async fn establecer_parametro(&self, ctx: &Context<'_>, especie: Option<Especie>, nombre: String, valor: String) -> Result<u32> {
+        let valor: serde_json::Value = serde_json::from_str(&valor).map_err(|e| format!("valor inválido: {}", e))?;
+        con_estado(ctx, |e| e.sim.establecer_parametro(especie.map(Into::into), &nombre, valor).map(|()| e.sim.dia))?
+            .map_err(Into::into)
+    }
+}
+
+/// Construye el esquema que sirve la simulación indicada.
+pub fn crear_esquema(sim: Simulacion) -> Esquema {
+    let mut estado = Estado { sim, historial_reserva: Vec::new() };
+    estado.anotar_dia();
+    Schema::build(Consulta, Mutacion, EmptySubscription)
+        .data(Mutex::new(estado))
+        .finish()
+}
+
+/// Acepta conexiones HTTP indefinidamente y atiende cada una en su propia tarea.
+pub async fn servir(esquema: Esquema, listener: TcpListener) -> std::io::Result<()> {
+    loop {
+        let (conexion, _) = listener.accept().await?;
+        let esquema = esquema.clone();
+        tokio::spawn(async move {
+            if let Err(e) = atender(esquema, conexion).await {
+                eprintln!("error en la conexión: {}", e);
+            }
+        });
+    }
+}
+
+/// Atiende una única petición HTTP/1.1 y cierra la conexión.
+async fn atender(esquema: Esquema, conexion: TcpStream) -> std::io::Result<()> {
+    let mut lector = BufReader::new(conexion);
+    let mut linea = String::new();
+    lector.read_line(&mut linea).await?;
+    let mut partes = linea.split_whitespace();
+    let metodo = partes.next().unwrap_or("").to_string();
+    let ruta = partes.next().unwrap_or("").to_string();
+
+    // Cabeceras: solo interesa la longitud del cuerpo.
+    let mut longitud = 0;
+    loop {
+        linea.clear();
+        if lector.read_line(&mut linea).await? == 0 || linea.trim().is_empty() {
+            break;
+        }
+        if let Some((nombre, valor)) = linea.split_once(':') {
+            if nombre.trim().eq_ignore_ascii_case("content-length") {
+                longitud = valor.trim().parse().unwrap_or(0);
+            }
+        }
+    }
+    let mut cuerpo = vec![0; longitud];
+    lector.read_exact(&mut cuerpo).await?;
+
+    let (estado, tipo, respuesta) = match (metodo.as_str(), ruta.as_str()) {
+        ("GET", "/") => ("200 OK", "text/html; charset=utf-8", GraphiQLSource::build().endpoint("/graphql").finish()),
+        ("POST", "/graphql") => match serde_json::from_slice::<async_graphql::Request>(&cuerpo) {
+            Ok(peticion) => {
+                let resultado = esquema.execute(peticion).await;
+                ("200 OK", "application/json", serde_json::to_string(&resultado).unwrap_or_default())
+            }
+            Err(e) => ("400 Bad Request", "text/plain; charset=utf-8", format!("petición GraphQL inválida: {}", e)),
+        },
+        _ => ("404 Not Found", "text/plain; charset=utf-8", "use POST /graphql o GET / para GraphiQL".to_string()),
+    };
+
+    let mut conexion = lector.into_inner();
+    let cabecera = format!("HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n", estado, tipo, respuesta.len());
+    conexion.write_all(cabecera.as_bytes()).await?;
+    conexion.write_all(respuesta.as_bytes()).await?;
+    conexion.shutdown().await
+}
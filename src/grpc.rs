@@ -0,0 +1,128 @@
+// src/grpc.rs
+
+// Servicio gRPC (feature `grpc`) para orquestar muchas simulaciones de forma remota.
+// Cada instancia se identifica por un id numérico y guarda su propio canal de difusión,
+// por el que se emite el estado de cada día a los clientes suscritos.
+
+// `tonic::Status` es grande, pero es el tipo de error que impone el protocolo.
+#![allow(clippy::result_large_err)]
+
+use std::collections::HashMap;
+use std::pin::Pin;
+use std::sync::Mutex;
+
+use tokio::sync::broadcast;
+use tokio_stream::wrappers::BroadcastStream;
+use tokio_stream::{Stream, StreamExt};
+use tonic::{Request, Response, Status};
+
+use crate::simulacion::Simulacion;
+
+/// Código generado a partir de `proto/simulacion.proto`.
+pub mod proto {
+    tonic::include_proto!("simulacion");
+}
+
+use proto::servicio_simulacion_server::ServicioSimulacion;
+use proto::*;
+
+pub use proto::servicio_simulacion_server::ServicioSimulacionServer;
+
+/// Cuántos estados puede acumular un suscriptor lento antes de empezar a perder días.
+const CAPACIDAD_CANAL: usize = 1024;
+
+/// Una simulación administrada por el servidor junto con su canal de difusión.
+struct Instancia {
+    sim: Simulacion,
+    canal: broadcast::Sender<EstadoSimulacion>,
+}
+
+/// Implementación del servicio: un registro de simulaciones protegido por un mutex.
+#[derive(Default)]
+pub struct Servidor {
+    instancias: Mutex<HashMap<u64, Instancia>>,
+    siguiente_id: Mutex<u64>,
+}
+
+impl Servidor {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Ejecuta `f` sobre la instancia pedida, o devuelve `NOT_FOUND` si no existe.
+    fn con_instancia<T>(&self, id: u64, f: impl FnOnce(&mut Instancia) -> T) -> Result<T, Status> {
+        let mut instancias = self.instancias.lock().map_err(|_| Status::internal("registro envenenado"))?;
+        let instancia = instancias.get_mut(&id)
+            .ok_or_else(|| Status::not_found(format!("no existe la simulación {}", id)))?;
+        Ok(f(instancia))
+    }
+}
+
+/// Convierte el estado de la simulación en el mensaje del protocolo.
+fn estado(id: u64, sim: &Simulacion) -> EstadoSimulacion {
+    let (conejos, cabras) = sim.contar_especies();
+    EstadoSimulacion {
+        id_simulacion: id,
+        dia: sim.dia,
+        conejos: conejos as u64,
+        cabras: cabras as u64,
+        reserva_depredador_kg: sim.depredador.reserva_comida_kg,
+        depredador_vivo: sim.depredador.vivo,
+        nacimientos: sim.estadisticas_dia.nacimientos,
+        muertes: sim.estadisticas_dia.muertes,
+        cazas: sim.estadisticas_dia.cazas,
+    }
+}
+
+#[tonic::async_trait]
+impl ServicioSimulacion for Servidor {
+    async fn create_simulation(&self, request: Request<CreateSimulationRequest>) -> Result<Response<CreateSimulationResponse>, Status> {
+        let sim = match request.into_inner().semilla {
+            Some(semilla) => Simulacion::con_semilla(semilla),
+            None => Simulacion::new(),
+        };
+        let semilla = sim.semilla();
+
+        let id = {
+            let mut siguiente = self.siguiente_id.lock().map_err(|_| Status::internal("contador envenenado"))?;
+            *siguiente += 1;
+            *siguiente
+        };
+        let (canal, _) = broadcast::channel(CAPACIDAD_CANAL);
+        self.instancias.lock().map_err(|_| Status::internal("registro envenenado"))?
+            .insert(id, Instancia { sim, canal });
+
+        Ok(Response::new(CreateSimulationResponse { id_simulacion: id, semilla }))
+    }
+
+    async fn step(&self, request: Request<StepRequest>) -> Result<Response<EstadoSimulacion>, Status> {
+        let peticion = request.into_inner();
+        let id = peticion.id_simulacion;
+        let final_ = self.con_instancia(id, |instancia| {
+            for _ in 0..peticion.dias.max(1) {
+                instancia.sim.avanzar_dia();
+                // Un error aquí solo significa que no hay suscriptores en este momento.
+                let _ = instancia.canal.send(estado(id, &instancia.sim));
+            }
+            estado(id, &instancia.sim)
+        })?;
+        Ok(Response::new(final_))
+    }
+
+    async fn get_state(&self, request: Request<GetStateRequest>) -> Result<Response<EstadoSimulacion>, Status> {
+        let id = request.into_inner().id_simulacion;
+        let actual = self.con_instancia(id, |instancia| estado(id, &instancia.sim))?;
+        Ok(Response::new(actual))
+    }
+
+    type SubscribeStream = Pin<Box<dyn Stream<Item = Result<EstadoSimulacion, Status>> + Send>>;
+
+    async fn subscribe(&self, request: Request<SubscribeRequest>) -> Result<Response<Self::SubscribeStream>, Status> {
+        let id = request.into_inner().id_simulacion;
+        let receptor = self.con_instancia(id, |instancia| instancia.canal.subscribe())?;
+        let flujo = BroadcastStream::new(receptor).map(|resultado| {
+            resultado.map_err(|e| Status::data_loss(format!("suscriptor demasiado lento: {}", e)))
+        });
+        Ok(Response::new(Box::pin(flujo)))
+    }
+}
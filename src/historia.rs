@@ -0,0 +1,71 @@
+// src/historia.rs
+
+// Historia de la población: conejos, cabras y reserva del depredador al final de cada día,
+// en un búfer circular de tamaño fijo. Con las cifras del día no se ven las oscilaciones del
+// sistema; con la curva de los últimos años, sí. El visualizador la dibuja en un gráfico al
+// pie de la ventana, y cualquier otra interfaz puede anotarla igual día a día.
+
+use std::collections::VecDeque;
+
+use crate::simulacion::Simulacion;
+
+/// Días que se conservan por defecto: diez años.
+pub const CAPACIDAD_HISTORIA: usize = 3650;
+
+/// Estado de la población al terminar un día.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MuestraHistoria {
+    pub dia: u32,
+    pub conejos: u32,
+    pub cabras: u32,
+    pub reserva_kg: f64,
+}
+
+/// Los últimos días de una corrida, del más antiguo al más reciente.
+#[derive(Debug, Clone)]
+pub struct Historia {
+    muestras: VecDeque<MuestraHistoria>,
+    capacidad: usize,
+}
+
+impl Default for Historia {
+    fn default() -> Self {
+        Self::new(CAPACIDAD_HISTORIA)
+    }
+}
+
+impl Historia {
+    pub fn new(capacidad: usize) -> Self {
+        Self { muestras: VecDeque::with_capacity(capacidad.min(CAPACIDAD_HISTORIA)), capacidad: capacidad.max(1) }
+    }
+
+    /// Anota el estado de `sim`; si el búfer está lleno se descarta el día más antiguo. Si el
+    /// día no es posterior al último anotado (se retrocedió o se cargó otra partida), se
+    /// olvidan los días desde ese en adelante, para que la curva siga a la corrida que se ve.
+    pub fn anotar(&mut self, sim: &Simulacion) {
+        while self.muestras.back().is_some_and(|m| m.dia >= sim.dia) {
+            self.muestras.pop_back();
+        }
+        if self.muestras.len() == self.capacidad {
+            self.muestras.pop_front();
+        }
+        let (conejos, cabras) = sim.contar_especies();
+        self.muestras.push_back(MuestraHistoria {
+            dia: sim.dia,
+            conejos: conejos as u32,
+            cabras: cabras as u32,
+            reserva_kg: sim.depredador.reserva_comida_kg,
+        });
+    }
+
+    pub fn muestras(&self) -> &VecDeque<MuestraHistoria> {
+        &self.muestras
+    }
+
+    /// Mayor población de una especie y mayor reserva del depredador de los días guardados.
+    pub fn maximos(&self) -> (u32, f64) {
+        self.muestras.iter().fold((0, 0.0), |(poblacion, reserva), m| {
+            (poblacion.max(m.conejos).max(m.cabras), reserva.max(m.reserva_kg))
+        })
+    }
+}
@@ -0,0 +1,406 @@
+// src/idioma.rs
+
+// Localización de los textos de la interfaz (español e inglés).
+// Cada texto tiene un identificador (`Texto`) y una fila en la tabla con su traducción a cada
+// idioma, así que añadir un idioma es añadir una columna. El idioma se elige en el archivo
+// de configuración (`idioma = "en"`) o, si no se indica, a partir del locale del sistema.
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Idiomas disponibles; el orden coincide con las columnas de la tabla de textos.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum Idioma {
+    #[default]
+    Es,
+    En,
+}
+
+/// Identificadores de los textos de la interfaz.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Texto {
+    TituloVentana,
+    Conejo,
+    Cabra,
+    Dia,
+    Conejos,
+    Cabras,
+    PoblacionTotal,
+    ReservaDepredador,
+    Depredadores,
+    EstadoOptimo,
+    EstadoMinimo,
+    EstadoPeligro,
+    DepredadorMuerto,
+    PresasExtinguidas,
+    EnlaceInvalido,
+    EnlaceCorrida,
+    ValoresPorDefecto,
+    EspecieImportada,
+    EspeciesIgnoradas,
+    RequierenReinicio,
+    RecargaRechazada,
+    VentanaEstadisticas,
+    VentanaInspector,
+    VentanaEventos,
+    VentanaParametros,
+    Nacimientos,
+    Muertes,
+    Cazas,
+    Escapadas,
+    Contagios,
+    Infectadas,
+    Salud,
+    Susceptible,
+    Infectada,
+    Recuperada,
+    Gestacion,
+    Estacion,
+    Primavera,
+    Verano,
+    Otono,
+    Invierno,
+    Hoy,
+    Presa,
+    Especie,
+    Sexo,
+    Macho,
+    Hembra,
+    Edad,
+    Peso,
+    Viva,
+    Muerta,
+    DiasUnidad,
+    SinSeleccion,
+    AyudaInspector,
+    SinEventos,
+    NacimientosYMuertes,
+    Aplicar,
+    Sonido,
+    SonidoNoDisponible,
+    AvisoExtincion,
+    AvisoDepredadorPeligro,
+    Pausa,
+    SuperposicionFallida,
+    Edicion,
+    AyudaEdicion,
+    Desafio,
+    DesafioSuperado,
+    DesafioFallido,
+    DiasEquilibrio,
+    BiomasaCosechada,
+    Puntos,
+    VentanaRecords,
+    CoexistenciaMasLarga,
+    PoblacionMaxima,
+    ColapsoMasRapido,
+    Logros,
+    SinLogros,
+    LogroDesbloqueado,
+    LogroUnAno,
+    LogroDecada,
+    LogroMilPresas,
+    LogroColapsoRelampago,
+    LogroDesafioSuperado,
+    RecordsNoGuardados,
+    Dificultad,
+    Facil,
+    Normal,
+    Dificil,
+    Tutorial,
+    Continuar,
+    TutorialBienvenida,
+    TutorialReserva,
+    TutorialParametros,
+    TutorialPeligro,
+    TutorialExtincion,
+    TutorialFin,
+    Simulacion,
+    NuevaPestana,
+    CerrarPestana,
+    NuevaCorrida,
+    VentanaSemillas,
+    VentanaPoblacion,
+    VentanaFases,
+    PresasTotales,
+    Maximo,
+    Semilla,
+    Repetir,
+    SinCorridas,
+    DepredadorVivo,
+    DepredadorSinVida,
+    SaltarHasta,
+    Saltando,
+    PrimeraExtincion,
+    DepredadorEntraEnPeligro,
+    MuerteDelDepredador,
+    UnaAlarma,
+    Alarma,
+    Reanudar,
+    Turbo,
+    DiasPorSegundo,
+    RitmoObjetivo,
+    UnaSobrepoblacion,
+    AvisoSobrepoblacion,
+    UnBrote,
+    AvisoBrote,
+    UnaOla,
+    AvisoOla,
+    ClasesEdad,
+    ClaseEdad,
+    Juvenil,
+    Subadulto,
+    Adulto,
+    Senescente,
+    Vulnerabilidad,
+    Despensa,
+    Pasto,
+    Depuracion,
+    Entidades,
+    MemoriaEstimada,
+    FaseCaza,
+    FaseEnvejecimiento,
+    FaseReproduccion,
+    FaseLimpieza,
+    TiempoPorDia,
+    PuntosGpu,
+    Rastros,
+    MuertesPorCausa,
+    CausaVejez,
+    CausaEnfermedad,
+    CausaHambre,
+    CausaCaza,
+    CausaSobrepoblacion,
+    CausaMortalidadClase,
+    AyudaOrden,
+    AyudaConsola,
+    ResumenCopiado,
+    PartidaGuardada,
+    PartidaCargada,
+    RepeticionGuardada,
+    AyudaReproductor,
+}
+
+/// Tabla de traducciones: una fila por texto, una columna por idioma (es, en).
+fn traducciones(texto: Texto) -> [&'static str; 2] {
+    match texto {
+        Texto::TituloVentana => ["Simulador de Ecosistema", "Ecosystem Simulator"],
+        Texto::Conejo => ["Conejo", "Rabbit"],
+        Texto::Cabra => ["Cabra", "Goat"],
+        Texto::Dia => ["Día", "Day"],
+        Texto::Conejos => ["Conejos", "Rabbits"],
+        Texto::Cabras => ["Cabras", "Goats"],
+        Texto::PoblacionTotal => ["Población Total", "Total Population"],
+        Texto::ReservaDepredador => ["Reserva Depredador", "Predator Reserve"],
+        Texto::Depredadores => ["Depredadores", "Predators"],
+        Texto::EstadoOptimo => ["Estado: Óptimo", "Status: Optimal"],
+        Texto::EstadoMinimo => ["Estado: Mínimo", "Status: Minimum"],
+        Texto::EstadoPeligro => ["Estado: Peligro", "Status: Danger"],
+        Texto::DepredadorMuerto => ["¡EL DEPREDADOR HA MUERTO!", "THE PREDATOR HAS DIED!"],
+        Texto::PresasExtinguidas => ["¡LAS PRESAS SE HAN EXTINGUIDO!", "THE PREY HAVE GONE EXTINCT!"],
+        Texto::EnlaceInvalido => ["Enlace inválido (se ignora)", "Invalid link (ignored)"],
+        Texto::EnlaceCorrida => ["Enlace de esta corrida", "Link to this run"],
+        Texto::ValoresPorDefecto => ["se usan los valores por defecto", "using default values"],
+        Texto::EspecieImportada => ["Especie importada", "Imported species"],
+        Texto::EspeciesIgnoradas => ["se ignoran las definiciones de especie", "ignoring species definitions"],
+        Texto::RequierenReinicio => ["Requieren reinicio", "Require restart"],
+        Texto::RecargaRechazada => ["Recarga rechazada", "Reload rejected"],
+        Texto::VentanaEstadisticas => ["Estadísticas", "Stats"],
+        Texto::VentanaInspector => ["Inspector", "Inspector"],
+        Texto::VentanaEventos => ["Registro de eventos", "Event Log"],
+        Texto::VentanaParametros => ["Parámetros", "Parameters"],
+        Texto::Nacimientos => ["Nacimientos", "Births"],
+        Texto::Muertes => ["Muertes", "Deaths"],
+        Texto::Cazas => ["Cazas", "Hunts"],
+        Texto::Escapadas => ["Escapadas", "Escapes"],
+        Texto::Contagios => ["Contagios", "Infections"],
+        Texto::Infectadas => ["Infectadas (conejos / cabras)", "Infected (rabbits / goats)"],
+        Texto::Salud => ["Salud", "Health"],
+        Texto::Susceptible => ["Susceptible", "Susceptible"],
+        Texto::Infectada => ["Infectada", "Infected"],
+        Texto::Recuperada => ["Recuperada", "Recovered"],
+        Texto::Gestacion => ["Parto en", "Birth in"],
+        Texto::Estacion => ["Estación", "Season"],
+        Texto::Primavera => ["Primavera", "Spring"],
+        Texto::Verano => ["Verano", "Summer"],
+        Texto::Otono => ["Otoño", "Autumn"],
+        Texto::Invierno => ["Invierno", "Winter"],
+        Texto::Hoy => ["Hoy", "Today"],
+        Texto::Presa => ["Presa", "Prey"],
+        Texto::Especie => ["Especie", "Species"],
+        Texto::Sexo => ["Sexo", "Sex"],
+        Texto::Macho => ["Macho", "Male"],
+        Texto::Hembra => ["Hembra", "Female"],
+        Texto::Edad => ["Edad", "Age"],
+        Texto::Peso => ["Peso", "Weight"],
+        Texto::Viva => ["Viva", "Alive"],
+        Texto::Muerta => ["Muerta", "Dead"],
+        Texto::DiasUnidad => ["días", "days"],
+        Texto::SinSeleccion => ["Ninguna presa seleccionada", "No prey selected"],
+        Texto::AyudaInspector => ["Haz clic en una presa para inspeccionarla", "Click a prey to inspect it"],
+        Texto::SinEventos => ["Todavía no hay eventos", "No events yet"],
+        Texto::NacimientosYMuertes => ["Nacimientos y muertes de cada presa", "Births and deaths of each prey"],
+        Texto::Aplicar => ["Aplicar", "Apply"],
+        Texto::Sonido => ["Sonido", "Sound"],
+        Texto::SonidoNoDisponible => ["se continúa sin sonido", "continuing without sound"],
+        Texto::AvisoExtincion => ["Extinción", "Extinction"],
+        Texto::AvisoDepredadorPeligro => ["El depredador entró en estado de peligro", "The predator is in danger"],
+        Texto::Pausa => ["Pausa", "Pause"],
+        Texto::SuperposicionFallida => ["No se pudo escribir la superposición", "Could not write the overlay file"],
+        Texto::Edicion => ["Edición", "Edit"],
+        Texto::AyudaEdicion => ["Clic: añadir presa; clic derecho: retirarla", "Click: add prey; right click: remove it"],
+        Texto::Desafio => ["Desafío", "Challenge"],
+        Texto::DesafioSuperado => ["¡Desafío superado!", "Challenge completed!"],
+        Texto::DesafioFallido => ["Desafío fallido: el depredador murió", "Challenge failed: the predator died"],
+        Texto::DiasEquilibrio => ["Días en equilibrio", "Days in balance"],
+        Texto::BiomasaCosechada => ["Biomasa cosechada", "Harvested biomass"],
+        Texto::Puntos => ["Puntos", "Points"],
+        Texto::VentanaRecords => ["Récords", "Records"],
+        Texto::CoexistenciaMasLarga => ["Coexistencia más larga", "Longest coexistence"],
+        Texto::PoblacionMaxima => ["Población máxima", "Largest population"],
+        Texto::ColapsoMasRapido => ["Colapso más rápido", "Fastest collapse"],
+        Texto::Logros => ["Logros", "Achievements"],
+        Texto::SinLogros => ["Todavía no hay logros", "No achievements yet"],
+        Texto::LogroDesbloqueado => ["Logro desbloqueado", "Achievement unlocked"],
+        Texto::LogroUnAno => ["Un año de coexistencia", "One year of coexistence"],
+        Texto::LogroDecada => ["Una década de coexistencia", "A decade of coexistence"],
+        Texto::LogroMilPresas => ["Mil presas a la vez", "A thousand prey at once"],
+        Texto::LogroColapsoRelampago => ["Colapso en el primer mes", "Collapse within a month"],
+        Texto::LogroDesafioSuperado => ["Desafío superado", "Challenge completed"],
+        Texto::RecordsNoGuardados => ["No se pudieron guardar los récords", "Could not save the records"],
+        Texto::Dificultad => ["Dificultad", "Difficulty"],
+        Texto::Facil => ["Fácil", "Easy"],
+        Texto::Normal => ["Normal", "Normal"],
+        Texto::Dificil => ["Difícil", "Hard"],
+        Texto::Tutorial => ["Tutorial", "Tutorial"],
+        Texto::Continuar => ["Continuar", "Continue"],
+        Texto::TutorialBienvenida => [
+            "Bienvenido. Los círculos blancos son conejos y los marrones, cabras. El círculo grande de arriba es el depredador: su color indica cuánto ha comido.",
+            "Welcome. White circles are rabbits and brown ones are goats. The big circle at the top is the predator: its color shows how well fed it is.",
+        ],
+        Texto::TutorialReserva => [
+            "Esta es la reserva de comida del depredador. Cada día gasta de ella; fíjate en qué pasa cuando baja del consumo mínimo diario",
+            "This is the predator's food reserve. It uses some every day; watch what happens when it drops below the minimum daily consumption",
+        ],
+        Texto::TutorialParametros => [
+            "En la ventana de parámetros puedes cambiar las tasas de reproducción y el consumo del depredador sin reiniciar. Pulsa \"Aplicar\" para probarlos.",
+            "In the parameters window you can change reproduction rates and the predator's consumption without restarting. Press \"Apply\" to try them.",
+        ],
+        Texto::TutorialPeligro => [
+            "La reserva del depredador ha bajado del consumo mínimo: está en peligro (gris) y, si no caza pronto, morirá de hambre. Mínimo diario",
+            "The predator's reserve dropped below the minimum consumption: it is in danger (gray) and will starve unless it hunts soon. Daily minimum",
+        ],
+        Texto::TutorialExtincion => [
+            "Una especie de presa se ha extinguido. El registro de eventos muestra cuándo ocurrió; el depredador depende ahora de una sola presa.",
+            "A prey species has gone extinct. The event log shows when it happened; the predator now depends on a single prey.",
+        ],
+        Texto::TutorialFin => [
+            "El depredador ha muerto y la corrida ha terminado. Prueba otra semilla o cambia los parámetros para buscar el equilibrio.",
+            "The predator has died and the run is over. Try another seed or change the parameters to look for balance.",
+        ],
+        Texto::Simulacion => ["Simulación", "Simulation"],
+        Texto::NuevaPestana => ["Abrir otra simulación con una semilla nueva", "Open another simulation with a new seed"],
+        Texto::CerrarPestana => ["Cerrar esta simulación", "Close this simulation"],
+        Texto::NuevaCorrida => ["Nueva corrida", "New run"],
+        Texto::VentanaSemillas => ["Semillas", "Seeds"],
+        Texto::VentanaPoblacion => ["Población", "Population"],
+        Texto::VentanaFases => ["Diagrama de fases", "Phase plot"],
+        Texto::PresasTotales => ["Presas", "Prey"],
+        Texto::Maximo => ["máx.", "max."],
+        Texto::Semilla => ["Semilla", "Seed"],
+        Texto::Repetir => ["Repetir", "Replay"],
+        Texto::SinCorridas => [
+            "Todavía no hay corridas archivadas. \"Nueva corrida\" guarda aquí la actual.",
+            "No archived runs yet. \"New run\" stores the current one here.",
+        ],
+        Texto::DepredadorVivo => ["depredador vivo", "predator alive"],
+        Texto::DepredadorSinVida => ["depredador muerto", "predator dead"],
+        Texto::SaltarHasta => ["Saltar hasta", "Skip to"],
+        Texto::Saltando => ["Saltando hasta", "Skipping to"],
+        Texto::PrimeraExtincion => ["la próxima extinción", "the next extinction"],
+        Texto::DepredadorEntraEnPeligro => ["el depredador en peligro", "the predator in danger"],
+        Texto::MuerteDelDepredador => ["la muerte del depredador", "the predator's death"],
+        Texto::UnaAlarma => ["una alarma", "an alarm"],
+        Texto::Alarma => ["Alarma", "Alarm"],
+        Texto::Reanudar => ["Reanudar", "Resume"],
+        Texto::Turbo => ["Turbo", "Turbo"],
+        Texto::DiasPorSegundo => ["días/s", "days/s"],
+        Texto::RitmoObjetivo => ["Ritmo fijo", "Target rate"],
+        Texto::UnaSobrepoblacion => ["una sobrepoblación", "an overpopulation"],
+        Texto::AvisoSobrepoblacion => [
+            "Sobrepoblación: mortalidad de emergencia",
+            "Overpopulation: emergency mortality",
+        ],
+        Texto::UnBrote => ["un brote de enfermedad", "a disease outbreak"],
+        Texto::AvisoBrote => ["Brote de enfermedad", "Disease outbreak"],
+        Texto::UnaOla => ["una ola epidémica", "an epidemic wave"],
+        Texto::AvisoOla => ["Ola epidémica", "Epidemic wave"],
+        Texto::ClasesEdad => ["Clases de edad", "Age classes"],
+        Texto::ClaseEdad => ["Clase de edad", "Age class"],
+        Texto::Juvenil => ["Juvenil", "Juvenile"],
+        Texto::Subadulto => ["Subadulto", "Subadult"],
+        Texto::Adulto => ["Adulto", "Adult"],
+        Texto::Senescente => ["Senescente", "Senescent"],
+        Texto::Vulnerabilidad => ["Cazabilidad diaria", "Daily catchability"],
+        Texto::Despensa => ["Despensa", "Cache"],
+        Texto::Pasto => ["Pasto", "Grass"],
+        Texto::Depuracion => ["Depuración", "Debug"],
+        Texto::Entidades => ["Entidades", "Entities"],
+        Texto::MemoriaEstimada => ["Memoria estimada", "Estimated memory"],
+        Texto::FaseCaza => ["Caza", "Hunt"],
+        Texto::FaseEnvejecimiento => ["Envejecimiento", "Aging"],
+        Texto::FaseReproduccion => ["Reproducción", "Reproduction"],
+        Texto::FaseLimpieza => ["Limpieza", "Cleanup"],
+        Texto::TiempoPorDia => ["Tiempo por día", "Time per day"],
+        Texto::PuntosGpu => ["Puntos GPU", "GPU points"],
+        Texto::Rastros => ["Rastros", "Trails"],
+        Texto::MuertesPorCausa => ["Muertes por causa", "Deaths by cause"],
+        Texto::CausaVejez => ["Vejez", "Old age"],
+        Texto::CausaEnfermedad => ["Enfermedad", "Disease"],
+        Texto::CausaHambre => ["Hambre", "Starvation"],
+        Texto::CausaCaza => ["Cazadas", "Hunted"],
+        Texto::CausaSobrepoblacion => ["Sobrepoblación", "Overcrowding"],
+        Texto::CausaMortalidadClase => ["Mortalidad de su edad", "Age-class mortality"],
+        Texto::AyudaConsola => ["Orden (help muestra las disponibles; ` cierra la consola)", "Command (help lists them; ` closes the console)"],
+        Texto::ResumenCopiado => ["Resumen del estado copiado al portapapeles", "State summary copied to the clipboard"],
+        Texto::PartidaGuardada => ["Partida guardada en", "Game saved to"],
+        Texto::PartidaCargada => ["Partida cargada de", "Game loaded from"],
+        Texto::RepeticionGuardada => ["Repetición guardada en", "Replay saved to"],
+        Texto::AyudaReproductor => [
+            "Espacio: reproducir o pausar · ←/→: un día (Mayús: un mes) · Inicio/Fin · clic en la línea de tiempo: ir a ese día",
+            "Space: play or pause · ←/→: one day (Shift: one month) · Home/End · click the timeline: go to that day",
+        ],
+        Texto::AyudaOrden => ["Orden, p. ej.: cabra TASA_REPRODUCCION_DIARIA 0.02", "Command, e.g.: cabra TASA_REPRODUCCION_DIARIA 0.02"],
+    }
+}
+
+impl Idioma {
+    /// Interpreta un código de idioma o de locale (`es`, `en-US`, `es_ES.UTF-8`).
+    pub fn desde_codigo(codigo: &str) -> Option<Self> {
+        let base = codigo.split(['_', '-', '.']).next().unwrap_or("").to_lowercase();
+        match base.as_str() {
+            "es" => Some(Idioma::Es),
+            "en" => Some(Idioma::En),
+            _ => None,
+        }
+    }
+
+    /// Idioma del locale del sistema (`LC_ALL`, `LC_MESSAGES`, `LANG`), si es uno disponible.
+    pub fn del_sistema() -> Option<Self> {
+        ["LC_ALL", "LC_MESSAGES", "LANG"].iter()
+            .filter_map(|variable| std::env::var(variable).ok())
+            .find(|valor| !valor.is_empty())
+            .and_then(|valor| Self::desde_codigo(&valor))
+    }
+
+    /// El idioma configurado o, si no hay, el del sistema; por defecto, español.
+    pub fn elegir(configurado: Option<Idioma>) -> Self {
+        configurado.or_else(Self::del_sistema).unwrap_or_default()
+    }
+
+    /// Texto de la interfaz en este idioma.
+    pub fn texto(self, texto: Texto) -> &'static str {
+        traducciones(texto)[self as usize]
+    }
+}
@@ -0,0 +1,794 @@
+// src/instantanea.rs
+
+// Este módulo define la "fotografía" serializable del estado completo de la simulación.
+// Las presas son trait objects, así que no se serializan
+// directamente: se guardan sus datos observables y se reconstruyen al cargar.
+//
+// El formato por defecto es binario: una firma, la versión del formato y el contenido en
+// bincode comprimido con zstd. Como la versión va fuera de la parte comprimida, al cargar
+// se sabe cómo decodificar (y migrar) un archivo antiguo. Se sigue pudiendo leer y escribir
+// JSON, que es más lento y ocupa mucho más, pero se inspecciona a simple vista.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+#[cfg(not(target_arch = "wasm32"))]
+use std::io::Read;
+use std::path::Path;
+
+use rand_chacha::ChaCha8Rng;
+use bincode::Options;
+use serde::{Deserialize, Serialize};
+
+use crate::alarmas::Alarma;
+use crate::energia::BalanceEnergia;
+use crate::enfermedad::Salud;
+use crate::entidades::*;
+use crate::espacio::Posicion;
+use crate::pastizal::Pastizal;
+use crate::simulacion::{MuertesPorCausa, Retencion};
+use crate::parametros::{
+    CadenaTrofica, Cazabilidad, ClasesEdad, Clima, CurvaCrecimiento, Dieta, Energia, Enfermedad, Epidemias, Espacio, EstrategiaCaza, ExitoCaza, Manada, Metabolismo, Parametros, RangoCrias, RespuestaFuncional, CABRA_CRECIMIENTO, CABRA_GESTACION_DIAS, CONEJO_CRECIMIENTO, CONEJO_GESTACION_DIAS,
+    DEPREDADOR_INGESTA_MAXIMA_KG, POBLACION_MAXIMA, TASA_DESCOMPOSICION_DIARIA, UMBRAL_ALLEE,
+};
+
+/// Versión del formato binario; se incrementa cuando cambia la estructura guardada.
+/// - 1: formato inicial, ids de presa de 32 bits.
+/// - 2: ids de 64 bits y `POBLACION_MAXIMA`. Después fue ganando campos sin cambiar de número
+///   (el estado de las presas, la manada, el pasto...), así que hay dos variantes legibles:
+///   la inicial y la última.
+/// - 3: los parámetros se guardan como JSON dentro del binario; los que se añadan después
+///   toman su valor por defecto sin cambiar de versión. El resto del estado sigue sin nombres
+///   de campo: cualquier cambio en él exige subir la versión y migrar aquí la anterior.
+/// - 4: los totales de la corrida, las alarmas y la retención.
+pub const VERSION_INSTANTANEA: u32 = 4;
+
+/// Firma con la que empiezan las instantáneas binarias.
+pub const FIRMA_BINARIA: &[u8; 4] = b"SPD\0";
+
+/// Nivel de compresión de zstd: el 3 es su valor por defecto, buen equilibrio velocidad/tamaño.
+#[cfg(not(target_arch = "wasm32"))]
+const NIVEL_COMPRESION: i32 = 3;
+
+/// Datos de una presa individual tal como se guardan en disco.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EstadoPresa {
+    pub id: u64,
+    pub especie: Especie,
+    pub sexo: Sexo,
+    pub edad_dias: u32,
+    pub peso_kg: f64,
+    pub vivo: bool,
+    /// Preñez en curso; ausente en archivos anteriores a la gestación.
+    #[serde(default)]
+    pub gestacion: Option<Gestacion>,
+}
+
+impl EstadoPresa {
+    /// Extrae el estado de cualquier presa a través del trait `Presa`.
+    pub fn desde_presa(presa: &dyn Presa) -> Self {
+        Self {
+            id: presa.id(),
+            especie: presa.especie(),
+            sexo: presa.sexo(),
+            edad_dias: presa.edad(),
+            peso_kg: presa.peso(),
+            vivo: presa.esta_viva(),
+            gestacion: presa.gestacion(),
+        }
+    }
+
+    /// Reconstruye la presa de la especie guardada.
+    pub fn a_presa(&self) -> Box<dyn Presa> {
+        Box::new(PresaGenerica::restaurar(self.especie, self.id, self.edad_dias, self.peso_kg, self.sexo, self.vivo, self.gestacion))
+    }
+}
+
+/// Estado completo de una simulación, incluido el generador aleatorio,
+/// de modo que una corrida restaurada continúa exactamente igual que la original.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Instantanea {
+    pub version: u32,
+    pub semilla: u64,
+    pub dia: u32,
+    pub next_id: u64,
+    pub depredador: Depredador,
+    #[serde(with = "parametros_autodescritos")]
+    pub parametros: Parametros,
+    pub presas: Vec<EstadoPresa>,
+    pub rng: ChaCha8Rng,
+    /// Días que le quedan al brote de enfermedad de cada especie (conejos, cabras); 0 si no hay.
+    #[serde(default)]
+    pub dias_brote: [u32; 2],
+    /// Pasto de cada celda, solo relevante con `CADENA_TROFICA.activa`.
+    #[serde(default)]
+    pub pastizal: Pastizal,
+    /// Celda del pastizal en que está cada presa, por id.
+    #[serde(default)]
+    pub celdas_presas: BTreeMap<u64, u32>,
+    /// Posición de cada presa en el terreno, por id; las que faltan se dispersan según su id.
+    #[serde(default)]
+    pub posiciones_presas: BTreeMap<u64, Posicion>,
+    /// Los demás depredadores en el modo de manada (ver `Simulacion::manada`).
+    #[serde(default)]
+    pub manada: Vec<Depredador>,
+    /// Presas infectadas o recuperadas de la enfermedad contagiosa, por id.
+    #[serde(default)]
+    pub salud_presas: BTreeMap<u64, Salud>,
+    /// Si cada especie (conejos, cabras) está en plena ola epidémica.
+    #[serde(default)]
+    pub olas: [bool; 2],
+    /// Flujos de energía y muertes acumulados en la corrida; los archivos anteriores a la
+    /// versión 4 empiezan de cero.
+    #[serde(default)]
+    pub balance_corrida: BalanceEnergia,
+    #[serde(default)]
+    pub muertes_corrida: MuertesPorCausa,
+    /// Alarmas definidas y si la condición de cada una se cumplía (ver `alarmas`).
+    #[serde(default)]
+    pub alarmas: Vec<Alarma>,
+    #[serde(default)]
+    pub alarmas_cumplidas: Vec<bool>,
+    /// Qué instantáneas retenía la corrida para retroceder; ellas mismas no se guardan.
+    #[serde(default)]
+    pub retencion: Retencion,
+}
+
+impl Instantanea {
+    /// Estimación de la memoria que ocupa en RAM (bytes), sin contar lo que reserva el asignador.
+    pub fn memoria_estimada(&self) -> usize {
+        std::mem::size_of::<Self>() + self.presas.capacity() * std::mem::size_of::<EstadoPresa>()
+            + self.celdas_presas.len() * std::mem::size_of::<(u64, u32)>()
+            + self.posiciones_presas.len() * std::mem::size_of::<(u64, Posicion)>()
+            + self.salud_presas.len() * std::mem::size_of::<(u64, Salud)>()
+            + self.manada.len() * std::mem::size_of::<Depredador>()
+            + self.pastizal.celdas.capacity() * std::mem::size_of::<f64>()
+            + self.alarmas.capacity() * std::mem::size_of::<Alarma>()
+            + self.alarmas_cumplidas.capacity()
+    }
+
+    /// Escribe la instantánea en el formato binario comprimido.
+    pub fn guardar(&self, ruta: impl AsRef<Path>) -> io::Result<()> {
+        fs::write(ruta, self.a_binario()?)
+    }
+
+    /// Lee una instantánea binaria; los archivos JSON antiguos se siguen aceptando.
+    pub fn cargar(ruta: impl AsRef<Path>) -> io::Result<Self> {
+        Self::desde_bytes(&fs::read(ruta)?)
+    }
+
+    /// Codifica la instantánea: firma, versión (u32 little endian) y bincode comprimido con zstd.
+    pub fn a_binario(&self) -> io::Result<Vec<u8>> {
+        let datos = bincode::serialize(self).map_err(io::Error::other)?;
+        let mut bytes = Vec::with_capacity(datos.len() / 4);
+        bytes.extend_from_slice(FIRMA_BINARIA);
+        bytes.extend_from_slice(&self.version.to_le_bytes());
+        bytes.extend(comprimir(&datos)?);
+        Ok(bytes)
+    }
+
+    /// Decodifica una instantánea en formato binario o JSON, según su contenido.
+    pub fn desde_bytes(bytes: &[u8]) -> io::Result<Self> {
+        let Some(resto) = bytes.strip_prefix(FIRMA_BINARIA) else {
+            return Self::desde_json(bytes);
+        };
+        let (version, comprimido) = resto.split_at_checked(4).ok_or_else(|| datos_invalidos("instantánea binaria truncada"))?;
+        let version = u32::from_le_bytes(version.try_into().unwrap_or_default());
+        Self::migrar(version, &descomprimir(comprimido)?)
+    }
+
+    /// Decodifica el contenido de una versión dada del formato y lo lleva a la actual: cada
+    /// versión anterior se lee con sus tipos antiguos y se convierte a la siguiente.
+    fn migrar(version: u32, datos: &[u8]) -> io::Result<Self> {
+        match version {
+            VERSION_INSTANTANEA => bincode::deserialize(datos).map_err(datos_invalidos),
+            3 => bincode::deserialize::<InstantaneaV3>(datos).map(Self::from).map_err(datos_invalidos),
+            2 => {
+                // Las dos variantes se distinguen porque cada una debe consumir todos los bytes.
+                let exacto = bincode::DefaultOptions::new().with_fixint_encoding().reject_trailing_bytes();
+                if let Ok(v2) = exacto.deserialize::<InstantaneaV2>(datos) {
+                    return Ok(InstantaneaV3::from(v2).into());
+                }
+                exacto.deserialize::<InstantaneaV2Inicial>(datos).map(|v2| InstantaneaV3::from(v2).into()).map_err(|_| {
+                    datos_invalidos("instantánea de versión 2 con un formato intermedio que ya no se puede leer; exporta la partida a JSON con la versión que la guardó")
+                })
+            }
+            1 => bincode::deserialize::<InstantaneaV1>(datos).map(|v1| InstantaneaV3::from(InstantaneaV2Inicial::from(v1)).into()).map_err(datos_invalidos),
+            otra => Err(datos_invalidos(format!("versión de instantánea no soportada: {}", otra))),
+        }
+    }
+
+    /// Escribe la instantánea en formato JSON.
+    pub fn guardar_json(&self, ruta: impl AsRef<Path>) -> io::Result<()> {
+        let json = serde_json::to_string(self).map_err(io::Error::other)?;
+        fs::write(ruta, json)
+    }
+
+    /// Lee una instantánea en formato JSON.
+    pub fn cargar_json(ruta: impl AsRef<Path>) -> io::Result<Self> {
+        Self::desde_json(&fs::read(ruta)?)
+    }
+
+    /// Interpreta una instantánea JSON, rechazando versiones desconocidas. En JSON las versiones
+    /// anteriores se leen tal cual: los ids caben en 64 bits y los campos nuevos toman su valor
+    /// por defecto.
+    fn desde_json(bytes: &[u8]) -> io::Result<Self> {
+        let mut instantanea: Self = serde_json::from_slice(bytes).map_err(datos_invalidos)?;
+        if !(1..=VERSION_INSTANTANEA).contains(&instantanea.version) {
+            return Err(datos_invalidos(format!("versión de instantánea no soportada: {}", instantanea.version)));
+        }
+        instantanea.version = VERSION_INSTANTANEA;
+        Ok(instantanea)
+    }
+}
+
+/// Parámetros que se guardan como texto JSON en el formato binario, para que añadir uno no
+/// rompa las instantáneas anteriores; en JSON van anidados como siempre.
+mod parametros_autodescritos {
+    use serde::{de, ser, Deserialize, Deserializer, Serialize, Serializer};
+
+    use crate::parametros::Parametros;
+
+    pub fn serialize<S: Serializer>(parametros: &Parametros, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            return parametros.serialize(serializer);
+        }
+        serde_json::to_string(parametros).map_err(ser::Error::custom)?.serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Parametros, D::Error> {
+        if deserializer.is_human_readable() {
+            return Parametros::deserialize(deserializer);
+        }
+        serde_json::from_str(&String::deserialize(deserializer)?).map_err(de::Error::custom)
+    }
+}
+
+// --- Formatos binarios anteriores ---
+// bincode no guarda nombres de campos, así que cada estructura antigua se lee con sus tipos
+// exactos y se convierte a la siguiente.
+
+/// Versión 3: la actual sin los totales de la corrida, las alarmas ni la retención, que al
+/// migrar toman los valores con que empezaba entonces una corrida restaurada. Como en la 2,
+/// los tipos que cambien habrá que congelarlos aquí (ver `tests/datos/instantanea_v3.spd`).
+#[derive(Deserialize)]
+struct InstantaneaV3 {
+    _version: u32,
+    semilla: u64,
+    dia: u32,
+    next_id: u64,
+    depredador: Depredador,
+    #[serde(with = "parametros_autodescritos")]
+    parametros: Parametros,
+    presas: Vec<EstadoPresa>,
+    rng: ChaCha8Rng,
+    dias_brote: [u32; 2],
+    pastizal: Pastizal,
+    celdas_presas: BTreeMap<u64, u32>,
+    posiciones_presas: BTreeMap<u64, Posicion>,
+    manada: Vec<Depredador>,
+    salud_presas: BTreeMap<u64, Salud>,
+    olas: [bool; 2],
+}
+
+impl From<InstantaneaV3> for Instantanea {
+    fn from(v3: InstantaneaV3) -> Self {
+        let InstantaneaV3 { _version: _, semilla, dia, next_id, depredador, parametros, presas, rng, dias_brote, pastizal, celdas_presas, posiciones_presas, manada, salud_presas, olas } = v3;
+        Instantanea {
+            version: VERSION_INSTANTANEA,
+            semilla,
+            dia,
+            next_id,
+            depredador,
+            parametros,
+            presas,
+            rng,
+            dias_brote,
+            pastizal,
+            celdas_presas,
+            posiciones_presas,
+            manada,
+            salud_presas,
+            olas,
+            balance_corrida: BalanceEnergia::default(),
+            muertes_corrida: MuertesPorCausa::default(),
+            alarmas: Vec::new(),
+            alarmas_cumplidas: Vec::new(),
+            retencion: Retencion::default(),
+        }
+    }
+}
+
+/// Última variante de la versión 2: la de la versión 3 con los parámetros en bincode. Usa los
+/// tipos actuales salvo los que ya cambiaron; si cambia alguno más, hay que congelar aquí su
+/// forma anterior (lo comprueba el test con `tests/datos/instantanea_v2.spd`).
+#[derive(Deserialize)]
+struct InstantaneaV2 {
+    _version: u32,
+    semilla: u64,
+    dia: u32,
+    next_id: u64,
+    depredador: Depredador,
+    parametros: ParametrosV2,
+    presas: Vec<EstadoPresa>,
+    rng: ChaCha8Rng,
+    dias_brote: [u32; 2],
+    pastizal: Pastizal,
+    celdas_presas: BTreeMap<u64, u32>,
+    posiciones_presas: BTreeMap<u64, Posicion>,
+    manada: Vec<Depredador>,
+    salud_presas: BTreeMap<u64, Salud>,
+    olas: [bool; 2],
+}
+
+impl From<InstantaneaV2> for InstantaneaV3 {
+    fn from(v2: InstantaneaV2) -> Self {
+        let InstantaneaV2 { _version: _, semilla, dia, next_id, depredador, parametros, presas, rng, dias_brote, pastizal, celdas_presas, posiciones_presas, manada, salud_presas, olas } = v2;
+        InstantaneaV3 { _version: 3, semilla, dia, next_id, depredador, parametros: parametros.into(), presas, rng, dias_brote, pastizal, celdas_presas, posiciones_presas, manada, salud_presas, olas }
+    }
+}
+
+/// Parámetros de la última variante de la versión 2, antes de `recuperacion_peso_diaria`.
+#[derive(Deserialize)]
+struct ParametrosV2 {
+    n_conejos_inicial: u32,
+    n_cabras_inicial: u32,
+    depredador_reserva_inicial_kg: f64,
+    depredador_consumo_minimo_diario_kg: f64,
+    depredador_consumo_optimo_diario_kg: f64,
+    energia: Energia,
+    metabolismo: Metabolismo,
+    depredador_ingesta_maxima_kg: f64,
+    tasa_descomposicion_diaria: f64,
+    dieta_depredador: Dieta,
+    estrategia_caza: EstrategiaCaza,
+    conejo_edad_maxima_dias: u32,
+    conejo_edad_reproductiva_dias: u32,
+    conejo_edad_sacrificio_dias: u32,
+    conejo_tasa_reproduccion_diaria: f64,
+    conejo_crias_por_parto: RangoCrias,
+    conejo_crecimiento: CurvaCrecimiento,
+    conejo_gestacion_dias: u32,
+    cabra_edad_maxima_dias: u32,
+    cabra_edad_reproductiva_dias: u32,
+    cabra_edad_sacrificio_dias: u32,
+    cabra_tasa_reproduccion_diaria: f64,
+    cabra_crias_por_parto: RangoCrias,
+    cabra_crecimiento: CurvaCrecimiento,
+    cabra_gestacion_dias: u32,
+    probabilidad_enfermar: f64,
+    probabilidad_nacer_macho: f64,
+    umbral_allee: u32,
+    epidemias: Epidemias,
+    enfermedad: Enfermedad,
+    clima: Clima,
+    clases_edad: ClasesEdad,
+    cazabilidad: Cazabilidad,
+    exito_caza: ExitoCaza,
+    respuesta_funcional: RespuestaFuncional,
+    cadena_trofica: CadenaTroficaV2,
+    espacio: Espacio,
+    manada: Manada,
+    poblacion_maxima: u32,
+    subflujos_aleatorios: bool,
+}
+
+#[derive(Deserialize)]
+struct CadenaTroficaV2 {
+    activa: bool,
+    columnas: u32,
+    filas: u32,
+    capacidad_kg: f64,
+    pasto_inicial_kg: f64,
+    crecimiento_diario: f64,
+    refugio_kg: f64,
+    consumo_relativo: f64,
+    mortalidad_hambre: f64,
+    perdida_peso_hambre: f64,
+    peso_minimo_relativo: f64,
+}
+
+impl From<ParametrosV2> for Parametros {
+    fn from(p: ParametrosV2) -> Self {
+        Parametros {
+            n_conejos_inicial: p.n_conejos_inicial,
+            n_cabras_inicial: p.n_cabras_inicial,
+            depredador_reserva_inicial_kg: p.depredador_reserva_inicial_kg,
+            depredador_consumo_minimo_diario_kg: p.depredador_consumo_minimo_diario_kg,
+            depredador_consumo_optimo_diario_kg: p.depredador_consumo_optimo_diario_kg,
+            energia: p.energia,
+            metabolismo: p.metabolismo,
+            depredador_ingesta_maxima_kg: p.depredador_ingesta_maxima_kg,
+            tasa_descomposicion_diaria: p.tasa_descomposicion_diaria,
+            dieta_depredador: p.dieta_depredador,
+            estrategia_caza: p.estrategia_caza,
+            conejo_edad_maxima_dias: p.conejo_edad_maxima_dias,
+            conejo_edad_reproductiva_dias: p.conejo_edad_reproductiva_dias,
+            conejo_edad_sacrificio_dias: p.conejo_edad_sacrificio_dias,
+            conejo_tasa_reproduccion_diaria: p.conejo_tasa_reproduccion_diaria,
+            conejo_crias_por_parto: p.conejo_crias_por_parto,
+            conejo_crecimiento: p.conejo_crecimiento,
+            conejo_gestacion_dias: p.conejo_gestacion_dias,
+            cabra_edad_maxima_dias: p.cabra_edad_maxima_dias,
+            cabra_edad_reproductiva_dias: p.cabra_edad_reproductiva_dias,
+            cabra_edad_sacrificio_dias: p.cabra_edad_sacrificio_dias,
+            cabra_tasa_reproduccion_diaria: p.cabra_tasa_reproduccion_diaria,
+            cabra_crias_por_parto: p.cabra_crias_por_parto,
+            cabra_crecimiento: p.cabra_crecimiento,
+            cabra_gestacion_dias: p.cabra_gestacion_dias,
+            probabilidad_enfermar: p.probabilidad_enfermar,
+            probabilidad_nacer_macho: p.probabilidad_nacer_macho,
+            umbral_allee: p.umbral_allee,
+            epidemias: p.epidemias,
+            enfermedad: p.enfermedad,
+            clima: p.clima,
+            clases_edad: p.clases_edad,
+            cazabilidad: p.cazabilidad,
+            exito_caza: p.exito_caza,
+            respuesta_funcional: p.respuesta_funcional,
+            cadena_trofica: p.cadena_trofica.into(),
+            espacio: p.espacio,
+            manada: p.manada,
+            poblacion_maxima: p.poblacion_maxima,
+            subflujos_aleatorios: p.subflujos_aleatorios,
+        }
+    }
+}
+
+/// Entonces el peso perdido se recuperaba de golpe, que equivale a recuperar hasta todo el peso en un día.
+impl From<CadenaTroficaV2> for CadenaTrofica {
+    fn from(c: CadenaTroficaV2) -> Self {
+        let CadenaTroficaV2 { activa, columnas, filas, capacidad_kg, pasto_inicial_kg, crecimiento_diario, refugio_kg, consumo_relativo, mortalidad_hambre, perdida_peso_hambre, peso_minimo_relativo } = c;
+        CadenaTrofica { activa, columnas, filas, capacidad_kg, pasto_inicial_kg, crecimiento_diario, refugio_kg, consumo_relativo, mortalidad_hambre, perdida_peso_hambre, peso_minimo_relativo, recuperacion_peso_diaria: 1.0 }
+    }
+}
+
+/// Primera variante de la versión 2: la 1 con ids de 64 bits y `POBLACION_MAXIMA`.
+#[derive(Deserialize)]
+#[cfg_attr(test, derive(Serialize))]
+struct InstantaneaV2Inicial {
+    _version: u32,
+    semilla: u64,
+    dia: u32,
+    next_id: u64,
+    depredador: DepredadorV1,
+    parametros: ParametrosV2Inicial,
+    presas: Vec<EstadoPresaV2Inicial>,
+    rng: ChaCha8Rng,
+}
+
+#[derive(Deserialize)]
+#[cfg_attr(test, derive(Serialize))]
+struct ParametrosV2Inicial {
+    base: ParametrosV1,
+    poblacion_maxima: u32,
+}
+
+#[derive(Deserialize)]
+#[cfg_attr(test, derive(Serialize))]
+struct EstadoPresaV2Inicial {
+    id: u64,
+    especie: Especie,
+    sexo: Sexo,
+    edad_dias: u32,
+    peso_kg: f64,
+    vivo: bool,
+}
+
+impl From<ParametrosV2Inicial> for Parametros {
+    fn from(ParametrosV2Inicial { base: p, poblacion_maxima }: ParametrosV2Inicial) -> Self {
+        Parametros {
+            n_conejos_inicial: p.n_conejos_inicial,
+            n_cabras_inicial: p.n_cabras_inicial,
+            depredador_reserva_inicial_kg: p.depredador_reserva_inicial_kg,
+            depredador_consumo_minimo_diario_kg: p.depredador_consumo_minimo_diario_kg,
+            depredador_consumo_optimo_diario_kg: p.depredador_consumo_optimo_diario_kg,
+            energia: Energia::default(),
+            metabolismo: Metabolismo::default(),
+            depredador_ingesta_maxima_kg: DEPREDADOR_INGESTA_MAXIMA_KG,
+            tasa_descomposicion_diaria: TASA_DESCOMPOSICION_DIARIA,
+            dieta_depredador: Dieta::default(),
+            estrategia_caza: EstrategiaCaza::default(),
+            conejo_edad_maxima_dias: p.conejo_edad_maxima_dias,
+            conejo_edad_reproductiva_dias: p.conejo_edad_reproductiva_dias,
+            conejo_edad_sacrificio_dias: p.conejo_edad_sacrificio_dias,
+            conejo_tasa_reproduccion_diaria: p.conejo_tasa_reproduccion_diaria,
+            conejo_crias_por_parto: p.conejo_crias_por_parto,
+            conejo_crecimiento: CONEJO_CRECIMIENTO.into(),
+            conejo_gestacion_dias: CONEJO_GESTACION_DIAS,
+            cabra_edad_maxima_dias: p.cabra_edad_maxima_dias,
+            cabra_edad_reproductiva_dias: p.cabra_edad_reproductiva_dias,
+            cabra_edad_sacrificio_dias: p.cabra_edad_sacrificio_dias,
+            cabra_tasa_reproduccion_diaria: p.cabra_tasa_reproduccion_diaria,
+            cabra_crias_por_parto: p.cabra_crias_por_parto,
+            cabra_crecimiento: CABRA_CRECIMIENTO.into(),
+            cabra_gestacion_dias: CABRA_GESTACION_DIAS,
+            probabilidad_enfermar: p.probabilidad_enfermar,
+            probabilidad_nacer_macho: p.probabilidad_nacer_macho,
+            umbral_allee: UMBRAL_ALLEE,
+            epidemias: Epidemias::default(),
+            enfermedad: Enfermedad::default(),
+            clima: Clima::default(),
+            clases_edad: ClasesEdad::default(),
+            cazabilidad: Cazabilidad::default(),
+            exito_caza: ExitoCaza::default(),
+            respuesta_funcional: RespuestaFuncional::default(),
+            // Antes de la cadena trófica las presas no comían.
+            cadena_trofica: CadenaTrofica { activa: false, ..CadenaTrofica::default() },
+            espacio: Espacio::default(),
+            manada: Manada::default(),
+            poblacion_maxima,
+            subflujos_aleatorios: false,
+        }
+    }
+}
+
+impl From<InstantaneaV2Inicial> for InstantaneaV3 {
+    fn from(v2: InstantaneaV2Inicial) -> Self {
+        InstantaneaV3 {
+            _version: 3,
+            semilla: v2.semilla,
+            dia: v2.dia,
+            next_id: v2.next_id,
+            depredador: Depredador { vivo: v2.depredador.vivo, ..Depredador::new(v2.depredador.reserva_comida_kg) },
+            parametros: v2.parametros.into(),
+            presas: v2.presas.into_iter().map(|p| EstadoPresa {
+                id: p.id,
+                especie: p.especie,
+                sexo: p.sexo,
+                edad_dias: p.edad_dias,
+                peso_kg: p.peso_kg,
+                vivo: p.vivo,
+                gestacion: None,
+            }).collect(),
+            rng: v2.rng,
+            dias_brote: [0, 0],
+            pastizal: Pastizal::default(),
+            celdas_presas: BTreeMap::new(),
+            posiciones_presas: BTreeMap::new(),
+            manada: Vec::new(),
+            salud_presas: BTreeMap::new(),
+            olas: [false, false],
+        }
+    }
+}
+
+// La versión 1 solo difiere de la variante inicial de la 2 en los ids de 32 bits y en que
+// los parámetros no tenían `POBLACION_MAXIMA`.
+
+#[derive(Deserialize)]
+#[cfg_attr(test, derive(Serialize))]
+struct EstadoPresaV1 {
+    id: u32,
+    especie: Especie,
+    sexo: Sexo,
+    edad_dias: u32,
+    peso_kg: f64,
+    vivo: bool,
+}
+
+#[derive(Deserialize)]
+#[cfg_attr(test, derive(Serialize))]
+struct ParametrosV1 {
+    n_conejos_inicial: u32,
+    n_cabras_inicial: u32,
+    depredador_reserva_inicial_kg: f64,
+    depredador_consumo_minimo_diario_kg: f64,
+    depredador_consumo_optimo_diario_kg: f64,
+    conejo_edad_maxima_dias: u32,
+    conejo_edad_reproductiva_dias: u32,
+    conejo_edad_sacrificio_dias: u32,
+    conejo_tasa_reproduccion_diaria: f64,
+    conejo_crias_por_parto: RangoCrias,
+    cabra_edad_maxima_dias: u32,
+    cabra_edad_reproductiva_dias: u32,
+    cabra_edad_sacrificio_dias: u32,
+    cabra_tasa_reproduccion_diaria: f64,
+    cabra_crias_por_parto: RangoCrias,
+    probabilidad_enfermar: f64,
+    probabilidad_nacer_macho: f64,
+}
+
+#[derive(Deserialize)]
+#[cfg_attr(test, derive(Serialize))]
+struct DepredadorV1 {
+    reserva_comida_kg: f64,
+    vivo: bool,
+}
+
+#[derive(Deserialize)]
+#[cfg_attr(test, derive(Serialize))]
+struct InstantaneaV1 {
+    _version: u32,
+    semilla: u64,
+    dia: u32,
+    next_id: u32,
+    depredador: DepredadorV1,
+    parametros: ParametrosV1,
+    presas: Vec<EstadoPresaV1>,
+    rng: ChaCha8Rng,
+}
+
+impl From<InstantaneaV1> for InstantaneaV2Inicial {
+    fn from(v1: InstantaneaV1) -> Self {
+        InstantaneaV2Inicial {
+            _version: 2,
+            semilla: v1.semilla,
+            dia: v1.dia,
+            next_id: v1.next_id as u64,
+            depredador: v1.depredador,
+            parametros: ParametrosV2Inicial { base: v1.parametros, poblacion_maxima: POBLACION_MAXIMA },
+            presas: v1.presas.into_iter().map(|p| EstadoPresaV2Inicial {
+                id: p.id as u64,
+                especie: p.especie,
+                sexo: p.sexo,
+                edad_dias: p.edad_dias,
+                peso_kg: p.peso_kg,
+                vivo: p.vivo,
+            }).collect(),
+            rng: v1.rng,
+        }
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) fn comprimir(datos: &[u8]) -> io::Result<Vec<u8>> {
+    zstd::encode_all(datos, NIVEL_COMPRESION)
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) fn descomprimir(comprimido: &[u8]) -> io::Result<Vec<u8>> {
+    let mut datos = Vec::new();
+    zstd::Decoder::new(comprimido)?.read_to_end(&mut datos)?;
+    Ok(datos)
+}
+
+// En la versión web no hay zstd: solo están disponibles las instantáneas JSON.
+#[cfg(target_arch = "wasm32")]
+pub(crate) fn comprimir(_datos: &[u8]) -> io::Result<Vec<u8>> {
+    Err(io::Error::new(io::ErrorKind::Unsupported, "las instantáneas binarias no están disponibles en la versión web"))
+}
+
+#[cfg(target_arch = "wasm32")]
+pub(crate) fn descomprimir(comprimido: &[u8]) -> io::Result<Vec<u8>> {
+    comprimir(comprimido)
+}
+
+pub(crate) fn datos_invalidos(error: impl Into<Box<dyn std::error::Error + Send + Sync>>) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, error)
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::SeedableRng;
+
+    use super::*;
+    use crate::simulacion::Simulacion;
+
+    fn binario(version: u32, contenido: &impl Serialize) -> Vec<u8> {
+        let mut bytes = FIRMA_BINARIA.to_vec();
+        bytes.extend_from_slice(&version.to_le_bytes());
+        bytes.extend(comprimir(&bincode::serialize(contenido).unwrap()).unwrap());
+        bytes
+    }
+
+    fn parametros_v1() -> ParametrosV1 {
+        let p = Parametros { n_conejos_inicial: 7, ..Parametros::default() };
+        ParametrosV1 {
+            n_conejos_inicial: p.n_conejos_inicial,
+            n_cabras_inicial: p.n_cabras_inicial,
+            depredador_reserva_inicial_kg: p.depredador_reserva_inicial_kg,
+            depredador_consumo_minimo_diario_kg: p.depredador_consumo_minimo_diario_kg,
+            depredador_consumo_optimo_diario_kg: p.depredador_consumo_optimo_diario_kg,
+            conejo_edad_maxima_dias: p.conejo_edad_maxima_dias,
+            conejo_edad_reproductiva_dias: p.conejo_edad_reproductiva_dias,
+            conejo_edad_sacrificio_dias: p.conejo_edad_sacrificio_dias,
+            conejo_tasa_reproduccion_diaria: p.conejo_tasa_reproduccion_diaria,
+            conejo_crias_por_parto: p.conejo_crias_por_parto,
+            cabra_edad_maxima_dias: p.cabra_edad_maxima_dias,
+            cabra_edad_reproductiva_dias: p.cabra_edad_reproductiva_dias,
+            cabra_edad_sacrificio_dias: p.cabra_edad_sacrificio_dias,
+            cabra_tasa_reproduccion_diaria: p.cabra_tasa_reproduccion_diaria,
+            cabra_crias_por_parto: p.cabra_crias_por_parto,
+            probabilidad_enfermar: p.probabilidad_enfermar,
+            probabilidad_nacer_macho: p.probabilidad_nacer_macho,
+        }
+    }
+
+    #[test]
+    fn la_version_actual_se_recupera_sin_perdidas() {
+        let parametros = Parametros { n_conejos_inicial: 20, n_cabras_inicial: 4, ..Parametros::default() };
+        let mut original = Simulacion::con_parametros(parametros, 3);
+        for _ in 0..10 {
+            original.avanzar_dia();
+        }
+        let instantanea = Instantanea::desde_bytes(&original.instantanea().a_binario().unwrap()).unwrap();
+        assert_eq!(instantanea.version, VERSION_INSTANTANEA);
+        let mut restaurada = Simulacion::desde_instantanea(instantanea);
+        for _ in 0..10 {
+            original.avanzar_dia();
+            restaurada.avanzar_dia();
+        }
+        assert_eq!(restaurada.parametros, original.parametros);
+        assert_eq!(restaurada.huella(), original.huella());
+    }
+
+    #[test]
+    fn la_ultima_variante_de_la_version_2_se_sigue_leyendo() {
+        // Guardada justo antes de la versión 3: 20 conejos y 4 cabras, semilla 42, 15 días.
+        let instantanea = Instantanea::desde_bytes(include_bytes!("../tests/datos/instantanea_v2.spd")).unwrap();
+        assert_eq!(instantanea.version, VERSION_INSTANTANEA);
+        assert_eq!((instantanea.semilla, instantanea.dia, instantanea.presas.len()), (42, 15, 24));
+        assert_eq!((instantanea.parametros.n_conejos_inicial, instantanea.parametros.n_cabras_inicial), (20, 4));
+        let mut sim = Simulacion::desde_instantanea(instantanea);
+        sim.avanzar_dia();
+        assert_eq!(sim.dia, 16);
+    }
+
+    #[test]
+    fn la_version_3_se_migra_sin_totales_ni_alarmas() {
+        // Guardada justo antes de la versión 4, con la misma corrida que la de la versión 2.
+        let instantanea = Instantanea::desde_bytes(include_bytes!("../tests/datos/instantanea_v3.spd")).unwrap();
+        assert_eq!(instantanea.version, VERSION_INSTANTANEA);
+        assert_eq!((instantanea.semilla, instantanea.dia, instantanea.presas.len()), (42, 15, 24));
+        assert_eq!(instantanea.muertes_corrida, MuertesPorCausa::default());
+        assert!(instantanea.alarmas.is_empty());
+        assert_eq!(instantanea.retencion, Retencion::default());
+        let mut sim = Simulacion::desde_instantanea(instantanea);
+        sim.avanzar_dia();
+        assert_eq!(sim.dia, 16);
+    }
+
+    /// Los parámetros por defecto tal como quedan en una instantánea anterior a la cadena trófica.
+    fn parametros_sin_pasto() -> Parametros {
+        let mut parametros = Parametros::default();
+        parametros.cadena_trofica.activa = false;
+        parametros
+    }
+
+    #[test]
+    fn la_variante_inicial_de_la_version_2_se_migra() {
+        let v2 = InstantaneaV2Inicial {
+            _version: 2,
+            semilla: 9,
+            dia: 30,
+            next_id: 1 << 40,
+            depredador: DepredadorV1 { reserva_comida_kg: 12.5, vivo: true },
+            parametros: ParametrosV2Inicial { base: parametros_v1(), poblacion_maxima: 500 },
+            presas: vec![EstadoPresaV2Inicial { id: (1 << 40) - 1, especie: Especie::Cabra, sexo: Sexo::Hembra, edad_dias: 400, peso_kg: 30.0, vivo: true }],
+            rng: ChaCha8Rng::seed_from_u64(9),
+        };
+        let instantanea = Instantanea::desde_bytes(&binario(2, &v2)).unwrap();
+        assert_eq!(instantanea.version, VERSION_INSTANTANEA);
+        assert_eq!((instantanea.dia, instantanea.next_id), (30, 1 << 40));
+        assert_eq!(instantanea.presas[0].id, (1 << 40) - 1);
+        assert_eq!(instantanea.depredador.reserva_comida_kg, 12.5);
+        assert_eq!(instantanea.parametros, Parametros { n_conejos_inicial: 7, poblacion_maxima: 500, ..parametros_sin_pasto() });
+    }
+
+    #[test]
+    fn la_version_1_pasa_por_la_2() {
+        let v1 = InstantaneaV1 {
+            _version: 1,
+            semilla: 4,
+            dia: 8,
+            next_id: 3,
+            depredador: DepredadorV1 { reserva_comida_kg: 2.0, vivo: false },
+            parametros: parametros_v1(),
+            presas: vec![EstadoPresaV1 { id: 2, especie: Especie::Conejo, sexo: Sexo::Macho, edad_dias: 40, peso_kg: 1.5, vivo: true }],
+            rng: ChaCha8Rng::seed_from_u64(4),
+        };
+        let instantanea = Instantanea::desde_bytes(&binario(1, &v1)).unwrap();
+        assert_eq!((instantanea.next_id, instantanea.presas[0].id), (3, 2));
+        assert!(!instantanea.depredador.vivo);
+        assert_eq!(instantanea.parametros, Parametros { n_conejos_inicial: 7, ..parametros_sin_pasto() });
+    }
+
+    #[test]
+    fn las_variantes_intermedias_y_las_versiones_desconocidas_dan_un_error_claro() {
+        let error = Instantanea::desde_bytes(&binario(2, &(2u32, 1u64, 2u32))).unwrap_err();
+        assert!(error.to_string().contains("formato intermedio"), "{}", error);
+        let error = Instantanea::desde_bytes(&binario(VERSION_INSTANTANEA + 1, &0u32)).unwrap_err();
+        assert!(error.to_string().contains("no soportada"), "{}", error);
+    }
+}
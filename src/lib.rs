@@ -0,0 +1,51 @@
+// src/lib.rs
+
+// Raíz de la biblioteca del simulador.
+// Expone el "backend" (entidades y motor de simulación) para que lo usen tanto
+// el visualizador de macroquad como las herramientas de línea de comandos.
+// No depende de macroquad: con `--no-default-features` se compila sin el visualizador.
+
+pub mod alarmas;
+pub mod analisis;
+pub mod ascii;
+pub mod bus;
+pub mod capas;
+pub mod censo;
+pub mod clima;
+pub mod configuracion;
+pub mod cuaderno;
+pub mod desafio;
+pub mod determinista;
+pub mod dificultad;
+pub mod energia;
+pub mod enfermedad;
+pub mod enlace;
+pub mod entidades;
+pub mod espacio;
+pub mod especies;
+pub mod eventos;
+pub mod experimento;
+pub mod genealogia;
+#[cfg(feature = "graphql")]
+pub mod graphql;
+#[cfg(feature = "grpc")]
+pub mod grpc;
+pub mod historia;
+pub mod idioma;
+pub mod instantanea;
+pub mod parametros;
+pub mod pastizal;
+#[cfg(feature = "protobuf")]
+pub mod protobuf;
+pub mod protocolo;
+pub mod publicacion;
+pub mod recarga;
+pub mod records;
+pub mod registro;
+pub mod remuestreo;
+pub mod repeticion;
+pub mod resumen;
+pub mod reloj;
+pub mod simulacion;
+pub mod superposicion;
+pub mod vegalite;
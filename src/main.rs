@@ -4,13 +4,32 @@
 // Utiliza la librería macroquad para dibujar el estado del "backend" (el módulo de simulación).
 // Su responsabilidad es pintar, no ejecutar la lógica de las reglas del ecosistema.
 
+mod avisos;
+mod cazas;
+mod consola;
+mod control;
+mod paneles;
+mod puntos;
+mod rastros;
+mod reproductor;
+mod sonido;
+mod sprites;
+mod tablero;
+mod tutorial;
+mod volcado;
+
+use std::sync::OnceLock;
+
 use macroquad::prelude::*;
-// Declara los otros módulos para que `main` pueda usarlos.
-mod entidades;
-mod simulacion;
+// Importa los módulos del "backend" desde la biblioteca del crate.
+use simulador_ecosistema_presa_depredador::{capas, configuracion, desafio, enfermedad, enlace, entidades, especies, eventos, pastizal, recarga, records, reloj, repeticion, simulacion, superposicion};
+use simulador_ecosistema_presa_depredador::alarmas::Alarma;
+use simulador_ecosistema_presa_depredador::dificultad::Dificultad;
+use simulador_ecosistema_presa_depredador::espacio::Posicion;
+use simulador_ecosistema_presa_depredador::idioma::{Idioma, Texto};
 
-/// Dibuja una leyenda en la esquina superior derecha para identificar los colores.
-fn dibujar_leyenda() {
+/// Dibuja una leyenda en la esquina superior derecha para identificar los colores (o las imágenes).
+fn dibujar_leyenda(idioma: Idioma, sprites: &sprites::Sprites) {
     let x_offset = screen_width() - 150.0;
     let y_offset = 20.0;
     let rect_size = 15.0;
@@ -18,135 +37,1083 @@ fn dibujar_leyenda() {
     let text_color = DARKGRAY;
     let font_size = 18.0;
 
+    let muestra = |especie: entidades::Especie, y: f32, color: Color| {
+        let centro = vec2(x_offset + rect_size / 2.0, y + rect_size / 2.0);
+        match sprites.presa(especie) {
+            Some(textura) => sprites::dibujar(textura, centro, rect_size / 2.0, WHITE),
+            None => draw_circle(centro.x, centro.y, rect_size / 2.0, color),
+        }
+    };
+
     // Leyenda Conejo
-    draw_circle(x_offset + rect_size / 2.0, y_offset + rect_size / 2.0, rect_size / 2.0, WHITE);
-    draw_text("Conejo", x_offset + text_offset, y_offset + rect_size / 2.0 + font_size / 2.0 - 5.0, font_size, text_color);
+    muestra(entidades::Especie::Conejo, y_offset, WHITE);
+    draw_text(idioma.texto(Texto::Conejo), x_offset + text_offset, y_offset + rect_size / 2.0 + font_size / 2.0 - 5.0, font_size, text_color);
 
     // Leyenda Cabra
-    draw_circle(x_offset + rect_size / 2.0, y_offset + rect_size / 2.0 + rect_size + 10.0, rect_size / 2.0, BROWN);
-    draw_text("Cabra", x_offset + text_offset, y_offset + rect_size / 2.0 + rect_size + 10.0 + font_size / 2.0 - 5.0, font_size, text_color);
+    muestra(entidades::Especie::Cabra, y_offset + rect_size + 10.0, BROWN);
+    draw_text(idioma.texto(Texto::Cabra), x_offset + text_offset, y_offset + rect_size / 2.0 + rect_size + 10.0 + font_size / 2.0 - 5.0, font_size, text_color);
+}
+
+
+/// Radio del círculo del depredador.
+const RADIO_DEPREDADOR: f32 = 20.0;
+
+/// Rectángulo de la escena en que se reparten las presas (y el pastizal, si lo hay).
+fn area_escena() -> Rect {
+    Rect::new(20.0, 100.0, (screen_width() - 40.0).max(1.0), (screen_height() - 120.0).max(1.0))
+}
+
+/// Rectángulo en pantalla de una celda del pastizal.
+fn rect_celda(pastizal: &pastizal::Pastizal, celda: u32) -> Rect {
+    let area = area_escena();
+    let (columna, fila) = pastizal.coordenadas(celda);
+    let (ancho, alto) = (area.w / pastizal.columnas as f32, area.h / pastizal.filas as f32);
+    Rect::new(area.x + columna as f32 * ancho, area.y + fila as f32 * alto, ancho, alto)
+}
+
+/// Punto de la escena que corresponde a un punto del terreno: el terreno (ver `espacio`)
+/// ocupa toda el área de la escena, igual que el pastizal.
+fn a_escena(sim: &simulacion::Simulacion, posicion: Posicion) -> Vec2 {
+    let area = area_escena();
+    let lado = sim.parametros.espacio.lado_m;
+    vec2(area.x + posicion.x / lado * area.w, area.y + posicion.y / lado * area.h)
+}
+
+/// Punto del terreno que corresponde a un punto de la escena.
+fn a_terreno(sim: &simulacion::Simulacion, punto: Vec2) -> Posicion {
+    let area = area_escena();
+    let lado = sim.parametros.espacio.lado_m;
+    Posicion::new((punto.x - area.x) / area.w * lado, (punto.y - area.y) / area.h * lado)
+}
+
+/// Posición en la escena de una presa: la suya en el terreno, a medio camino desde la del
+/// día anterior según el reloj (ver `reloj::Interpolacion`).
+fn posicion_presa(presa: &dyn entidades::Presa, sim: &simulacion::Simulacion, interpolacion: &reloj::Interpolacion) -> Vec2 {
+    a_escena(sim, interpolacion.presa(sim, presa.id()))
 }
 
+/// El radio del círculo es proporcional al peso de la presa.
+fn radio_presa(presa: &dyn entidades::Presa) -> f32 {
+    4.0 + (presa.peso() / 15.0) as f32
+}
 
-/// Dibuja el estado actual de la simulación en la pantalla.
-fn dibujar_simulacion(sim: &simulacion::Simulacion) {
+/// Posición en la escena del depredador: la suya en el terreno (interpolada, como la de las
+/// presas) o, mientras se arrastra, el punto de la escena al que se está llevando.
+fn posicion_depredador(sim: &simulacion::Simulacion, arrastre: Option<Vec2>, interpolacion: &reloj::Interpolacion) -> Vec2 {
+    arrastre.unwrap_or_else(|| a_escena(sim, interpolacion.depredador(sim)))
+}
+
+/// Color de un depredador según su reserva: rojo por encima del consumo óptimo, naranja por
+/// encima del mínimo y gris en peligro de muerte.
+fn color_depredador(depredador: &entidades::Depredador, parametros: &simulador_ecosistema_presa_depredador::parametros::Parametros) -> Color {
+    let (consumo_minimo, consumo_optimo) = parametros.consumo_depredador_kg();
+    if depredador.reserva_comida_kg >= consumo_optimo {
+        RED
+    } else if depredador.reserva_comida_kg >= consumo_minimo {
+        ORANGE
+    } else {
+        DARKGRAY
+    }
+}
+
+/// Lienzo que se pasa a las capas propias (ver `capas`): dibuja con macroquad en la escena.
+struct LienzoEscena;
+
+fn color_capa(color: capas::Rgba) -> Color {
+    Color::new(color[0], color[1], color[2], color[3])
+}
+
+impl capas::Lienzo for LienzoEscena {
+    fn area(&self) -> [f32; 4] {
+        let area = area_escena();
+        [area.x, area.y, area.w, area.h]
+    }
+
+    fn rectangulo(&mut self, x: f32, y: f32, ancho: f32, alto: f32, color: capas::Rgba) {
+        draw_rectangle(x, y, ancho, alto, color_capa(color));
+    }
+
+    fn circulo(&mut self, x: f32, y: f32, radio: f32, color: capas::Rgba) {
+        draw_circle(x, y, radio, color_capa(color));
+    }
+
+    fn linea(&mut self, desde: [f32; 2], hasta: [f32; 2], grosor: f32, color: capas::Rgba) {
+        draw_line(desde[0], desde[1], hasta[0], hasta[1], grosor, color_capa(color));
+    }
+
+    fn texto(&mut self, texto: &str, x: f32, y: f32, tamano: f32, color: capas::Rgba) {
+        draw_text(texto, x, y, tamano, color_capa(color));
+    }
+}
+
+/// Presa bajo el punto indicado; si se solapan varias, la dibujada encima (la última).
+fn presa_en(sim: &simulacion::Simulacion, punto: Vec2, interpolacion: &reloj::Interpolacion) -> Option<u64> {
+    sim.presas.iter().rev()
+        .find(|presa| posicion_presa(presa.as_ref(), sim, interpolacion).distance(punto) <= radio_presa(presa.as_ref()))
+        .map(|presa| presa.id())
+}
+
+/// Dibuja un mensaje centrado; en pantallas estrechas (móvil en vertical) se reduce para que quepa.
+fn dibujar_mensaje_central(texto: &str) {
+    let ancho = measure_text(texto, None, 40, 1.0).width;
+    let tamano = (40.0 * (screen_width() - 20.0) / ancho).min(40.0);
+    let dims = measure_text(texto, None, tamano as u16, 1.0);
+    draw_text(texto, screen_width() / 2.0 - dims.width / 2.0, screen_height() / 2.0, tamano, BLACK);
+}
+
+/// Dibuja el estado actual de la simulación en la pantalla. Las cifras se muestran
+/// en las ventanas de `paneles`; aquí solo se pinta la escena.
+/// Con `puntos`, las presas se dibujan en una sola llamada a la GPU (ver `puntos`); si no,
+/// con su imagen de `sprites` o, si no la hay, con un círculo. Encima van las marcas y la
+/// embestida de las cazas recientes (ver `cazas`) y, si se piden, los `rastros`; sobre todo
+/// ello, las capas propias registradas (ver `capas`). Las posiciones se interpolan entre el
+/// día anterior y el actual con `interpolacion`.
+#[allow(clippy::too_many_arguments)]
+fn dibujar_simulacion(sim: &simulacion::Simulacion, interpolacion: &reloj::Interpolacion, idioma: Idioma, seleccion: Option<u64>, arrastre: Option<Vec2>, camara: &control::Camara, mut puntos: Option<&mut puntos::PuntosGpu>, sprites: &sprites::Sprites, cazas: &cazas::Cazas, rastros: Option<&rastros::Rastros>) {
     clear_background(Color::from_rgba(135, 206, 235, 255)); // Sky Blue
 
+    // La escena se dibuja a través de la cámara (zoom y desplazamiento); los textos, no.
+    set_camera(&camara.camara2d());
+
+    // En el modo de cadena trófica, el pastizal se pinta de fondo: cuanto más verde, más pasto.
+    if sim.parametros.cadena_trofica.activa {
+        let pastizal = sim.pastizal();
+        for celda in 0..pastizal.n_celdas() as u32 {
+            let rect = rect_celda(pastizal, celda);
+            let cobertura = pastizal.cobertura(celda, &sim.parametros.cadena_trofica) as f32;
+            let color = Color::new(0.76 - 0.5 * cobertura, 0.65 + 0.1 * cobertura, 0.4 - 0.25 * cobertura, 1.0);
+            draw_rectangle(rect.x, rect.y, rect.w, rect.h, color);
+        }
+    }
+
     // Dibuja cada presa en la simulación.
     for presa in &sim.presas {
-        // El color depende de la especie.
+        // El color depende de la especie; las infectadas por la enfermedad contagiosa se ven verdes.
+        let infectada = sim.salud_presa(presa.id()) == enfermedad::Salud::Infectada;
         let color = match presa.especie() {
+            _ if infectada => LIME,
             entidades::Especie::Conejo => WHITE,
             entidades::Especie::Cabra => BROWN,
         };
-        
-        // Genera una posición consistente usando el ID para que no salten por la pantalla.
-        let mut x = (presa.id() * 27) as f32 % (screen_width() - 40.0) + 20.0;
-        let mut y = (presa.id() * 53) as f32 % (screen_height() - 120.0) + 100.0;
-        
-        // Añade un pequeño movimiento basado en la edad para que no se apilen.
-        x = (x + presa.edad() as f32 * 0.1) % (screen_width() - 40.0) + 20.0;
-        y = (y + presa.edad() as f32 * 0.1) % (screen_height() - 120.0) + 100.0;
+        let posicion = posicion_presa(presa.as_ref(), sim, interpolacion);
+        let radio = radio_presa(presa.as_ref());
+        let clase = presa.especie().clase_edad(presa.edad(), &sim.parametros);
+        // La clase de edad se distingue por el contorno: los juveniles se ven translúcidos,
+        // los adultos con un borde fino y los senescentes con uno gris más grueso.
+        // Con imágenes, los juveniles también se ven translúcidos y los senescentes, apagados.
+        if let Some(puntos) = puntos.as_deref_mut() {
+            let alfa = if clase == entidades::ClaseEdad::Juvenil { 0.6 } else { 1.0 };
+            puntos.anadir(posicion, radio, Color { a: alfa, ..color });
+        } else if let Some(textura) = sprites.presa(presa.especie()) {
+            let tinte = match clase {
+                _ if infectada => LIME,
+                entidades::ClaseEdad::Juvenil => Color { a: 0.6, ..WHITE },
+                entidades::ClaseEdad::Senescente => LIGHTGRAY,
+                _ => WHITE,
+            };
+            sprites::dibujar(textura, posicion, radio, tinte);
+        } else {
+            match clase {
+                entidades::ClaseEdad::Juvenil => draw_circle(posicion.x, posicion.y, radio, Color { a: 0.6, ..color }),
+                entidades::ClaseEdad::Subadulto => draw_circle(posicion.x, posicion.y, radio, color),
+                entidades::ClaseEdad::Adulto => {
+                    draw_circle(posicion.x, posicion.y, radio, color);
+                    draw_circle_lines(posicion.x, posicion.y, radio, 1.0, BLACK);
+                }
+                entidades::ClaseEdad::Senescente => {
+                    draw_circle(posicion.x, posicion.y, radio, color);
+                    draw_circle_lines(posicion.x, posicion.y, radio, 2.5, DARKGRAY);
+                }
+            }
+        }
 
-        // El radio del círculo es proporcional al peso de la presa.
-        let radio = 4.0 + (presa.peso() / 15.0) as f32;
-        draw_circle(x, y, radio, color);
+        // Resalta la presa que muestra el inspector.
+        if seleccion == Some(presa.id()) {
+            draw_circle_lines(posicion.x, posicion.y, radio + 4.0, 2.0, YELLOW);
+        }
+    }
+    if let Some(puntos) = puntos {
+        puntos.dibujar(&camara.camara2d());
+    }
+    if let Some(rastros) = rastros {
+        rastros.dibujar();
     }
+    cazas.dibujar();
     
+    // Con la manada, los demás depredadores se dibujan más pequeños; mientras no se han movido
+    // del centro del terreno, en corro alrededor del de referencia.
+    for (i, otro) in sim.manada.iter().enumerate().filter(|(_, d)| d.vivo) {
+        let posicion = match otro.posicion {
+            Some(posicion) => a_escena(sim, posicion),
+            None => a_escena(sim, interpolacion.depredador(sim)) + Vec2::from_angle(i as f32 * 0.9) * RADIO_DEPREDADOR * 2.0,
+        };
+        draw_circle(posicion.x, posicion.y, RADIO_DEPREDADOR * 0.7, color_depredador(otro, &sim.parametros));
+    }
+
     // Dibuja al depredador, cambiando de color según su estado de alimentación.
     if sim.depredador.vivo {
-        let depredador_color = if sim.depredador.reserva_comida_kg >= entidades::DEPREDADOR_CONSUMO_OPTIMO_DIARIO_KG {
-            RED // Óptimo
-        } else if sim.depredador.reserva_comida_kg >= entidades::DEPREDADOR_CONSUMO_MINIMO_DIARIO_KG {
-            ORANGE // Mínimo
-        } else {
-            DARKGRAY // Peligro de muerte
-        };
-        draw_circle(screen_width() / 2.0, 50.0, 20.0, depredador_color);
+        let depredador_color = color_depredador(&sim.depredador, &sim.parametros);
+        let posicion = cazas.posicion_depredador(posicion_depredador(sim, arrastre, interpolacion));
+        match sprites.depredador() {
+            // La imagen se tiñe solo cuando no está en su estado óptimo.
+            Some(textura) => sprites::dibujar(textura, posicion, RADIO_DEPREDADOR, if depredador_color == RED { WHITE } else { depredador_color }),
+            None => draw_circle(posicion.x, posicion.y, RADIO_DEPREDADOR, depredador_color),
+        }
+    }
+    capas::dibujar_todas(sim, &mut LienzoEscena);
+    set_default_camera();
+
+    // Muestra un mensaje de fin de juego si el depredador muere.
+    if !sim.depredador.vivo {
+        dibujar_mensaje_central(idioma.texto(Texto::DepredadorMuerto));
+    }
+     // Muestra un mensaje si las presas se extinguen.
+     if sim.presas.is_empty() && sim.depredador.vivo {
+        dibujar_mensaje_central(idioma.texto(Texto::PresasExtinguidas));
     }
 
-    // Muestra las estadísticas de la simulación como texto.
-    let font_size = 20.0;
-    let mut current_y = 20.0;
+    // Dibuja la leyenda al final para que esté en primer plano.
+    dibujar_leyenda(idioma, sprites);
+}
 
-    // Información general
-    draw_text(&format!("Día: {}", sim.dia), 10.0, current_y, font_size, DARKGRAY);
-    current_y += 25.0;
+// --- Enlaces compartibles ---
 
-    // Conteo de especies
-    let (conejos, cabras) = sim.contar_especies();
-    draw_text(&format!("Conejos: {}", conejos), 10.0, current_y, font_size, DARKGRAY);
-    current_y += 25.0;
-    draw_text(&format!("Cabras: {}", cabras), 10.0, current_y, font_size, DARKGRAY);
-    current_y += 25.0;
-    draw_text(&format!("Población Total: {}", sim.presas.len()), 10.0, current_y, font_size, DARKGRAY);
-    current_y += 25.0;
+/// Integración con el navegador en la versión web: lectura y escritura de `location.hash`
+/// (a través del plugin `web/enlace.js`) y fuente de entropía para el generador aleatorio.
+#[cfg(target_arch = "wasm32")]
+mod navegador {
+    use std::sync::atomic::{AtomicU64, Ordering};
 
+    extern "C" {
+        fn enlace_longitud_fragmento() -> u32;
+        fn enlace_leer_fragmento(destino: *mut u8, longitud: u32);
+        fn enlace_publicar_fragmento(origen: *const u8, longitud: u32);
+    }
 
-    // Estado del depredador
-    draw_text(&format!("Reserva Depredador: {:.1} kg", sim.depredador.reserva_comida_kg), 10.0, current_y, font_size, DARKGRAY);
-    current_y += 25.0;
+    /// Versión que el plugin de JavaScript comprueba al iniciarse.
+    #[no_mangle]
+    pub extern "C" fn enlace_crate_version() -> u32 {
+        1
+    }
 
-    if sim.depredador.vivo {
-        let estado_depredador = if sim.depredador.reserva_comida_kg >= entidades::DEPREDADOR_CONSUMO_OPTIMO_DIARIO_KG {
-            "Estado: Óptimo"
-        } else if sim.depredador.reserva_comida_kg >= entidades::DEPREDADOR_CONSUMO_MINIMO_DIARIO_KG {
-            "Estado: Mínimo"
-        } else {
-            "Estado: Peligro"
+    /// Fragmento de la URL actual, sin el `#`.
+    pub fn leer_fragmento() -> String {
+        let mut bytes = vec![0u8; unsafe { enlace_longitud_fragmento() } as usize];
+        unsafe { enlace_leer_fragmento(bytes.as_mut_ptr(), bytes.len() as u32) };
+        String::from_utf8_lossy(&bytes).into_owned()
+    }
+
+    /// Reemplaza el fragmento de la URL, de modo que la barra de direcciones ya es el enlace.
+    pub fn publicar_fragmento(fragmento: &str) {
+        unsafe { enlace_publicar_fragmento(fragmento.as_ptr(), fragmento.len() as u32) };
+    }
+
+    /// El navegador no ofrece entropía del sistema sin wasm-bindgen: se mezcla el reloj
+    /// con un contador (SplitMix64). Solo se usa para sortear semillas, no para criptografía.
+    fn entropia(destino: &mut [u8]) -> Result<(), getrandom::Error> {
+        static CONTADOR: AtomicU64 = AtomicU64::new(0);
+        for trozo in destino.chunks_mut(8) {
+            let mut z = (macroquad::miniquad::date::now() * 1000.0) as u64
+                ^ CONTADOR.fetch_add(0x9E37_79B9_7F4A_7C15, Ordering::Relaxed);
+            z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+            z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+            z ^= z >> 31;
+            trozo.copy_from_slice(&z.to_le_bytes()[..trozo.len()]);
+        }
+        Ok(())
+    }
+
+    getrandom::register_custom_getrandom!(entropia);
+}
+
+/// Escenario recibido por enlace: el fragmento de la URL en la web, o `--enlace "#..."`
+/// en la versión de escritorio para reproducir un enlace compartido.
+fn escenario_desde_enlace(idioma: Idioma) -> Option<enlace::EscenarioEnlace> {
+    #[cfg(target_arch = "wasm32")]
+    let fragmento = Some(navegador::leer_fragmento()).filter(|f| !f.is_empty());
+    #[cfg(not(target_arch = "wasm32"))]
+    let fragmento = std::env::args().skip_while(|a| a != "--enlace").nth(1);
+
+    match enlace::decodificar(&fragmento?) {
+        Ok(escenario) => Some(escenario),
+        Err(e) => {
+            eprintln!("{}: {}", idioma.texto(Texto::EnlaceInvalido), e);
+            None
+        }
+    }
+}
+
+/// Muestra (y en la web, fija en la barra de direcciones) el enlace que reproduce la corrida.
+fn compartir_enlace(semilla: u64, parametros: &simulador_ecosistema_presa_depredador::parametros::Parametros, idioma: Idioma) {
+    let fragmento = enlace::codificar(semilla, parametros);
+    #[cfg(target_arch = "wasm32")]
+    navegador::publicar_fragmento(&fragmento);
+    println!("{}: #{}", idioma.texto(Texto::EnlaceCorrida), fragmento);
+}
+
+// --- Android ---
+
+/// En Android no hay carpeta de trabajo: `simulacion.toml` y las definiciones de especie se
+/// empaquetan como recursos del APK (carpeta `android/assets`) y se leen desde ahí.
+#[cfg(target_os = "android")]
+mod recursos {
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    use simulador_ecosistema_presa_depredador::entidades::Especie;
+    use simulador_ecosistema_presa_depredador::especies;
+    use simulador_ecosistema_presa_depredador::parametros::Parametros;
+
+    /// Contenido de un recurso del APK, si existe. En Android la lectura es inmediata.
+    pub fn leer(ruta: &str) -> Option<String> {
+        let contenido = Rc::new(RefCell::new(None));
+        let destino = Rc::clone(&contenido);
+        macroquad::miniquad::fs::load_file(ruta, move |respuesta| *destino.borrow_mut() = respuesta.ok());
+        let bytes = contenido.borrow_mut().take()?;
+        String::from_utf8(bytes).ok()
+    }
+
+    /// Importa las definiciones de especie empaquetadas. Los recursos no se pueden listar,
+    /// así que se buscan por su nombre de archivo (`especies/conejo.json`).
+    pub fn importar_especies(parametros: &mut Parametros) -> Result<Vec<String>, String> {
+        let mut importadas = Vec::new();
+        for especie in [Especie::Conejo, Especie::Cabra] {
+            let ruta = format!("{}/{}", especies::CARPETA_ESPECIES, especies::nombre_archivo(especie));
+            if let Some(texto) = leer(&ruta) {
+                especies::DefinicionEspecie::desde_json(&texto)
+                    .map_err(|e| format!("{}: {}", ruta, e))?
+                    .aplicar(parametros)?;
+                importadas.push(ruta);
+            }
+        }
+        Ok(importadas)
+    }
+}
+
+// --- Creación de la simulación ---
+
+/// Escenario de `simulacion.toml` si existe (o el por defecto), leído una sola vez.
+fn escenario_local() -> &'static configuracion::Escenario {
+    static ESCENARIO: OnceLock<configuracion::Escenario> = OnceLock::new();
+    ESCENARIO.get_or_init(|| {
+        let ruta = recarga::ARCHIVO_PARAMETROS;
+        #[cfg(target_os = "android")]
+        let leido = recursos::leer(ruta).map(|texto| configuracion::interpretar_escenario(&texto, false));
+        #[cfg(not(target_os = "android"))]
+        let leido = std::path::Path::new(ruta).exists().then(|| configuracion::cargar_escenario(ruta));
+        leido.unwrap_or_else(|| Ok(configuracion::Escenario::default())).unwrap_or_else(|e| {
+            eprintln!("{} ({})", e, Idioma::elegir(None).texto(Texto::ValoresPorDefecto));
+            configuracion::Escenario::default()
+        })
+    })
+}
+
+/// Alarmas del escenario más las pedidas con `--alarma "conejos < 10"`, leídas una sola vez.
+fn alarmas_configuradas() -> &'static [Alarma] {
+    static ALARMAS: OnceLock<Vec<Alarma>> = OnceLock::new();
+    ALARMAS.get_or_init(|| {
+        let mut alarmas = escenario_local().alarmas.clone();
+        let mut args = std::env::args();
+        while let Some(arg) = args.next() {
+            if arg != "--alarma" {
+                continue;
+            }
+            match args.next().map(|texto| Alarma::desde_texto(&texto)) {
+                Some(Ok(alarma)) => alarmas.push(alarma),
+                Some(Err(e)) => eprintln!("{}", e),
+                None => eprintln!("falta el valor de --alarma"),
+            }
+        }
+        alarmas
+    })
+}
+
+/// Idioma de la interfaz: el del escenario o, si no lo indica, el del sistema.
+fn idioma_interfaz() -> Idioma {
+    Idioma::elegir(escenario_local().idioma)
+}
+
+/// Volcado de fotogramas pedido por línea de comandos, leído una sola vez. Si los flags son
+/// inválidos, se avisa y se cierra antes de abrir la ventana.
+fn opciones_volcado() -> Option<&'static volcado::OpcionesVolcado> {
+    static OPCIONES: OnceLock<Option<volcado::OpcionesVolcado>> = OnceLock::new();
+    OPCIONES.get_or_init(|| {
+        volcado::OpcionesVolcado::desde_args(std::env::args().skip(1)).unwrap_or_else(|e| {
+            eprintln!("{}", e);
+            std::process::exit(2);
+        })
+    }).as_ref()
+}
+
+/// Tablero de escenarios pedido por línea de comandos, leído una sola vez. Si los flags son
+/// inválidos, se avisa y se cierra antes de abrir la ventana.
+fn opciones_tablero() -> Option<&'static tablero::OpcionesTablero> {
+    static OPCIONES: OnceLock<Option<tablero::OpcionesTablero>> = OnceLock::new();
+    OPCIONES.get_or_init(|| {
+        tablero::OpcionesTablero::desde_args(std::env::args().skip(1)).unwrap_or_else(|e| {
+            eprintln!("{}", e);
+            std::process::exit(2);
+        })
+    }).as_ref()
+}
+
+/// Repetición pedida por línea de comandos, leída una sola vez. Si los flags son inválidos,
+/// se avisa y se cierra antes de abrir la ventana.
+fn opciones_reproductor() -> Option<&'static reproductor::OpcionesReproductor> {
+    static OPCIONES: OnceLock<Option<reproductor::OpcionesReproductor>> = OnceLock::new();
+    OPCIONES.get_or_init(|| {
+        reproductor::OpcionesReproductor::desde_args(std::env::args().skip(1)).unwrap_or_else(|e| {
+            eprintln!("{}", e);
+            std::process::exit(2);
+        })
+    }).as_ref()
+}
+
+/// Configuración de la ventana; el título ya sale en el idioma elegido. Al volcar fotogramas,
+/// la ventana tiene exactamente la resolución pedida.
+fn configuracion_ventana() -> Conf {
+    let titulo = idioma_interfaz().texto(Texto::TituloVentana).to_string();
+    match opciones_volcado() {
+        Some(volcado) => Conf {
+            window_title: titulo,
+            window_width: volcado.ancho as i32,
+            window_height: volcado.alto as i32,
+            window_resizable: false,
+            high_dpi: false,
+            ..Default::default()
+        },
+        None => Conf { window_title: titulo, ..Default::default() },
+    }
+}
+
+/// Crea la simulación a partir de un enlace compartido, de `simulacion.toml` si existe,
+/// o con los valores por defecto. Las definiciones de especie de la carpeta `especies/`
+/// se aplican sobre los parámetros del archivo, y después la dificultad del escenario.
+/// Devuelve también la dificultad aplicada (la de un enlace ya va incluida en sus parámetros).
+fn crear_simulacion(idioma: Idioma) -> (simulacion::Simulacion, Dificultad) {
+    if let Some(escenario) = escenario_desde_enlace(idioma) {
+        let constructor = simulacion::SimulacionBuilder::new().parametros(escenario.parametros);
+        let sim = match escenario.semilla {
+            Some(semilla) => constructor.semilla(semilla).construir(),
+            None => constructor.construir(),
         };
-        draw_text(estado_depredador, 10.0, current_y, font_size, DARKGRAY);
+        return (sim, Dificultad::Normal);
     }
 
+    let escenario = escenario_local().clone();
+    let mut parametros = escenario.parametros;
+    #[cfg(target_os = "android")]
+    let importadas = recursos::importar_especies(&mut parametros);
+    #[cfg(not(target_os = "android"))]
+    let importadas = especies::importar_carpeta(especies::CARPETA_ESPECIES, &mut parametros)
+        .map(|rutas| rutas.iter().map(|r| r.display().to_string()).collect::<Vec<_>>());
+    match importadas {
+        Ok(rutas) => {
+            for ruta in rutas {
+                println!("{}: {}", idioma.texto(Texto::EspecieImportada), ruta);
+            }
+        }
+        Err(e) => eprintln!("{} ({})", e, idioma.texto(Texto::EspeciesIgnoradas)),
+    }
+    let dificultad = escenario.dificultad.unwrap_or_default();
+    dificultad.aplicar(&mut parametros);
 
-    // Muestra un mensaje de fin de juego si el depredador muere.
-    if !sim.depredador.vivo {
-        let texto_fin = "¡EL DEPREDADOR HA MUERTO!";
-        let text_dims = measure_text(texto_fin, None, 40, 1.0);
-        draw_text(texto_fin, screen_width() / 2.0 - text_dims.width / 2.0, screen_height() / 2.0, 40.0, BLACK);
+    let constructor = match escenario.censo.as_deref().map(simulacion::SimulacionBuilder::desde_censo) {
+        Some(Ok(constructor)) => constructor,
+        Some(Err(e)) => {
+            eprintln!("{}", e);
+            simulacion::SimulacionBuilder::new()
+        }
+        None => simulacion::SimulacionBuilder::new(),
+    };
+    let constructor = constructor.parametros(parametros);
+    let sim = match escenario.semilla {
+        Some(semilla) => constructor.semilla(semilla).construir(),
+        None => constructor.construir(),
+    };
+    (sim, dificultad)
+}
+
+/// Duración de fotograma a partir de la cual se considera que la app estuvo suspendida.
+/// Duración de fotograma a partir de la cual se considera que la app estuvo suspendida.
+const SEGUNDOS_SUSPENSION: f32 = 2.0;
+
+/// Archivo en que F5 guarda la partida de la pestaña activa y del que F9 la carga.
+const ARCHIVO_PARTIDA: &str = "partida.sim";
+/// Archivo en que F6 guarda la repetición de la corrida de la pestaña activa.
+const ARCHIVO_REPETICION: &str = "corrida.replay";
+
+/// Días que se simulan por fotograma al saltar hasta un evento.
+const DIAS_POR_FOTOGRAMA_SALTO: u32 = 200;
+/// Segundos de cada fotograma que el modo turbo dedica a simular; con lo que tarda en
+/// dibujarse, la escena queda en unos 30 FPS.
+const PRESUPUESTO_TURBO: f64 = 0.025;
+
+/// Una simulación abierta en una pestaña, con su propio estado de interfaz: parámetros,
+/// semilla, ventanas, cámara, arrastre del depredador y desafío no se comparten con las demás.
+/// Solo avanza la pestaña activa; las demás quedan congeladas hasta que se vuelve a ellas.
+struct Pestana {
+    sim: simulacion::Simulacion,
+    paneles: paneles::Paneles,
+    camara: control::Camara,
+    /// Punto de la escena al que se está arrastrando al depredador, hasta que se suelta.
+    arrastre: Option<Vec2>,
+    cazas: cazas::Cazas,
+    rastros: rastros::Rastros,
+    corrida_records: records::CorridaRecords,
+    /// Lo que se ha visto de la corrida día a día, para volver a verla con `--reproducir`.
+    repeticion: repeticion::Repeticion,
+    // El enlace reproduce la corrida desde el principio, así que usa los parámetros iniciales.
+    parametros_iniciales: simulador_ecosistema_presa_depredador::parametros::Parametros,
+    dificultad_inicial: Dificultad,
+    reloj: reloj::RelojSimulacion,
+    interpolacion: reloj::Interpolacion,
+    medidor: reloj::MedidorRitmo,
+}
+
+impl Pestana {
+    fn new(mut sim: simulacion::Simulacion, dificultad: Dificultad) -> Self {
+        sim.definir_alarmas(alarmas_configuradas().to_vec());
+        let mut paneles = paneles::Paneles::new(&sim);
+        paneles.dificultad = dificultad;
+        paneles.desafio = escenario_local().desafio.clone().map(desafio::Desafio::new);
+        Self {
+            parametros_iniciales: sim.parametros.clone(),
+            repeticion: repeticion::Repeticion::new(&sim),
+            sim,
+            paneles,
+            camara: control::Camara::new(),
+            arrastre: None,
+            cazas: cazas::Cazas::default(),
+            rastros: rastros::Rastros::default(),
+            corrida_records: records::CorridaRecords::new(),
+            dificultad_inicial: dificultad,
+            reloj: reloj::RelojSimulacion::new(0.1),
+            interpolacion: reloj::Interpolacion::default(),
+            medidor: reloj::MedidorRitmo::new(),
+        }
     }
-     // Muestra un mensaje si las presas se extinguen.
-     if sim.presas.is_empty() && sim.depredador.vivo {
-        let texto_fin = "¡LAS PRESAS SE HAN EXTINGUIDO!";
-        let text_dims = measure_text(texto_fin, None, 40, 1.0);
-        draw_text(texto_fin, screen_width() / 2.0 - text_dims.width / 2.0, screen_height() / 2.0, 40.0, BLACK);
+
+    /// Archiva la corrida actual en el historial de semillas y empieza otra en la misma
+    /// pestaña: con una semilla al azar y los parámetros actuales, o repitiendo una archivada.
+    fn reiniciar(&mut self, pedida: paneles::NuevaCorrida) {
+        let archivada = paneles::CorridaArchivada::de(&self.sim, &self.parametros_iniciales, self.dificultad_inicial);
+        let (parametros, dificultad, semilla) = match pedida {
+            paneles::NuevaCorrida::Aleatoria => (self.sim.parametros.clone(), self.paneles.dificultad, None),
+            paneles::NuevaCorrida::Repetir(i) => {
+                let corrida = &self.paneles.historial[i];
+                (corrida.parametros.clone(), corrida.dificultad, Some(corrida.semilla))
+            }
+        };
+        let constructor = simulacion::SimulacionBuilder::new().parametros(parametros);
+        let sim = match semilla {
+            Some(semilla) => constructor.semilla(semilla).construir(),
+            None => constructor.construir(),
+        };
+        let mut nueva = Pestana::new(sim, dificultad);
+        nueva.paneles.copiar_ventanas(&self.paneles);
+        nueva.paneles.historial = std::mem::take(&mut self.paneles.historial);
+        nueva.paneles.archivar(archivada);
+        *self = nueva;
     }
 
-    // Dibuja la leyenda al final para que esté en primer plano.
-    dibujar_leyenda();
+    /// Sustituye la corrida de la pestaña por una partida guardada, que sigue exactamente donde
+    /// quedó; las ventanas y el historial de semillas se conservan.
+    fn reanudar(&mut self, sim: simulacion::Simulacion) {
+        let mut nueva = Pestana::new(sim, self.paneles.dificultad);
+        nueva.paneles.copiar_ventanas(&self.paneles);
+        nueva.paneles.historial = std::mem::take(&mut self.paneles.historial);
+        *self = nueva;
+    }
+
+    /// Texto de la pestaña: número y semilla, para distinguir corridas con los mismos parámetros.
+    fn etiqueta(&self, numero: usize, idioma: Idioma) -> String {
+        format!("{} {} ({})", idioma.texto(Texto::Simulacion), numero, self.sim.semilla())
+    }
 }
 
 /// Punto de entrada de la aplicación, marcado para ser ejecutado por macroquad.
-#[macroquad::main("Simulador de Ecosistema")]
+#[macroquad::main(configuracion_ventana)]
 async fn main() {
+    let idioma = idioma_interfaz();
     // Se crea la instancia de la simulación una sola vez.
-    let mut sim = simulacion::Simulacion::new();
-    let mut tiempo_desde_ultimo_dia = 0.0;
+    let (mut sim, dificultad) = crear_simulacion(idioma);
+    // Las figuras del escenario se dibujan como una capa propia más.
+    for figura in &escenario_local().capas {
+        capas::registrar(Box::new(figura.clone()));
+    }
+
+    // Con `--tablero` se muestran varias corridas a la vez en lugar de la simulación.
+    if let Some(opciones) = opciones_tablero() {
+        if let Err(e) = tablero::mostrar(opciones, sim.parametros.clone(), escenario_local().semilla, idioma).await {
+            eprintln!("{}", e);
+            std::process::exit(1);
+        }
+        return;
+    }
+
+    // Con `--reproducir` se muestra una corrida grabada en lugar de la simulación.
+    if let Some(opciones) = opciones_reproductor() {
+        if let Err(e) = reproductor::mostrar(opciones, idioma).await {
+            eprintln!("{}", e);
+            std::process::exit(1);
+        }
+        return;
+    }
+
+    // Con `--fotogramas` solo se dibuja y guarda cada fotograma, sin esperar al reloj.
+    if let Some(opciones) = opciones_volcado() {
+        match volcado::volcar(&mut sim, opciones, idioma).await {
+            Ok(escritos) => println!("{} PNG en {}", escritos, opciones.carpeta.display()),
+            Err(e) => {
+                eprintln!("{}", e);
+                std::process::exit(1);
+            }
+        }
+        return;
+    }
+
+    // Vigila el archivo de parámetros para aplicar cambios sin reiniciar (en la pestaña activa).
+    let mut vigilante = recarga::VigilanteParametros::new(recarga::ARCHIVO_PARAMETROS);
+    let mut tiempo_desde_revision = 0.0;
+    let mut avisos = avisos::Avisos::default();
+    let mut consola = consola::Consola::default();
+
+    // Archivo para la superposición de una transmisión en directo, si el escenario lo pide.
+    let superposicion = escenario_local().superposicion.as_ref().map(superposicion::Superposicion::new);
+
+    // Efectos y fondo estacional; si el audio falla, la simulación sigue sin sonido.
+    let mut sonido = match sonido::Sonido::cargar(&escenario_local().audio, &sim).await {
+        Ok(sonido) => Some(sonido),
+        Err(e) => {
+            eprintln!("{} ({})", e, idioma.texto(Texto::SonidoNoDisponible));
+            None
+        }
+    };
+
+    // Las pestañas nuevas parten de los parámetros de la primera, con otra semilla.
+    let parametros_base = sim.parametros.clone();
+    let mut pestanas = vec![Pestana::new(sim, dificultad)];
+    let mut activa = 0;
+    pestanas[0].paneles.sonido = sonido.as_ref().map(|s| s.activado());
+    // Imágenes de las presas y del depredador; las que falten se dibujan como círculos.
+    let carpeta_sprites = escenario_local().sprites.as_deref().unwrap_or(sprites::CARPETA_SPRITES);
+    let sprites = sprites::Sprites::cargar(carpeta_sprites).await;
+
+    // Dibujo de las presas por instancias, si la GPU lo admite; se activa desde la barra.
+    let mut puntos_gpu = puntos::PuntosGpu::new();
+    pestanas[0].paneles.puntos_gpu = puntos_gpu.is_some().then_some(false);
+
+    // Récords de esta máquina: se muestran al arrancar y se guardan como mucho una vez por segundo.
+    let mut records = records::Records::cargar(records::ARCHIVO_RECORDS).unwrap_or_else(|e| {
+        eprintln!("{}", e);
+        records::Records::default()
+    });
+
+    // Tutorial guiado, si el escenario o la línea de comandos lo piden.
+    let pedir_tutorial = escenario_local().tutorial || std::env::args().any(|a| a == "--tutorial");
+    let mut tutorial = pedir_tutorial.then(tutorial::Tutorial::new);
+    let mut records_pendientes = false;
+    
+    // Teclado, ratón, táctil y mando.
+    let mut control = control::Control::new();
     
     // Bucle principal que se ejecuta en cada fotograma.
     loop {
-        // Permite controlar la velocidad de la simulación con las teclas de flecha.
-        let tiempo_por_dia = if is_key_down(KeyCode::Right) {
-            0.02 // Cámara rápida
-        } else if is_key_down(KeyCode::Left) {
-            0.5  // Cámara lenta
-        } else {
-            0.1  // Velocidad normal (10 días por segundo)
+        let etiquetas: Vec<String> = pestanas.iter().enumerate().map(|(i, p)| p.etiqueta(i + 1, idioma)).collect();
+        let Pestana { sim, paneles, camara, arrastre, cazas, rastros, corrida_records, repeticion, parametros_iniciales, reloj, interpolacion, medidor, .. } =
+            &mut pestanas[activa];
+
+        // Un fotograma tan largo solo ocurre si la app estuvo suspendida (Android en segundo
+        // plano, pestaña oculta en la web): se pausa la simulación y se descarta ese tiempo,
+        // en lugar de simular de golpe lo que no se pudo ver.
+        let mut segundos = get_frame_time();
+        if segundos > SEGUNDOS_SUSPENSION {
+            paneles.pausado = true;
+            segundos = 0.0;
+        }
+        tiempo_desde_revision += segundos;
+
+        // Una vez por segundo, revisa si el archivo de parámetros cambió.
+        if tiempo_desde_revision > 1.0 {
+            tiempo_desde_revision = 0.0;
+            if records_pendientes {
+                records_pendientes = false;
+                if let Err(e) = records.guardar(records::ARCHIVO_RECORDS) {
+                    eprintln!("{}: {}", idioma.texto(Texto::RecordsNoGuardados), e);
+                }
+            }
+            match vigilante.revisar(sim) {
+                Ok(Some(resultado)) => {
+                    paneles.parametros_recargados(sim);
+                    for evento in &sim.eventos_dia {
+                        if matches!(evento, eventos::Evento::ParametroCambiado { .. }) {
+                            println!("{} {}: {}", idioma.texto(Texto::Dia), sim.dia, evento.descripcion());
+                        }
+                    }
+                    if !resultado.ignorados.is_empty() {
+                        println!("{}: {}", idioma.texto(Texto::RequierenReinicio), resultado.ignorados.join(", "));
+                    }
+                }
+                Ok(None) => {}
+                Err(e) => eprintln!("{}: {}", idioma.texto(Texto::RecargaRechazada), e),
+            }
+        }
+
+        // Las ventanas se construyen antes de leer el teclado y el ratón, para saber
+        // si la entrada de este fotograma es suya o de la escena.
+        let mut entrada_en_ventanas = (false, false);
+        let mut orden_pestana = None;
+        let mut partida_cargada = None;
+        let mut eventos_consola = None;
+        egui_macroquad::ui(|ctx| {
+            orden_pestana = paneles::barra_pestanas(ctx, &etiquetas, activa, idioma);
+            paneles.mostrar(ctx, sim, &records, idioma);
+            let previos = sim.eventos_dia.len();
+            if consola.mostrar(ctx, sim, idioma) {
+                eventos_consola = Some(previos);
+            }
+            avisos.mostrar(ctx, segundos);
+            if let Some(tutorial) = &mut tutorial {
+                if tutorial.mostrar(ctx, sim, paneles, idioma) {
+                    paneles.pausado = false;
+                }
+            }
+            entrada_en_ventanas = (ctx.wants_pointer_input() || ctx.is_pointer_over_area(), ctx.wants_keyboard_input());
+        });
+        let (raton_en_ventanas, teclado_en_ventanas) = entrada_en_ventanas;
+
+        // Las órdenes de la consola son intervenciones como las del ratón: quedan en el registro
+        // (y en el desafío), y la ventana de parámetros vuelve a mostrar los vigentes.
+        if let Some(previos) = eventos_consola {
+            if let Some(desafio) = &mut paneles.desafio {
+                desafio.anotar_eventos(&sim.eventos_dia[previos..]);
+            }
+            paneles.anotar_eventos(sim.dia, &sim.eventos_dia[previos..]);
+            paneles.descartar_borrador(sim);
+            if paneles.seleccion.is_some_and(|id| !sim.presas.iter().any(|p| p.id() == id)) {
+                paneles.seleccion = None;
+            }
+        }
+        // La tecla ` despliega y recoge la consola.
+        if is_key_pressed(KeyCode::GraveAccent) {
+            consola.alternar();
+        }
+        let mut acciones = control.leer(!raton_en_ventanas, !teclado_en_ventanas);
+        camara.aplicar(&acciones);
+        paneles.tactil = control.tactil();
+
+        // El interruptor de sonido de la barra superior.
+        if let (Some(sonido), Some(activado)) = (&mut sonido, paneles.sonido) {
+            if sonido.activado() != activado {
+                sonido.alternar(activado, sim);
+            }
+        }
+
+        // El depredador se puede arrastrar a otro sitio (reubicación); al soltarlo, la
+        // intervención queda en el registro de eventos.
+        if let Some(punto) = acciones.pulsar {
+            if sim.depredador.vivo && camara.a_escena(punto).distance(posicion_depredador(sim, None, interpolacion)) <= RADIO_DEPREDADOR {
+                control.capturar_arrastre();
+            }
+        }
+        if let Some(punto) = acciones.arrastrar {
+            *arrastre = Some(camara.a_escena(punto));
+        }
+        if let Some(punto) = acciones.soltar {
+            *arrastre = None;
+            let destino = a_terreno(sim, camara.a_escena(punto));
+            let previos = sim.eventos_dia.len();
+            sim.reubicar_depredador(destino.x, destino.y);
+            interpolacion.olvidar();
+            paneles.anotar_eventos(sim.dia, &sim.eventos_dia[previos..]);
+        }
+
+        // En el modo de edición, un clic añade una presa donde se hizo y el clic derecho retira
+        // la que está debajo; ambas intervenciones quedan en el registro de eventos.
+        if paneles.edicion && sim.depredador.vivo {
+            let previos = sim.eventos_dia.len();
+            if let Some(punto) = acciones.inspeccionar.take() {
+                if let Some(id) = sim.agregar_presa(paneles.especie_edicion) {
+                    sim.colocar_presa(id, a_terreno(sim, camara.a_escena(punto)));
+                }
+            }
+            if let Some(id) = acciones.retirar.and_then(|punto| presa_en(sim, camara.a_escena(punto), interpolacion)) {
+                sim.retirar_presa(id);
+                if paneles.seleccion == Some(id) {
+                    paneles.seleccion = None;
+                }
+            }
+            // Las presas retiradas cuentan como biomasa cosechada en el desafío.
+            if let Some(desafio) = &mut paneles.desafio {
+                desafio.anotar_eventos(&sim.eventos_dia[previos..]);
+            }
+            paneles.anotar_eventos(sim.dia, &sim.eventos_dia[previos..]);
+        }
+
+        // Un clic o toque sobre una presa la selecciona en el inspector; sobre el fondo, la deselecciona.
+        if let Some(punto) = acciones.inspeccionar {
+            paneles.seleccion = presa_en(sim, camara.a_escena(punto), interpolacion);
+            if paneles.seleccion.is_some() {
+                paneles.inspector = true;
+            }
+        }
+        // Con el mando se recorren las presas una a una.
+        if acciones.cambiar_seleccion != 0 && !sim.presas.is_empty() {
+            let actual = paneles.seleccion.and_then(|id| sim.presas.iter().position(|p| p.id() == id));
+            let total = sim.presas.len() as i64;
+            let indice = match actual {
+                Some(i) => (i as i64 + acciones.cambiar_seleccion as i64).rem_euclid(total),
+                None => 0,
+            };
+            paneles.seleccion = Some(sim.presas[indice as usize].id());
+            paneles.inspector = true;
+        }
+
+        // La tecla L comparte el enlace que reproduce esta corrida.
+        if is_key_pressed(KeyCode::L) && !teclado_en_ventanas {
+            compartir_enlace(sim.semilla(), parametros_iniciales, idioma);
+        }
+        // La tecla R copia un resumen del estado en texto, para lectores de pantalla o para
+        // pegarlo al pedir ayuda; también se escribe en la salida estándar.
+        if is_key_pressed(KeyCode::R) && !teclado_en_ventanas && !acciones.reiniciar {
+            let resumen = paneles.resumen(sim);
+            miniquad::window::clipboard_set(&resumen);
+            println!("{}", resumen);
+            avisos.anotar_mensaje(idioma.texto(Texto::ResumenCopiado).to_string());
+        }
+        // F5 guarda la partida de la pestaña activa y F9 la vuelve a cargar, también después de
+        // cerrar la aplicación; la carga se aplica al final del fotograma.
+        if is_key_pressed(KeyCode::F5) {
+            match sim.guardar(ARCHIVO_PARTIDA) {
+                Ok(()) => avisos.anotar_mensaje(format!("{} {}", idioma.texto(Texto::PartidaGuardada), ARCHIVO_PARTIDA)),
+                Err(e) => avisos.anotar_mensaje(e),
+            }
+        }
+        if is_key_pressed(KeyCode::F9) {
+            match simulacion::Simulacion::cargar(ARCHIVO_PARTIDA) {
+                Ok(guardada) => {
+                    partida_cargada = Some(guardada);
+                    avisos.anotar_mensaje(format!("{} {}", idioma.texto(Texto::PartidaCargada), ARCHIVO_PARTIDA));
+                }
+                Err(e) => avisos.anotar_mensaje(e),
+            }
+        }
+        // F6 guarda lo visto de la corrida de la pestaña activa, para reproducirlo después.
+        if is_key_pressed(KeyCode::F6) {
+            match repeticion.guardar(ARCHIVO_REPETICION) {
+                Ok(()) => avisos.anotar_mensaje(format!("{} {}", idioma.texto(Texto::RepeticionGuardada), ARCHIVO_REPETICION)),
+                Err(e) => avisos.anotar_mensaje(e.to_string()),
+            }
+        }
+        // F3 muestra u oculta el panel de depuración; solo entonces se mide cada fase del día.
+        if is_key_pressed(KeyCode::F3) {
+            paneles.depuracion = !paneles.depuracion;
+        }
+        sim.perfilar(paneles.depuracion.then_some(get_time as fn() -> f64));
+        paneles.fps = get_fps();
+
+        if acciones.pausar {
+            paneles.pausado = !paneles.pausado;
+        }
+        // Mayús+R empieza otra corrida, como el botón "Nueva corrida" de la barra.
+        if acciones.reiniciar {
+            paneles.nueva_corrida = Some(paneles::NuevaCorrida::Aleatoria);
+        }
+        if acciones.turbo {
+            paneles.turbo = !paneles.turbo;
+        }
+        // La tecla F empieza a saltar hasta el evento elegido o cancela el salto en curso;
+        // pausar también lo cancela.
+        if acciones.saltar {
+            if paneles.saltando {
+                paneles.saltando = false;
+            } else {
+                paneles.saltar();
+            }
+        }
+        if paneles.pausado {
+            paneles.saltando = false;
+        }
+
+        // Permite controlar la velocidad de la simulación con las flechas o los gatillos, salvo
+        // que se haya fijado un ritmo exacto en la barra.
+        let tiempo_por_dia = match (paneles.ritmo_objetivo, acciones.velocidad) {
+            (Some(ritmo), _) => 1.0 / ritmo,
+            (None, control::Velocidad::Rapida) => 0.02, // Cámara rápida
+            (None, control::Velocidad::Lenta) => 0.5,   // Cámara lenta
+            (None, control::Velocidad::Normal) => 0.1,  // Velocidad normal (10 días por segundo)
         };
+        // A ritmos altos, el reloj debe poder recuperar el retraso de fotogramas de hasta 0,1 s.
+        reloj.maximo_dias = reloj::MAXIMO_DIAS_POR_DEFECTO.max((0.1 / tiempo_por_dia).ceil() as u32);
 
-        // Acumula el tiempo transcurrido desde el último fotograma.
-        tiempo_desde_ultimo_dia += get_frame_time();
-        
-        // Si ha pasado suficiente tiempo, avanza la simulación un día.
-        if tiempo_desde_ultimo_dia > tiempo_por_dia {
+        // El reloj de paso fijo convierte el tiempo transcurrido en días: a más velocidad que
+        // fotogramas por segundo, varios por fotograma, y tras un tirón, los que faltaron (hasta
+        // un máximo). En pausa, solo a petición. Al saltar hasta un evento se simulan muchos días
+        // por fotograma, y en turbo, todos los que quepan en el presupuesto del fotograma (la
+        // escena se dibuja a unos 30 FPS).
+        reloj.cambiar_velocidad(tiempo_por_dia);
+        // Solo se interpola al ritmo del reloj; en pausa, en turbo o al saltar se dibuja el día actual.
+        let interpolar = !(paneles.saltando || paneles.pausado || paneles.turbo);
+        let dias = if !interpolar {
+            reloj.reiniciar();
+            if paneles.saltando {
+                DIAS_POR_FOTOGRAMA_SALTO
+            } else if paneles.pausado {
+                acciones.paso as u32
+            } else {
+                u32::MAX
+            }
+        } else {
+            reloj.avanzar(segundos as f64)
+        };
+        let inicio_fotograma = get_time();
+        let mut simulados = 0;
+        for _ in 0..dias {
+            if paneles.turbo && simulados > 0 && get_time() - inicio_fotograma > PRESUPUESTO_TURBO {
+                break;
+            }
+            simulados += 1;
+            if interpolar {
+                interpolacion.anotar(sim);
+            }
             sim.avanzar_dia();
-            tiempo_desde_ultimo_dia = 0.0;
+            repeticion.grabar(sim);
+            if paneles.depuracion {
+                paneles.anotar_perfil(&sim.perfil_dia);
+            }
+            paneles.anotar_eventos(sim.dia, &sim.eventos_dia);
+            paneles.anotar_muertes(&sim.estadisticas_dia.muertes_por_causa);
+            paneles.anotar_poblacion(sim);
+            avisos.anotar_eventos(&sim.eventos_dia, idioma);
+            for evento in &sim.eventos_dia {
+                if let eventos::Evento::Caza { posicion, .. } = evento {
+                    cazas.anotar(a_escena(sim, *posicion));
+                }
+            }
+            // Una alarma pausa la simulación y deja un cartel hasta que se reanude.
+            let alarma = sim.eventos_dia.iter().find_map(|e| match e {
+                eventos::Evento::AlarmaDisparada { alarma } => Some(alarma.clone()),
+                _ => None,
+            });
+            if let Some(alarma) = alarma {
+                paneles.pausado = true;
+                paneles.alarma = Some((sim.dia, alarma));
+            }
+            // Con varios días por fotograma, los efectos se pisarían unos a otros.
+            if dias == 1 {
+                if let Some(sonido) = &mut sonido {
+                    sonido.dia_avanzado(sim);
+                }
+            }
+            if let Some(superposicion) = &superposicion {
+                if let Err(e) = superposicion.escribir(sim) {
+                    eprintln!("{}: {}", idioma.texto(Texto::SuperposicionFallida), e);
+                }
+            }
+            // Al terminar el desafío se pausa para mostrar la puntuación.
+            if let Some(desafio) = &mut paneles.desafio {
+                if let Some(desenlace) = desafio.dia_completado(sim) {
+                    paneles.pausado = true;
+                    if desenlace == desafio::Desenlace::Superado && records.desbloquear(records::Logro::DesafioSuperado) {
+                        avisos.anotar_logro(records::Logro::DesafioSuperado, idioma);
+                        records_pendientes = true;
+                    }
+                }
+            }
+            let novedades = corrida_records.dia_completado(&mut records, sim);
+            for logro in &novedades.logros {
+                avisos.anotar_logro(*logro, idioma);
+            }
+            records_pendientes |= novedades.record_batido || !novedades.logros.is_empty();
+            // El salto termina, en pausa, al llegar el evento, al morir el depredador (ya no
+            // habrá más días) o si otra cosa pausó la simulación (p. ej. el fin del desafío).
+            if paneles.saltando {
+                let llegado = sim.eventos_dia.iter().any(|e| e.tipo() == Some(paneles.tipo_salto));
+                if llegado || !sim.depredador.vivo || paneles.pausado {
+                    paneles.saltando = false;
+                    paneles.pausado = true;
+                }
+            }
+            // Una alarma o el fin del desafío pueden pausar a mitad de una ráfaga.
+            if paneles.pausado || !sim.depredador.vivo {
+                break;
+            }
         }
+        paneles.dias_por_fotograma = simulados;
+        interpolacion.fijar_fraccion(if interpolar { reloj.fraccion() } else { 1.0 });
+        cazas.avanzar(segundos);
+        if paneles.rastros {
+            let depredador = sim.depredador.vivo.then(|| cazas.posicion_depredador(posicion_depredador(sim, *arrastre, interpolacion)));
+            let presa = paneles.seleccion
+                .and_then(|id| sim.presas.iter().find(|p| p.id() == id))
+                .map(|p| (p.id(), posicion_presa(p.as_ref(), sim, interpolacion)));
+            rastros.anotar(depredador, presa);
+        } else {
+            *rastros = rastros::Rastros::default();
+        }
+        medidor.anotar(simulados, segundos as f64);
+        paneles.ritmo_medido = medidor.ritmo();
+
+        // El tutorial pausa la simulación cuando llega uno de sus momentos clave.
+        if let Some(tutorial) = &mut tutorial {
+            if tutorial.revisar(sim) {
+                paneles.pausado = true;
+            }
+        }
+
+        // Dibuja el estado actual y, encima, las ventanas. Durante un salto solo se muestra el progreso.
+        if paneles.saltando {
+            clear_background(Color::from_rgba(135, 206, 235, 255));
+            dibujar_mensaje_central(&format!(
+                "{} {}... ({} {})",
+                idioma.texto(Texto::Saltando),
+                idioma.texto(paneles::texto_tipo_evento(paneles.tipo_salto)),
+                idioma.texto(Texto::Dia),
+                sim.dia,
+            ));
+        } else {
+            dibujar_simulacion(sim, interpolacion, idioma, paneles.seleccion, *arrastre, camara, puntos_gpu.as_mut().filter(|_| paneles.puntos_gpu == Some(true)), &sprites, cazas, paneles.rastros.then_some(&*rastros));
+        }
+        if tutorial.as_ref().and_then(|t| t.resaltado()) == Some(tutorial::Resaltado::Depredador) {
+            set_camera(&camara.camara2d());
+            let posicion = posicion_depredador(sim, *arrastre, interpolacion);
+            let pulso = (get_time() * 4.0).sin() as f32 * 4.0;
+            draw_circle_lines(posicion.x, posicion.y, RADIO_DEPREDADOR + 8.0 + pulso, 3.0, YELLOW);
+            set_default_camera();
+        }
+        egui_macroquad::draw();
 
-        // Dibuja el estado actual.
-        dibujar_simulacion(&sim);
+        // Los cambios de corrida y de pestaña se aplican al final del fotograma, con la activa ya dibujada.
+        if let Some(pedida) = pestanas[activa].paneles.nueva_corrida.take() {
+            pestanas[activa].reiniciar(pedida);
+        }
+        if let Some(guardada) = partida_cargada {
+            pestanas[activa].reanudar(guardada);
+        }
+        match orden_pestana {
+            Some(paneles::OrdenPestana::Activar(i)) => activa = i,
+            Some(paneles::OrdenPestana::Abrir) => {
+                let sim = simulacion::SimulacionBuilder::new().parametros(parametros_base.clone()).construir();
+                let mut nueva = Pestana::new(sim, dificultad);
+                nueva.paneles.copiar_ventanas(&pestanas[activa].paneles);
+                pestanas.push(nueva);
+                activa = pestanas.len() - 1;
+            }
+            Some(paneles::OrdenPestana::Cerrar(i)) => {
+                pestanas.remove(i);
+                if activa > i || activa == pestanas.len() {
+                    activa -= 1;
+                }
+            }
+            None => {}
+        }
+        if orden_pestana.is_some() {
+            // El sonido es uno para todas las pestañas.
+            pestanas[activa].paneles.sonido = sonido.as_ref().map(|s| s.activado());
+        }
         
         // Espera al siguiente fotograma.
         next_frame().await
     }
-}
\ No newline at end of file
+}
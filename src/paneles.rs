@@ -0,0 +1,1035 @@
+// src/paneles.rs
+
+// Ventanas de egui del visualizador (parte del binario, no de la biblioteca).
+// Sustituyen al texto fijo de la esquina superior izquierda, que en pantallas pequeñas
+// tapaba a las presas: cada ventana se puede mover, plegar y ocultar desde la barra superior.
+//
+//   Estadísticas: día, poblaciones, depredador y balance del día, con las muertes acumuladas
+//   por causa y un gráfico apilado de las de los últimos días.
+//   Inspector: datos de la presa seleccionada con un clic.
+//   Registro de eventos: los eventos notables de toda la corrida.
+//   Parámetros: los parámetros que se pueden cambiar en caliente, con una línea de órdenes
+//   para los que no tienen control propio (`cabra TASA_REPRODUCCION_DIARIA 0.02`).
+//   Récords: los récords y logros de esta máquina; se abre al arrancar.
+//   Semillas: las corridas archivadas con "Nueva corrida", para repetir las interesantes.
+//   Población: al pie de la ventana, la curva de conejos, cabras y reserva del depredador de
+//   los últimos años (ver `historia`), para ver las oscilaciones.
+//   Diagrama de fases: los mismos días como trayectoria de las presas frente a la reserva del
+//   depredador, la vista clásica de Lotka-Volterra; un ciclo límite es un lazo, y la extinción,
+//   una espiral que acaba en un eje.
+//
+// "Turbo" simula tantos días por fotograma como quepan sin bajar de unos 30 FPS; la barra
+// muestra "x N" siempre que se simule más de un día por fotograma, y siempre los días por
+// segundo conseguidos. "Ritmo fijo" pide un número exacto de días por segundo.
+// Cuando salta una alarma configurada, un cartel rojo bajo la barra la recuerda hasta reanudar.
+// El menú "Saltar hasta" de la barra (o la tecla F) simula sin dibujar hasta el próximo evento
+// del tipo elegido y entonces pausa. El selector de dificultad de la barra la cambia en cualquier momento, como una recarga de
+// parámetros. El botón "Edición" de la barra activa el modo en que los clics añaden y retiran presas.
+// En el modo desafío la barra muestra el progreso y, al terminar, una ventana con la puntuación.
+// "Puntos GPU" dibuja las presas en una sola llamada a la GPU (ver `puntos`), para
+// poblaciones enormes; solo aparece si la GPU admite instanciado.
+// F3 abre un panel de depuración en la esquina superior derecha con los FPS, las entidades,
+// una estimación de la memoria y el tiempo medio de cada fase del día.
+// Encima de todo, la barra de pestañas cambia entre las simulaciones abiertas; cada pestaña
+// tiene sus propios `Paneles`.
+
+use std::collections::VecDeque;
+
+use egui_macroquad::egui;
+
+use simulador_ecosistema_presa_depredador::clima::Estacion;
+use simulador_ecosistema_presa_depredador::desafio::{Desafio, Desenlace};
+use simulador_ecosistema_presa_depredador::dificultad::Dificultad;
+use simulador_ecosistema_presa_depredador::enfermedad::{self, Salud};
+use simulador_ecosistema_presa_depredador::entidades::{self, CausaMuerte, ClaseEdad, Especie, Sexo};
+use simulador_ecosistema_presa_depredador::eventos::{Evento, TipoEvento};
+use simulador_ecosistema_presa_depredador::historia::{Historia, MuestraHistoria};
+use simulador_ecosistema_presa_depredador::idioma::{Idioma, Texto};
+
+use crate::avisos::texto_logro;
+use crate::consola;
+use simulador_ecosistema_presa_depredador::parametros::{Dieta, EstrategiaCaza, Parametros};
+use simulador_ecosistema_presa_depredador::recarga;
+use simulador_ecosistema_presa_depredador::records::Records;
+use simulador_ecosistema_presa_depredador::simulacion::{MuertesPorCausa, PerfilDia, Simulacion};
+
+/// Eventos que conserva el registro; los más antiguos se descartan.
+const MAXIMO_EVENTOS: usize = 500;
+/// Ritmo que se propone al fijar uno: un año por segundo.
+const RITMO_OBJETIVO_INICIAL: f64 = 365.0;
+/// Corridas que conserva la ventana de semillas.
+const MAXIMO_HISTORIAL: usize = 20;
+/// Días que abarca el gráfico de muertes por causa.
+const DIAS_GRAFICO_MUERTES: usize = 200;
+/// Alto inicial del gráfico de población; se puede cambiar arrastrando su borde.
+const ALTO_GRAFICO_POBLACION: f32 = 140.0;
+/// Color de cada curva del gráfico de población.
+const COLOR_CONEJOS: egui::Color32 = egui::Color32::from_rgb(220, 220, 220);
+const COLOR_CABRAS: egui::Color32 = egui::Color32::from_rgb(160, 120, 80);
+const COLOR_RESERVA: egui::Color32 = egui::Color32::from_rgb(200, 50, 50);
+/// Color de la trayectoria del diagrama de fases, que se aclara hacia los días más antiguos.
+const COLOR_FASES: egui::Color32 = egui::Color32::from_rgb(70, 130, 200);
+/// Peso de cada día nuevo en la media móvil del perfil, para que las cifras se puedan leer.
+const SUAVIZADO_PERFIL: f64 = 0.05;
+
+/// Resumen de una corrida archivada al pulsar "Nueva corrida".
+#[derive(Debug, Clone)]
+pub struct CorridaArchivada {
+    pub semilla: u64,
+    /// Parámetros con los que empezó: junto con la semilla, la reproducen.
+    pub parametros: Parametros,
+    pub dificultad: Dificultad,
+    /// Último día al que llegó.
+    pub dia: u32,
+    pub conejos: usize,
+    pub cabras: usize,
+    pub depredador_vivo: bool,
+}
+
+impl CorridaArchivada {
+    pub fn de(sim: &Simulacion, parametros: &Parametros, dificultad: Dificultad) -> Self {
+        let (conejos, cabras) = sim.contar_especies();
+        Self {
+            semilla: sim.semilla(),
+            parametros: parametros.clone(),
+            dificultad,
+            dia: sim.dia,
+            conejos,
+            cabras,
+            depredador_vivo: sim.depredador.vivo,
+        }
+    }
+}
+
+/// Corrida que se pidió empezar en la pestaña.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NuevaCorrida {
+    /// Semilla al azar con los parámetros actuales.
+    Aleatoria,
+    /// La corrida del historial con este índice.
+    Repetir(usize),
+}
+
+/// Lo que se pidió en la barra de pestañas.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OrdenPestana {
+    Activar(usize),
+    Abrir,
+    Cerrar(usize),
+}
+
+/// Dibuja la barra de pestañas con una etiqueta por simulación abierta.
+pub fn barra_pestanas(ctx: &egui::Context, etiquetas: &[String], activa: usize, idioma: Idioma) -> Option<OrdenPestana> {
+    let mut orden = None;
+    egui::TopBottomPanel::top("barra_pestanas").show(ctx, |ui| {
+        ui.horizontal_wrapped(|ui| {
+            for (i, etiqueta) in etiquetas.iter().enumerate() {
+                if ui.selectable_label(i == activa, etiqueta).clicked() && i != activa {
+                    orden = Some(OrdenPestana::Activar(i));
+                }
+                // La última simulación no se puede cerrar.
+                if etiquetas.len() > 1 && ui.small_button("×").on_hover_text(idioma.texto(Texto::CerrarPestana)).clicked() {
+                    orden = Some(OrdenPestana::Cerrar(i));
+                }
+                ui.separator();
+            }
+            if ui.button("+").on_hover_text(idioma.texto(Texto::NuevaPestana)).clicked() {
+                orden = Some(OrdenPestana::Abrir);
+            }
+        });
+    });
+    orden
+}
+
+/// Estado de las ventanas entre fotogramas.
+pub struct Paneles {
+    pub estadisticas: bool,
+    pub inspector: bool,
+    pub eventos: bool,
+    pub parametros: bool,
+    pub ventana_records: bool,
+    pub semillas: bool,
+    /// La simulación está en pausa (se avanza día a día).
+    pub pausado: bool,
+    /// Modo turbo: tantos días por fotograma como quepan en su presupuesto de tiempo.
+    pub turbo: bool,
+    /// Días simulados en el último fotograma; con más de uno, la barra muestra "x N".
+    pub dias_por_fotograma: u32,
+    /// Días por segundo conseguidos, que muestra la barra.
+    pub ritmo_medido: f64,
+    /// Ritmo pedido en días por segundo (p. ej. 365, "un año por segundo"); mientras está
+    /// fijado, sustituye a las velocidades de las flechas.
+    pub ritmo_objetivo: Option<f64>,
+    /// Se está saltando hasta el próximo evento de tipo `tipo_salto`.
+    pub saltando: bool,
+    /// Tipo de evento del último salto, que repite la tecla F.
+    pub tipo_salto: TipoEvento,
+    /// Última alarma disparada (día y condición); el cartel se quita al reanudar.
+    pub alarma: Option<(u32, String)>,
+    /// Pantalla táctil: los controles se agrandan para poder pulsarlos con el dedo.
+    pub tactil: bool,
+    estilo_tactil: bool,
+    /// Sonido activado; `None` si el audio no está disponible y no se muestra el interruptor.
+    pub sonido: Option<bool>,
+    /// Presas dibujadas por instancias en la GPU; `None` si no se puede y no se muestra el interruptor.
+    pub puntos_gpu: Option<bool>,
+    /// Rastro del depredador y de la presa seleccionada.
+    pub rastros: bool,
+    /// Modo de edición: un clic añade una presa de `especie_edicion` y el clic derecho la retira.
+    pub edicion: bool,
+    pub especie_edicion: Especie,
+    /// Nivel de dificultad de los parámetros actuales.
+    pub dificultad: Dificultad,
+    /// Marcador del modo desafío, si el escenario lo activa.
+    pub desafio: Option<Desafio>,
+    /// Id de la presa que muestra el inspector.
+    pub seleccion: Option<u64>,
+    /// Corridas archivadas, de la más reciente a la más antigua.
+    pub historial: VecDeque<CorridaArchivada>,
+    /// Corrida pedida desde la barra o la ventana de semillas; la atiende el bucle principal.
+    pub nueva_corrida: Option<NuevaCorrida>,
+    /// Panel de depuración (F3).
+    pub depuracion: bool,
+    /// Fotogramas por segundo del último fotograma, para el panel de depuración.
+    pub fps: i32,
+    /// Media móvil del tiempo de cada fase del día.
+    perfil: PerfilDia,
+    /// Muertes por causa de los últimos días, para el gráfico apilado.
+    muertes_recientes: VecDeque<MuertesPorCausa>,
+    /// Gráfico de población al pie de la ventana.
+    pub poblacion: bool,
+    /// Diagrama de fases, con los días de `historia`.
+    pub fases: bool,
+    historia: Historia,
+    registro: VecDeque<(u32, String)>,
+    /// Si el registro anota también los nacimientos y las muertes naturales de cada presa.
+    registro_demografico: bool,
+    /// Copia editable de los parámetros; se aplica con el botón "Aplicar".
+    borrador: Parametros,
+    error_parametros: Option<String>,
+    /// Orden a medio escribir en la línea de la ventana de parámetros.
+    orden: String,
+}
+
+impl Paneles {
+    pub fn new(sim: &Simulacion) -> Self {
+        Self {
+            estadisticas: true,
+            inspector: false,
+            eventos: false,
+            parametros: false,
+            ventana_records: true,
+            semillas: false,
+            pausado: false,
+            turbo: false,
+            dias_por_fotograma: 0,
+            ritmo_medido: 0.0,
+            ritmo_objetivo: None,
+            saltando: false,
+            tipo_salto: TipoEvento::Extincion,
+            alarma: None,
+            tactil: false,
+            estilo_tactil: false,
+            sonido: None,
+            puntos_gpu: None,
+            rastros: false,
+            edicion: false,
+            especie_edicion: Especie::Conejo,
+            dificultad: Dificultad::Normal,
+            desafio: None,
+            seleccion: None,
+            historial: VecDeque::new(),
+            nueva_corrida: None,
+            depuracion: false,
+            fps: 0,
+            perfil: PerfilDia::default(),
+            muertes_recientes: VecDeque::new(),
+            poblacion: true,
+            fases: false,
+            historia: {
+                let mut historia = Historia::default();
+                historia.anotar(sim);
+                historia
+            },
+            registro: VecDeque::new(),
+            registro_demografico: false,
+            borrador: sim.parametros.clone(),
+            error_parametros: None,
+            orden: String::new(),
+        }
+    }
+
+    /// Abre y cierra las mismas ventanas que otra pestaña, para que al abrir una nueva no
+    /// cambie la disposición de la pantalla.
+    pub fn copiar_ventanas(&mut self, otros: &Paneles) {
+        self.estadisticas = otros.estadisticas;
+        self.inspector = otros.inspector;
+        self.eventos = otros.eventos;
+        self.parametros = otros.parametros;
+        self.ventana_records = otros.ventana_records;
+        self.semillas = otros.semillas;
+        self.sonido = otros.sonido;
+        self.puntos_gpu = otros.puntos_gpu;
+        self.rastros = otros.rastros;
+        self.depuracion = otros.depuracion;
+        self.poblacion = otros.poblacion;
+        self.fases = otros.fases;
+    }
+
+    /// Empieza a saltar hasta el próximo evento de `tipo_salto`.
+    pub fn saltar(&mut self) {
+        self.saltando = true;
+        self.pausado = false;
+    }
+
+    /// Guarda una corrida al principio del historial.
+    pub fn archivar(&mut self, corrida: CorridaArchivada) {
+        if self.historial.len() == MAXIMO_HISTORIAL {
+            self.historial.pop_back();
+        }
+        self.historial.push_front(corrida);
+    }
+
+    /// Incorpora el perfil de un día a la media móvil del panel de depuración.
+    /// Añade las muertes de un día al gráfico.
+    pub fn anotar_muertes(&mut self, muertes: &MuertesPorCausa) {
+        if self.muertes_recientes.len() == DIAS_GRAFICO_MUERTES {
+            self.muertes_recientes.pop_front();
+        }
+        self.muertes_recientes.push_back(*muertes);
+    }
+
+    /// Añade el día que acaba de terminar al gráfico de población.
+    pub fn anotar_poblacion(&mut self, sim: &Simulacion) {
+        self.historia.anotar(sim);
+    }
+
+    pub fn anotar_perfil(&mut self, dia: &PerfilDia) {
+        let suavizar = |media: &mut f64, valor: f64| *media += SUAVIZADO_PERFIL * (valor - *media);
+        suavizar(&mut self.perfil.caza, dia.caza);
+        suavizar(&mut self.perfil.envejecimiento, dia.envejecimiento);
+        suavizar(&mut self.perfil.reproduccion, dia.reproduccion);
+        suavizar(&mut self.perfil.limpieza, dia.limpieza);
+    }
+
+    /// Añade eventos al registro con el día en que ocurrieron.
+    pub fn anotar_eventos<'a>(&mut self, dia: u32, eventos: impl IntoIterator<Item = &'a Evento>) {
+        // Las cazas se ven en la escena; en el registro lo taparían todo. Los nacimientos y las
+        // muertes naturales, también muy numerosos, solo si se piden.
+        let demograficos = self.registro_demografico;
+        for evento in eventos.into_iter().filter(|e| !matches!(e, Evento::Caza { .. }) && (demograficos || !e.es_demografico())) {
+            if self.registro.len() == MAXIMO_EVENTOS {
+                self.registro.pop_front();
+            }
+            self.registro.push_back((dia, evento.descripcion()));
+        }
+    }
+
+    /// Resumen del estado en texto (ver `Simulacion::resumen_texto`) con los sucesos del registro.
+    pub fn resumen(&mut self, sim: &Simulacion) -> String {
+        sim.resumen_texto(self.registro.make_contiguous())
+    }
+
+    /// Anota los cambios de parámetros recién aplicados y descarta el borrador sin aplicar,
+    /// para que la ventana refleje también las recargas desde archivo.
+    pub fn parametros_recargados(&mut self, sim: &Simulacion) {
+        let cambios = sim.eventos_dia.iter().filter(|e| matches!(e, Evento::ParametroCambiado { .. }));
+        self.anotar_eventos(sim.dia, cambios);
+        self.descartar_borrador(sim);
+    }
+
+    /// Vuelve a tomar el borrador de la ventana de parámetros de los de la simulación, p. ej.
+    /// tras cambiarlos desde la consola.
+    pub fn descartar_borrador(&mut self, sim: &Simulacion) {
+        self.borrador = sim.parametros.clone();
+    }
+
+    /// Dibuja la barra superior y las ventanas visibles. Los récords son de la máquina, no de
+    /// la simulación, así que se comparten entre pestañas.
+    pub fn mostrar(&mut self, ctx: &egui::Context, sim: &mut Simulacion, records: &Records, idioma: Idioma) {
+        if self.tactil && !self.estilo_tactil {
+            ctx.style_mut(|estilo| {
+                estilo.spacing.interact_size = egui::vec2(48.0, 40.0);
+                estilo.spacing.button_padding = egui::vec2(12.0, 8.0);
+                estilo.spacing.item_spacing = egui::vec2(10.0, 10.0);
+                estilo.spacing.slider_width = 180.0;
+                estilo.spacing.scroll.bar_width = 16.0;
+            });
+            self.estilo_tactil = true;
+        }
+
+        egui::TopBottomPanel::top("barra_ventanas").show(ctx, |ui| {
+            // En vertical la barra no cabe en una fila, así que se parte en varias.
+            ui.horizontal_wrapped(|ui| {
+                ui.toggle_value(&mut self.estadisticas, idioma.texto(Texto::VentanaEstadisticas));
+                ui.toggle_value(&mut self.inspector, idioma.texto(Texto::VentanaInspector));
+                ui.toggle_value(&mut self.eventos, idioma.texto(Texto::VentanaEventos));
+                ui.toggle_value(&mut self.parametros, idioma.texto(Texto::VentanaParametros));
+                ui.toggle_value(&mut self.ventana_records, idioma.texto(Texto::VentanaRecords));
+                ui.toggle_value(&mut self.semillas, idioma.texto(Texto::VentanaSemillas));
+                ui.toggle_value(&mut self.poblacion, idioma.texto(Texto::VentanaPoblacion));
+                ui.toggle_value(&mut self.fases, idioma.texto(Texto::VentanaFases));
+                ui.separator();
+                ui.toggle_value(&mut self.pausado, idioma.texto(Texto::Pausa));
+                ui.toggle_value(&mut self.turbo, idioma.texto(Texto::Turbo));
+                if self.dias_por_fotograma > 1 {
+                    ui.monospace(format!("x {}", self.dias_por_fotograma));
+                }
+                ui.monospace(format!("{:.0} {}", self.ritmo_medido, idioma.texto(Texto::DiasPorSegundo)));
+                let mut fijo = self.ritmo_objetivo.is_some();
+                if ui.checkbox(&mut fijo, idioma.texto(Texto::RitmoObjetivo)).changed() {
+                    self.ritmo_objetivo = fijo.then_some(RITMO_OBJETIVO_INICIAL);
+                }
+                if let Some(ritmo) = &mut self.ritmo_objetivo {
+                    ui.add(egui::DragValue::new(ritmo).range(0.1..=100_000.0).speed(1.0).suffix(format!(" {}", idioma.texto(Texto::DiasPorSegundo))));
+                }
+                ui.menu_button(idioma.texto(Texto::SaltarHasta), |ui| {
+                    for tipo in TipoEvento::TODOS {
+                        if ui.button(idioma.texto(texto_tipo_evento(tipo))).clicked() {
+                            self.tipo_salto = tipo;
+                            self.saltar();
+                            ui.close_menu();
+                        }
+                    }
+                });
+                if ui.button(idioma.texto(Texto::NuevaCorrida)).clicked() {
+                    self.nueva_corrida = Some(NuevaCorrida::Aleatoria);
+                }
+                if let Some(sonido) = &mut self.sonido {
+                    ui.separator();
+                    ui.toggle_value(sonido, idioma.texto(Texto::Sonido));
+                }
+                if let Some(puntos) = &mut self.puntos_gpu {
+                    ui.toggle_value(puntos, idioma.texto(Texto::PuntosGpu));
+                }
+                ui.toggle_value(&mut self.rastros, idioma.texto(Texto::Rastros));
+                ui.separator();
+                let mut dificultad = self.dificultad;
+                egui::ComboBox::from_id_salt("dificultad")
+                    .selected_text(texto_dificultad(dificultad, idioma))
+                    .show_ui(ui, |ui| {
+                        for nivel in Dificultad::TODAS {
+                            ui.selectable_value(&mut dificultad, nivel, texto_dificultad(nivel, idioma));
+                        }
+                    });
+                if dificultad != self.dificultad {
+                    self.cambiar_dificultad(sim, dificultad);
+                }
+                ui.separator();
+                ui.toggle_value(&mut self.edicion, idioma.texto(Texto::Edicion));
+                if self.edicion {
+                    ui.selectable_value(&mut self.especie_edicion, Especie::Conejo, idioma.texto(Texto::Conejo));
+                    ui.selectable_value(&mut self.especie_edicion, Especie::Cabra, idioma.texto(Texto::Cabra));
+                    ui.weak(idioma.texto(Texto::AyudaEdicion));
+                }
+                if let Some(desafio) = &self.desafio {
+                    ui.separator();
+                    ui.label(format!(
+                        "{}: {}/{} {} · {} {}",
+                        idioma.texto(Texto::Desafio),
+                        sim.dia.min(desafio.objetivos.dias), desafio.objetivos.dias, idioma.texto(Texto::DiasUnidad),
+                        desafio.puntos(), idioma.texto(Texto::Puntos).to_lowercase(),
+                    ));
+                }
+            });
+        });
+
+        if !self.pausado {
+            self.alarma = None;
+        }
+        if let Some((dia, alarma)) = &self.alarma {
+            egui::TopBottomPanel::top("cartel_alarma").show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.colored_label(
+                        egui::Color32::RED,
+                        format!("{} ({} {}): {}", idioma.texto(Texto::Alarma), idioma.texto(Texto::Dia), dia, alarma),
+                    );
+                    if ui.button(idioma.texto(Texto::Reanudar)).clicked() {
+                        self.pausado = false;
+                    }
+                });
+            });
+        }
+
+        // El gráfico de población va antes que las ventanas, que se colocan sobre él.
+        if self.poblacion {
+            egui::TopBottomPanel::bottom("grafico_poblacion")
+                .resizable(true)
+                .default_height(ALTO_GRAFICO_POBLACION)
+                .show(ctx, |ui| grafico_poblacion(ui, &self.historia, idioma));
+        }
+
+        // En horizontal las ventanas se reparten en dos columnas a la izquierda; en vertical
+        // (móvil) se apilan en la mitad inferior para dejar ver la escena.
+        let pantalla = ctx.screen_rect();
+        let vertical = pantalla.height() > pantalla.width();
+        let posicion = |columna: f32, fila: f32, fraccion_vertical: f32| {
+            if vertical { egui::pos2(10.0, pantalla.height() * fraccion_vertical) } else { egui::pos2(columna, fila) }
+        };
+        let ancho_maximo = pantalla.width() - 20.0;
+
+        let mut abierta = self.estadisticas;
+        egui::Window::new(idioma.texto(Texto::VentanaEstadisticas))
+            .open(&mut abierta)
+            .default_pos(posicion(10.0, 40.0, 0.45))
+            .max_width(ancho_maximo)
+            .resizable(false)
+            .show(ctx, |ui| self.ventana_estadisticas(ui, sim, idioma));
+        self.estadisticas = abierta;
+
+        let mut abierta = self.inspector;
+        egui::Window::new(idioma.texto(Texto::VentanaInspector))
+            .open(&mut abierta)
+            .default_pos(posicion(10.0, 260.0, 0.6))
+            .max_width(ancho_maximo)
+            .resizable(false)
+            .show(ctx, |ui| self.ventana_inspector(ui, sim, idioma));
+        self.inspector = abierta;
+
+        let mut abierta = self.eventos;
+        egui::Window::new(idioma.texto(Texto::VentanaEventos))
+            .open(&mut abierta)
+            .default_pos(posicion(260.0, 40.0, 0.75))
+            .default_size([320.0_f32.min(ancho_maximo), 200.0])
+            .show(ctx, |ui| self.ventana_eventos(ui, idioma));
+        self.eventos = abierta;
+
+        let mut abierta = self.parametros;
+        egui::Window::new(idioma.texto(Texto::VentanaParametros))
+            .open(&mut abierta)
+            .default_pos(posicion(260.0, 280.0, 0.3))
+            .max_width(ancho_maximo)
+            .resizable(false)
+            .show(ctx, |ui| self.ventana_parametros(ui, sim, idioma));
+        self.parametros = abierta;
+
+        let mut abierta = self.ventana_records;
+        egui::Window::new(idioma.texto(Texto::VentanaRecords))
+            .open(&mut abierta)
+            .default_pos(posicion(260.0, 40.0, 0.15))
+            .max_width(ancho_maximo)
+            .resizable(false)
+            .show(ctx, |ui| ventana_records(ui, records, idioma));
+        self.ventana_records = abierta;
+
+        let mut abierta = self.semillas;
+        egui::Window::new(idioma.texto(Texto::VentanaSemillas))
+            .open(&mut abierta)
+            .default_pos(posicion(510.0, 40.0, 0.3))
+            .max_width(ancho_maximo)
+            .resizable(false)
+            .show(ctx, |ui| self.ventana_semillas(ui, idioma));
+        self.semillas = abierta;
+
+        let mut abierta = self.fases;
+        egui::Window::new(idioma.texto(Texto::VentanaFases))
+            .open(&mut abierta)
+            .default_pos(posicion(510.0, 280.0, 0.45))
+            .default_size([280.0_f32.min(ancho_maximo), 240.0])
+            .show(ctx, |ui| diagrama_fases(ui, &self.historia, idioma));
+        self.fases = abierta;
+
+        if self.depuracion {
+            self.panel_depuracion(ctx, sim, idioma);
+        }
+
+        // Pantalla de puntuación al terminar el desafío.
+        if let Some(desafio) = &self.desafio {
+            if let Some((dia, desenlace)) = desafio.terminado {
+                egui::Window::new(idioma.texto(Texto::Desafio))
+                    .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+                    .collapsible(false)
+                    .resizable(false)
+                    .show(ctx, |ui| ventana_desafio(ui, desafio, dia, desenlace, idioma));
+            }
+        }
+    }
+
+    /// Panel de depuración: no se puede mover ni tapa la entrada de la escena.
+    fn panel_depuracion(&self, ctx: &egui::Context, sim: &Simulacion, idioma: Idioma) {
+        let milisegundos = |segundos: f64| format!("{:.3} ms", segundos * 1000.0);
+        egui::Area::new(egui::Id::new("depuracion"))
+            .anchor(egui::Align2::RIGHT_TOP, [-10.0, 40.0])
+            .interactable(false)
+            .show(ctx, |ui| {
+                egui::Frame::popup(ui.style()).show(ui, |ui| {
+                    ui.strong(idioma.texto(Texto::Depuracion));
+                    egui::Grid::new("depuracion_cifras").num_columns(2).show(ui, |ui| {
+                        fila(ui, "FPS", self.fps.to_string());
+                        fila(ui, idioma.texto(Texto::Entidades), (sim.presas.len() + 1).to_string());
+                        fila(ui, idioma.texto(Texto::MemoriaEstimada), format!("{:.1} KiB", sim.memoria_estimada() as f64 / 1024.0));
+                        fila(ui, idioma.texto(Texto::FaseCaza), milisegundos(self.perfil.caza));
+                        fila(ui, idioma.texto(Texto::FaseEnvejecimiento), milisegundos(self.perfil.envejecimiento));
+                        fila(ui, idioma.texto(Texto::FaseReproduccion), milisegundos(self.perfil.reproduccion));
+                        fila(ui, idioma.texto(Texto::FaseLimpieza), milisegundos(self.perfil.limpieza));
+                        fila(ui, idioma.texto(Texto::TiempoPorDia), milisegundos(self.perfil.total()));
+                    });
+                });
+            });
+    }
+
+    /// Cambia la dificultad de la corrida en curso; el cambio se valida y se anuncia como una
+    /// recarga de parámetros.
+    fn cambiar_dificultad(&mut self, sim: &mut Simulacion, dificultad: Dificultad) {
+        let mut nuevos = sim.parametros.clone();
+        self.dificultad.cambiar(dificultad, &mut nuevos);
+        match recarga::aplicar_recarga(sim, &nuevos) {
+            Ok(_) => {
+                self.dificultad = dificultad;
+                self.parametros_recargados(sim);
+            }
+            Err(e) => self.error_parametros = Some(e),
+        }
+    }
+
+    fn ventana_estadisticas(&self, ui: &mut egui::Ui, sim: &Simulacion, idioma: Idioma) {
+        let (conejos, cabras) = sim.contar_especies();
+        let params = &sim.parametros;
+        let reserva = sim.depredador.reserva_comida_kg;
+        let estado = if !sim.depredador.vivo {
+            idioma.texto(Texto::DepredadorMuerto)
+        } else if reserva >= params.consumo_depredador_kg().1 {
+            idioma.texto(Texto::EstadoOptimo)
+        } else if reserva >= params.consumo_depredador_kg().0 {
+            idioma.texto(Texto::EstadoMinimo)
+        } else {
+            idioma.texto(Texto::EstadoPeligro)
+        };
+
+        egui::Grid::new("estadisticas").num_columns(2).show(ui, |ui| {
+            fila(ui, idioma.texto(Texto::Dia), sim.dia.to_string());
+            fila(ui, idioma.texto(Texto::Conejos), conejos.to_string());
+            fila(ui, idioma.texto(Texto::Cabras), cabras.to_string());
+            fila(ui, idioma.texto(Texto::PoblacionTotal), sim.presas.len().to_string());
+            fila(ui, idioma.texto(Texto::ReservaDepredador), format!("{:.1} kg", reserva));
+            if sim.parametros.manada.activa {
+                fila(ui, idioma.texto(Texto::Depredadores), sim.depredadores().count().to_string());
+            }
+            if sim.depredador.despensa_kg > 0.0 {
+                fila(ui, idioma.texto(Texto::Despensa), format!("{:.1} kg", sim.depredador.despensa_kg));
+            }
+            if sim.parametros.cadena_trofica.activa {
+                fila(ui, idioma.texto(Texto::Pasto), format!("{:.0} kg", sim.pasto_kg()));
+            }
+        });
+        ui.collapsing(idioma.texto(Texto::ClasesEdad), |ui| {
+            egui::Grid::new("clases_edad").num_columns(3).striped(true).show(ui, |ui| {
+                ui.label("");
+                ui.label(idioma.texto(Texto::Conejos));
+                ui.label(idioma.texto(Texto::Cabras));
+                ui.end_row();
+                for (clase, (conejos, cabras)) in ClaseEdad::TODAS.into_iter().zip(sim.contar_clases()) {
+                    ui.label(idioma.texto(texto_clase_edad(clase)));
+                    ui.label(conejos.to_string());
+                    ui.label(cabras.to_string());
+                    ui.end_row();
+                }
+            });
+        });
+        ui.collapsing(idioma.texto(Texto::MuertesPorCausa), |ui| {
+            egui::Grid::new("muertes_causa").num_columns(3).show(ui, |ui| {
+                for causa in CausaMuerte::TODAS {
+                    ui.colored_label(color_causa(causa), idioma.texto(texto_causa_muerte(causa)));
+                    ui.label(sim.muertes_corrida.de(causa).to_string());
+                    ui.label(sim.muertes_corrida.fraccion(causa).map_or("-".to_string(), |v| format!("{:.0} %", v * 100.0)));
+                    ui.end_row();
+                }
+            });
+            grafico_muertes(ui, &self.muertes_recientes);
+        });
+        ui.label(estado);
+        if let Some(estacion) = sim.estacion() {
+            ui.label(format!("{}: {}", idioma.texto(Texto::Estacion), idioma.texto(texto_estacion(estacion))));
+        }
+        ui.separator();
+        let mut hoy = format!(
+            "{}: {} {}, {} {}, {} {}",
+            idioma.texto(Texto::Hoy),
+            sim.estadisticas_dia.nacimientos, idioma.texto(Texto::Nacimientos).to_lowercase(),
+            sim.estadisticas_dia.muertes, idioma.texto(Texto::Muertes).to_lowercase(),
+            sim.estadisticas_dia.cazas, idioma.texto(Texto::Cazas).to_lowercase(),
+        );
+        if sim.parametros.exito_caza.activo {
+            hoy += &format!(", {} {}", sim.estadisticas_dia.cazas_fallidas, idioma.texto(Texto::Escapadas).to_lowercase());
+        }
+        if sim.parametros.enfermedad.activa {
+            hoy += &format!(", {} {}", sim.estadisticas_dia.contagios, idioma.texto(Texto::Contagios).to_lowercase());
+        }
+        ui.label(hoy);
+        if sim.parametros.enfermedad.activa {
+            ui.label(format!(
+                "{}: {} / {} (R0 {:.1})",
+                idioma.texto(Texto::Infectadas),
+                sim.recuento_sir(Especie::Conejo).infectadas,
+                sim.recuento_sir(Especie::Cabra).infectadas,
+                enfermedad::r0(&sim.parametros.enfermedad),
+            ));
+        }
+    }
+
+    fn ventana_inspector(&self, ui: &mut egui::Ui, sim: &Simulacion, idioma: Idioma) {
+        let presa = self.seleccion.and_then(|id| sim.presas.iter().find(|p| p.id() == id));
+        let Some(presa) = presa else {
+            ui.label(idioma.texto(Texto::SinSeleccion));
+            ui.weak(idioma.texto(Texto::AyudaInspector));
+            return;
+        };
+        egui::Grid::new("inspector").num_columns(2).show(ui, |ui| {
+            fila(ui, idioma.texto(Texto::Presa), format!("#{}", presa.id()));
+            let especie = match presa.especie() {
+                Especie::Conejo => idioma.texto(Texto::Conejo),
+                Especie::Cabra => idioma.texto(Texto::Cabra),
+            };
+            fila(ui, idioma.texto(Texto::Especie), especie.to_string());
+            let sexo = match presa.sexo() {
+                Sexo::Macho => idioma.texto(Texto::Macho),
+                Sexo::Hembra => idioma.texto(Texto::Hembra),
+            };
+            fila(ui, idioma.texto(Texto::Sexo), sexo.to_string());
+            fila(ui, idioma.texto(Texto::Edad), format!("{} {}", presa.edad(), idioma.texto(Texto::DiasUnidad)));
+            let clase = presa.especie().clase_edad(presa.edad(), &sim.parametros);
+            fila(ui, idioma.texto(Texto::ClaseEdad), idioma.texto(texto_clase_edad(clase)).to_string());
+            if sim.parametros.cazabilidad.activa {
+                let vulnerabilidad = entidades::vulnerabilidad(presa.as_ref(), &sim.parametros);
+                fila(ui, idioma.texto(Texto::Vulnerabilidad), format!("{:.0} %", vulnerabilidad * 100.0));
+            }
+            fila(ui, idioma.texto(Texto::Peso), format!("{:.1} kg", presa.peso()));
+            if sim.parametros.enfermedad.activa {
+                let salud = match sim.salud_presa(presa.id()) {
+                    Salud::Susceptible => Texto::Susceptible,
+                    Salud::Infectada => Texto::Infectada,
+                    Salud::Recuperada => Texto::Recuperada,
+                };
+                fila(ui, idioma.texto(Texto::Salud), idioma.texto(salud).to_string());
+            }
+            if let Some(gestacion) = presa.gestacion() {
+                fila(ui, idioma.texto(Texto::Gestacion), format!("{} {}", gestacion.dias_restantes, idioma.texto(Texto::DiasUnidad)));
+            }
+            let viva = if presa.esta_viva() { Texto::Viva } else { Texto::Muerta };
+            fila(ui, "", idioma.texto(viva).to_string());
+        });
+    }
+
+    fn ventana_eventos(&mut self, ui: &mut egui::Ui, idioma: Idioma) {
+        ui.checkbox(&mut self.registro_demografico, idioma.texto(Texto::NacimientosYMuertes));
+        if self.registro.is_empty() {
+            ui.weak(idioma.texto(Texto::SinEventos));
+            return;
+        }
+        egui::ScrollArea::vertical().stick_to_bottom(true).show(ui, |ui| {
+            for (dia, descripcion) in &self.registro {
+                ui.label(format!("{} {}: {}", idioma.texto(Texto::Dia), dia, descripcion));
+            }
+        });
+    }
+
+    fn ventana_semillas(&mut self, ui: &mut egui::Ui, idioma: Idioma) {
+        if self.historial.is_empty() {
+            ui.weak(idioma.texto(Texto::SinCorridas));
+            return;
+        }
+        egui::Grid::new("semillas").num_columns(4).striped(true).show(ui, |ui| {
+            for (i, corrida) in self.historial.iter().enumerate() {
+                ui.label(format!("{} {}", idioma.texto(Texto::Semilla), corrida.semilla));
+                ui.label(format!("{} {}", idioma.texto(Texto::Dia), corrida.dia));
+                let depredador = if corrida.depredador_vivo { Texto::DepredadorVivo } else { Texto::DepredadorSinVida };
+                ui.label(format!(
+                    "{} {}, {} {}, {}",
+                    corrida.conejos, idioma.texto(Texto::Conejos).to_lowercase(),
+                    corrida.cabras, idioma.texto(Texto::Cabras).to_lowercase(),
+                    idioma.texto(depredador),
+                ));
+                if ui.button(idioma.texto(Texto::Repetir)).clicked() {
+                    self.nueva_corrida = Some(NuevaCorrida::Repetir(i));
+                }
+                ui.end_row();
+            }
+        });
+    }
+
+    fn ventana_parametros(&mut self, ui: &mut egui::Ui, sim: &mut Simulacion, idioma: Idioma) {
+        let b = &mut self.borrador;
+        egui::Grid::new("parametros").num_columns(2).show(ui, |ui| {
+            deslizador(ui, "DEPREDADOR_CONSUMO_MINIMO_DIARIO_KG", &mut b.depredador_consumo_minimo_diario_kg, 0.0..=20.0);
+            deslizador(ui, "DEPREDADOR_CONSUMO_OPTIMO_DIARIO_KG", &mut b.depredador_consumo_optimo_diario_kg, 0.0..=20.0);
+            dieta(ui, &mut b.dieta_depredador);
+            estrategia_caza(ui, &mut b.estrategia_caza);
+            deslizador(ui, "CONEJO_TASA_REPRODUCCION_DIARIA", &mut b.conejo_tasa_reproduccion_diaria, 0.0..=0.2);
+            rango(ui, "CONEJO_CRIAS_POR_PARTO", &mut b.conejo_crias_por_parto.min, &mut b.conejo_crias_por_parto.max);
+            deslizador(ui, "CABRA_TASA_REPRODUCCION_DIARIA", &mut b.cabra_tasa_reproduccion_diaria, 0.0..=0.1);
+            rango(ui, "CABRA_CRIAS_POR_PARTO", &mut b.cabra_crias_por_parto.min, &mut b.cabra_crias_por_parto.max);
+            deslizador(ui, "PROBABILIDAD_ENFERMAR", &mut b.probabilidad_enfermar, 0.0..=0.05);
+            deslizador(ui, "PROBABILIDAD_NACER_MACHO", &mut b.probabilidad_nacer_macho, 0.0..=1.0);
+        });
+
+        let cambiado = self.borrador != sim.parametros;
+        if ui.add_enabled(cambiado, egui::Button::new(idioma.texto(Texto::Aplicar))).clicked() {
+            // Se aplica como una recarga en caliente, que valida y anuncia cada cambio.
+            match recarga::aplicar_recarga(sim, &self.borrador) {
+                Ok(_) => {
+                    self.error_parametros = None;
+                    self.parametros_recargados(sim);
+                }
+                Err(e) => self.error_parametros = Some(e),
+            }
+        }
+        ui.separator();
+        let linea = ui.add(egui::TextEdit::singleline(&mut self.orden).hint_text(idioma.texto(Texto::AyudaOrden)));
+        if linea.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter)) && !self.orden.trim().is_empty() {
+            let partes: Vec<&str> = self.orden.split_whitespace().collect();
+            let aplicada = consola::interpretar_parametro(&partes)
+                .and_then(|(especie, nombre, valor)| sim.establecer_parametro(especie, &nombre, valor));
+            match aplicada {
+                Ok(()) => {
+                    self.orden.clear();
+                    self.error_parametros = None;
+                    self.parametros_recargados(sim);
+                }
+                Err(e) => self.error_parametros = Some(e),
+            }
+        }
+        if let Some(error) = &self.error_parametros {
+            ui.colored_label(egui::Color32::RED, error);
+        }
+    }
+}
+
+
+fn texto_clase_edad(clase: ClaseEdad) -> Texto {
+    match clase {
+        ClaseEdad::Juvenil => Texto::Juvenil,
+        ClaseEdad::Subadulto => Texto::Subadulto,
+        ClaseEdad::Adulto => Texto::Adulto,
+        ClaseEdad::Senescente => Texto::Senescente,
+    }
+}
+
+fn texto_estacion(estacion: Estacion) -> Texto {
+    match estacion {
+        Estacion::Primavera => Texto::Primavera,
+        Estacion::Verano => Texto::Verano,
+        Estacion::Otono => Texto::Otono,
+        Estacion::Invierno => Texto::Invierno,
+    }
+}
+
+fn texto_causa_muerte(causa: CausaMuerte) -> Texto {
+    match causa {
+        CausaMuerte::Vejez => Texto::CausaVejez,
+        CausaMuerte::Enfermedad => Texto::CausaEnfermedad,
+        CausaMuerte::Hambre => Texto::CausaHambre,
+        CausaMuerte::Caza => Texto::CausaCaza,
+        CausaMuerte::Sobrepoblacion => Texto::CausaSobrepoblacion,
+        CausaMuerte::MortalidadClase => Texto::CausaMortalidadClase,
+    }
+}
+
+fn color_causa(causa: CausaMuerte) -> egui::Color32 {
+    match causa {
+        CausaMuerte::Vejez => egui::Color32::GRAY,
+        CausaMuerte::Enfermedad => egui::Color32::from_rgb(120, 190, 60),
+        CausaMuerte::Hambre => egui::Color32::from_rgb(210, 160, 60),
+        CausaMuerte::Caza => egui::Color32::from_rgb(200, 50, 50),
+        CausaMuerte::Sobrepoblacion => egui::Color32::from_rgb(90, 110, 200),
+        CausaMuerte::MortalidadClase => egui::Color32::from_rgb(160, 110, 190),
+    }
+}
+
+/// Gráfico de áreas apiladas de las muertes diarias por causa, con el día más reciente a la
+/// derecha. Cada día es una columna de rectángulos, uno por causa, en el orden de `TODAS`;
+/// la escala vertical se ajusta al día con más muertes.
+fn grafico_muertes(ui: &mut egui::Ui, dias: &VecDeque<MuertesPorCausa>) {
+    let (rect, _) = ui.allocate_exact_size(egui::vec2(ui.available_width().max(200.0), 80.0), egui::Sense::hover());
+    let pintor = ui.painter_at(rect);
+    pintor.rect_filled(rect, 2.0, ui.visuals().extreme_bg_color);
+    let maximo = dias.iter().map(MuertesPorCausa::total).max().unwrap_or(0);
+    if maximo == 0 {
+        return;
+    }
+    let ancho = rect.width() / DIAS_GRAFICO_MUERTES as f32;
+    let escala = rect.height() / maximo as f32;
+    // Los días se alinean a la derecha: con menos de los que caben, el gráfico se va llenando.
+    let primero = DIAS_GRAFICO_MUERTES - dias.len();
+    for (i, dia) in dias.iter().enumerate() {
+        let x = rect.left() + (primero + i) as f32 * ancho;
+        let mut base = rect.bottom();
+        for causa in CausaMuerte::TODAS {
+            let alto = dia.de(causa) as f32 * escala;
+            if alto > 0.0 {
+                let columna = egui::Rect::from_min_max(egui::pos2(x, base - alto), egui::pos2(x + ancho, base));
+                pintor.rect_filled(columna, 0.0, color_causa(causa));
+                base -= alto;
+            }
+        }
+    }
+}
+
+/// Gráfico de líneas de la población a lo largo de los días guardados en `historia`, del más
+/// antiguo a la izquierda al más reciente a la derecha. Conejos y cabras comparten la escala
+/// (la mayor población vista); la reserva del depredador, en kg, usa la suya.
+fn grafico_poblacion(ui: &mut egui::Ui, historia: &Historia, idioma: Idioma) {
+    let (poblacion_maxima, reserva_maxima) = historia.maximos();
+    let ultima = historia.muestras().back().copied();
+    ui.horizontal(|ui| {
+        if let (Some(primera), Some(ultima)) = (historia.muestras().front(), ultima) {
+            ui.label(format!("{} {}-{}", idioma.texto(Texto::Dia), primera.dia, ultima.dia));
+            ui.colored_label(COLOR_CONEJOS, format!("{} {}", idioma.texto(Texto::Conejos), ultima.conejos));
+            ui.colored_label(COLOR_CABRAS, format!("{} {}", idioma.texto(Texto::Cabras), ultima.cabras));
+            ui.colored_label(COLOR_RESERVA, format!("{} {:.1} kg", idioma.texto(Texto::ReservaDepredador), ultima.reserva_kg));
+            ui.weak(format!("({} {} / {:.0} kg)", idioma.texto(Texto::Maximo), poblacion_maxima, reserva_maxima));
+        }
+    });
+    let (rect, _) = ui.allocate_exact_size(ui.available_size().max(egui::vec2(200.0, 40.0)), egui::Sense::hover());
+    let pintor = ui.painter_at(rect);
+    pintor.rect_filled(rect, 2.0, ui.visuals().extreme_bg_color);
+    let muestras = historia.muestras();
+    if muestras.len() < 2 {
+        return;
+    }
+    // Con más días que píxeles se toma un día por columna.
+    let paso = (muestras.len() as f32 / rect.width()).ceil().max(1.0) as usize;
+    let ultimo = (muestras.len() - 1) as f32;
+    let curva = |valor: &dyn Fn(usize) -> f32, maximo: f32, color: egui::Color32| {
+        if maximo <= 0.0 {
+            return;
+        }
+        let puntos: Vec<egui::Pos2> = (0..muestras.len()).step_by(paso).chain([muestras.len() - 1])
+            .map(|i| egui::pos2(rect.left() + rect.width() * i as f32 / ultimo, rect.bottom() - rect.height() * valor(i) / maximo))
+            .collect();
+        pintor.add(egui::Shape::line(puntos, egui::Stroke::new(1.5, color)));
+    };
+    curva(&|i| muestras[i].reserva_kg as f32, reserva_maxima as f32, COLOR_RESERVA);
+    curva(&|i| muestras[i].cabras as f32, poblacion_maxima as f32, COLOR_CABRAS);
+    curva(&|i| muestras[i].conejos as f32, poblacion_maxima as f32, COLOR_CONEJOS);
+}
+
+/// Diagrama de fases de los días guardados en `historia`: cada día es un punto con las presas
+/// (conejos y cabras) en el eje x y la reserva del depredador en el y, unidos en orden. El trazo
+/// se aclara hacia los días más antiguos y el día actual se marca con un punto.
+fn diagrama_fases(ui: &mut egui::Ui, historia: &Historia, idioma: Idioma) {
+    let muestras = historia.muestras();
+    let presas_maximas = muestras.iter().map(|m| m.conejos + m.cabras).max().unwrap_or(0);
+    let (_, reserva_maxima) = historia.maximos();
+    ui.horizontal(|ui| {
+        ui.colored_label(COLOR_FASES, format!("x: {} (0-{})", idioma.texto(Texto::PresasTotales), presas_maximas));
+        ui.colored_label(COLOR_RESERVA, format!("y: {} (0-{:.0} kg)", idioma.texto(Texto::ReservaDepredador), reserva_maxima));
+    });
+    let (rect, _) = ui.allocate_exact_size(ui.available_size().max(egui::vec2(160.0, 120.0)), egui::Sense::hover());
+    let pintor = ui.painter_at(rect);
+    pintor.rect_filled(rect, 2.0, ui.visuals().extreme_bg_color);
+    if muestras.len() < 2 || presas_maximas == 0 || reserva_maxima <= 0.0 {
+        return;
+    }
+    let punto = |m: &MuestraHistoria| egui::pos2(
+        rect.left() + rect.width() * (m.conejos + m.cabras) as f32 / presas_maximas as f32,
+        rect.bottom() - rect.height() * (m.reserva_kg / reserva_maxima) as f32,
+    );
+    // Con más días que píxeles de ancho se toma uno de cada tantos.
+    let paso = (muestras.len() as f32 / rect.width()).ceil().max(1.0) as usize;
+    let indices: Vec<usize> = (0..muestras.len()).step_by(paso).chain([muestras.len() - 1]).collect();
+    let tramos = (indices.len() - 1) as f32;
+    for (tramo, par) in indices.windows(2).enumerate() {
+        let color = COLOR_FASES.gamma_multiply(0.15 + 0.85 * tramo as f32 / tramos);
+        pintor.line_segment([punto(&muestras[par[0]]), punto(&muestras[par[1]])], egui::Stroke::new(1.5, color));
+    }
+    if let Some(ultima) = muestras.back() {
+        pintor.circle_filled(punto(ultima), 4.0, COLOR_RESERVA);
+    }
+}
+
+pub fn texto_tipo_evento(tipo: TipoEvento) -> Texto {
+    match tipo {
+        TipoEvento::Extincion => Texto::PrimeraExtincion,
+        TipoEvento::DepredadorEnPeligro => Texto::DepredadorEntraEnPeligro,
+        TipoEvento::MuerteDepredador => Texto::MuerteDelDepredador,
+        TipoEvento::Alarma => Texto::UnaAlarma,
+        TipoEvento::Sobrepoblacion => Texto::UnaSobrepoblacion,
+        TipoEvento::Brote => Texto::UnBrote,
+        TipoEvento::Ola => Texto::UnaOla,
+    }
+}
+
+fn texto_dificultad(dificultad: Dificultad, idioma: Idioma) -> String {
+    let nivel = match dificultad {
+        Dificultad::Facil => Texto::Facil,
+        Dificultad::Normal => Texto::Normal,
+        Dificultad::Dificil => Texto::Dificil,
+    };
+    format!("{}: {}", idioma.texto(Texto::Dificultad), idioma.texto(nivel))
+}
+
+fn ventana_records(ui: &mut egui::Ui, records: &Records, idioma: Idioma) {
+    let dias = idioma.texto(Texto::DiasUnidad);
+    egui::Grid::new("records").num_columns(2).show(ui, |ui| {
+        fila(ui, idioma.texto(Texto::CoexistenciaMasLarga), format!("{} {}", records.coexistencia_mas_larga_dias, dias));
+        fila(ui, idioma.texto(Texto::PoblacionMaxima), records.poblacion_maxima.to_string());
+        let colapso = records.colapso_mas_rapido_dia.map_or("-".to_string(), |dia| format!("{} {}", dia, dias));
+        fila(ui, idioma.texto(Texto::ColapsoMasRapido), colapso);
+    });
+    ui.separator();
+    ui.label(idioma.texto(Texto::Logros));
+    if records.logros.is_empty() {
+        ui.weak(idioma.texto(Texto::SinLogros));
+    }
+    for logro in &records.logros {
+        ui.label(idioma.texto(texto_logro(*logro)));
+    }
+}
+
+fn ventana_desafio(ui: &mut egui::Ui, desafio: &Desafio, dia: u32, desenlace: Desenlace, idioma: Idioma) {
+    let titulo = match desenlace {
+        Desenlace::Superado => idioma.texto(Texto::DesafioSuperado),
+        Desenlace::DepredadorMuerto => idioma.texto(Texto::DesafioFallido),
+    };
+    ui.heading(titulo);
+    egui::Grid::new("desafio").num_columns(2).show(ui, |ui| {
+        fila(ui, idioma.texto(Texto::Dia), format!("{} / {}", dia, desafio.objetivos.dias));
+        fila(ui, idioma.texto(Texto::DiasEquilibrio), desafio.dias_equilibrio.to_string());
+        fila(ui, idioma.texto(Texto::BiomasaCosechada), format!("{:.1} kg", desafio.biomasa_cosechada_kg));
+        fila(ui, idioma.texto(Texto::Puntos), desafio.puntos().to_string());
+    });
+}
+
+fn fila(ui: &mut egui::Ui, etiqueta: &str, valor: String) {
+    ui.label(etiqueta);
+    ui.label(valor);
+    ui.end_row();
+}
+
+fn deslizador(ui: &mut egui::Ui, nombre: &str, valor: &mut f64, rango: std::ops::RangeInclusive<f64>) {
+    ui.label(nombre);
+    ui.add(egui::Slider::new(valor, rango));
+    ui.end_row();
+}
+
+fn dieta(ui: &mut egui::Ui, dieta: &mut Dieta) {
+    ui.label("DIETA_DEPREDADOR");
+    ui.horizontal(|ui| {
+        ui.checkbox(&mut dieta.conejos, "conejos");
+        ui.checkbox(&mut dieta.cabras, "cabras");
+        ui.checkbox(&mut dieta.respetar_hembras_prenadas, "respetar_hembras_prenadas");
+    });
+    ui.end_row();
+}
+
+fn estrategia_caza(ui: &mut egui::Ui, estrategia: &mut EstrategiaCaza) {
+    ui.label("ESTRATEGIA_CAZA");
+    egui::ComboBox::from_id_salt("estrategia_caza")
+        .selected_text(format!("{:?}", estrategia))
+        .show_ui(ui, |ui| {
+            for opcion in EstrategiaCaza::TODAS {
+                ui.selectable_value(estrategia, opcion, format!("{:?}", opcion));
+            }
+        });
+    ui.end_row();
+}
+
+fn rango(ui: &mut egui::Ui, nombre: &str, min: &mut u32, max: &mut u32) {
+    ui.label(nombre);
+    ui.horizontal(|ui| {
+        ui.add(egui::DragValue::new(min).range(0..=20));
+        ui.label("-");
+        ui.add(egui::DragValue::new(max).range(0..=20));
+    });
+    ui.end_row();
+}
@@ -0,0 +1,1025 @@
+// src/parametros.rs
+
+// Este módulo reúne los parámetros que gobiernan el ecosistema.
+// Las constantes son los valores por defecto; la struct `Parametros` es la copia viva que
+// usa cada simulación, de modo que se pueda ajustar sin recompilar.
+
+use std::fmt;
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::entidades::ClaseEdad;
+use crate::simulacion::Fnv1a;
+
+// =================================================
+// PARÁMETROS GLOBALES DE LA SIMULACIÓN (VALORES POR DEFECTO)
+// Estas constantes actúan como "perillas" para ajustar el comportamiento del ecosistema.
+// =================================================
+
+// --- Población Inicial (AJUSTADO) ---
+pub const N_CONEJOS_INICIAL: u32 = 60;
+pub const N_CABRAS_INICIAL: u32 = 25;
+
+// --- Parámetros del Depredador ---
+pub const DEPREDADOR_RESERVA_INICIAL_KG: f64 = 900.0;
+pub const DEPREDADOR_CONSUMO_MINIMO_DIARIO_KG: f64 = 3.0;
+pub const DEPREDADOR_CONSUMO_OPTIMO_DIARIO_KG: f64 = 5.0;
+// Lo que no come de una presa al momento lo guarda en su despensa, donde se descompone.
+// Una ingesta máxima de 0 significa sin límite: toda la presa pasa a la reserva.
+pub const DEPREDADOR_INGESTA_MAXIMA_KG: f64 = 0.0;
+pub const TASA_DESCOMPOSICION_DIARIA: f64 = 0.0;
+
+// --- Parámetros de CONEJO (AJUSTADO) ---
+pub const CONEJO_EDAD_MAXIMA_DIAS: u32 = 1825;
+pub const CONEJO_EDAD_REPRODUCTIVA_DIAS: u32 = 100;
+pub const CONEJO_EDAD_SACRIFICIO_DIAS: u32 = 150;
+pub const CONEJO_TASA_REPRODUCCION_DIARIA: f64 = 0.05;
+pub const CONEJO_CRIAS_POR_PARTO: (u32, u32) = (3, 6);
+// Curva de Gompertz: (peso máximo kg, tasa, punto de inflexión en días).
+pub const CONEJO_CRECIMIENTO: (f64, f64, f64) = (5.0, 0.05, 90.0);
+// Días de gestación; con 0 la hembra pare el mismo día en que concibe (una coneja gesta unos 31).
+pub const CONEJO_GESTACION_DIAS: u32 = 0;
+
+// --- Parámetros de CABRA (AJUSTADO) ---
+pub const CABRA_EDAD_MAXIMA_DIAS: u32 = 5475;
+pub const CABRA_EDAD_REPRODUCTIVA_DIAS: u32 = 300;
+pub const CABRA_EDAD_SACRIFICIO_DIAS: u32 = 250;
+pub const CABRA_TASA_REPRODUCCION_DIARIA: f64 = 0.01;
+pub const CABRA_CRIAS_POR_PARTO: (u32, u32) = (1, 2);
+pub const CABRA_CRECIMIENTO: (f64, f64, f64) = (75.0, 0.01, 180.0);
+// Una cabra gesta unos 150 días.
+pub const CABRA_GESTACION_DIAS: u32 = 0;
+
+// --- Probabilidades Comunes ---
+pub const PROBABILIDAD_ENFERMAR: f64 = 0.001;
+pub const PROBABILIDAD_NACER_MACHO: f64 = 0.5;
+
+// --- Efecto Allee ---
+// Por debajo de esta población, a una especie le cuesta encontrar pareja y su reproducción
+// por individuo cae en proporción. 0 lo desactiva.
+pub const UMBRAL_ALLEE: u32 = 0;
+
+// --- Válvula de Seguridad ---
+// Tope de presas vivas; por encima se aplica una mortalidad de emergencia por densidad
+// para que una tasa de reproducción desbocada no agote la memoria.
+pub const POBLACION_MAXIMA: u32 = 200_000;
+
+// =================================================
+// PARÁMETROS EN TIEMPO DE EJECUCIÓN
+// =================================================
+
+/// Rango inclusivo de crías por parto.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct RangoCrias {
+    /// Mínimo de crías por parto.
+    pub min: u32,
+    /// Máximo de crías por parto (debe ser >= min).
+    pub max: u32,
+}
+
+impl From<(u32, u32)> for RangoCrias {
+    fn from((min, max): (u32, u32)) -> Self {
+        Self { min, max }
+    }
+}
+
+/// Curva de crecimiento de Gompertz de una especie: a la edad `t` (días) pesa
+/// `peso_maximo_kg · e^(−e^(−tasa · (t − inflexion_dias)))`.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct CurvaCrecimiento {
+    /// Peso de un adulto plenamente desarrollado (kg).
+    #[schemars(range(min = 0.0))]
+    pub peso_maximo_kg: f64,
+    /// Rapidez con que se acerca a ese peso.
+    #[schemars(range(min = 0.0))]
+    pub tasa: f64,
+    /// Edad de crecimiento más rápido (días).
+    #[schemars(range(min = 0.0))]
+    pub inflexion_dias: f64,
+}
+
+impl From<(f64, f64, f64)> for CurvaCrecimiento {
+    fn from((peso_maximo_kg, tasa, inflexion_dias): (f64, f64, f64)) -> Self {
+        Self { peso_maximo_kg, tasa, inflexion_dias }
+    }
+}
+
+/// Presas que acepta el depredador, además de la edad de sacrificio. Por defecto es
+/// generalista; restringiéndola se puede comparar con un depredador especialista.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(default, deny_unknown_fields)]
+pub struct Dieta {
+    /// Caza conejos.
+    pub conejos: bool,
+    /// Caza cabras.
+    pub cabras: bool,
+    /// Peso mínimo de los conejos que caza (kg).
+    #[schemars(range(min = 0.0))]
+    pub conejo_peso_minimo_kg: f64,
+    /// Peso mínimo de las cabras que caza (kg).
+    #[schemars(range(min = 0.0))]
+    pub cabra_peso_minimo_kg: f64,
+    /// Respeta a las hembras preñadas (ver `Gestacion`). Las instantáneas anteriores lo llaman
+    /// `respetar_hembras_reproductoras`.
+    #[serde(alias = "respetar_hembras_reproductoras")]
+    pub respetar_hembras_prenadas: bool,
+}
+
+/// Qué presa elige el depredador entre las que tiene a su alcance. Los empates se deshacen al
+/// azar y nunca elige una presa de una clase de edad con vulnerabilidad 0 (ver `ClasesEdad`).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub enum EstrategiaCaza {
+    /// La de más peso, ponderado por la vulnerabilidad de su clase de edad.
+    #[default]
+    Pesada,
+    /// La más cercana. Fuera del modo espacial todas están igual de cerca y elige al azar.
+    Cercana,
+    /// La más fácil de alcanzar por su edad y su condición corporal (ver `vulnerabilidad`).
+    Debil,
+    /// Cualquiera, al azar.
+    Aleatoria,
+    /// La de más edad.
+    Vieja,
+}
+
+impl EstrategiaCaza {
+    pub const TODAS: [EstrategiaCaza; 5] = [
+        EstrategiaCaza::Pesada,
+        EstrategiaCaza::Cercana,
+        EstrategiaCaza::Debil,
+        EstrategiaCaza::Aleatoria,
+        EstrategiaCaza::Vieja,
+    ];
+}
+
+/// Conversión de la comida (que se sigue contando en kg) a energía, para la contabilidad
+/// trófica de `energia`. La eficiencia de asimilación sí afecta a la simulación: es la fracción
+/// de lo que come el depredador que pasa a su reserva.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(default, deny_unknown_fields)]
+pub struct Energia {
+    /// Megajulios por kg de presa.
+    #[schemars(range(min = 0.0))]
+    pub mj_por_kg: f64,
+    /// Fracción de la energía del forraje que las presas convierten en biomasa. Solo se usa
+    /// para estimar el forraje cuando no se simula el pasto (ver `CadenaTrofica`).
+    #[schemars(range(min = 0.0, max = 1.0))]
+    pub eficiencia_pasto_presa: f64,
+    /// Fracción de lo que come el depredador que asimila.
+    #[schemars(range(min = 0.0, max = 1.0))]
+    pub eficiencia_asimilacion: f64,
+}
+
+impl Default for Energia {
+    fn default() -> Self {
+        Self { mj_por_kg: 7.0, eficiencia_pasto_presa: 0.1, eficiencia_asimilacion: 1.0 }
+    }
+}
+
+/// Modo de cadena trófica completa pasto → presas → depredador. Con él activo, las presas
+/// solo crecen, se reproducen y sobreviven en la medida en que encuentran pasto, y el pasto
+/// se regenera de forma logística hasta la capacidad del terreno. El terreno se divide en una
+/// cuadrícula (ver `pastizal`): capacidad, pasto inicial y refugio se reparten por igual entre
+/// las celdas. El depredador, como siempre, solo repone su reserva con lo que caza. Está activo
+/// por defecto: sin él, las presas no comen y solo las frenan la caza, la enfermedad y
+/// `POBLACION_MAXIMA`.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(default, deny_unknown_fields)]
+pub struct CadenaTrofica {
+    pub activa: bool,
+    /// Columnas de la cuadrícula de pasto.
+    #[schemars(range(min = 1))]
+    pub columnas: u32,
+    /// Filas de la cuadrícula de pasto.
+    #[schemars(range(min = 1))]
+    pub filas: u32,
+    /// Pasto que admite todo el terreno (kg).
+    #[schemars(range(min = 0.0))]
+    pub capacidad_kg: f64,
+    /// Pasto al empezar la corrida (kg).
+    #[schemars(range(min = 0.0))]
+    pub pasto_inicial_kg: f64,
+    /// Tasa diaria de crecimiento logístico del pasto.
+    #[schemars(range(min = 0.0, max = 1.0))]
+    pub crecimiento_diario: f64,
+    /// Pasto que las presas no alcanzan a comer (raíces, semillas) y desde el que rebrota (kg).
+    #[schemars(range(min = 0.0))]
+    pub refugio_kg: f64,
+    /// Ración diaria de pasto de cada presa, como fracción de su peso.
+    #[schemars(range(min = 0.0))]
+    pub consumo_relativo: f64,
+    /// Probabilidad diaria de morir de hambre de una presa que no come nada; con media ración, la mitad.
+    #[schemars(range(min = 0.0, max = 1.0))]
+    pub mortalidad_hambre: f64,
+    /// Fracción de su peso que pierde en un día una presa que no come nada; con media ración,
+    /// la mitad. Al volver a comer recupera lo perdido poco a poco (`recuperacion_peso_diaria`).
+    #[schemars(range(min = 0.0, max = 1.0))]
+    pub perdida_peso_hambre: f64,
+    /// Fracción de su peso que puede recuperar en un día una presa por debajo de la curva de su
+    /// especie, además de lo que la curva crece ese día; con media ración, la mitad.
+    #[schemars(range(min = 0.0, max = 1.0))]
+    pub recuperacion_peso_diaria: f64,
+    /// Peso, como fracción del que le corresponde por edad, por debajo del cual una presa
+    /// desnutrida muere de inanición (sin sorteo). 0 desactiva esta muerte.
+    #[schemars(range(min = 0.0, max = 1.0))]
+    pub peso_minimo_relativo: f64,
+}
+
+impl Default for CadenaTrofica {
+    fn default() -> Self {
+        Self {
+            activa: true,
+            columnas: 16,
+            filas: 10,
+            capacidad_kg: 20_000.0,
+            pasto_inicial_kg: 20_000.0,
+            crecimiento_diario: 0.1,
+            refugio_kg: 500.0,
+            consumo_relativo: 0.05,
+            mortalidad_hambre: 0.05,
+            perdida_peso_hambre: 0.02,
+            recuperacion_peso_diaria: 0.02,
+            peso_minimo_relativo: 0.6,
+        }
+    }
+}
+
+/// Modo espacial: cada presa ocupa un punto de un terreno cuadrado (ver `espacio`) y da cada
+/// día un paso al azar, y el depredador solo caza entre las presas que tiene a su alcance. Si
+/// no caza, se acerca a la presa más próxima. Con la cadena trófica, la cuadrícula del
+/// pastizal cubre el terreno y las presas no salen de la celda en que pastan.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(default, deny_unknown_fields)]
+pub struct Espacio {
+    pub activo: bool,
+    /// Lado del terreno (m).
+    #[schemars(range(min = 0.0))]
+    pub lado_m: f32,
+    /// Distancia máxima que recorre una presa en un día (m).
+    #[schemars(range(min = 0.0))]
+    pub paso_presa_m: f32,
+    /// Distancia máxima que recorre el depredador en un día hacia la presa más cercana (m).
+    #[schemars(range(min = 0.0))]
+    pub paso_depredador_m: f32,
+    /// Distancia a la que el depredador alcanza a una presa (m).
+    #[schemars(range(min = 0.0))]
+    pub radio_caza_m: f32,
+    /// Distancia a la que una hembra encuentra a un macho adulto de su especie para aparearse (m).
+    #[schemars(range(min = 0.0))]
+    pub radio_apareamiento_m: f32,
+}
+
+impl Default for Espacio {
+    fn default() -> Self {
+        Self { activo: false, lado_m: 1000.0, paso_presa_m: 20.0, paso_depredador_m: 150.0, radio_caza_m: 100.0, radio_apareamiento_m: 100.0 }
+    }
+}
+
+/// Modo de manada: en lugar de un solo depredador que solo muere de hambre, una población que
+/// envejece, muere de vieja y se reproduce cuando le sobra comida. Cada depredador come y caza
+/// por su cuenta con los consumos de siempre. La corrida termina cuando mueren todos.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(default, deny_unknown_fields)]
+pub struct Manada {
+    pub activa: bool,
+    /// Depredadores al empezar la corrida, todos adultos, alternando hembras y machos.
+    #[schemars(range(min = 1))]
+    pub n_inicial: u32,
+    /// Edad a la que muere de viejo un depredador.
+    pub edad_maxima_dias: u32,
+    /// Edad desde la que un depredador puede reproducirse.
+    pub edad_reproductiva_dias: u32,
+    /// Probabilidad diaria de parir de una hembra adulta, si hay algún macho adulto.
+    #[schemars(range(min = 0.0, max = 1.0))]
+    pub tasa_reproduccion_diaria: f64,
+    pub crias_por_parto: RangoCrias,
+    /// Reserva que necesita una hembra para parir (kg).
+    #[schemars(range(min = 0.0))]
+    pub reserva_reproduccion_kg: f64,
+    /// Reserva que la madre cede a cada cría al nacer (kg).
+    #[schemars(range(min = 0.0))]
+    pub reserva_cria_kg: f64,
+}
+
+impl Default for Manada {
+    fn default() -> Self {
+        Self {
+            activa: false,
+            n_inicial: 2,
+            edad_maxima_dias: 4380,
+            edad_reproductiva_dias: 730,
+            tasa_reproduccion_diaria: 0.002,
+            crias_por_parto: RangoCrias { min: 1, max: 3 },
+            reserva_reproduccion_kg: 50.0,
+            reserva_cria_kg: 10.0,
+        }
+    }
+}
+
+/// Consumo del depredador según su masa y su actividad, en lugar de los consumos fijos.
+/// El mínimo diario sigue la ley de Kleiber (`coeficiente_kg × masa_kg ^ exponente`), el óptimo
+/// es `factor_optimo` veces el mínimo y cada día que sale a cazar gasta además `coste_caza`
+/// veces el mínimo. Con los valores por defecto, un depredador de 40 kg come lo mismo que con
+/// los consumos fijos (3 y 5 kg).
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(default, deny_unknown_fields)]
+pub struct Metabolismo {
+    /// Usa esta función en lugar de `DEPREDADOR_CONSUMO_MINIMO_DIARIO_KG` y `DEPREDADOR_CONSUMO_OPTIMO_DIARIO_KG`.
+    pub activo: bool,
+    /// Masa corporal del depredador (kg).
+    #[schemars(range(min = 0.0))]
+    pub masa_kg: f64,
+    /// Kilos de comida diarios por unidad de masa metabólica.
+    #[schemars(range(min = 0.0))]
+    pub coeficiente_kg: f64,
+    /// Exponente de la masa (0,75 en la ley de Kleiber).
+    #[schemars(range(min = 0.0))]
+    pub exponente: f64,
+    /// Consumo óptimo respecto al mínimo (debe ser >= 1).
+    #[schemars(range(min = 1.0))]
+    pub factor_optimo: f64,
+    /// Gasto extra de un día de caza, en fracción del consumo mínimo.
+    #[schemars(range(min = 0.0))]
+    pub coste_caza: f64,
+}
+
+impl Default for Metabolismo {
+    fn default() -> Self {
+        Self { activo: false, masa_kg: 40.0, coeficiente_kg: 0.1886, exponente: 0.75, factor_optimo: 5.0 / 3.0, coste_caza: 0.0 }
+    }
+}
+
+/// Cazabilidad en función de la edad y la condición corporal, en lugar de la edad de sacrificio
+/// fija: cada día, cada presa está al alcance del depredador con una probabilidad que parte de
+/// `cria` al nacer, baja hasta `adulto` al llegar a la edad reproductiva, se mantiene en la
+/// plenitud y sube hasta `vejez` en el último 20 % de la vida. Un peso por debajo del esperado
+/// para su edad la multiplica por `1 + peso_condicion × déficit`. Desactivada, rige la edad de
+/// sacrificio de cada especie.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(default, deny_unknown_fields)]
+pub struct Cazabilidad {
+    /// Usa esta función en lugar de `CONEJO_EDAD_SACRIFICIO_DIAS` y `CABRA_EDAD_SACRIFICIO_DIAS`.
+    pub activa: bool,
+    /// Probabilidad diaria de alcanzar a un recién nacido.
+    #[schemars(range(min = 0.0, max = 1.0))]
+    pub cria: f64,
+    /// Probabilidad diaria de alcanzar a un adulto en plenitud.
+    #[schemars(range(min = 0.0, max = 1.0))]
+    pub adulto: f64,
+    /// Probabilidad diaria de alcanzar a una presa en su edad máxima.
+    #[schemars(range(min = 0.0, max = 1.0))]
+    pub vejez: f64,
+    /// Peso de la mala condición corporal en la probabilidad.
+    #[schemars(range(min = 0.0))]
+    pub peso_condicion: f64,
+}
+
+impl Default for Cazabilidad {
+    fn default() -> Self {
+        Self { activa: false, cria: 0.8, adulto: 0.05, vejez: 0.9, peso_condicion: 1.0 }
+    }
+}
+
+/// Caza con riesgo de fallo: la presa elegida escapa con cierta probabilidad y el intento
+/// fallido le cuesta reserva al depredador. El éxito parte del de un adulto en buena condición
+/// de su especie y cubre el resto en proporción a su vulnerabilidad por edad y condición
+/// corporal (ver `Cazabilidad`): `éxito = base + (1 − base) × vulnerabilidad`. Desactivado,
+/// toda caza tiene éxito.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(default, deny_unknown_fields)]
+pub struct ExitoCaza {
+    pub activo: bool,
+    /// Probabilidad de abatir a un conejo adulto en buena condición.
+    #[schemars(range(min = 0.0, max = 1.0))]
+    pub conejo: f64,
+    /// Probabilidad de abatir a una cabra adulta en buena condición.
+    #[schemars(range(min = 0.0, max = 1.0))]
+    pub cabra: f64,
+    /// Reserva que gasta el depredador en cada intento fallido (kg).
+    #[schemars(range(min = 0.0))]
+    pub coste_fallo_kg: f64,
+}
+
+impl Default for ExitoCaza {
+    fn default() -> Self {
+        Self { activo: false, conejo: 0.6, cabra: 0.35, coste_fallo_kg: 0.5 }
+    }
+}
+
+/// Respuesta funcional de Holling tipo II: en lugar de cazar una presa al día, el depredador
+/// caza de media `a·N / (1 + a·h·N)`, donde `N` son las presas a su alcance, `a` la tasa de
+/// ataque y `h` el tiempo de manipulación de cada presa. Con pocas presas caza en proporción a
+/// ellas; con muchas se satura en `1/h` al día. La parte fraccionaria de la media se sortea.
+/// Desactivada, caza una presa al día siempre que tenga alguna a su alcance.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(default, deny_unknown_fields)]
+pub struct RespuestaFuncional {
+    pub activa: bool,
+    /// Presas que encuentra y ataca al día por cada presa a su alcance (`a`).
+    #[schemars(range(min = 0.0))]
+    pub tasa_ataque: f64,
+    /// Días que le lleva perseguir, abatir y comer cada presa (`h`); con 0 la respuesta es
+    /// lineal (tipo I) y no se satura.
+    #[schemars(range(min = 0.0))]
+    pub tiempo_manipulacion_dias: f64,
+}
+
+impl Default for RespuestaFuncional {
+    fn default() -> Self {
+        Self { activa: false, tasa_ataque: 0.05, tiempo_manipulacion_dias: 1.0 }
+    }
+}
+
+impl RespuestaFuncional {
+    /// Presas que caza de media al día con `disponibles` a su alcance.
+    pub fn capturas_esperadas(&self, disponibles: f64) -> f64 {
+        self.por_presa(disponibles) * disponibles.max(0.0)
+    }
+
+    /// Fracción de las presas a su alcance que caza de media al día: `a / (1 + a·h·N)`.
+    pub fn por_presa(&self, disponibles: f64) -> f64 {
+        self.tasa_ataque / (1.0 + self.tasa_ataque * self.tiempo_manipulacion_dias * disponibles.max(0.0))
+    }
+}
+
+/// Ajustes de una clase de edad de las presas.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(default, deny_unknown_fields)]
+pub struct AjustesClase {
+    /// Multiplica el atractivo para el depredador, que caza la presa de mayor peso por
+    /// vulnerabilidad. Con 0, la clase no se caza.
+    #[schemars(range(min = 0.0))]
+    pub vulnerabilidad: f64,
+    /// Probabilidad diaria de morir, además de la enfermedad y la vejez.
+    #[schemars(range(min = 0.0, max = 1.0))]
+    pub mortalidad_diaria: f64,
+}
+
+impl Default for AjustesClase {
+    fn default() -> Self {
+        Self { vulnerabilidad: 1.0, mortalidad_diaria: 0.0 }
+    }
+}
+
+/// Ajustes de cada clase de edad (ver `ClaseEdad`). Por defecto todas son igual de
+/// vulnerables y no tienen mortalidad propia.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(default, deny_unknown_fields)]
+pub struct ClasesEdad {
+    pub juvenil: AjustesClase,
+    pub subadulto: AjustesClase,
+    pub adulto: AjustesClase,
+    pub senescente: AjustesClase,
+}
+
+impl ClasesEdad {
+    pub fn de(&self, clase: ClaseEdad) -> &AjustesClase {
+        match clase {
+            ClaseEdad::Juvenil => &self.juvenil,
+            ClaseEdad::Subadulto => &self.subadulto,
+            ClaseEdad::Adulto => &self.adulto,
+            ClaseEdad::Senescente => &self.senescente,
+        }
+    }
+}
+
+/// Brotes de enfermedad ligados al hacinamiento. Cuando una especie supera el umbral, puede
+/// estallar un brote que durante unos días mata a una parte de ella; la probabilidad de brote y
+/// la mortalidad crecen con la densidad (al doble del umbral, el doble), lo que produce el ciclo
+/// de auge, desplome y recuperación de las poblaciones reales de conejos.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(default, deny_unknown_fields)]
+pub struct Epidemias {
+    /// Presas de una especie a partir de las cuales puede estallar un brote. 0 lo desactiva.
+    pub umbral_densidad: u32,
+    /// Probabilidad diaria de brote con la especie justo en el umbral.
+    #[schemars(range(min = 0.0, max = 1.0))]
+    pub probabilidad_brote: f64,
+    /// Probabilidad diaria de morir de cada presa afectada, con la especie justo en el umbral.
+    #[schemars(range(min = 0.0, max = 1.0))]
+    pub mortalidad: f64,
+    /// Días que dura un brote.
+    pub duracion_dias: u32,
+}
+
+impl Default for Epidemias {
+    fn default() -> Self {
+        Self { umbral_densidad: 0, probabilidad_brote: 0.01, mortalidad: 0.02, duracion_dias: 30 }
+    }
+}
+
+/// Enfermedad contagiosa entre presas de la misma especie (modelo SIR, ver `enfermedad`).
+/// Activa, sustituye a `PROBABILIDAD_ENFERMAR`: solo mueren de enfermedad las infectadas.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(default, deny_unknown_fields)]
+pub struct Enfermedad {
+    pub activa: bool,
+    /// Contagios diarios que causaría una infectada si todas las demás fueran susceptibles.
+    #[schemars(range(min = 0.0))]
+    pub transmision: f64,
+    /// Probabilidad diaria de que una infectada se recupere.
+    #[schemars(range(min = 0.0, max = 1.0))]
+    pub recuperacion: f64,
+    /// Probabilidad diaria de que una infectada muera.
+    #[schemars(range(min = 0.0, max = 1.0))]
+    pub mortalidad: f64,
+    /// Probabilidad diaria de que una recuperada pierda la inmunidad.
+    #[schemars(range(min = 0.0, max = 1.0))]
+    pub perdida_inmunidad: f64,
+    /// Probabilidad diaria de que llegue un caso a una especie sin infectadas.
+    #[schemars(range(min = 0.0, max = 1.0))]
+    pub introduccion: f64,
+    /// Fracción infectada de una especie a partir de la cual se anuncia una ola epidémica.
+    #[schemars(range(min = 0.0, max = 1.0))]
+    pub umbral_ola: f64,
+}
+
+impl Default for Enfermedad {
+    fn default() -> Self {
+        Self {
+            activa: false,
+            transmision: 0.3,
+            recuperacion: 0.1,
+            mortalidad: 0.02,
+            perdida_inmunidad: 0.005,
+            introduccion: 0.01,
+            umbral_ola: 0.05,
+        }
+    }
+}
+
+/// Ciclo anual de cuatro estaciones (ver `clima`), que empieza en primavera. Cada estación
+/// multiplica la tasa de reproducción de las presas, su probabilidad de enfermar (también el
+/// contagio de `ENFERMEDAD`) y el rebrote del pasto por sus factores.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(default, deny_unknown_fields)]
+pub struct Clima {
+    pub activo: bool,
+    /// Días que dura cada estación.
+    pub duracion_estacion_dias: u32,
+    pub primavera: FactoresEstacion,
+    pub verano: FactoresEstacion,
+    pub otono: FactoresEstacion,
+    pub invierno: FactoresEstacion,
+}
+
+impl Default for Clima {
+    fn default() -> Self {
+        Self {
+            activo: false,
+            duracion_estacion_dias: 91,
+            primavera: FactoresEstacion { reproduccion: 1.5, enfermedad: 0.8, pasto: 1.5 },
+            verano: FactoresEstacion { reproduccion: 1.0, enfermedad: 1.0, pasto: 0.7 },
+            otono: FactoresEstacion { reproduccion: 0.7, enfermedad: 1.0, pasto: 1.0 },
+            invierno: FactoresEstacion { reproduccion: 0.2, enfermedad: 1.5, pasto: 0.2 },
+        }
+    }
+}
+
+/// Multiplicadores de una estación; 1 deja el parámetro como está.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(default, deny_unknown_fields)]
+pub struct FactoresEstacion {
+    #[schemars(range(min = 0.0))]
+    pub reproduccion: f64,
+    #[schemars(range(min = 0.0))]
+    pub enfermedad: f64,
+    #[schemars(range(min = 0.0))]
+    pub pasto: f64,
+}
+
+impl Default for FactoresEstacion {
+    fn default() -> Self {
+        Self { reproduccion: 1.0, enfermedad: 1.0, pasto: 1.0 }
+    }
+}
+
+impl Default for Dieta {
+    fn default() -> Self {
+        Self { conejos: true, cabras: true, conejo_peso_minimo_kg: 0.0, cabra_peso_minimo_kg: 0.0, respetar_hembras_prenadas: false }
+    }
+}
+
+/// Todos los parámetros de una simulación. Los nombres serializados coinciden con los de
+/// las constantes (p. ej. `CONEJO_TASA_REPRODUCCION_DIARIA`), que es como se les conoce.
+/// Los campos omitidos toman el valor por defecto; los desconocidos son un error.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE", default, deny_unknown_fields)]
+pub struct Parametros {
+    /// Conejos al inicio de la simulación.
+    pub n_conejos_inicial: u32,
+    /// Cabras al inicio de la simulación.
+    pub n_cabras_inicial: u32,
+
+    /// Reserva de comida inicial del depredador (kg).
+    #[schemars(range(min = 0.0))]
+    pub depredador_reserva_inicial_kg: f64,
+    /// Consumo diario mínimo para sobrevivir (kg).
+    #[schemars(range(min = 0.0))]
+    pub depredador_consumo_minimo_diario_kg: f64,
+    /// Consumo diario cuando la reserva lo permite (kg); debe ser >= el mínimo.
+    #[schemars(range(min = 0.0))]
+    pub depredador_consumo_optimo_diario_kg: f64,
+    /// Conversión a energía y eficiencias entre niveles tróficos (tabla `ENERGIA`).
+    pub energia: Energia,
+    /// Consumo según la masa y la actividad del depredador (tabla `METABOLISMO`); desactivado por defecto.
+    pub metabolismo: Metabolismo,
+    /// Kilos que el depredador come de una presa recién cazada, y cada día de su despensa;
+    /// el resto se guarda en la despensa. 0 = sin límite (toda la presa va a la reserva).
+    #[schemars(range(min = 0.0))]
+    pub depredador_ingesta_maxima_kg: f64,
+    /// Fracción de la despensa que se pudre cada día.
+    #[schemars(range(min = 0.0, max = 1.0))]
+    pub tasa_descomposicion_diaria: f64,
+    /// Qué presas caza el depredador (tabla `DIETA_DEPREDADOR`).
+    pub dieta_depredador: Dieta,
+    /// Cuál de ellas elige cuando puede cazar varias.
+    pub estrategia_caza: EstrategiaCaza,
+
+    /// Edad a partir de la cual un conejo muere de viejo (días).
+    pub conejo_edad_maxima_dias: u32,
+    /// Edad mínima de reproducción de las conejas (días).
+    pub conejo_edad_reproductiva_dias: u32,
+    /// Edad a partir de la cual un conejo puede ser cazado (días); sin efecto con `CAZABILIDAD.activa`.
+    pub conejo_edad_sacrificio_dias: u32,
+    /// Probabilidad diaria de parto de una coneja adulta; con gestación, de que conciba.
+    #[schemars(range(min = 0.0, max = 1.0))]
+    pub conejo_tasa_reproduccion_diaria: f64,
+    /// Crías por parto de una coneja.
+    pub conejo_crias_por_parto: RangoCrias,
+    /// Curva de crecimiento del conejo.
+    pub conejo_crecimiento: CurvaCrecimiento,
+    /// Días que una coneja lleva las crías antes de parir; mientras tanto no vuelve a concebir.
+    pub conejo_gestacion_dias: u32,
+
+    /// Edad a partir de la cual una cabra muere de vieja (días).
+    pub cabra_edad_maxima_dias: u32,
+    /// Edad mínima de reproducción de las cabras (días).
+    pub cabra_edad_reproductiva_dias: u32,
+    /// Edad a partir de la cual una cabra puede ser cazada (días); sin efecto con `CAZABILIDAD.activa`.
+    pub cabra_edad_sacrificio_dias: u32,
+    /// Probabilidad diaria de parto de una cabra adulta; con gestación, de que conciba.
+    #[schemars(range(min = 0.0, max = 1.0))]
+    pub cabra_tasa_reproduccion_diaria: f64,
+    /// Crías por parto de una cabra.
+    pub cabra_crias_por_parto: RangoCrias,
+    /// Curva de crecimiento de la cabra.
+    pub cabra_crecimiento: CurvaCrecimiento,
+    /// Días que una cabra lleva las crías antes de parir; mientras tanto no vuelve a concebir.
+    pub cabra_gestacion_dias: u32,
+
+    /// Probabilidad diaria de que una presa muera por enfermedad; sin efecto con `ENFERMEDAD.activa`.
+    #[schemars(range(min = 0.0, max = 1.0))]
+    pub probabilidad_enfermar: f64,
+    /// Probabilidad de que una cría nazca macho.
+    #[schemars(range(min = 0.0, max = 1.0))]
+    pub probabilidad_nacer_macho: f64,
+    /// Población de una especie por debajo de la cual su reproducción se reduce en proporción
+    /// (efecto Allee: con 5 conejos y umbral 20, cada coneja pare con 1/4 de su tasa). 0 lo desactiva.
+    pub umbral_allee: u32,
+    /// Brotes de enfermedad por hacinamiento (tabla `EPIDEMIAS`).
+    pub epidemias: Epidemias,
+    /// Enfermedad contagiosa entre presas (tabla `ENFERMEDAD`); desactivada por defecto.
+    pub enfermedad: Enfermedad,
+    /// Ciclo de estaciones (tabla `CLIMA`); desactivado por defecto.
+    pub clima: Clima,
+    /// Vulnerabilidad y mortalidad de cada clase de edad (tabla `CLASES_EDAD`).
+    pub clases_edad: ClasesEdad,
+    /// Cazabilidad según edad y condición (tabla `CAZABILIDAD`); desactivada por defecto.
+    pub cazabilidad: Cazabilidad,
+    /// Probabilidad de que una caza salga bien (tabla `EXITO_CAZA`); desactivada por defecto.
+    pub exito_caza: ExitoCaza,
+    /// Cuántas presas caza al día según cuántas tiene a su alcance (tabla `RESPUESTA_FUNCIONAL`);
+    /// desactivada por defecto.
+    pub respuesta_funcional: RespuestaFuncional,
+    /// Pasto como recurso de las presas (tabla `CADENA_TROFICA`); desactivado por defecto.
+    pub cadena_trofica: CadenaTrofica,
+    /// Posiciones de las presas y caza por cercanía (tabla `ESPACIO`); desactivado por defecto.
+    pub espacio: Espacio,
+    /// Población de depredadores que envejece y se reproduce (tabla `MANADA`); desactivada por defecto.
+    pub manada: Manada,
+
+    /// Presas vivas como máximo; el exceso muere al final del día (mortalidad de emergencia).
+    #[schemars(range(min = 1))]
+    pub poblacion_maxima: u32,
+    /// Usa un flujo de números aleatorios propio para cada presa y cada tipo de decisión, derivado
+    /// de la semilla, del día y del id, en lugar de un único generador compartido. Así, dos
+    /// corridas con la misma semilla y parámetros distintos reciben los mismos sorteos donde no
+    /// difieren (números aleatorios comunes), y las comparaciones necesitan menos réplicas.
+    pub subflujos_aleatorios: bool,
+}
+
+/// Un valor de parámetro fuera de rango, con la ruta del campo (p. ej. `CONEJO_CRIAS_POR_PARTO.max`).
+#[derive(Debug, Clone, PartialEq)]
+pub struct ErrorValidacion {
+    pub campo: String,
+    pub mensaje: String,
+}
+
+impl fmt::Display for ErrorValidacion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.mensaje)
+    }
+}
+
+impl ErrorValidacion {
+    fn new(campo: &str, mensaje: String) -> Self {
+        Self { campo: campo.to_string(), mensaje }
+    }
+}
+
+impl Default for Parametros {
+    fn default() -> Self {
+        Self {
+            n_conejos_inicial: N_CONEJOS_INICIAL,
+            n_cabras_inicial: N_CABRAS_INICIAL,
+
+            depredador_reserva_inicial_kg: DEPREDADOR_RESERVA_INICIAL_KG,
+            depredador_consumo_minimo_diario_kg: DEPREDADOR_CONSUMO_MINIMO_DIARIO_KG,
+            depredador_consumo_optimo_diario_kg: DEPREDADOR_CONSUMO_OPTIMO_DIARIO_KG,
+            energia: Energia::default(),
+            metabolismo: Metabolismo::default(),
+            depredador_ingesta_maxima_kg: DEPREDADOR_INGESTA_MAXIMA_KG,
+            tasa_descomposicion_diaria: TASA_DESCOMPOSICION_DIARIA,
+            dieta_depredador: Dieta::default(),
+            estrategia_caza: EstrategiaCaza::default(),
+
+            conejo_edad_maxima_dias: CONEJO_EDAD_MAXIMA_DIAS,
+            conejo_edad_reproductiva_dias: CONEJO_EDAD_REPRODUCTIVA_DIAS,
+            conejo_edad_sacrificio_dias: CONEJO_EDAD_SACRIFICIO_DIAS,
+            conejo_tasa_reproduccion_diaria: CONEJO_TASA_REPRODUCCION_DIARIA,
+            conejo_crias_por_parto: CONEJO_CRIAS_POR_PARTO.into(),
+            conejo_crecimiento: CONEJO_CRECIMIENTO.into(),
+            conejo_gestacion_dias: CONEJO_GESTACION_DIAS,
+
+            cabra_edad_maxima_dias: CABRA_EDAD_MAXIMA_DIAS,
+            cabra_edad_reproductiva_dias: CABRA_EDAD_REPRODUCTIVA_DIAS,
+            cabra_edad_sacrificio_dias: CABRA_EDAD_SACRIFICIO_DIAS,
+            cabra_tasa_reproduccion_diaria: CABRA_TASA_REPRODUCCION_DIARIA,
+            cabra_crias_por_parto: CABRA_CRIAS_POR_PARTO.into(),
+            cabra_crecimiento: CABRA_CRECIMIENTO.into(),
+            cabra_gestacion_dias: CABRA_GESTACION_DIAS,
+
+            probabilidad_enfermar: PROBABILIDAD_ENFERMAR,
+            probabilidad_nacer_macho: PROBABILIDAD_NACER_MACHO,
+            umbral_allee: UMBRAL_ALLEE,
+            epidemias: Epidemias::default(),
+            enfermedad: Enfermedad::default(),
+            clima: Clima::default(),
+            clases_edad: ClasesEdad::default(),
+            cazabilidad: Cazabilidad::default(),
+            exito_caza: ExitoCaza::default(),
+            respuesta_funcional: RespuestaFuncional::default(),
+            cadena_trofica: CadenaTrofica::default(),
+            espacio: Espacio::default(),
+            manada: Manada::default(),
+
+            poblacion_maxima: POBLACION_MAXIMA,
+            subflujos_aleatorios: false,
+        }
+    }
+}
+
+impl Parametros {
+    /// Huella estable de todos los valores (FNV-1a de su JSON), para saber si dos corridas
+    /// usaron los mismos parámetros aunque se dieran por caminos distintos.
+    pub fn huella(&self) -> u64 {
+        let mut h = Fnv1a::new();
+        for byte in serde_json::to_vec(self).unwrap_or_default() {
+            h.escribir(byte as u64);
+        }
+        h.terminar()
+    }
+
+    /// Comprueba las restricciones entre valores que el sistema de tipos no puede expresar.
+    pub fn validar(&self) -> Result<(), ErrorValidacion> {
+        let probabilidades = [
+            ("CONEJO_TASA_REPRODUCCION_DIARIA", self.conejo_tasa_reproduccion_diaria),
+            ("CABRA_TASA_REPRODUCCION_DIARIA", self.cabra_tasa_reproduccion_diaria),
+            ("PROBABILIDAD_ENFERMAR", self.probabilidad_enfermar),
+            ("PROBABILIDAD_NACER_MACHO", self.probabilidad_nacer_macho),
+            ("TASA_DESCOMPOSICION_DIARIA", self.tasa_descomposicion_diaria),
+            ("ENERGIA.eficiencia_pasto_presa", self.energia.eficiencia_pasto_presa),
+            ("ENERGIA.eficiencia_asimilacion", self.energia.eficiencia_asimilacion),
+            ("EPIDEMIAS.probabilidad_brote", self.epidemias.probabilidad_brote),
+            ("EPIDEMIAS.mortalidad", self.epidemias.mortalidad),
+            ("ENFERMEDAD.recuperacion", self.enfermedad.recuperacion),
+            ("ENFERMEDAD.mortalidad", self.enfermedad.mortalidad),
+            ("ENFERMEDAD.perdida_inmunidad", self.enfermedad.perdida_inmunidad),
+            ("ENFERMEDAD.introduccion", self.enfermedad.introduccion),
+            ("ENFERMEDAD.umbral_ola", self.enfermedad.umbral_ola),
+            ("CLASES_EDAD.juvenil.mortalidad_diaria", self.clases_edad.juvenil.mortalidad_diaria),
+            ("CLASES_EDAD.subadulto.mortalidad_diaria", self.clases_edad.subadulto.mortalidad_diaria),
+            ("CLASES_EDAD.adulto.mortalidad_diaria", self.clases_edad.adulto.mortalidad_diaria),
+            ("CLASES_EDAD.senescente.mortalidad_diaria", self.clases_edad.senescente.mortalidad_diaria),
+            ("CAZABILIDAD.cria", self.cazabilidad.cria),
+            ("CAZABILIDAD.adulto", self.cazabilidad.adulto),
+            ("CAZABILIDAD.vejez", self.cazabilidad.vejez),
+            ("EXITO_CAZA.conejo", self.exito_caza.conejo),
+            ("EXITO_CAZA.cabra", self.exito_caza.cabra),
+            ("CADENA_TROFICA.crecimiento_diario", self.cadena_trofica.crecimiento_diario),
+            ("CADENA_TROFICA.mortalidad_hambre", self.cadena_trofica.mortalidad_hambre),
+            ("CADENA_TROFICA.perdida_peso_hambre", self.cadena_trofica.perdida_peso_hambre),
+            ("CADENA_TROFICA.recuperacion_peso_diaria", self.cadena_trofica.recuperacion_peso_diaria),
+            ("CADENA_TROFICA.peso_minimo_relativo", self.cadena_trofica.peso_minimo_relativo),
+            ("MANADA.tasa_reproduccion_diaria", self.manada.tasa_reproduccion_diaria),
+        ];
+        for (nombre, valor) in probabilidades {
+            if !(0.0..=1.0).contains(&valor) {
+                return Err(ErrorValidacion::new(nombre, format!("{} debe estar entre 0 y 1 (es {})", nombre, valor)));
+            }
+        }
+        let rangos = [
+            ("CONEJO_CRIAS_POR_PARTO", self.conejo_crias_por_parto),
+            ("CABRA_CRIAS_POR_PARTO", self.cabra_crias_por_parto),
+            ("MANADA.crias_por_parto", self.manada.crias_por_parto),
+        ];
+        for (nombre, rango) in rangos {
+            if rango.max < rango.min {
+                let campo = format!("{}.max", nombre);
+                return Err(ErrorValidacion::new(&campo, format!("{} < min ({} < {})", campo, rango.max, rango.min)));
+            }
+        }
+        let consumos = [
+            ("DEPREDADOR_RESERVA_INICIAL_KG", self.depredador_reserva_inicial_kg),
+            ("DEPREDADOR_CONSUMO_MINIMO_DIARIO_KG", self.depredador_consumo_minimo_diario_kg),
+            ("DEPREDADOR_CONSUMO_OPTIMO_DIARIO_KG", self.depredador_consumo_optimo_diario_kg),
+            ("DEPREDADOR_INGESTA_MAXIMA_KG", self.depredador_ingesta_maxima_kg),
+            ("CONEJO_CRECIMIENTO.peso_maximo_kg", self.conejo_crecimiento.peso_maximo_kg),
+            ("CONEJO_CRECIMIENTO.tasa", self.conejo_crecimiento.tasa),
+            ("CONEJO_CRECIMIENTO.inflexion_dias", self.conejo_crecimiento.inflexion_dias),
+            ("CABRA_CRECIMIENTO.peso_maximo_kg", self.cabra_crecimiento.peso_maximo_kg),
+            ("CABRA_CRECIMIENTO.tasa", self.cabra_crecimiento.tasa),
+            ("CABRA_CRECIMIENTO.inflexion_dias", self.cabra_crecimiento.inflexion_dias),
+            ("ENERGIA.mj_por_kg", self.energia.mj_por_kg),
+            ("METABOLISMO.masa_kg", self.metabolismo.masa_kg),
+            ("METABOLISMO.coeficiente_kg", self.metabolismo.coeficiente_kg),
+            ("METABOLISMO.exponente", self.metabolismo.exponente),
+            ("METABOLISMO.coste_caza", self.metabolismo.coste_caza),
+            ("DIETA_DEPREDADOR.conejo_peso_minimo_kg", self.dieta_depredador.conejo_peso_minimo_kg),
+            ("DIETA_DEPREDADOR.cabra_peso_minimo_kg", self.dieta_depredador.cabra_peso_minimo_kg),
+            ("CLASES_EDAD.juvenil.vulnerabilidad", self.clases_edad.juvenil.vulnerabilidad),
+            ("CLASES_EDAD.subadulto.vulnerabilidad", self.clases_edad.subadulto.vulnerabilidad),
+            ("CLASES_EDAD.adulto.vulnerabilidad", self.clases_edad.adulto.vulnerabilidad),
+            ("CLASES_EDAD.senescente.vulnerabilidad", self.clases_edad.senescente.vulnerabilidad),
+            ("CAZABILIDAD.peso_condicion", self.cazabilidad.peso_condicion),
+            ("EXITO_CAZA.coste_fallo_kg", self.exito_caza.coste_fallo_kg),
+            ("RESPUESTA_FUNCIONAL.tasa_ataque", self.respuesta_funcional.tasa_ataque),
+            ("RESPUESTA_FUNCIONAL.tiempo_manipulacion_dias", self.respuesta_funcional.tiempo_manipulacion_dias),
+            ("ENFERMEDAD.transmision", self.enfermedad.transmision),
+            ("CLIMA.primavera.reproduccion", self.clima.primavera.reproduccion),
+            ("CLIMA.primavera.enfermedad", self.clima.primavera.enfermedad),
+            ("CLIMA.primavera.pasto", self.clima.primavera.pasto),
+            ("CLIMA.verano.reproduccion", self.clima.verano.reproduccion),
+            ("CLIMA.verano.enfermedad", self.clima.verano.enfermedad),
+            ("CLIMA.verano.pasto", self.clima.verano.pasto),
+            ("CLIMA.otono.reproduccion", self.clima.otono.reproduccion),
+            ("CLIMA.otono.enfermedad", self.clima.otono.enfermedad),
+            ("CLIMA.otono.pasto", self.clima.otono.pasto),
+            ("CLIMA.invierno.reproduccion", self.clima.invierno.reproduccion),
+            ("CLIMA.invierno.enfermedad", self.clima.invierno.enfermedad),
+            ("CLIMA.invierno.pasto", self.clima.invierno.pasto),
+            ("CADENA_TROFICA.capacidad_kg", self.cadena_trofica.capacidad_kg),
+            ("CADENA_TROFICA.pasto_inicial_kg", self.cadena_trofica.pasto_inicial_kg),
+            ("CADENA_TROFICA.refugio_kg", self.cadena_trofica.refugio_kg),
+            ("CADENA_TROFICA.consumo_relativo", self.cadena_trofica.consumo_relativo),
+            ("ESPACIO.paso_presa_m", self.espacio.paso_presa_m as f64),
+            ("ESPACIO.paso_depredador_m", self.espacio.paso_depredador_m as f64),
+            ("ESPACIO.radio_caza_m", self.espacio.radio_caza_m as f64),
+            ("ESPACIO.radio_apareamiento_m", self.espacio.radio_apareamiento_m as f64),
+            ("MANADA.reserva_reproduccion_kg", self.manada.reserva_reproduccion_kg),
+            ("MANADA.reserva_cria_kg", self.manada.reserva_cria_kg),
+        ];
+        for (nombre, valor) in consumos {
+            if !valor.is_finite() || valor < 0.0 {
+                return Err(ErrorValidacion::new(nombre, format!("{} debe ser un número no negativo (es {})", nombre, valor)));
+            }
+        }
+        if self.depredador_consumo_optimo_diario_kg < self.depredador_consumo_minimo_diario_kg {
+            let campo = "DEPREDADOR_CONSUMO_OPTIMO_DIARIO_KG";
+            return Err(ErrorValidacion::new(campo, format!("{} < DEPREDADOR_CONSUMO_MINIMO_DIARIO_KG", campo)));
+        }
+        if self.metabolismo.factor_optimo.is_nan() || self.metabolismo.factor_optimo < 1.0 {
+            let campo = "METABOLISMO.factor_optimo";
+            return Err(ErrorValidacion::new(campo, format!("{} debe ser al menos 1 (es {})", campo, self.metabolismo.factor_optimo)));
+        }
+        if self.poblacion_maxima == 0 {
+            return Err(ErrorValidacion::new("POBLACION_MAXIMA", "POBLACION_MAXIMA debe ser al menos 1".to_string()));
+        }
+        for (campo, valor) in [("CADENA_TROFICA.columnas", self.cadena_trofica.columnas), ("CADENA_TROFICA.filas", self.cadena_trofica.filas)] {
+            if valor == 0 {
+                return Err(ErrorValidacion::new(campo, format!("{} debe ser al menos 1", campo)));
+            }
+        }
+        if self.clima.duracion_estacion_dias == 0 {
+            let campo = "CLIMA.duracion_estacion_dias";
+            return Err(ErrorValidacion::new(campo, format!("{} debe ser al menos 1", campo)));
+        }
+        if self.manada.n_inicial == 0 {
+            return Err(ErrorValidacion::new("MANADA.n_inicial", "MANADA.n_inicial debe ser al menos 1".to_string()));
+        }
+        if !self.espacio.lado_m.is_finite() || self.espacio.lado_m <= 0.0 {
+            let campo = "ESPACIO.lado_m";
+            return Err(ErrorValidacion::new(campo, format!("{} debe ser positivo (es {})", campo, self.espacio.lado_m)));
+        }
+        Ok(())
+    }
+
+    /// Consumo diario del depredador (mínimo, óptimo) en kg: los valores fijos o, con el
+    /// metabolismo activo, los que corresponden a su masa.
+    pub fn consumo_depredador_kg(&self) -> (f64, f64) {
+        let m = &self.metabolismo;
+        if m.activo {
+            let minimo = m.coeficiente_kg * m.masa_kg.powf(m.exponente);
+            (minimo, minimo * m.factor_optimo)
+        } else {
+            (self.depredador_consumo_minimo_diario_kg, self.depredador_consumo_optimo_diario_kg)
+        }
+    }
+
+    /// Gasto extra (kg) de un día en que el depredador sale a cazar; solo con el metabolismo activo.
+    pub fn coste_caza_kg(&self) -> f64 {
+        if self.metabolismo.activo {
+            self.consumo_depredador_kg().0 * self.metabolismo.coste_caza
+        } else {
+            0.0
+        }
+    }
+
+    /// Cambia un parámetro por su nombre. Los campos anidados se indican con punto
+    /// (p. ej. `CONEJO_CRIAS_POR_PARTO.max`). El cambio solo se aplica si el resultado es válido.
+    /// Los nombres no distinguen mayúsculas y admiten un prefijo sin ambigüedad
+    /// (`CONEJO_TASA_REPRODUCCION` equivale a `CONEJO_TASA_REPRODUCCION_DIARIA`).
+    pub fn establecer(&mut self, nombre: &str, valor: Value) -> Result<(), String> {
+        let mut arbol = self.a_arbol();
+        asignar_en_arbol(&mut arbol, nombre, valor)?;
+        *self = Self::desde_arbol(arbol)?;
+        Ok(())
+    }
+
+    /// Variante encadenable de `establecer`, cómoda en notebooks:
+    /// `Parametros::default().con("CONEJO_TASA", 0.08)?`.
+    pub fn con(mut self, nombre: &str, valor: impl Into<Value>) -> Result<Self, String> {
+        self.establecer(nombre, valor.into())?;
+        Ok(self)
+    }
+
+    /// Representación JSON de los parámetros, sobre la que se aplican cambios por nombre.
+    pub fn a_arbol(&self) -> Value {
+        serde_json::to_value(self).unwrap_or(Value::Null)
+    }
+
+    /// Reconstruye y valida los parámetros a partir de su representación JSON.
+    pub fn desde_arbol(arbol: Value) -> Result<Self, String> {
+        let parametros: Parametros = serde_json::from_value(arbol).map_err(|e| format!("valor inválido: {}", e))?;
+        parametros.validar().map_err(|e| e.to_string())?;
+        Ok(parametros)
+    }
+}
+
+/// Asigna un valor dentro del árbol JSON de los parámetros, resolviendo el nombre por partes.
+/// No valida: así se pueden aplicar varios cambios relacionados y validar solo el resultado.
+/// Lo único que comprueba es que un parámetro entero reciba un número entero (`12.0` vale).
+pub fn asignar_en_arbol(arbol: &mut Value, nombre: &str, valor: Value) -> Result<(), String> {
+    let mut nodo = arbol;
+    for parte in nombre.split('.') {
+        let clave = resolver_clave(nodo, parte)?;
+        nodo = nodo.get_mut(&clave).ok_or_else(|| format!("parámetro desconocido: {}", nombre))?;
+    }
+    if nodo.is_object() {
+        return Err(format!("{} es un grupo; indique un campo (p. ej. {}.min)", nombre, nombre));
+    }
+    *nodo = match valor.as_f64() {
+        Some(numero) if (nodo.is_u64() || nodo.is_i64()) && valor.is_f64() => {
+            if numero.fract() != 0.0 {
+                return Err(format!("{} es entero y no admite {}", nombre, numero));
+            }
+            Value::from(numero as i64)
+        }
+        _ => valor,
+    };
+    Ok(())
+}
+
+/// Busca en un objeto JSON la clave que corresponde a `parte`: coincidencia exacta, luego sin
+/// distinguir mayúsculas y, por último, el único campo que empiece por ese prefijo.
+fn resolver_clave(nodo: &Value, parte: &str) -> Result<String, String> {
+    let objeto = nodo.as_object().ok_or_else(|| format!("'{}' no tiene subcampos", parte))?;
+    if objeto.contains_key(parte) {
+        return Ok(parte.to_string());
+    }
+    if let Some(clave) = objeto.keys().find(|k| k.eq_ignore_ascii_case(parte)) {
+        return Ok(clave.clone());
+    }
+    let parte_mayus = parte.to_ascii_uppercase();
+    let candidatas: Vec<&String> = objeto.keys().filter(|k| k.to_ascii_uppercase().starts_with(&parte_mayus)).collect();
+    match candidatas.as_slice() {
+        [unica] => Ok((*unica).clone()),
+        [] => Err(format!("parámetro desconocido: {}", parte)),
+        varias => Err(format!("nombre ambiguo '{}': puede ser {}", parte, varias.iter().map(|k| k.as_str()).collect::<Vec<_>>().join(", "))),
+    }
+}
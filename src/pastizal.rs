@@ -0,0 +1,106 @@
+// src/pastizal.rs
+
+// El pasto del modo de cadena trófica, repartido en una cuadrícula de celdas. Cada celda
+// rebrota de forma logística hacia su parte de la capacidad del terreno y solo la pastan las
+// presas que están en ella, así que un rebaño que se queda demasiado tiempo en un sitio lo
+// deja pelado y tiene que desplazarse a las celdas vecinas con más pasto.
+
+use serde::{Deserialize, Serialize};
+
+use crate::parametros::CadenaTrofica;
+
+/// Cuadrícula de pasto, con las celdas ordenadas por filas.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Pastizal {
+    pub columnas: u32,
+    pub filas: u32,
+    /// Pasto de cada celda (kg).
+    pub celdas: Vec<f64>,
+}
+
+impl Default for Pastizal {
+    fn default() -> Self {
+        Self::new(&CadenaTrofica::default())
+    }
+}
+
+impl Pastizal {
+    /// Cuadrícula con el pasto inicial de los parámetros repartido por igual entre las celdas.
+    pub fn new(cadena: &CadenaTrofica) -> Self {
+        let columnas = cadena.columnas.max(1);
+        let filas = cadena.filas.max(1);
+        let n = (columnas * filas) as usize;
+        Self { columnas, filas, celdas: vec![cadena.pasto_inicial_kg / n as f64; n] }
+    }
+
+    /// Si los parámetros piden otra cuadrícula (p. ej. tras una recarga), la rehace desde el pasto inicial.
+    pub fn ajustar(&mut self, cadena: &CadenaTrofica) {
+        if self.columnas != cadena.columnas.max(1) || self.filas != cadena.filas.max(1) || self.celdas.len() != self.n_celdas() {
+            *self = Self::new(cadena);
+        }
+    }
+
+    pub fn n_celdas(&self) -> usize {
+        (self.columnas * self.filas) as usize
+    }
+
+    /// Pasto de todo el terreno (kg).
+    pub fn total(&self) -> f64 {
+        self.celdas.iter().sum()
+    }
+
+    /// Columna y fila de una celda.
+    pub fn coordenadas(&self, celda: u32) -> (u32, u32) {
+        (celda % self.columnas, celda / self.columnas)
+    }
+
+    /// Celda de partida de una presa que todavía no tiene ninguna: se dispersa según su id.
+    pub fn celda_inicial(&self, id: u64) -> u32 {
+        (id.wrapping_mul(2_654_435_761) % self.n_celdas() as u64) as u32
+    }
+
+    /// Pasto de una celda como fracción de lo que admite (0 pelada, 1 llena), para dibujarla.
+    pub fn cobertura(&self, celda: u32, cadena: &CadenaTrofica) -> f64 {
+        let capacidad = cadena.capacidad_kg / self.n_celdas() as f64;
+        if capacidad > 0.0 { (self.celdas[celda as usize] / capacidad).clamp(0.0, 1.0) } else { 0.0 }
+    }
+
+    /// Un día de rebrote logístico en cada celda.
+    pub fn crecer(&mut self, cadena: &CadenaTrofica) {
+        let capacidad = cadena.capacidad_kg / self.n_celdas() as f64;
+        if capacidad <= 0.0 {
+            return;
+        }
+        for pasto in &mut self.celdas {
+            *pasto = (*pasto + cadena.crecimiento_diario * *pasto * (1.0 - *pasto / capacidad)).max(0.0);
+        }
+    }
+
+    /// Come hasta `racion` kg de una celda, sin bajar del refugio. Devuelve los kg comidos.
+    pub fn pastar(&mut self, celda: u32, racion: f64, cadena: &CadenaTrofica) -> f64 {
+        let refugio = cadena.refugio_kg / self.n_celdas() as f64;
+        let pasto = &mut self.celdas[celda as usize];
+        let comido = racion.min((*pasto - refugio).max(0.0));
+        *pasto -= comido;
+        comido
+    }
+
+    /// La celda con más pasto entre la dada y sus ocho vecinas; en caso de empate, se queda.
+    pub fn mejor_vecina(&self, celda: u32) -> u32 {
+        let (columna, fila) = self.coordenadas(celda);
+        let mut mejor = celda;
+        for df in -1i64..=1 {
+            for dc in -1i64..=1 {
+                let (c, f) = (columna as i64 + dc, fila as i64 + df);
+                if c < 0 || f < 0 || c >= self.columnas as i64 || f >= self.filas as i64 {
+                    continue;
+                }
+                let vecina = (f * self.columnas as i64 + c) as u32;
+                if self.celdas[vecina as usize] > self.celdas[mejor as usize] {
+                    mejor = vecina;
+                }
+            }
+        }
+        mejor
+    }
+}
@@ -0,0 +1,812 @@
+// src/protobuf.rs
+
+// Codificación en protobuf (feature `protobuf`) de las instantáneas y de la telemetría diaria,
+// según `proto/instantanea.proto`. Los tipos generados viven en `mensajes`; aquí solo se
+// convierten desde y hacia los tipos del motor, sin pérdida (incluido el estado del RNG).
+
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use prost::Message;
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+
+use crate::alarmas::{Alarma, Comparacion, Magnitud};
+use crate::energia::BalanceEnergia;
+use crate::enfermedad::Salud;
+use crate::entidades::{CausaMuerte, Depredador, Especie, Gestacion, Sexo};
+use crate::espacio::Posicion;
+use crate::eventos::Evento;
+use crate::instantanea::{EstadoPresa, Instantanea, VERSION_INSTANTANEA};
+use crate::parametros::{
+    AjustesClase, CadenaTrofica, Cazabilidad, ClasesEdad, Clima, CurvaCrecimiento, Dieta, Energia, Enfermedad, Epidemias, Espacio, EstrategiaCaza, ExitoCaza, FactoresEstacion, Manada, Metabolismo, Parametros, RangoCrias, RespuestaFuncional,
+    CABRA_CRECIMIENTO, CONEJO_CRECIMIENTO, POBLACION_MAXIMA,
+};
+use crate::pastizal::Pastizal;
+use crate::registro::RegistroDiario;
+use crate::simulacion::{MuertesPorCausa, Retencion};
+
+/// Código generado a partir de `proto/instantanea.proto`.
+pub mod mensajes {
+    include!(concat!(env!("OUT_DIR"), "/instantanea.rs"));
+}
+
+fn datos_invalidos(mensaje: impl Into<String>) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, mensaje.into())
+}
+
+// --- Conversiones ---
+
+fn especie_a_proto(especie: Especie) -> mensajes::Especie {
+    match especie {
+        Especie::Conejo => mensajes::Especie::Conejo,
+        Especie::Cabra => mensajes::Especie::Cabra,
+    }
+}
+
+fn especie_desde_proto(valor: i32) -> io::Result<Especie> {
+    match mensajes::Especie::try_from(valor) {
+        Ok(mensajes::Especie::Conejo) => Ok(Especie::Conejo),
+        Ok(mensajes::Especie::Cabra) => Ok(Especie::Cabra),
+        Err(_) => Err(datos_invalidos(format!("especie desconocida: {}", valor))),
+    }
+}
+
+fn causa_a_proto(causa: CausaMuerte) -> mensajes::CausaMuerte {
+    match causa {
+        CausaMuerte::Vejez => mensajes::CausaMuerte::Vejez,
+        CausaMuerte::Enfermedad => mensajes::CausaMuerte::Enfermedad,
+        CausaMuerte::Hambre => mensajes::CausaMuerte::Hambre,
+        CausaMuerte::Caza => mensajes::CausaMuerte::Caza,
+        CausaMuerte::Sobrepoblacion => mensajes::CausaMuerte::Sobrepoblacion,
+        CausaMuerte::MortalidadClase => mensajes::CausaMuerte::MortalidadClase,
+    }
+}
+
+fn causa_desde_proto(valor: i32) -> io::Result<CausaMuerte> {
+    match mensajes::CausaMuerte::try_from(valor) {
+        Ok(mensajes::CausaMuerte::Vejez) => Ok(CausaMuerte::Vejez),
+        Ok(mensajes::CausaMuerte::Enfermedad) => Ok(CausaMuerte::Enfermedad),
+        Ok(mensajes::CausaMuerte::Hambre) => Ok(CausaMuerte::Hambre),
+        Ok(mensajes::CausaMuerte::Caza) => Ok(CausaMuerte::Caza),
+        Ok(mensajes::CausaMuerte::Sobrepoblacion) => Ok(CausaMuerte::Sobrepoblacion),
+        Ok(mensajes::CausaMuerte::MortalidadClase) => Ok(CausaMuerte::MortalidadClase),
+        Err(_) => Err(datos_invalidos(format!("causa de muerte desconocida: {}", valor))),
+    }
+}
+
+fn salud_a_proto(salud: Salud) -> mensajes::Salud {
+    match salud {
+        Salud::Susceptible => mensajes::Salud::Susceptible,
+        Salud::Infectada => mensajes::Salud::Infectada,
+        Salud::Recuperada => mensajes::Salud::Recuperada,
+    }
+}
+
+fn salud_desde_proto(valor: i32) -> io::Result<Salud> {
+    match mensajes::Salud::try_from(valor) {
+        Ok(mensajes::Salud::Susceptible) => Ok(Salud::Susceptible),
+        Ok(mensajes::Salud::Infectada) => Ok(Salud::Infectada),
+        Ok(mensajes::Salud::Recuperada) => Ok(Salud::Recuperada),
+        Err(_) => Err(datos_invalidos(format!("estado de salud desconocido: {}", valor))),
+    }
+}
+
+fn estrategia_a_proto(estrategia: EstrategiaCaza) -> mensajes::EstrategiaCaza {
+    match estrategia {
+        EstrategiaCaza::Pesada => mensajes::EstrategiaCaza::Pesada,
+        EstrategiaCaza::Cercana => mensajes::EstrategiaCaza::Cercana,
+        EstrategiaCaza::Debil => mensajes::EstrategiaCaza::Debil,
+        EstrategiaCaza::Aleatoria => mensajes::EstrategiaCaza::Aleatoria,
+        EstrategiaCaza::Vieja => mensajes::EstrategiaCaza::Vieja,
+    }
+}
+
+fn estrategia_desde_proto(valor: i32) -> io::Result<EstrategiaCaza> {
+    match mensajes::EstrategiaCaza::try_from(valor) {
+        Ok(mensajes::EstrategiaCaza::Pesada) => Ok(EstrategiaCaza::Pesada),
+        Ok(mensajes::EstrategiaCaza::Cercana) => Ok(EstrategiaCaza::Cercana),
+        Ok(mensajes::EstrategiaCaza::Debil) => Ok(EstrategiaCaza::Debil),
+        Ok(mensajes::EstrategiaCaza::Aleatoria) => Ok(EstrategiaCaza::Aleatoria),
+        Ok(mensajes::EstrategiaCaza::Vieja) => Ok(EstrategiaCaza::Vieja),
+        Err(_) => Err(datos_invalidos(format!("estrategia de caza desconocida: {}", valor))),
+    }
+}
+
+fn sexo_desde_proto(valor: i32) -> io::Result<Sexo> {
+    match mensajes::Sexo::try_from(valor) {
+        Ok(mensajes::Sexo::Macho) => Ok(Sexo::Macho),
+        Ok(mensajes::Sexo::Hembra) => Ok(Sexo::Hembra),
+        Err(_) => Err(datos_invalidos(format!("sexo desconocido: {}", valor))),
+    }
+}
+
+fn depredador_a_proto(depredador: &Depredador) -> mensajes::Depredador {
+    mensajes::Depredador {
+        reserva_comida_kg: depredador.reserva_comida_kg,
+        vivo: depredador.vivo,
+        despensa_kg: depredador.despensa_kg,
+        edad_dias: depredador.edad_dias,
+        sexo: match depredador.sexo {
+            Sexo::Macho => mensajes::Sexo::Macho,
+            Sexo::Hembra => mensajes::Sexo::Hembra,
+        } as i32,
+        posicion: depredador.posicion.map(|p| mensajes::Posicion { x: p.x, y: p.y }),
+    }
+}
+
+fn depredador_desde_proto(depredador: mensajes::Depredador) -> io::Result<Depredador> {
+    Ok(Depredador {
+        reserva_comida_kg: depredador.reserva_comida_kg,
+        vivo: depredador.vivo,
+        despensa_kg: depredador.despensa_kg,
+        edad_dias: depredador.edad_dias,
+        sexo: sexo_desde_proto(depredador.sexo)?,
+        posicion: depredador.posicion.map(|p| Posicion::new(p.x, p.y)),
+    })
+}
+
+fn rango_a_proto(rango: RangoCrias) -> Option<mensajes::RangoCrias> {
+    Some(mensajes::RangoCrias { min: rango.min, max: rango.max })
+}
+
+fn rango_desde_proto(rango: Option<mensajes::RangoCrias>) -> io::Result<RangoCrias> {
+    let rango = rango.ok_or_else(|| datos_invalidos("falta un rango de crías"))?;
+    Ok(RangoCrias { min: rango.min, max: rango.max })
+}
+
+fn curva_a_proto(curva: CurvaCrecimiento) -> Option<mensajes::CurvaCrecimiento> {
+    Some(mensajes::CurvaCrecimiento { peso_maximo_kg: curva.peso_maximo_kg, tasa: curva.tasa, inflexion_dias: curva.inflexion_dias })
+}
+
+fn curva_desde_proto(curva: Option<mensajes::CurvaCrecimiento>, por_defecto: (f64, f64, f64)) -> CurvaCrecimiento {
+    curva.map(|c| CurvaCrecimiento { peso_maximo_kg: c.peso_maximo_kg, tasa: c.tasa, inflexion_dias: c.inflexion_dias })
+        .unwrap_or_else(|| por_defecto.into())
+}
+
+fn factores_a_proto(f: FactoresEstacion) -> Option<mensajes::FactoresEstacion> {
+    Some(mensajes::FactoresEstacion { reproduccion: f.reproduccion, enfermedad: f.enfermedad, pasto: f.pasto })
+}
+
+fn factores_desde_proto(f: Option<mensajes::FactoresEstacion>) -> FactoresEstacion {
+    f.map(|f| FactoresEstacion { reproduccion: f.reproduccion, enfermedad: f.enfermedad, pasto: f.pasto }).unwrap_or_default()
+}
+
+fn dieta_a_proto(d: &Dieta) -> mensajes::Dieta {
+    mensajes::Dieta {
+        conejos: d.conejos,
+        cabras: d.cabras,
+        conejo_peso_minimo_kg: d.conejo_peso_minimo_kg,
+        cabra_peso_minimo_kg: d.cabra_peso_minimo_kg,
+        respetar_hembras_prenadas: d.respetar_hembras_prenadas,
+    }
+}
+
+fn dieta_desde_proto(d: mensajes::Dieta) -> Dieta {
+    Dieta {
+        conejos: d.conejos,
+        cabras: d.cabras,
+        conejo_peso_minimo_kg: d.conejo_peso_minimo_kg,
+        cabra_peso_minimo_kg: d.cabra_peso_minimo_kg,
+        respetar_hembras_prenadas: d.respetar_hembras_prenadas,
+    }
+}
+
+fn clases_a_proto(c: &ClasesEdad) -> mensajes::ClasesEdad {
+    let clase = |a: &AjustesClase| Some(mensajes::AjustesClase { vulnerabilidad: a.vulnerabilidad, mortalidad_diaria: a.mortalidad_diaria });
+    mensajes::ClasesEdad {
+        juvenil: clase(&c.juvenil),
+        subadulto: clase(&c.subadulto),
+        adulto: clase(&c.adulto),
+        senescente: clase(&c.senescente),
+    }
+}
+
+fn clases_desde_proto(c: mensajes::ClasesEdad) -> ClasesEdad {
+    let clase = |a: Option<mensajes::AjustesClase>| {
+        a.map(|a| AjustesClase { vulnerabilidad: a.vulnerabilidad, mortalidad_diaria: a.mortalidad_diaria }).unwrap_or_default()
+    };
+    ClasesEdad {
+        juvenil: clase(c.juvenil),
+        subadulto: clase(c.subadulto),
+        adulto: clase(c.adulto),
+        senescente: clase(c.senescente),
+    }
+}
+
+fn parametros_a_proto(p: &Parametros) -> mensajes::Parametros {
+    mensajes::Parametros {
+        n_conejos_inicial: p.n_conejos_inicial,
+        n_cabras_inicial: p.n_cabras_inicial,
+        depredador_reserva_inicial_kg: p.depredador_reserva_inicial_kg,
+        depredador_consumo_minimo_diario_kg: p.depredador_consumo_minimo_diario_kg,
+        depredador_consumo_optimo_diario_kg: p.depredador_consumo_optimo_diario_kg,
+        conejo_edad_maxima_dias: p.conejo_edad_maxima_dias,
+        conejo_edad_reproductiva_dias: p.conejo_edad_reproductiva_dias,
+        conejo_edad_sacrificio_dias: p.conejo_edad_sacrificio_dias,
+        conejo_tasa_reproduccion_diaria: p.conejo_tasa_reproduccion_diaria,
+        conejo_crias_por_parto: rango_a_proto(p.conejo_crias_por_parto),
+        conejo_crecimiento: curva_a_proto(p.conejo_crecimiento),
+        conejo_gestacion_dias: p.conejo_gestacion_dias,
+        cabra_edad_maxima_dias: p.cabra_edad_maxima_dias,
+        cabra_edad_reproductiva_dias: p.cabra_edad_reproductiva_dias,
+        cabra_edad_sacrificio_dias: p.cabra_edad_sacrificio_dias,
+        cabra_tasa_reproduccion_diaria: p.cabra_tasa_reproduccion_diaria,
+        cabra_crias_por_parto: rango_a_proto(p.cabra_crias_por_parto),
+        cabra_crecimiento: curva_a_proto(p.cabra_crecimiento),
+        cabra_gestacion_dias: p.cabra_gestacion_dias,
+        probabilidad_enfermar: p.probabilidad_enfermar,
+        probabilidad_nacer_macho: p.probabilidad_nacer_macho,
+        umbral_allee: p.umbral_allee,
+        epidemias: Some(mensajes::Epidemias {
+            umbral_densidad: p.epidemias.umbral_densidad,
+            probabilidad_brote: p.epidemias.probabilidad_brote,
+            mortalidad: p.epidemias.mortalidad,
+            duracion_dias: p.epidemias.duracion_dias,
+        }),
+        enfermedad: Some(mensajes::Enfermedad {
+            activa: p.enfermedad.activa,
+            transmision: p.enfermedad.transmision,
+            recuperacion: p.enfermedad.recuperacion,
+            mortalidad: p.enfermedad.mortalidad,
+            perdida_inmunidad: p.enfermedad.perdida_inmunidad,
+            introduccion: p.enfermedad.introduccion,
+            umbral_ola: p.enfermedad.umbral_ola,
+        }),
+        clima: Some(mensajes::Clima {
+            activo: p.clima.activo,
+            duracion_estacion_dias: p.clima.duracion_estacion_dias,
+            primavera: factores_a_proto(p.clima.primavera),
+            verano: factores_a_proto(p.clima.verano),
+            otono: factores_a_proto(p.clima.otono),
+            invierno: factores_a_proto(p.clima.invierno),
+        }),
+        clases_edad: Some(clases_a_proto(&p.clases_edad)),
+        depredador_ingesta_maxima_kg: p.depredador_ingesta_maxima_kg,
+        tasa_descomposicion_diaria: p.tasa_descomposicion_diaria,
+        energia: Some(mensajes::Energia {
+            mj_por_kg: p.energia.mj_por_kg,
+            eficiencia_pasto_presa: p.energia.eficiencia_pasto_presa,
+            eficiencia_asimilacion: p.energia.eficiencia_asimilacion,
+        }),
+        metabolismo: Some(mensajes::Metabolismo {
+            activo: p.metabolismo.activo,
+            masa_kg: p.metabolismo.masa_kg,
+            coeficiente_kg: p.metabolismo.coeficiente_kg,
+            exponente: p.metabolismo.exponente,
+            factor_optimo: p.metabolismo.factor_optimo,
+            coste_caza: p.metabolismo.coste_caza,
+        }),
+        cazabilidad: Some(mensajes::Cazabilidad {
+            activa: p.cazabilidad.activa,
+            cria: p.cazabilidad.cria,
+            adulto: p.cazabilidad.adulto,
+            vejez: p.cazabilidad.vejez,
+            peso_condicion: p.cazabilidad.peso_condicion,
+        }),
+        exito_caza: Some(mensajes::ExitoCaza {
+            activo: p.exito_caza.activo,
+            conejo: p.exito_caza.conejo,
+            cabra: p.exito_caza.cabra,
+            coste_fallo_kg: p.exito_caza.coste_fallo_kg,
+        }),
+        respuesta_funcional: Some(mensajes::RespuestaFuncional {
+            activa: p.respuesta_funcional.activa,
+            tasa_ataque: p.respuesta_funcional.tasa_ataque,
+            tiempo_manipulacion_dias: p.respuesta_funcional.tiempo_manipulacion_dias,
+        }),
+        cadena_trofica: Some(mensajes::CadenaTrofica {
+            activa: p.cadena_trofica.activa,
+            capacidad_kg: p.cadena_trofica.capacidad_kg,
+            pasto_inicial_kg: p.cadena_trofica.pasto_inicial_kg,
+            crecimiento_diario: p.cadena_trofica.crecimiento_diario,
+            refugio_kg: p.cadena_trofica.refugio_kg,
+            consumo_relativo: p.cadena_trofica.consumo_relativo,
+            mortalidad_hambre: p.cadena_trofica.mortalidad_hambre,
+            columnas: p.cadena_trofica.columnas,
+            filas: p.cadena_trofica.filas,
+            perdida_peso_hambre: p.cadena_trofica.perdida_peso_hambre,
+            recuperacion_peso_diaria: p.cadena_trofica.recuperacion_peso_diaria,
+            peso_minimo_relativo: p.cadena_trofica.peso_minimo_relativo,
+        }),
+        manada: Some(mensajes::Manada {
+            activa: p.manada.activa,
+            n_inicial: p.manada.n_inicial,
+            edad_maxima_dias: p.manada.edad_maxima_dias,
+            edad_reproductiva_dias: p.manada.edad_reproductiva_dias,
+            tasa_reproduccion_diaria: p.manada.tasa_reproduccion_diaria,
+            crias_por_parto: rango_a_proto(p.manada.crias_por_parto),
+            reserva_reproduccion_kg: p.manada.reserva_reproduccion_kg,
+            reserva_cria_kg: p.manada.reserva_cria_kg,
+        }),
+        espacio: Some(mensajes::Espacio {
+            activo: p.espacio.activo,
+            lado_m: p.espacio.lado_m,
+            paso_presa_m: p.espacio.paso_presa_m,
+            paso_depredador_m: p.espacio.paso_depredador_m,
+            radio_caza_m: p.espacio.radio_caza_m,
+            radio_apareamiento_m: p.espacio.radio_apareamiento_m,
+        }),
+        poblacion_maxima: p.poblacion_maxima,
+        subflujos_aleatorios: p.subflujos_aleatorios,
+        dieta_depredador: Some(dieta_a_proto(&p.dieta_depredador)),
+        estrategia_caza: estrategia_a_proto(p.estrategia_caza) as i32,
+    }
+}
+
+fn parametros_desde_proto(p: mensajes::Parametros) -> io::Result<Parametros> {
+    let parametros = Parametros {
+        n_conejos_inicial: p.n_conejos_inicial,
+        n_cabras_inicial: p.n_cabras_inicial,
+        depredador_reserva_inicial_kg: p.depredador_reserva_inicial_kg,
+        depredador_consumo_minimo_diario_kg: p.depredador_consumo_minimo_diario_kg,
+        depredador_consumo_optimo_diario_kg: p.depredador_consumo_optimo_diario_kg,
+        conejo_edad_maxima_dias: p.conejo_edad_maxima_dias,
+        conejo_edad_reproductiva_dias: p.conejo_edad_reproductiva_dias,
+        conejo_edad_sacrificio_dias: p.conejo_edad_sacrificio_dias,
+        conejo_tasa_reproduccion_diaria: p.conejo_tasa_reproduccion_diaria,
+        conejo_crias_por_parto: rango_desde_proto(p.conejo_crias_por_parto)?,
+        conejo_crecimiento: curva_desde_proto(p.conejo_crecimiento, CONEJO_CRECIMIENTO),
+        conejo_gestacion_dias: p.conejo_gestacion_dias,
+        cabra_edad_maxima_dias: p.cabra_edad_maxima_dias,
+        cabra_edad_reproductiva_dias: p.cabra_edad_reproductiva_dias,
+        cabra_edad_sacrificio_dias: p.cabra_edad_sacrificio_dias,
+        cabra_tasa_reproduccion_diaria: p.cabra_tasa_reproduccion_diaria,
+        cabra_crias_por_parto: rango_desde_proto(p.cabra_crias_por_parto)?,
+        cabra_crecimiento: curva_desde_proto(p.cabra_crecimiento, CABRA_CRECIMIENTO),
+        cabra_gestacion_dias: p.cabra_gestacion_dias,
+        probabilidad_enfermar: p.probabilidad_enfermar,
+        probabilidad_nacer_macho: p.probabilidad_nacer_macho,
+        umbral_allee: p.umbral_allee,
+        epidemias: p.epidemias.map(|e| Epidemias {
+            umbral_densidad: e.umbral_densidad,
+            probabilidad_brote: e.probabilidad_brote,
+            mortalidad: e.mortalidad,
+            duracion_dias: e.duracion_dias,
+        }).unwrap_or_default(),
+        enfermedad: p.enfermedad.map(|e| Enfermedad {
+            activa: e.activa,
+            transmision: e.transmision,
+            recuperacion: e.recuperacion,
+            mortalidad: e.mortalidad,
+            perdida_inmunidad: e.perdida_inmunidad,
+            introduccion: e.introduccion,
+            umbral_ola: e.umbral_ola,
+        }).unwrap_or_default(),
+        clima: p.clima.map(|c| Clima {
+            activo: c.activo,
+            duracion_estacion_dias: c.duracion_estacion_dias,
+            primavera: factores_desde_proto(c.primavera),
+            verano: factores_desde_proto(c.verano),
+            otono: factores_desde_proto(c.otono),
+            invierno: factores_desde_proto(c.invierno),
+        }).unwrap_or_default(),
+        clases_edad: p.clases_edad.map(clases_desde_proto).unwrap_or_default(),
+        depredador_ingesta_maxima_kg: p.depredador_ingesta_maxima_kg,
+        tasa_descomposicion_diaria: p.tasa_descomposicion_diaria,
+        energia: p.energia.map(|e| Energia {
+            mj_por_kg: e.mj_por_kg,
+            eficiencia_pasto_presa: e.eficiencia_pasto_presa,
+            eficiencia_asimilacion: e.eficiencia_asimilacion,
+        }).unwrap_or_default(),
+        metabolismo: p.metabolismo.map(|m| Metabolismo {
+            activo: m.activo,
+            masa_kg: m.masa_kg,
+            coeficiente_kg: m.coeficiente_kg,
+            exponente: m.exponente,
+            factor_optimo: m.factor_optimo,
+            coste_caza: m.coste_caza,
+        }).unwrap_or_default(),
+        cazabilidad: p.cazabilidad.map(|c| Cazabilidad {
+            activa: c.activa,
+            cria: c.cria,
+            adulto: c.adulto,
+            vejez: c.vejez,
+            peso_condicion: c.peso_condicion,
+        }).unwrap_or_default(),
+        exito_caza: p.exito_caza.map(|e| ExitoCaza {
+            activo: e.activo,
+            conejo: e.conejo,
+            cabra: e.cabra,
+            coste_fallo_kg: e.coste_fallo_kg,
+        }).unwrap_or_default(),
+        respuesta_funcional: p.respuesta_funcional.map(|r| RespuestaFuncional {
+            activa: r.activa,
+            tasa_ataque: r.tasa_ataque,
+            tiempo_manipulacion_dias: r.tiempo_manipulacion_dias,
+        }).unwrap_or_default(),
+        cadena_trofica: p.cadena_trofica.map(|c| CadenaTrofica {
+            activa: c.activa,
+            capacidad_kg: c.capacidad_kg,
+            pasto_inicial_kg: c.pasto_inicial_kg,
+            crecimiento_diario: c.crecimiento_diario,
+            refugio_kg: c.refugio_kg,
+            consumo_relativo: c.consumo_relativo,
+            mortalidad_hambre: c.mortalidad_hambre,
+            columnas: c.columnas,
+            filas: c.filas,
+            perdida_peso_hambre: c.perdida_peso_hambre,
+            recuperacion_peso_diaria: if c.recuperacion_peso_diaria == 0.0 { 1.0 } else { c.recuperacion_peso_diaria },
+            peso_minimo_relativo: c.peso_minimo_relativo,
+        }).unwrap_or(CadenaTrofica { activa: false, ..CadenaTrofica::default() }), // Archivos de antes del modo.
+        manada: match p.manada {
+            Some(m) => Manada {
+                activa: m.activa,
+                n_inicial: m.n_inicial,
+                edad_maxima_dias: m.edad_maxima_dias,
+                edad_reproductiva_dias: m.edad_reproductiva_dias,
+                tasa_reproduccion_diaria: m.tasa_reproduccion_diaria,
+                crias_por_parto: rango_desde_proto(m.crias_por_parto)?,
+                reserva_reproduccion_kg: m.reserva_reproduccion_kg,
+                reserva_cria_kg: m.reserva_cria_kg,
+            },
+            None => Manada::default(),
+        },
+        espacio: p.espacio.map(|e| Espacio {
+            activo: e.activo,
+            lado_m: e.lado_m,
+            paso_presa_m: e.paso_presa_m,
+            paso_depredador_m: e.paso_depredador_m,
+            radio_caza_m: e.radio_caza_m,
+            radio_apareamiento_m: e.radio_apareamiento_m,
+        }).unwrap_or_default(),
+        poblacion_maxima: if p.poblacion_maxima == 0 { POBLACION_MAXIMA } else { p.poblacion_maxima },
+        subflujos_aleatorios: p.subflujos_aleatorios,
+        dieta_depredador: p.dieta_depredador.map(dieta_desde_proto).unwrap_or_default(),
+        estrategia_caza: estrategia_desde_proto(p.estrategia_caza)?,
+    };
+    parametros.validar().map_err(|e| datos_invalidos(e.to_string()))?;
+    Ok(parametros)
+}
+
+fn balance_a_proto(b: &BalanceEnergia) -> mensajes::BalanceEnergia {
+    mensajes::BalanceEnergia {
+        forraje_mj: b.forraje_mj,
+        produccion_presas_mj: b.produccion_presas_mj,
+        muertes_presas_mj: b.muertes_presas_mj,
+        cazado_mj: b.cazado_mj,
+        asimilado_mj: b.asimilado_mj,
+        podrido_mj: b.podrido_mj,
+        metabolizado_mj: b.metabolizado_mj,
+    }
+}
+
+fn balance_desde_proto(b: mensajes::BalanceEnergia) -> BalanceEnergia {
+    BalanceEnergia {
+        forraje_mj: b.forraje_mj,
+        produccion_presas_mj: b.produccion_presas_mj,
+        muertes_presas_mj: b.muertes_presas_mj,
+        cazado_mj: b.cazado_mj,
+        asimilado_mj: b.asimilado_mj,
+        podrido_mj: b.podrido_mj,
+        metabolizado_mj: b.metabolizado_mj,
+    }
+}
+
+fn muertes_a_proto(m: &MuertesPorCausa) -> mensajes::MuertesPorCausa {
+    mensajes::MuertesPorCausa {
+        vejez: m.vejez,
+        enfermedad: m.enfermedad,
+        hambre: m.hambre,
+        caza: m.caza,
+        sobrepoblacion: m.sobrepoblacion,
+        mortalidad_clase: m.mortalidad_clase,
+    }
+}
+
+fn muertes_desde_proto(m: mensajes::MuertesPorCausa) -> MuertesPorCausa {
+    MuertesPorCausa {
+        vejez: m.vejez,
+        enfermedad: m.enfermedad,
+        hambre: m.hambre,
+        caza: m.caza,
+        sobrepoblacion: m.sobrepoblacion,
+        mortalidad_clase: m.mortalidad_clase,
+    }
+}
+
+fn alarma_a_proto(alarma: &Alarma, cumplida: bool) -> mensajes::Alarma {
+    mensajes::Alarma {
+        magnitud: match alarma.magnitud {
+            Magnitud::Conejos => mensajes::Magnitud::Conejos,
+            Magnitud::Cabras => mensajes::Magnitud::Cabras,
+            Magnitud::Poblacion => mensajes::Magnitud::Poblacion,
+            Magnitud::ReservaDepredadorKg => mensajes::Magnitud::ReservaDepredadorKg,
+        } as i32,
+        comparacion: match alarma.comparacion {
+            Comparacion::Menor => mensajes::Comparacion::Menor,
+            Comparacion::Mayor => mensajes::Comparacion::Mayor,
+        } as i32,
+        umbral: alarma.umbral,
+        cumplida,
+    }
+}
+
+fn alarma_desde_proto(alarma: mensajes::Alarma) -> io::Result<(Alarma, bool)> {
+    let magnitud = match mensajes::Magnitud::try_from(alarma.magnitud) {
+        Ok(mensajes::Magnitud::Conejos) => Magnitud::Conejos,
+        Ok(mensajes::Magnitud::Cabras) => Magnitud::Cabras,
+        Ok(mensajes::Magnitud::Poblacion) => Magnitud::Poblacion,
+        Ok(mensajes::Magnitud::ReservaDepredadorKg) => Magnitud::ReservaDepredadorKg,
+        Err(_) => return Err(datos_invalidos(format!("magnitud de alarma desconocida: {}", alarma.magnitud))),
+    };
+    let comparacion = match mensajes::Comparacion::try_from(alarma.comparacion) {
+        Ok(mensajes::Comparacion::Menor) => Comparacion::Menor,
+        Ok(mensajes::Comparacion::Mayor) => Comparacion::Mayor,
+        Err(_) => return Err(datos_invalidos(format!("comparación de alarma desconocida: {}", alarma.comparacion))),
+    };
+    Ok((Alarma { magnitud, comparacion, umbral: alarma.umbral }, alarma.cumplida))
+}
+
+fn rng_a_proto(rng: &ChaCha8Rng) -> mensajes::EstadoRng {
+    let posicion = rng.get_word_pos();
+    mensajes::EstadoRng {
+        semilla: rng.get_seed().to_vec(),
+        flujo: rng.get_stream(),
+        posicion_alta: (posicion >> 64) as u64,
+        posicion_baja: posicion as u64,
+    }
+}
+
+fn rng_desde_proto(estado: mensajes::EstadoRng) -> io::Result<ChaCha8Rng> {
+    let semilla: [u8; 32] = estado.semilla.try_into()
+        .map_err(|_| datos_invalidos("la semilla del RNG debe tener 32 bytes"))?;
+    let mut rng = ChaCha8Rng::from_seed(semilla);
+    rng.set_stream(estado.flujo);
+    rng.set_word_pos(((estado.posicion_alta as u128) << 64) | estado.posicion_baja as u128);
+    Ok(rng)
+}
+
+impl Instantanea {
+    /// Convierte la instantánea en su mensaje protobuf.
+    pub fn a_mensaje(&self) -> mensajes::Instantanea {
+        mensajes::Instantanea {
+            version: self.version,
+            semilla: self.semilla,
+            dia: self.dia,
+            next_id: self.next_id,
+            depredador: Some(depredador_a_proto(&self.depredador)),
+            parametros: Some(parametros_a_proto(&self.parametros)),
+            presas: self.presas.iter().map(|p| mensajes::Presa {
+                id: p.id,
+                especie: especie_a_proto(p.especie) as i32,
+                sexo: match p.sexo {
+                    Sexo::Macho => mensajes::Sexo::Macho,
+                    Sexo::Hembra => mensajes::Sexo::Hembra,
+                } as i32,
+                edad_dias: p.edad_dias,
+                peso_kg: p.peso_kg,
+                vivo: p.vivo,
+                gestacion: p.gestacion.map(|g| mensajes::Gestacion { dias_restantes: g.dias_restantes, crias: g.crias }),
+            }).collect(),
+            rng: Some(rng_a_proto(&self.rng)),
+            dias_brote_conejos: self.dias_brote[0],
+            dias_brote_cabras: self.dias_brote[1],
+            pastizal: Some(mensajes::Pastizal {
+                columnas: self.pastizal.columnas,
+                filas: self.pastizal.filas,
+                celdas: self.pastizal.celdas.clone(),
+            }),
+            celdas_presas: self.celdas_presas.iter().map(|(&id, &celda)| (id, celda)).collect(),
+            posiciones_presas: self.posiciones_presas.iter().map(|(&id, p)| (id, mensajes::Posicion { x: p.x, y: p.y })).collect(),
+            manada: self.manada.iter().map(depredador_a_proto).collect(),
+            salud_presas: self.salud_presas.iter().map(|(&id, &salud)| (id, salud_a_proto(salud) as i32)).collect(),
+            ola_conejos: self.olas[0],
+            ola_cabras: self.olas[1],
+            balance_corrida: Some(balance_a_proto(&self.balance_corrida)),
+            muertes_corrida: Some(muertes_a_proto(&self.muertes_corrida)),
+            alarmas: self.alarmas.iter().enumerate()
+                .map(|(i, alarma)| alarma_a_proto(alarma, self.alarmas_cumplidas.get(i).copied().unwrap_or(false)))
+                .collect(),
+            retencion: Some(mensajes::Retencion {
+                intervalo_dias: self.retencion.intervalo_dias,
+                horizonte_dias: self.retencion.horizonte_dias,
+                presupuesto_bytes: self.retencion.presupuesto_bytes as u64,
+            }),
+        }
+    }
+
+    /// Reconstruye la instantánea desde su mensaje protobuf, rechazando versiones desconocidas.
+    pub fn desde_mensaje(mensaje: mensajes::Instantanea) -> io::Result<Self> {
+        // La versión 1 es compatible en protobuf: los ids uint32 se leen como uint64.
+        if !(1..=VERSION_INSTANTANEA).contains(&mensaje.version) {
+            return Err(datos_invalidos(format!("versión de instantánea no soportada: {}", mensaje.version)));
+        }
+        let depredador = mensaje.depredador.ok_or_else(|| datos_invalidos("falta el depredador"))?;
+        let presas = mensaje.presas.into_iter()
+            .map(|p| Ok(EstadoPresa {
+                id: p.id,
+                especie: especie_desde_proto(p.especie)?,
+                sexo: sexo_desde_proto(p.sexo)?,
+                edad_dias: p.edad_dias,
+                peso_kg: p.peso_kg,
+                vivo: p.vivo,
+                gestacion: p.gestacion.map(|g| Gestacion { dias_restantes: g.dias_restantes, crias: g.crias }),
+            }))
+            .collect::<io::Result<Vec<_>>>()?;
+        let (alarmas, alarmas_cumplidas) = mensaje.alarmas.into_iter()
+            .map(alarma_desde_proto)
+            .collect::<io::Result<(Vec<_>, Vec<_>)>>()?;
+        Ok(Self {
+            version: VERSION_INSTANTANEA,
+            semilla: mensaje.semilla,
+            dia: mensaje.dia,
+            next_id: mensaje.next_id,
+            depredador: depredador_desde_proto(depredador)?,
+            parametros: parametros_desde_proto(mensaje.parametros.ok_or_else(|| datos_invalidos("faltan los parámetros"))?)?,
+            presas,
+            rng: rng_desde_proto(mensaje.rng.ok_or_else(|| datos_invalidos("falta el estado del RNG"))?)?,
+            dias_brote: [mensaje.dias_brote_conejos, mensaje.dias_brote_cabras],
+            pastizal: mensaje.pastizal.map(|p| Pastizal { columnas: p.columnas, filas: p.filas, celdas: p.celdas }).unwrap_or_default(),
+            celdas_presas: mensaje.celdas_presas.into_iter().collect(),
+            posiciones_presas: mensaje.posiciones_presas.into_iter().map(|(id, p)| (id, Posicion::new(p.x, p.y))).collect(),
+            manada: mensaje.manada.into_iter().map(depredador_desde_proto).collect::<io::Result<_>>()?,
+            salud_presas: mensaje.salud_presas.into_iter().map(|(id, salud)| Ok((id, salud_desde_proto(salud)?))).collect::<io::Result<_>>()?,
+            olas: [mensaje.ola_conejos, mensaje.ola_cabras],
+            balance_corrida: mensaje.balance_corrida.map(balance_desde_proto).unwrap_or_default(),
+            muertes_corrida: mensaje.muertes_corrida.map(muertes_desde_proto).unwrap_or_default(),
+            alarmas,
+            alarmas_cumplidas,
+            retencion: mensaje.retencion.map_or_else(Retencion::default, |r| Retencion {
+                intervalo_dias: r.intervalo_dias,
+                horizonte_dias: r.horizonte_dias,
+                presupuesto_bytes: usize::try_from(r.presupuesto_bytes).unwrap_or(usize::MAX),
+            }),
+        })
+    }
+
+    /// Codifica la instantánea en bytes protobuf.
+    pub fn a_protobuf(&self) -> Vec<u8> {
+        self.a_mensaje().encode_to_vec()
+    }
+
+    /// Decodifica una instantánea desde bytes protobuf.
+    pub fn desde_protobuf(bytes: &[u8]) -> io::Result<Self> {
+        let mensaje = mensajes::Instantanea::decode(bytes).map_err(|e| datos_invalidos(e.to_string()))?;
+        Self::desde_mensaje(mensaje)
+    }
+
+    /// Escribe la instantánea en un archivo protobuf.
+    pub fn guardar_protobuf(&self, ruta: impl AsRef<Path>) -> io::Result<()> {
+        fs::write(ruta, self.a_protobuf())
+    }
+
+    /// Lee una instantánea desde un archivo protobuf.
+    pub fn cargar_protobuf(ruta: impl AsRef<Path>) -> io::Result<Self> {
+        Self::desde_protobuf(&fs::read(ruta)?)
+    }
+}
+
+// --- Telemetría ---
+
+/// Codifica las métricas de un día.
+pub fn codificar_estadisticas(fila: &RegistroDiario) -> Vec<u8> {
+    mensajes::EstadisticasDia {
+        dia: fila.dia,
+        conejos: fila.conejos as u64,
+        cabras: fila.cabras as u64,
+        reserva_depredador_kg: fila.reserva_depredador_kg,
+        nacimientos: fila.nacimientos,
+        muertes: fila.muertes,
+        cazas: fila.cazas,
+        pasto_kg: fila.pasto_kg,
+        presion_depredacion: fila.presion_depredacion,
+        presion_pastoreo: fila.presion_pastoreo,
+        indice_control: fila.indice_control,
+    }.encode_to_vec()
+}
+
+/// Decodifica las métricas de un día.
+pub fn decodificar_estadisticas(bytes: &[u8]) -> io::Result<RegistroDiario> {
+    let m = mensajes::EstadisticasDia::decode(bytes).map_err(|e| datos_invalidos(e.to_string()))?;
+    Ok(RegistroDiario {
+        dia: m.dia,
+        conejos: m.conejos as usize,
+        cabras: m.cabras as usize,
+        reserva_depredador_kg: m.reserva_depredador_kg,
+        nacimientos: m.nacimientos,
+        muertes: m.muertes,
+        cazas: m.cazas,
+        pasto_kg: m.pasto_kg,
+        presion_depredacion: m.presion_depredacion,
+        presion_pastoreo: m.presion_pastoreo,
+        indice_control: m.indice_control,
+    })
+}
+
+fn evento_a_proto(evento: &Evento) -> mensajes::Evento {
+    use mensajes::evento::{self, Tipo};
+    let tipo = match evento {
+        Evento::Extincion { especie } => Tipo::Extincion(evento::Extincion { especie: especie_a_proto(*especie) as i32 }),
+        Evento::DepredadorEnPeligro { reserva_kg } => Tipo::DepredadorEnPeligro(evento::DepredadorEnPeligro { reserva_kg: *reserva_kg }),
+        Evento::MuerteDepredador => Tipo::MuerteDepredador(evento::MuerteDepredador {}),
+        Evento::ParametroCambiado { nombre, anterior, nuevo } => Tipo::ParametroCambiado(evento::ParametroCambiado {
+            nombre: nombre.clone(),
+            anterior: anterior.clone(),
+            nuevo: nuevo.clone(),
+        }),
+        Evento::PresaAgregada { id, especie } => Tipo::PresaAgregada(evento::PresaAgregada { id: *id, especie: especie_a_proto(*especie) as i32 }),
+        Evento::PresaRetirada { id, especie, peso_kg } => Tipo::PresaRetirada(evento::PresaRetirada {
+            id: *id,
+            especie: especie_a_proto(*especie) as i32,
+            peso_kg: *peso_kg,
+        }),
+        Evento::DepredadorReubicado { x, y } => Tipo::DepredadorReubicado(evento::DepredadorReubicado { x: *x, y: *y }),
+        Evento::AlarmaDisparada { alarma } => Tipo::AlarmaDisparada(evento::AlarmaDisparada { alarma: alarma.clone() }),
+        Evento::BroteEpidemia { especie } => Tipo::BroteEpidemia(evento::BroteEpidemia { especie: especie_a_proto(*especie) as i32 }),
+        Evento::OlaEpidemica { especie, infectadas } => Tipo::OlaEpidemica(evento::OlaEpidemica {
+            especie: especie_a_proto(*especie) as i32,
+            infectadas: *infectadas,
+        }),
+        Evento::MortalidadEmergencia { poblacion, tope, muertes } => Tipo::MortalidadEmergencia(evento::MortalidadEmergencia {
+            poblacion: *poblacion,
+            tope: *tope,
+            muertes: *muertes,
+        }),
+        Evento::Caza { id, especie, edad_dias, peso_kg, celda, posicion } => Tipo::Caza(evento::Caza {
+            id: *id,
+            especie: especie_a_proto(*especie) as i32,
+            edad_dias: *edad_dias,
+            peso_kg: *peso_kg,
+            celda: *celda,
+            posicion: Some(mensajes::Posicion { x: posicion.x, y: posicion.y }),
+        }),
+        Evento::Nacimiento { madre, especie, crias } => Tipo::Nacimiento(evento::Nacimiento {
+            madre: *madre,
+            especie: especie_a_proto(*especie) as i32,
+            crias: crias.clone(),
+        }),
+        Evento::MuertePresa { id, especie, edad_dias, causa } => Tipo::MuertePresa(evento::MuertePresa {
+            id: *id,
+            especie: especie_a_proto(*especie) as i32,
+            edad_dias: *edad_dias,
+            causa: causa_a_proto(*causa) as i32,
+        }),
+    };
+    mensajes::Evento { tipo: Some(tipo) }
+}
+
+fn evento_desde_proto(evento: mensajes::Evento) -> io::Result<Evento> {
+    use mensajes::evento::Tipo;
+    match evento.tipo.ok_or_else(|| datos_invalidos("evento sin tipo"))? {
+        Tipo::Extincion(e) => Ok(Evento::Extincion { especie: especie_desde_proto(e.especie)? }),
+        Tipo::DepredadorEnPeligro(e) => Ok(Evento::DepredadorEnPeligro { reserva_kg: e.reserva_kg }),
+        Tipo::MuerteDepredador(_) => Ok(Evento::MuerteDepredador),
+        Tipo::ParametroCambiado(e) => Ok(Evento::ParametroCambiado { nombre: e.nombre, anterior: e.anterior, nuevo: e.nuevo }),
+        Tipo::PresaAgregada(e) => Ok(Evento::PresaAgregada { id: e.id, especie: especie_desde_proto(e.especie)? }),
+        Tipo::PresaRetirada(e) => Ok(Evento::PresaRetirada { id: e.id, especie: especie_desde_proto(e.especie)?, peso_kg: e.peso_kg }),
+        Tipo::DepredadorReubicado(e) => Ok(Evento::DepredadorReubicado { x: e.x, y: e.y }),
+        Tipo::AlarmaDisparada(e) => Ok(Evento::AlarmaDisparada { alarma: e.alarma }),
+        Tipo::BroteEpidemia(e) => Ok(Evento::BroteEpidemia { especie: especie_desde_proto(e.especie)? }),
+        Tipo::OlaEpidemica(e) => Ok(Evento::OlaEpidemica { especie: especie_desde_proto(e.especie)?, infectadas: e.infectadas }),
+        Tipo::MortalidadEmergencia(e) => Ok(Evento::MortalidadEmergencia { poblacion: e.poblacion, tope: e.tope, muertes: e.muertes }),
+        Tipo::Caza(e) => Ok(Evento::Caza {
+            id: e.id,
+            especie: especie_desde_proto(e.especie)?,
+            edad_dias: e.edad_dias,
+            peso_kg: e.peso_kg,
+            celda: e.celda,
+            posicion: e.posicion.map(|p| Posicion::new(p.x, p.y)).unwrap_or_default(),
+        }),
+        Tipo::Nacimiento(e) => Ok(Evento::Nacimiento { madre: e.madre, especie: especie_desde_proto(e.especie)?, crias: e.crias }),
+        Tipo::MuertePresa(e) => Ok(Evento::MuertePresa {
+            id: e.id,
+            especie: especie_desde_proto(e.especie)?,
+            edad_dias: e.edad_dias,
+            causa: causa_desde_proto(e.causa)?,
+        }),
+    }
+}
+
+/// Codifica un evento del día indicado, con su descripción legible.
+pub fn codificar_evento(dia: u32, evento: &Evento) -> Vec<u8> {
+    mensajes::EventoDia { dia, descripcion: evento.descripcion(), evento: Some(evento_a_proto(evento)) }.encode_to_vec()
+}
+
+/// Decodifica un evento y el día en que ocurrió.
+pub fn decodificar_evento(bytes: &[u8]) -> io::Result<(u32, Evento)> {
+    let mensaje = mensajes::EventoDia::decode(bytes).map_err(|e| datos_invalidos(e.to_string()))?;
+    let evento = evento_desde_proto(mensaje.evento.ok_or_else(|| datos_invalidos("falta el evento"))?)?;
+    Ok((mensaje.dia, evento))
+}
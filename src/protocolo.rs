@@ -0,0 +1,191 @@
+// src/protocolo.rs
+
+// Protocolo de líneas JSON (NDJSON) para controlar la simulación como subproceso.
+// Cada línea de entrada es un comando y cada comando recibe exactamente una línea
+// de respuesta, así que cualquier lenguaje con stdin/stdout puede manejar el motor.
+//
+// Comandos:
+//   {"cmd":"step","dias":10}
+//   {"cmd":"get_state"}
+//   {"cmd":"set_param","nombre":"CONEJO_TASA_REPRODUCCION_DIARIA","valor":0.08}
+//   {"cmd":"set_param","especie":"Cabra","nombre":"TASA_REPRODUCCION_DIARIA","valor":0.02}
+//   {"cmd":"spawn","especie":"Conejo","cantidad":5}
+//   {"cmd":"rewind","dia":120}
+//   {"cmd":"run_until","evento":"depredador_en_peligro","max_dias":1000}
+//   {"cmd":"summary"}   resumen en frases (ver `Simulacion::resumen_texto`) en el campo "resumen"
+
+use std::io::{self, BufRead, Write};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::entidades::Especie;
+use crate::eventos::{Evento, TipoEvento};
+use crate::simulacion::{EstadisticasDia, Simulacion};
+
+/// Un comando recibido por la entrada estándar.
+#[derive(Debug, Deserialize)]
+#[serde(tag = "cmd", rename_all = "snake_case")]
+pub enum Comando {
+    Step {
+        #[serde(default = "un_dia")]
+        dias: u32,
+    },
+    GetState,
+    /// Cambia un parámetro en caliente (ver `Simulacion::establecer_parametro`).
+    SetParam {
+        #[serde(default)]
+        especie: Option<Especie>,
+        nombre: String,
+        valor: Value,
+    },
+    Spawn { especie: Especie, cantidad: u32 },
+    /// Vuelve a un día reciente (ver `Simulacion::retroceder_a`).
+    Rewind { dia: u32 },
+    /// Avanza hasta un evento (ver `Simulacion::ejecutar_hasta_evento`).
+    RunUntil {
+        evento: TipoEvento,
+        #[serde(default = "mil_dias")]
+        max_dias: u32,
+    },
+    /// Devuelve, además del estado, el resumen en texto para lectores de pantalla.
+    Summary,
+}
+
+fn un_dia() -> u32 { 1 }
+
+fn mil_dias() -> u32 { 1000 }
+
+/// Resumen del estado que se devuelve tras cada comando exitoso.
+#[derive(Debug, Serialize)]
+pub struct Estado {
+    pub dia: u32,
+    pub conejos: usize,
+    pub cabras: usize,
+    pub reserva_depredador_kg: f64,
+    pub depredador_vivo: bool,
+    pub nacimientos: u32,
+    pub muertes: u32,
+    pub cazas: u32,
+    /// Eventos notables, sin los nacimientos y muertes de cada presa (ver `Evento::es_demografico`).
+    pub eventos: Vec<Evento>,
+}
+
+impl Estado {
+    pub fn capturar(sim: &Simulacion) -> Self {
+        let (conejos, cabras) = sim.contar_especies();
+        let EstadisticasDia { nacimientos, muertes, cazas, .. } = sim.estadisticas_dia;
+        Self {
+            dia: sim.dia,
+            conejos,
+            cabras,
+            reserva_depredador_kg: sim.depredador.reserva_comida_kg,
+            depredador_vivo: sim.depredador.vivo,
+            nacimientos,
+            muertes,
+            cazas,
+            eventos: sim.eventos_dia.iter().filter(|e| !e.es_demografico()).cloned().collect(),
+        }
+    }
+}
+
+/// Respuesta a un comando: el estado resultante o un mensaje de error.
+#[derive(Debug, Serialize)]
+pub struct Respuesta {
+    pub ok: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub estado: Option<Estado>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ids: Option<Vec<u64>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub resumen: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl Respuesta {
+    fn exito(sim: &Simulacion) -> Self {
+        Self { ok: true, estado: Some(Estado::capturar(sim)), ids: None, resumen: None, error: None }
+    }
+
+    fn fallo(error: String) -> Self {
+        Self { ok: false, estado: None, ids: None, resumen: None, error: Some(error) }
+    }
+}
+
+/// Ejecuta un comando sobre la simulación.
+pub fn ejecutar(sim: &mut Simulacion, comando: Comando) -> Respuesta {
+    match comando {
+        Comando::Step { dias } => {
+            // Los eventos se acumulan para no perder los de días intermedios.
+            let mut eventos = Vec::new();
+            for _ in 0..dias {
+                sim.avanzar_dia();
+                eventos.extend(sim.eventos_dia.iter().filter(|e| !e.es_demografico()).cloned());
+            }
+            let mut respuesta = Respuesta::exito(sim);
+            if let Some(estado) = &mut respuesta.estado {
+                estado.eventos = eventos;
+            }
+            respuesta
+        }
+        Comando::GetState => Respuesta::exito(sim),
+        Comando::SetParam { especie, nombre, valor } => match sim.establecer_parametro(especie, &nombre, valor) {
+            Ok(()) => Respuesta::exito(sim),
+            Err(error) => Respuesta::fallo(error),
+        },
+        Comando::Spawn { especie, cantidad } => {
+            let ids = sim.generar_presas(especie, cantidad);
+            Respuesta { ids: Some(ids), ..Respuesta::exito(sim) }
+        }
+        Comando::RunUntil { evento, max_dias } => {
+            let dia_inicial = sim.dia;
+            match sim.ejecutar_hasta_evento(evento, max_dias) {
+                Some(_) => Respuesta::exito(sim),
+                None => Respuesta::fallo(format!(
+                    "no ocurrió {:?} en {} días (día actual: {})", evento, sim.dia - dia_inicial, sim.dia
+                )),
+            }
+        }
+        Comando::Rewind { dia } => match sim.retroceder_a(dia) {
+            Ok(()) => Respuesta::exito(sim),
+            Err(error) => Respuesta::fallo(error),
+        },
+        Comando::Summary => Respuesta { resumen: Some(sim.resumen_texto(&[])), ..Respuesta::exito(sim) },
+    }
+}
+
+/// Procesa una línea de texto: la interpreta como comando y devuelve la respuesta en JSON.
+pub fn procesar_linea(sim: &mut Simulacion, linea: &str) -> String {
+    let respuesta = match serde_json::from_str::<Comando>(linea) {
+        Ok(comando) => ejecutar(sim, comando),
+        Err(e) => Respuesta::fallo(format!("comando inválido: {}", e)),
+    };
+    serde_json::to_string(&respuesta).unwrap_or_else(|e| format!("{{\"ok\":false,\"error\":\"{}\"}}", e))
+}
+
+/// Atiende comandos línea a línea hasta que se cierre la entrada.
+pub fn atender(sim: &mut Simulacion, entrada: impl BufRead, mut salida: impl Write) -> io::Result<()> {
+    for linea in entrada.lines() {
+        let linea = linea?;
+        if linea.trim().is_empty() {
+            continue;
+        }
+        writeln!(salida, "{}", procesar_linea(sim, &linea))?;
+        salida.flush()?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn los_pasos_no_devuelven_los_eventos_de_cada_presa() {
+        let mut sim = Simulacion::con_semilla(8);
+        let respuesta = ejecutar(&mut sim, Comando::Step { dias: 60 });
+        let eventos = respuesta.estado.unwrap().eventos;
+        assert!(eventos.iter().all(|e| !e.es_demografico()), "{:?}", eventos);
+    }
+}
@@ -0,0 +1,330 @@
+// src/publicacion.rs
+
+// Publicación opcional de la simulación en un broker de mensajería (NATS o MQTT).
+// Cada día se envían las estadísticas y los eventos notables como JSON (o como protobuf,
+// con la feature `protobuf`), de modo que varios tableros o grabadores puedan consumir
+// una misma corrida larga.
+//
+// Ambos protocolos se implementan a mano sobre `TcpStream` (solo lo necesario para
+// publicar sin confirmación), para no arrastrar un runtime asíncrono al motor.
+
+use std::io::{self, Read, Write};
+use std::net::TcpStream;
+
+use serde::Serialize;
+
+use crate::eventos::Evento;
+use crate::registro::RegistroDiario;
+use crate::simulacion::Simulacion;
+
+/// Un broker capaz de publicar mensajes en un tema.
+pub trait Broker {
+    /// Publica una carga útil en el tema indicado.
+    fn publicar(&mut self, tema: &str, carga: &[u8]) -> io::Result<()>;
+    /// Separador jerárquico de temas del protocolo ("." en NATS, "/" en MQTT).
+    fn separador(&self) -> char;
+}
+
+// --- Cliente NATS ---
+
+/// Cliente mínimo del protocolo de texto de NATS.
+pub struct ClienteNats {
+    conexion: TcpStream,
+    /// Lo recibido tras la última línea completa: un `PING` puede llegar partido en dos lecturas.
+    pendiente: Vec<u8>,
+}
+
+impl ClienteNats {
+    /// Se conecta a `host:puerto` y completa el saludo `INFO`/`CONNECT`.
+    pub fn conectar(direccion: &str) -> io::Result<Self> {
+        let mut conexion = TcpStream::connect(direccion)?;
+        let mut saludo = [0u8; 4096];
+        let leidos = conexion.read(&mut saludo)?;
+        if !saludo[..leidos].starts_with(b"INFO") {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "el servidor no respondió con INFO"));
+        }
+        conexion.write_all(b"CONNECT {\"verbose\":false,\"pedantic\":false,\"name\":\"simulador\"}\r\n")?;
+        // Lo que llegó detrás del INFO se atiende con los PING.
+        let pendiente = saludo[..leidos].windows(2).position(|w| w == b"\r\n").map_or(Vec::new(), |fin| saludo[fin + 2..leidos].to_vec());
+        Ok(Self { conexion, pendiente })
+    }
+
+    /// Atiende lo que el servidor haya enviado: responde a cada `PING` para que no cierre la
+    /// conexión y convierte un `-ERR` en error.
+    fn atender_pings(&mut self) -> io::Result<()> {
+        self.conexion.set_nonblocking(true)?;
+        let mut buffer = [0u8; 1024];
+        let leido = loop {
+            match self.conexion.read(&mut buffer) {
+                Ok(0) => break Err(io::Error::new(io::ErrorKind::ConnectionAborted, "el servidor cerró la conexión")),
+                Ok(n) => self.pendiente.extend_from_slice(&buffer[..n]),
+                Err(e) if e.kind() == io::ErrorKind::WouldBlock => break Ok(()),
+                Err(e) => break Err(e),
+            }
+        };
+        self.conexion.set_nonblocking(false)?;
+        // Las líneas completas se atienden aunque el servidor haya cerrado después: suele cerrar tras un -ERR.
+        while let Some(fin) = self.pendiente.windows(2).position(|w| w == b"\r\n") {
+            let linea: Vec<u8> = self.pendiente.drain(..fin + 2).take(fin).collect();
+            if linea == b"PING" {
+                self.conexion.write_all(b"PONG\r\n")?;
+            } else if let Some(motivo) = linea.strip_prefix(b"-ERR") {
+                let motivo = String::from_utf8_lossy(motivo);
+                return Err(io::Error::other(format!("el servidor NATS respondió con un error: {}", motivo.trim())));
+            }
+        }
+        leido
+    }
+}
+
+impl Broker for ClienteNats {
+    fn publicar(&mut self, tema: &str, carga: &[u8]) -> io::Result<()> {
+        self.atender_pings()?;
+        self.conexion.write_all(format!("PUB {} {}\r\n", tema, carga.len()).as_bytes())?;
+        self.conexion.write_all(carga)?;
+        self.conexion.write_all(b"\r\n")
+    }
+
+    fn separador(&self) -> char { '.' }
+}
+
+// --- Cliente MQTT ---
+
+/// Cliente mínimo de MQTT 3.1.1 que publica con QoS 0.
+pub struct ClienteMqtt {
+    conexion: TcpStream,
+}
+
+impl ClienteMqtt {
+    /// Se conecta a `host:puerto`, envía `CONNECT` y espera un `CONNACK` aceptado.
+    pub fn conectar(direccion: &str) -> io::Result<Self> {
+        let mut conexion = TcpStream::connect(direccion)?;
+
+        let mut variable = Vec::new();
+        escribir_cadena_mqtt(&mut variable, "MQTT");
+        variable.push(4); // Nivel de protocolo 3.1.1.
+        variable.push(0x02); // Sesión limpia.
+        variable.extend_from_slice(&0u16.to_be_bytes()); // Sin keep-alive.
+        escribir_cadena_mqtt(&mut variable, &format!("simulador-{}", std::process::id()));
+        conexion.write_all(&paquete_mqtt(0x10, &variable))?;
+
+        let mut connack = [0u8; 4];
+        conexion.read_exact(&mut connack)?;
+        if connack[0] != 0x20 || connack[3] != 0 {
+            return Err(io::Error::new(io::ErrorKind::ConnectionRefused, format!("CONNACK rechazado (código {})", connack[3])));
+        }
+        Ok(Self { conexion })
+    }
+}
+
+impl Broker for ClienteMqtt {
+    fn publicar(&mut self, tema: &str, carga: &[u8]) -> io::Result<()> {
+        let mut cuerpo = Vec::new();
+        escribir_cadena_mqtt(&mut cuerpo, tema);
+        cuerpo.extend_from_slice(carga);
+        self.conexion.write_all(&paquete_mqtt(0x30, &cuerpo))
+    }
+
+    fn separador(&self) -> char { '/' }
+}
+
+/// Escribe una cadena MQTT: longitud de 2 bytes seguida de los bytes UTF-8.
+fn escribir_cadena_mqtt(destino: &mut Vec<u8>, texto: &str) {
+    destino.extend_from_slice(&(texto.len() as u16).to_be_bytes());
+    destino.extend_from_slice(texto.as_bytes());
+}
+
+/// Arma un paquete MQTT con su cabecera fija y la longitud restante codificada en base 128.
+fn paquete_mqtt(tipo: u8, cuerpo: &[u8]) -> Vec<u8> {
+    let mut paquete = vec![tipo];
+    let mut restante = cuerpo.len();
+    loop {
+        let mut byte = (restante % 128) as u8;
+        restante /= 128;
+        if restante > 0 {
+            byte |= 0x80;
+        }
+        paquete.push(byte);
+        if restante == 0 {
+            break;
+        }
+    }
+    paquete.extend_from_slice(cuerpo);
+    paquete
+}
+
+// --- Publicador de la simulación ---
+
+/// Mensaje publicado para cada evento notable.
+#[derive(Serialize)]
+struct MensajeEvento<'a> {
+    dia: u32,
+    descripcion: String,
+    evento: &'a Evento,
+}
+
+/// Codificación de las cargas publicadas.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Formato {
+    #[default]
+    Json,
+    /// Mensajes `EstadisticasDia` y `EventoDia` de `proto/instantanea.proto`.
+    #[cfg(feature = "protobuf")]
+    Protobuf,
+}
+
+impl Formato {
+    /// Interpreta el nombre de un formato (`json` o `protobuf`).
+    pub fn desde_nombre(nombre: &str) -> Result<Self, String> {
+        match nombre {
+            "json" => Ok(Formato::Json),
+            #[cfg(feature = "protobuf")]
+            "protobuf" => Ok(Formato::Protobuf),
+            #[cfg(not(feature = "protobuf"))]
+            "protobuf" => Err("el formato protobuf requiere compilar con la feature `protobuf`".to_string()),
+            otro => Err(format!("formato desconocido: {} (use json o protobuf)", otro)),
+        }
+    }
+}
+
+/// Publica las estadísticas y eventos diarios bajo un tema raíz:
+/// `<tema>.estadisticas` y `<tema>.eventos` (o con "/" en MQTT).
+pub struct Publicador {
+    broker: Box<dyn Broker>,
+    tema: String,
+    formato: Formato,
+}
+
+impl Publicador {
+    pub fn new(broker: Box<dyn Broker>, tema: &str) -> Self {
+        Self { broker, tema: tema.to_string(), formato: Formato::Json }
+    }
+
+    /// Cambia la codificación de los mensajes publicados.
+    pub fn con_formato(mut self, formato: Formato) -> Self {
+        self.formato = formato;
+        self
+    }
+
+    /// Crea un publicador a partir de una URL `nats://host:puerto` o `mqtt://host:puerto`.
+    pub fn desde_url(url: &str, tema: &str) -> io::Result<Self> {
+        let broker: Box<dyn Broker> = if let Some(direccion) = url.strip_prefix("nats://") {
+            Box::new(ClienteNats::conectar(direccion)?)
+        } else if let Some(direccion) = url.strip_prefix("mqtt://") {
+            Box::new(ClienteMqtt::conectar(direccion)?)
+        } else {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, format!("esquema de broker desconocido: {}", url)));
+        };
+        Ok(Self::new(broker, tema))
+    }
+
+    /// Publica el día que la simulación acaba de completar.
+    pub fn publicar_dia(&mut self, sim: &Simulacion) -> io::Result<()> {
+        let separador = self.broker.separador();
+
+        let tema_estadisticas = format!("{}{}estadisticas", self.tema, separador);
+        let fila = RegistroDiario::capturar(sim);
+        let estadisticas = match self.formato {
+            Formato::Json => serde_json::to_vec(&fila).map_err(io::Error::other)?,
+            #[cfg(feature = "protobuf")]
+            Formato::Protobuf => crate::protobuf::codificar_estadisticas(&fila),
+        };
+        self.broker.publicar(&tema_estadisticas, &estadisticas)?;
+
+        let tema_eventos = format!("{}{}eventos", self.tema, separador);
+        // Los nacimientos y muertes de cada presa ya van en las estadísticas; solo se publican los notables.
+        for evento in sim.eventos_dia.iter().filter(|e| !e.es_demografico()) {
+            let carga = match self.formato {
+                Formato::Json => {
+                    let mensaje = MensajeEvento { dia: sim.dia, descripcion: evento.descripcion(), evento };
+                    serde_json::to_vec(&mensaje).map_err(io::Error::other)?
+                }
+                #[cfg(feature = "protobuf")]
+                Formato::Protobuf => crate::protobuf::codificar_evento(sim.dia, evento),
+            };
+            self.broker.publicar(&tema_eventos, &carga)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::TcpListener;
+    use std::thread;
+    use std::time::Duration;
+
+    use super::*;
+
+    #[test]
+    fn la_longitud_restante_se_codifica_en_base_128() {
+        let cabecera = |longitud: usize| {
+            let paquete = paquete_mqtt(0x30, &vec![0; longitud]);
+            paquete[..paquete.len() - longitud].to_vec()
+        };
+        assert_eq!(cabecera(0), [0x30, 0x00]);
+        assert_eq!(cabecera(127), [0x30, 0x7f]);
+        assert_eq!(cabecera(128), [0x30, 0x80, 0x01]);
+        assert_eq!(cabecera(16_383), [0x30, 0xff, 0x7f]);
+        assert_eq!(cabecera(16_384), [0x30, 0x80, 0x80, 0x01]);
+        assert_eq!(paquete_mqtt(0xc0, b"ab"), [0xc0, 0x02, b'a', b'b']);
+    }
+
+    #[test]
+    fn las_cadenas_mqtt_llevan_su_longitud_en_bytes() {
+        let mut destino = vec![0x99];
+        escribir_cadena_mqtt(&mut destino, "MQTT");
+        escribir_cadena_mqtt(&mut destino, "");
+        escribir_cadena_mqtt(&mut destino, "año");
+        assert_eq!(destino, [0x99, 0, 4, b'M', b'Q', b'T', b'T', 0, 0, 0, 4, b'a', 0xc3, 0xb1, b'o']);
+    }
+
+    /// Un servidor NATS de pega: acepta al cliente, le saluda y se queda con su `CONNECT`.
+    fn servidor_nats() -> (ClienteNats, TcpStream) {
+        let escucha = TcpListener::bind("127.0.0.1:0").unwrap();
+        let direccion = escucha.local_addr().unwrap().to_string();
+        let cliente = thread::spawn(move || ClienteNats::conectar(&direccion));
+        let (mut servidor, _) = escucha.accept().unwrap();
+        servidor.write_all(b"INFO {\"server_id\":\"prueba\"}\r\n").unwrap();
+        let cliente = cliente.join().unwrap().unwrap();
+        servidor.set_read_timeout(Some(Duration::from_millis(50))).unwrap();
+        assert!(leer(&mut servidor).starts_with("CONNECT "));
+        (cliente, servidor)
+    }
+
+    /// Todo lo que el servidor recibe hasta que el cliente deja de enviar.
+    fn leer(servidor: &mut TcpStream) -> String {
+        let mut recibido = Vec::new();
+        let mut buffer = [0u8; 1024];
+        while let Ok(n @ 1..) = servidor.read(&mut buffer) {
+            recibido.extend_from_slice(&buffer[..n]);
+        }
+        String::from_utf8(recibido).unwrap()
+    }
+
+    /// Deja que lo escrito por el servidor llegue al cliente antes de que este publique.
+    fn enviar(servidor: &mut TcpStream, datos: &[u8]) {
+        servidor.write_all(datos).unwrap();
+        thread::sleep(Duration::from_millis(20));
+    }
+
+    #[test]
+    fn el_cliente_nats_responde_al_ping_aunque_llegue_partido() {
+        let (mut cliente, mut servidor) = servidor_nats();
+        enviar(&mut servidor, b"PI");
+        cliente.publicar("sim.estadisticas", b"1").unwrap();
+        assert_eq!(leer(&mut servidor), "PUB sim.estadisticas 1\r\n1\r\n");
+        enviar(&mut servidor, b"NG\r\n+OK\r\nPING\r\n");
+        cliente.publicar("sim.eventos", b"{}").unwrap();
+        assert_eq!(leer(&mut servidor), "PONG\r\nPONG\r\nPUB sim.eventos 2\r\n{}\r\n");
+    }
+
+    #[test]
+    fn un_error_del_servidor_nats_se_devuelve() {
+        let (mut cliente, mut servidor) = servidor_nats();
+        enviar(&mut servidor, b"-ERR 'Authorization Violation'\r\n");
+        drop(servidor);
+        let error = cliente.publicar("sim.estadisticas", b"1").unwrap_err();
+        assert!(error.to_string().contains("'Authorization Violation'"), "{}", error);
+    }
+}
@@ -0,0 +1,140 @@
+// src/puntos.rs
+
+// Dibujo alternativo de las presas para poblaciones muy grandes (parte del binario).
+// En lugar de un círculo de macroquad por presa, se sube la posición, el radio y el color de
+// todas a un búfer de instancias y se dibujan con una sola llamada: cada instancia es un
+// cuadrado que un shader mínimo recorta en círculo. Necesita instanciado en la GPU; sin él
+// (WebGL 1 sin la extensión), `PuntosGpu::new` devuelve `None` y se usa el dibujo normal.
+// Los contornos de las clases de edad no se dibujan: solo la transparencia de los juveniles.
+
+use macroquad::miniquad as mq;
+use macroquad::prelude::*;
+
+/// Instancias para las que se reserva el búfer al principio; crece al doble cuando no caben.
+const CAPACIDAD_INICIAL: usize = 4096;
+
+const SHADER_VERTICES: &str = r#"#version 100
+attribute vec2 esquina;
+attribute vec2 centro;
+attribute float radio;
+attribute vec4 color;
+uniform mat4 mvp;
+varying lowp vec4 color_punto;
+varying mediump vec2 local;
+void main() {
+    local = esquina;
+    color_punto = color;
+    gl_Position = mvp * vec4(centro + esquina * radio, 0.0, 1.0);
+}
+"#;
+
+const SHADER_FRAGMENTOS: &str = r#"#version 100
+precision mediump float;
+varying lowp vec4 color_punto;
+varying mediump vec2 local;
+void main() {
+    if (dot(local, local) > 1.0) {
+        discard;
+    }
+    gl_FragColor = color_punto;
+}
+"#;
+
+/// Lo que se sube a la GPU de cada presa.
+#[repr(C)]
+struct Instancia {
+    centro: [f32; 2],
+    radio: f32,
+    color: [f32; 4],
+}
+
+#[repr(C)]
+struct Uniformes {
+    mvp: Mat4,
+}
+
+/// Búferes y pipeline del dibujo por instancias.
+pub struct PuntosGpu {
+    pipeline: mq::Pipeline,
+    bindings: mq::Bindings,
+    capacidad: usize,
+    instancias: Vec<Instancia>,
+}
+
+impl PuntosGpu {
+    /// Prepara los búferes y el shader; `None` si la GPU no admite instanciado o el shader no compila.
+    pub fn new() -> Option<Self> {
+        let gl = unsafe { get_internal_gl() };
+        let ctx = gl.quad_context;
+        if !ctx.info().features.instancing {
+            return None;
+        }
+        let esquinas: [[f32; 2]; 4] = [[-1.0, -1.0], [1.0, -1.0], [1.0, 1.0], [-1.0, 1.0]];
+        let indices: [u16; 6] = [0, 1, 2, 0, 2, 3];
+        let meta = mq::ShaderMeta {
+            uniforms: mq::UniformBlockLayout { uniforms: vec![mq::UniformDesc::new("mvp", mq::UniformType::Mat4)] },
+            images: Vec::new(),
+        };
+        let shader = ctx.new_shader(mq::ShaderSource::Glsl { vertex: SHADER_VERTICES, fragment: SHADER_FRAGMENTOS }, meta).ok()?;
+        let pipeline = ctx.new_pipeline(
+            &[
+                mq::BufferLayout::default(),
+                mq::BufferLayout { step_func: mq::VertexStep::PerInstance, ..Default::default() },
+            ],
+            &[
+                mq::VertexAttribute::with_buffer("esquina", mq::VertexFormat::Float2, 0),
+                mq::VertexAttribute::with_buffer("centro", mq::VertexFormat::Float2, 1),
+                mq::VertexAttribute::with_buffer("radio", mq::VertexFormat::Float1, 1),
+                mq::VertexAttribute::with_buffer("color", mq::VertexFormat::Float4, 1),
+            ],
+            shader,
+            mq::PipelineParams {
+                color_blend: Some(mq::BlendState::new(
+                    mq::Equation::Add,
+                    mq::BlendFactor::Value(mq::BlendValue::SourceAlpha),
+                    mq::BlendFactor::OneMinusValue(mq::BlendValue::SourceAlpha),
+                )),
+                ..Default::default()
+            },
+        );
+        let bindings = mq::Bindings {
+            vertex_buffers: vec![
+                ctx.new_buffer(mq::BufferType::VertexBuffer, mq::BufferUsage::Immutable, mq::BufferSource::slice(&esquinas)),
+                ctx.new_buffer(mq::BufferType::VertexBuffer, mq::BufferUsage::Stream, mq::BufferSource::empty::<Instancia>(CAPACIDAD_INICIAL)),
+            ],
+            index_buffer: ctx.new_buffer(mq::BufferType::IndexBuffer, mq::BufferUsage::Immutable, mq::BufferSource::slice(&indices)),
+            images: Vec::new(),
+        };
+        Some(Self { pipeline, bindings, capacidad: CAPACIDAD_INICIAL, instancias: Vec::with_capacity(CAPACIDAD_INICIAL) })
+    }
+
+    /// Añade una presa al lote que se dibujará con `dibujar`.
+    pub fn anadir(&mut self, centro: Vec2, radio: f32, color: Color) {
+        self.instancias.push(Instancia { centro: [centro.x, centro.y], radio, color: [color.r, color.g, color.b, color.a] });
+    }
+
+    /// Dibuja de una vez las presas añadidas, con la cámara de la escena, y vacía el lote.
+    /// Lo que macroquad tenía pendiente se dibuja antes, para que quede debajo.
+    pub fn dibujar(&mut self, camara: &Camera2D) {
+        if self.instancias.is_empty() {
+            return;
+        }
+        let mut gl = unsafe { get_internal_gl() };
+        gl.flush();
+        let ctx = gl.quad_context;
+        if self.instancias.len() > self.capacidad {
+            self.capacidad = self.instancias.len().next_power_of_two();
+            ctx.delete_buffer(self.bindings.vertex_buffers[1]);
+            self.bindings.vertex_buffers[1] =
+                ctx.new_buffer(mq::BufferType::VertexBuffer, mq::BufferUsage::Stream, mq::BufferSource::empty::<Instancia>(self.capacidad));
+        }
+        ctx.buffer_update(self.bindings.vertex_buffers[1], mq::BufferSource::slice(&self.instancias));
+        ctx.begin_default_pass(mq::PassAction::Nothing);
+        ctx.apply_pipeline(&self.pipeline);
+        ctx.apply_bindings(&self.bindings);
+        ctx.apply_uniforms(mq::UniformsSource::table(&Uniformes { mvp: camara.matrix() }));
+        ctx.draw(0, 6, self.instancias.len() as i32);
+        ctx.end_render_pass();
+        self.instancias.clear();
+    }
+}
@@ -0,0 +1,84 @@
+// src/rastros.rs
+
+// Rastros de movimiento en la escena (parte del binario, no de la biblioteca).
+// Guarda el camino reciente del depredador y de la presa seleccionada en el inspector y lo
+// dibuja como una línea que se desvanece hacia los puntos más antiguos. Sirve para comprobar
+// a simple vista el territorio que recorre cada uno: las embestidas de caza, las mudanzas de
+// celda en el pastizal o las reubicaciones a mano. Solo se anota un punto cuando la posición
+// cambia de verdad, así que un animal quieto conserva su último recorrido.
+
+use std::collections::VecDeque;
+
+use macroquad::prelude::*;
+
+/// Puntos que se conservan de cada rastro; los más antiguos se descartan.
+const MAXIMO_PUNTOS: usize = 120;
+/// Desplazamiento mínimo (en unidades de la escena) para anotar un punto nuevo.
+const DISTANCIA_MINIMA: f32 = 1.5;
+/// Grosor de la línea.
+const GROSOR: f32 = 2.0;
+
+/// Camino reciente de una entidad, del punto más antiguo al más reciente.
+#[derive(Default)]
+pub struct Rastro {
+    puntos: VecDeque<Vec2>,
+}
+
+impl Rastro {
+    /// Anota la posición actual si se ha movido desde la última.
+    pub fn anotar(&mut self, posicion: Vec2) {
+        if self.puntos.back().is_some_and(|ultimo| ultimo.distance(posicion) < DISTANCIA_MINIMA) {
+            return;
+        }
+        if self.puntos.len() == MAXIMO_PUNTOS {
+            self.puntos.pop_front();
+        }
+        self.puntos.push_back(posicion);
+    }
+
+    /// Dibuja el rastro con `color`, más transparente cuanto más antiguo es el tramo.
+    pub fn dibujar(&self, color: Color) {
+        let tramos = self.puntos.len().saturating_sub(1);
+        for (i, (a, b)) in self.puntos.iter().zip(self.puntos.iter().skip(1)).enumerate() {
+            let alfa = color.a * (i + 1) as f32 / tramos as f32;
+            draw_line(a.x, a.y, b.x, b.y, GROSOR, Color { a: alfa, ..color });
+        }
+    }
+}
+
+/// Rastros del depredador y de la presa seleccionada (con su id, para empezar de cero al
+/// seleccionar otra).
+#[derive(Default)]
+pub struct Rastros {
+    depredador: Rastro,
+    presa: Option<(u64, Rastro)>,
+}
+
+impl Rastros {
+    /// Anota las posiciones de este fotograma; `None` si el depredador murió o no hay presa
+    /// seleccionada (o ya no existe), lo que borra su rastro.
+    pub fn anotar(&mut self, depredador: Option<Vec2>, presa: Option<(u64, Vec2)>) {
+        match depredador {
+            Some(posicion) => self.depredador.anotar(posicion),
+            None => self.depredador = Rastro::default(),
+        }
+        match presa {
+            Some((id, posicion)) => {
+                if self.presa.as_ref().is_none_or(|(actual, _)| *actual != id) {
+                    self.presa = Some((id, Rastro::default()));
+                }
+                if let Some((_, rastro)) = &mut self.presa {
+                    rastro.anotar(posicion);
+                }
+            }
+            None => self.presa = None,
+        }
+    }
+
+    pub fn dibujar(&self) {
+        self.depredador.dibujar(Color { a: 0.8, ..MAROON });
+        if let Some((_, rastro)) = &self.presa {
+            rastro.dibujar(Color { a: 0.8, ..YELLOW });
+        }
+    }
+}
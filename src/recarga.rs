@@ -0,0 +1,132 @@
+// src/recarga.rs
+
+// Recarga en caliente del archivo de parámetros (por defecto `simulacion.toml`).
+// Se vigila la fecha de modificación del archivo y, cuando cambia, se aplican a la
+// simulación en curso solo los parámetros "seguros": tasas, consumos y probabilidades.
+// Los que definen el estado inicial o las edades se ignoran hasta el próximo reinicio.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use serde_json::Value;
+
+use crate::configuracion;
+use crate::eventos::Evento;
+use crate::parametros::Parametros;
+use crate::simulacion::Simulacion;
+
+/// Archivo de parámetros que se vigila si no se indica otro.
+pub const ARCHIVO_PARAMETROS: &str = "simulacion.toml";
+
+/// Parámetros que se pueden cambiar a mitad de una corrida sin romper su coherencia.
+pub const PARAMETROS_RECARGABLES: &[&str] = &[
+    "DEPREDADOR_CONSUMO_MINIMO_DIARIO_KG",
+    "DEPREDADOR_CONSUMO_OPTIMO_DIARIO_KG",
+    "ENERGIA",
+    "METABOLISMO",
+    "DEPREDADOR_INGESTA_MAXIMA_KG",
+    "TASA_DESCOMPOSICION_DIARIA",
+    "DIETA_DEPREDADOR",
+    "ESTRATEGIA_CAZA",
+    "CADENA_TROFICA",
+    "ESPACIO",
+    "CONEJO_TASA_REPRODUCCION_DIARIA",
+    "CONEJO_CRIAS_POR_PARTO",
+    "CONEJO_GESTACION_DIAS",
+    "CABRA_TASA_REPRODUCCION_DIARIA",
+    "CABRA_CRIAS_POR_PARTO",
+    "CABRA_GESTACION_DIAS",
+    "PROBABILIDAD_ENFERMAR",
+    "PROBABILIDAD_NACER_MACHO",
+    "UMBRAL_ALLEE",
+    "EPIDEMIAS",
+    "ENFERMEDAD",
+    "CLIMA",
+    "CLASES_EDAD",
+    "CAZABILIDAD",
+    "EXITO_CAZA",
+    "RESPUESTA_FUNCIONAL",
+    "POBLACION_MAXIMA",
+];
+
+/// Resultado de aplicar un archivo recargado.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ResultadoRecarga {
+    /// Parámetros cuyo nuevo valor se aplicó.
+    pub aplicados: Vec<String>,
+    /// Parámetros que cambiaron en el archivo pero no se pueden aplicar en caliente.
+    pub ignorados: Vec<String>,
+}
+
+/// Aplica a la simulación los cambios seguros entre sus parámetros y `nuevos`,
+/// anunciando cada uno como evento `ParametroCambiado`.
+pub fn aplicar_recarga(sim: &mut Simulacion, nuevos: &Parametros) -> Result<ResultadoRecarga, String> {
+    let actual = sim.parametros.a_arbol();
+    let propuesto = nuevos.a_arbol();
+    let (Value::Object(campos_actuales), Value::Object(campos_propuestos)) = (&actual, &propuesto) else {
+        return Err("parámetros con formato inesperado".to_string());
+    };
+
+    let mut resultado = ResultadoRecarga::default();
+    let mut combinado = actual.clone();
+    let mut eventos = Vec::new();
+    for (nombre, valor_nuevo) in campos_propuestos {
+        let valor_actual = &campos_actuales[nombre];
+        if valor_actual == valor_nuevo {
+            continue;
+        }
+        if PARAMETROS_RECARGABLES.contains(&nombre.as_str()) {
+            combinado[nombre] = valor_nuevo.clone();
+            eventos.push(Evento::ParametroCambiado {
+                nombre: nombre.clone(),
+                anterior: valor_actual.to_string(),
+                nuevo: valor_nuevo.to_string(),
+            });
+            resultado.aplicados.push(nombre.clone());
+        } else {
+            resultado.ignorados.push(nombre.clone());
+        }
+    }
+
+    // La combinación debe seguir siendo válida (p. ej. consumo óptimo >= mínimo).
+    sim.parametros = Parametros::desde_arbol(combinado)?;
+    sim.eventos_dia.extend(eventos);
+    sim.retener_instantanea();
+    Ok(resultado)
+}
+
+/// Vigila un archivo de parámetros y lo reaplica cada vez que cambia en disco.
+pub struct VigilanteParametros {
+    ruta: PathBuf,
+    ultima_modificacion: Option<SystemTime>,
+}
+
+impl VigilanteParametros {
+    /// Empieza a vigilar `ruta`; la versión actual del archivo se considera ya aplicada.
+    pub fn new(ruta: impl AsRef<Path>) -> Self {
+        let ruta = ruta.as_ref().to_path_buf();
+        let ultima_modificacion = fecha_modificacion(&ruta);
+        Self { ruta, ultima_modificacion }
+    }
+
+    pub fn ruta(&self) -> &Path {
+        &self.ruta
+    }
+
+    /// Si el archivo cambió desde la última revisión, lo lee y aplica los cambios seguros.
+    /// Devuelve `Ok(None)` si no hubo cambios. Un archivo inválido no altera la simulación.
+    pub fn revisar(&mut self, sim: &mut Simulacion) -> Result<Option<ResultadoRecarga>, String> {
+        let modificacion = fecha_modificacion(&self.ruta);
+        if modificacion.is_none() || modificacion == self.ultima_modificacion {
+            return Ok(None);
+        }
+        self.ultima_modificacion = modificacion;
+        let escenario = configuracion::cargar_escenario(&self.ruta)?;
+        aplicar_recarga(sim, &escenario.parametros).map(Some)
+    }
+}
+
+fn fecha_modificacion(ruta: &Path) -> Option<SystemTime> {
+    fs::metadata(ruta).and_then(|m| m.modified()).ok()
+}
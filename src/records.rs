@@ -0,0 +1,133 @@
+// src/records.rs
+
+// Récords y logros de esta máquina, guardados en un archivo JSON pequeño (`records.json`)
+// para que las corridas del visualizador tengan algo que batir:
+//
+//   - Coexistencia más larga: días con ambas especies y el depredador vivos.
+//   - Población máxima alcanzada.
+//   - Colapso más rápido: el día más temprano en que se extinguió una especie o murió el depredador.
+//
+// Los logros se desbloquean una sola vez y quedan en el mismo archivo.
+
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::simulacion::Simulacion;
+
+/// Archivo de récords en la carpeta de trabajo.
+pub const ARCHIVO_RECORDS: &str = "records.json";
+
+/// Logros que se desbloquean una vez por máquina.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Logro {
+    /// Un año de coexistencia.
+    UnAno,
+    /// Diez años de coexistencia.
+    Decada,
+    /// Mil presas a la vez.
+    MilPresas,
+    /// Un colapso en el primer mes.
+    ColapsoRelampago,
+    /// Un desafío superado.
+    DesafioSuperado,
+}
+
+/// Récords guardados.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Records {
+    pub coexistencia_mas_larga_dias: u32,
+    pub poblacion_maxima: usize,
+    pub colapso_mas_rapido_dia: Option<u32>,
+    pub logros: Vec<Logro>,
+}
+
+/// Lo que cambió en los récords al completar un día.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Novedades {
+    /// Se batió al menos un récord.
+    pub record_batido: bool,
+    /// Logros recién desbloqueados.
+    pub logros: Vec<Logro>,
+}
+
+impl Records {
+    /// Lee los récords; si el archivo no existe, empieza desde cero.
+    pub fn cargar(ruta: impl AsRef<Path>) -> Result<Self, String> {
+        let ruta = ruta.as_ref();
+        if !ruta.exists() {
+            return Ok(Self::default());
+        }
+        let texto = fs::read_to_string(ruta).map_err(|e| format!("no se pudo leer {}: {}", ruta.display(), e))?;
+        serde_json::from_str(&texto).map_err(|e| format!("{}: {}", ruta.display(), e))
+    }
+
+    pub fn guardar(&self, ruta: impl AsRef<Path>) -> io::Result<()> {
+        let texto = serde_json::to_string_pretty(self).map_err(io::Error::other)?;
+        fs::write(ruta, texto)
+    }
+
+    /// Desbloquea un logro; devuelve `true` si es nuevo.
+    pub fn desbloquear(&mut self, logro: Logro) -> bool {
+        if self.logros.contains(&logro) {
+            return false;
+        }
+        self.logros.push(logro);
+        true
+    }
+}
+
+/// Seguimiento de una corrida frente a los récords.
+#[derive(Debug, Clone, Default)]
+pub struct CorridaRecords {
+    /// Día en que colapsó esta corrida; a partir de ahí ya no cuenta la coexistencia.
+    colapso: Option<u32>,
+}
+
+impl CorridaRecords {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Compara el día que la simulación acaba de completar con los récords y los actualiza.
+    pub fn dia_completado(&mut self, records: &mut Records, sim: &Simulacion) -> Novedades {
+        let mut novedades = Novedades::default();
+        let (conejos, cabras) = sim.contar_especies();
+
+        if self.colapso.is_none() && (conejos == 0 || cabras == 0 || !sim.depredador.vivo) {
+            self.colapso = Some(sim.dia);
+            if !matches!(records.colapso_mas_rapido_dia, Some(record) if record <= sim.dia) {
+                records.colapso_mas_rapido_dia = Some(sim.dia);
+                novedades.record_batido = true;
+            }
+            if sim.dia <= 30 && records.desbloquear(Logro::ColapsoRelampago) {
+                novedades.logros.push(Logro::ColapsoRelampago);
+            }
+        }
+
+        if self.colapso.is_none() {
+            if sim.dia > records.coexistencia_mas_larga_dias {
+                records.coexistencia_mas_larga_dias = sim.dia;
+                novedades.record_batido = true;
+            }
+            for (dias, logro) in [(365, Logro::UnAno), (3650, Logro::Decada)] {
+                if sim.dia >= dias && records.desbloquear(logro) {
+                    novedades.logros.push(logro);
+                }
+            }
+        }
+
+        let poblacion = conejos + cabras;
+        if poblacion > records.poblacion_maxima {
+            records.poblacion_maxima = poblacion;
+            novedades.record_batido = true;
+        }
+        if poblacion >= 1000 && records.desbloquear(Logro::MilPresas) {
+            novedades.logros.push(Logro::MilPresas);
+        }
+        novedades
+    }
+}
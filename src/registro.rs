@@ -0,0 +1,423 @@
+// src/registro.rs
+
+// Este módulo acumula las estadísticas de cada día de una corrida y las exporta como CSV.
+// Cuánto se guarda lo decide la telemetría: una fila cada N días, solo algunas columnas y
+// un tope de filas, para que las corridas muy largas no se coman la memoria. El CSV se puede
+// escribir de una vez al final o ir añadiendo cada fila al archivo según se registra
+// (`CsvIncremental`), para seguir una corrida larga con `tail -f` o no perderla si se corta.
+// También sabe combinar los CSV de muchas corridas en una sola tabla en formato "largo"
+// (una fila por corrida, día y variable), lista para ggplot o pandas, y resumir el lote con
+// intervalos de confianza bootstrap de sus resultados clave.
+
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::parametros::Parametros;
+use crate::remuestreo::{self, Desenlace};
+use crate::simulacion::Simulacion;
+
+/// Encabezado de las columnas de datos del CSV diario.
+pub const ENCABEZADO_CSV: &str = "dia,conejos,cabras,reserva_depredador_kg,nacimientos,muertes,cazas,pasto_kg,presion_depredacion,presion_pastoreo,indice_control";
+
+/// Las métricas de un único día.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RegistroDiario {
+    pub dia: u32,
+    pub conejos: usize,
+    pub cabras: usize,
+    pub reserva_depredador_kg: f64,
+    pub nacimientos: u32,
+    pub muertes: u32,
+    pub cazas: u32,
+    /// Pasto disponible (kg); 0 fuera del modo de cadena trófica.
+    pub pasto_kg: f64,
+    /// Fracción de la biomasa cazable que se llevó el depredador.
+    pub presion_depredacion: f64,
+    /// Fracción del pasto disponible que comieron las presas.
+    pub presion_pastoreo: f64,
+    /// Control desde abajo (-1) o desde arriba (1); ver `MetricasTroficas`.
+    pub indice_control: f64,
+}
+
+impl RegistroDiario {
+    /// Toma las métricas del día que la simulación acaba de completar.
+    pub fn capturar(sim: &Simulacion) -> Self {
+        let (conejos, cabras) = sim.contar_especies();
+        Self {
+            dia: sim.dia,
+            conejos,
+            cabras,
+            reserva_depredador_kg: sim.depredador.reserva_comida_kg,
+            nacimientos: sim.estadisticas_dia.nacimientos,
+            muertes: sim.estadisticas_dia.muertes,
+            cazas: sim.estadisticas_dia.cazas,
+            pasto_kg: sim.pasto_kg(),
+            presion_depredacion: sim.metricas_dia.presion_depredacion,
+            presion_pastoreo: sim.metricas_dia.presion_pastoreo,
+            indice_control: sim.metricas_dia.indice_control,
+        }
+    }
+
+    fn a_linea_csv(&self) -> String {
+        format!(
+            "{},{},{},{:.3},{},{},{},{:.1},{:.4},{:.4},{:.4}",
+            self.dia, self.conejos, self.cabras, self.reserva_depredador_kg,
+            self.nacimientos, self.muertes, self.cazas, self.pasto_kg,
+            self.presion_depredacion, self.presion_pastoreo, self.indice_control
+        )
+    }
+}
+
+/// Qué se guarda del historial diario y cuánto (tabla `[telemetria]` del escenario).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(default, deny_unknown_fields)]
+pub struct Telemetria {
+    /// Guarda una fila cada tantos días; nacimientos, muertes y cazas suman los días intermedios.
+    #[schemars(range(min = 1))]
+    pub cada_dias: u32,
+    /// Columnas del CSV que se escriben además de `dia`; si se omite, todas.
+    pub metricas: Vec<String>,
+    /// Filas que se conservan como máximo (se descartan las más antiguas); sin tope si se omite.
+    #[schemars(range(min = 1))]
+    pub maximo_filas: Option<usize>,
+}
+
+impl Default for Telemetria {
+    fn default() -> Self {
+        Self { cada_dias: 1, metricas: Vec::new(), maximo_filas: None }
+    }
+}
+
+impl Telemetria {
+    pub fn validar(&self) -> Result<(), String> {
+        if self.cada_dias == 0 {
+            return Err("telemetria.cada_dias debe ser al menos 1".to_string());
+        }
+        if self.maximo_filas == Some(0) {
+            return Err("telemetria.maximo_filas debe ser al menos 1".to_string());
+        }
+        let columnas: Vec<&str> = ENCABEZADO_CSV.split(',').skip(1).collect();
+        match self.metricas.iter().find(|m| !columnas.contains(&m.as_str())) {
+            Some(metrica) => Err(format!("métrica desconocida: {} (opciones: {})", metrica, columnas.join(", "))),
+            None => Ok(()),
+        }
+    }
+
+    /// Interpreta una lista de métricas separadas por comas.
+    pub fn metricas_desde_texto(texto: &str) -> Vec<String> {
+        texto.split(',').map(str::trim).filter(|m| !m.is_empty()).map(str::to_string).collect()
+    }
+}
+
+/// Historial diario de una corrida, junto con los metadatos (semilla, parámetros) que la describen.
+#[derive(Debug, Clone, Default)]
+pub struct Registro {
+    pub metadatos: Vec<(String, String)>,
+    pub filas: VecDeque<RegistroDiario>,
+    pub telemetria: Telemetria,
+    /// Nacimientos, muertes y cazas de los días que todavía no se guardaron.
+    pendientes: (u32, u32, u32),
+}
+
+impl Registro {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn con_telemetria(telemetria: Telemetria) -> Self {
+        Self { telemetria, ..Self::default() }
+    }
+
+    /// Añade un par clave/valor que se escribirá como comentario `# clave=valor` al inicio del CSV.
+    pub fn anotar(&mut self, clave: &str, valor: impl ToString) {
+        self.metadatos.push((clave.to_string(), valor.to_string()));
+    }
+
+    /// Anota la huella de los parámetros efectivos (ver `Parametros::huella`) como metadato
+    /// `parametros`, con el que `resumen_bootstrap` agrupa las corridas.
+    pub fn anotar_parametros(&mut self, parametros: &Parametros) {
+        self.anotar("parametros", format!("{:016x}", parametros.huella()));
+    }
+
+    /// Registra el día que la simulación acaba de completar, si le toca según el muestreo.
+    /// El último día de la corrida (el de la muerte del depredador) se guarda siempre.
+    pub fn registrar(&mut self, sim: &Simulacion) {
+        let estadisticas = &sim.estadisticas_dia;
+        self.pendientes.0 += estadisticas.nacimientos;
+        self.pendientes.1 += estadisticas.muertes;
+        self.pendientes.2 += estadisticas.cazas;
+        if !sim.dia.is_multiple_of(self.telemetria.cada_dias.max(1)) && sim.depredador.vivo {
+            return;
+        }
+        let mut fila = RegistroDiario::capturar(sim);
+        (fila.nacimientos, fila.muertes, fila.cazas) = std::mem::take(&mut self.pendientes);
+        self.filas.push_back(fila);
+        if let Some(maximo) = self.telemetria.maximo_filas {
+            while self.filas.len() > maximo.max(1) {
+                self.filas.pop_front();
+            }
+        }
+    }
+
+    /// Genera el CSV completo: metadatos como comentarios, encabezado y una fila por día
+    /// guardado, con `dia` y las métricas elegidas en la telemetría.
+    pub fn a_csv(&self) -> String {
+        let seleccion = self.seleccion_columnas();
+        let mut csv = self.cabecera_csv(&seleccion);
+        for fila in &self.filas {
+            csv.push_str(&filtrar_columnas(&fila.a_linea_csv(), &seleccion));
+            csv.push('\n');
+        }
+        csv
+    }
+
+    /// Qué columnas de `ENCABEZADO_CSV` se escriben: `dia` y las métricas de la telemetría.
+    fn seleccion_columnas(&self) -> Vec<bool> {
+        let metricas = &self.telemetria.metricas;
+        let elegida = |columna: &str| metricas.is_empty() || columna == "dia" || metricas.iter().any(|m| m == columna);
+        ENCABEZADO_CSV.split(',').map(elegida).collect()
+    }
+
+    /// Metadatos como comentarios y la línea de encabezado.
+    fn cabecera_csv(&self, seleccion: &[bool]) -> String {
+        let mut csv = String::new();
+        for (clave, valor) in &self.metadatos {
+            csv.push_str(&format!("# {}={}\n", clave, valor));
+        }
+        csv.push_str(&filtrar_columnas(ENCABEZADO_CSV, seleccion));
+        csv.push('\n');
+        csv
+    }
+
+    pub fn guardar_csv(&self, ruta: impl AsRef<Path>) -> io::Result<()> {
+        fs::write(ruta, self.a_csv())
+    }
+}
+
+/// Deja solo los campos de `linea` marcados en `seleccion`.
+fn filtrar_columnas(linea: &str, seleccion: &[bool]) -> String {
+    linea.split(',').zip(seleccion).filter(|(_, &si)| si).map(|(campo, _)| campo).collect::<Vec<_>>().join(",")
+}
+
+/// CSV diario que se escribe mientras avanza la corrida: la cabecera al crearlo y, después de
+/// cada `Registro::registrar`, las filas nuevas. El archivo queda igual que el de
+/// `Registro::guardar_csv` salvo con `maximo_filas`, que aquí no descarta las filas ya escritas.
+pub struct CsvIncremental {
+    archivo: BufWriter<File>,
+    seleccion: Vec<bool>,
+    /// Último día escrito; las filas del registro van en orden de día.
+    ultimo_dia: Option<u32>,
+}
+
+impl CsvIncremental {
+    /// Crea (o vacía) el archivo y escribe los metadatos y el encabezado de `registro`, así que
+    /// las anotaciones tienen que estar hechas antes.
+    pub fn crear(ruta: impl AsRef<Path>, registro: &Registro) -> io::Result<Self> {
+        let seleccion = registro.seleccion_columnas();
+        let mut archivo = BufWriter::new(File::create(ruta)?);
+        archivo.write_all(registro.cabecera_csv(&seleccion).as_bytes())?;
+        archivo.flush()?;
+        Ok(Self { archivo, seleccion, ultimo_dia: None })
+    }
+
+    /// Escribe las filas de `registro` posteriores a la última escrita y vacía el búfer, para
+    /// que el archivo esté al día aunque la corrida se interrumpa.
+    pub fn escribir_nuevas(&mut self, registro: &Registro) -> io::Result<()> {
+        let ultimo_dia = self.ultimo_dia;
+        for fila in registro.filas.iter().filter(|f| ultimo_dia.is_none_or(|dia| f.dia > dia)) {
+            writeln!(self.archivo, "{}", filtrar_columnas(&fila.a_linea_csv(), &self.seleccion))?;
+            self.ultimo_dia = Some(fila.dia);
+        }
+        self.archivo.flush()
+    }
+}
+
+/// Un CSV diario ya leído: sus metadatos, los nombres de columna y las filas de valores.
+struct CsvCorrida {
+    id_corrida: String,
+    metadatos: Vec<(String, String)>,
+    columnas: Vec<String>,
+    filas: Vec<Vec<String>>,
+}
+
+impl CsvCorrida {
+    fn leer(id_corrida: &str, contenido: &str) -> Result<Self, String> {
+        let mut metadatos = Vec::new();
+        let mut columnas: Option<Vec<String>> = None;
+        let mut filas = Vec::new();
+        for (numero, linea) in contenido.lines().enumerate() {
+            let linea = linea.trim();
+            if linea.is_empty() {
+                continue;
+            }
+            if let Some(comentario) = linea.strip_prefix('#') {
+                if let Some((clave, valor)) = comentario.trim().split_once('=') {
+                    metadatos.push((clave.trim().to_string(), valor.trim().to_string()));
+                }
+                continue;
+            }
+            let campos: Vec<String> = linea.split(',').map(|c| c.trim().to_string()).collect();
+            match &columnas {
+                None => columnas = Some(campos),
+                Some(cols) if cols.len() != campos.len() => {
+                    return Err(format!("{}: la línea {} tiene {} columnas, se esperaban {}", id_corrida, numero + 1, campos.len(), cols.len()));
+                }
+                Some(_) => filas.push(campos),
+            }
+        }
+        let columnas = columnas.ok_or(format!("{}: no tiene encabezado", id_corrida))?;
+        if columnas.first().map(String::as_str) != Some("dia") {
+            return Err(format!("{}: la primera columna debe ser 'dia'", id_corrida));
+        }
+        Ok(Self { id_corrida: id_corrida.to_string(), metadatos, columnas, filas })
+    }
+
+    fn parametro(&self, clave: &str) -> &str {
+        self.metadatos.iter().find(|(k, _)| k == clave).map(|(_, v)| v.as_str()).unwrap_or("")
+    }
+
+    /// Picos, poblaciones finales y extinciones a partir de las columnas `dia`, `conejos` y
+    /// `cabras`. La primera fila es el final del primer día simulado. La corrida se corta cuando
+    /// muere el depredador, así que si cubre menos días que los del metadato `dias` se da por
+    /// muerto el último día.
+    fn desenlace(&self) -> Result<Desenlace, String> {
+        let indice = |nombre: &str| self.columnas.iter().position(|c| c == nombre)
+            .ok_or_else(|| format!("{}: falta la columna '{}'", self.id_corrida, nombre));
+        let (i_dia, i_conejos, i_cabras) = (indice("dia")?, indice("conejos")?, indice("cabras")?);
+        let mut desenlace = Desenlace::default();
+        for (n, fila) in self.filas.iter().enumerate() {
+            let valor = |i: usize| fila[i].parse::<f64>()
+                .map(|v| v.round() as u32)
+                .map_err(|_| format!("{}: valor inválido '{}' en la columna '{}'", self.id_corrida, fila[i], self.columnas[i]));
+            let dia = valor(i_dia)?;
+            if n == 0 {
+                desenlace = Desenlace::desde(dia.saturating_sub(1));
+            }
+            desenlace.observar(dia, valor(i_conejos)?, valor(i_cabras)?);
+        }
+        if let Ok(dias) = self.parametro("dias").parse::<u32>() {
+            desenlace.depredador_muerto = desenlace.vida_depredador() < dias;
+        }
+        Ok(desenlace)
+    }
+}
+
+/// Un metadato como celda CSV: entre comillas si lleva comas o comillas (p. ej. un `--set`
+/// con una estructura).
+fn celda_csv(valor: &str) -> String {
+    if valor.contains([',', '"']) {
+        format!("\"{}\"", valor.replace('"', "\"\""))
+    } else {
+        valor.to_string()
+    }
+}
+
+/// Combina los CSV diarios de varias corridas `(id_corrida, contenido)` en una tabla larga:
+/// `run_id,<parámetros...>,dia,variable,valor`. Los parámetros son la unión de los metadatos
+/// de todas las corridas; si una corrida no define alguno, la celda queda vacía.
+pub fn combinar_formato_largo(corridas: &[(String, String)]) -> Result<String, String> {
+    let corridas: Vec<CsvCorrida> = corridas.iter()
+        .map(|(id, contenido)| CsvCorrida::leer(id, contenido))
+        .collect::<Result<_, _>>()?;
+
+    let parametros: BTreeSet<&str> = corridas.iter()
+        .flat_map(|c| c.metadatos.iter().map(|(k, _)| k.as_str()))
+        .collect();
+
+    let mut tabla = String::from("run_id");
+    for parametro in &parametros {
+        tabla.push(',');
+        tabla.push_str(parametro);
+    }
+    tabla.push_str(",dia,variable,valor\n");
+
+    for corrida in &corridas {
+        let prefijo: String = parametros.iter()
+            .map(|p| format!(",{}", celda_csv(corrida.parametro(p))))
+            .collect();
+        for fila in &corrida.filas {
+            for (variable, valor) in corrida.columnas.iter().zip(fila).skip(1) {
+                tabla.push_str(&format!("{}{},{},{},{}\n", corrida.id_corrida, prefijo, fila[0], variable, valor));
+            }
+        }
+    }
+    Ok(tabla)
+}
+
+/// Metadatos que no distinguen grupos en `resumen_bootstrap`: la semilla, y de dónde salieron
+/// los parámetros, que ya cubre su huella.
+const METADATOS_SIN_GRUPO: [&str; 3] = ["semilla", "config", "set"];
+
+/// Resume los CSV diarios de varias corridas con intervalos de confianza bootstrap:
+/// `<parámetros...>,variable,n,media,ic95_inferior,ic95_superior,minimo,maximo`. Las corridas
+/// con la misma huella de parámetros (metadato `parametros`) y los mismos días forman un
+/// grupo, y cada grupo da una fila por resultado clave. Una corrida sin huella es un error:
+/// no se puede saber con cuáles se puede juntar.
+pub fn resumen_bootstrap(corridas: &[(String, String)]) -> Result<String, String> {
+    let corridas: Vec<CsvCorrida> = corridas.iter()
+        .map(|(id, contenido)| CsvCorrida::leer(id, contenido))
+        .collect::<Result<_, _>>()?;
+    if let Some(corrida) = corridas.iter().find(|c| c.parametro("parametros").is_empty()) {
+        return Err(format!("{}: falta el metadato 'parametros' (la huella de los parámetros); vuelve a generar el CSV", corrida.id_corrida));
+    }
+
+    let parametros: BTreeSet<&str> = corridas.iter()
+        .flat_map(|c| c.metadatos.iter().map(|(k, _)| k.as_str()))
+        .filter(|k| !METADATOS_SIN_GRUPO.contains(k))
+        .collect();
+
+    let mut grupos: BTreeMap<String, Vec<Desenlace>> = BTreeMap::new();
+    for corrida in &corridas {
+        let prefijo: String = parametros.iter()
+            .map(|p| format!("{},", celda_csv(corrida.parametro(p))))
+            .collect();
+        grupos.entry(prefijo).or_default().push(corrida.desenlace()?);
+    }
+
+    let mut tabla: String = parametros.iter().map(|p| format!("{},", p)).collect();
+    tabla.push_str(remuestreo::ENCABEZADO_CSV);
+    tabla.push('\n');
+    for (prefijo, desenlaces) in &grupos {
+        tabla.push_str(&remuestreo::filas_csv(prefijo, desenlaces));
+    }
+    Ok(tabla)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn csv(semilla: u64, huella: Option<&str>, set: &str) -> String {
+        let mut csv = format!("# semilla={}\n# dias=2\n", semilla);
+        if let Some(huella) = huella {
+            csv.push_str(&format!("# parametros={}\n# set={}\n", huella, set));
+        }
+        csv + "dia,conejos,cabras\n1,10,5\n2,12,5\n"
+    }
+
+    #[test]
+    fn el_resumen_agrupa_por_la_huella_de_los_parametros() {
+        let corridas = [
+            ("a".to_string(), csv(1, Some("aaaa"), "N_CONEJOS_INICIAL=10")),
+            ("b".to_string(), csv(2, Some("aaaa"), "")),
+            ("c".to_string(), csv(3, Some("bbbb"), "N_CONEJOS_INICIAL=10")),
+        ];
+        let resumen = resumen_bootstrap(&corridas).unwrap();
+        let cabecera = resumen.lines().next().unwrap();
+        assert!(cabecera.starts_with("dias,parametros,variable,"), "{}", cabecera);
+        let grupos: BTreeSet<&str> = resumen.lines().skip(1).map(|l| l.split(',').nth(1).unwrap()).collect();
+        assert_eq!(grupos, BTreeSet::from(["aaaa", "bbbb"]));
+        assert!(resumen.lines().any(|l| l.starts_with("2,aaaa,") && l.split(',').nth(3) == Some("2")));
+    }
+
+    #[test]
+    fn el_resumen_rechaza_corridas_sin_huella() {
+        let corridas = [("a".to_string(), csv(1, Some("aaaa"), "")), ("viejo".to_string(), csv(2, None, ""))];
+        assert!(resumen_bootstrap(&corridas).unwrap_err().starts_with("viejo:"));
+    }
+}
@@ -0,0 +1,221 @@
+// src/reloj.rs
+
+// Reloj de paso fijo para los bucles en tiempo real (el visualizador u otros frontends).
+// Acumula el tiempo real transcurrido y lo convierte en días enteros de simulación, así que la
+// velocidad no depende de los fotogramas por segundo: tras un tirón se simulan los días que
+// faltaron. Para no entrar en una "espiral de la muerte" (simular cada vez más días porque
+// cada fotograma tarda más), el retraso que supere `maximo_dias` se descarta.
+// La fracción del día siguiente ya transcurrida permite interpolar lo que se dibuja (ver
+// `Interpolacion`). `MedidorRitmo` mide los días por segundo que se consiguen de verdad.
+
+use std::collections::HashMap;
+
+use crate::espacio::Posicion;
+use crate::simulacion::Simulacion;
+
+/// Días que se simulan como máximo por llamada a `avanzar`, si no se indica otro.
+pub const MAXIMO_DIAS_POR_DEFECTO: u32 = 10;
+
+/// Acumulador de tiempo real con paso fijo de un día.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RelojSimulacion {
+    /// Segundos de tiempo real que dura un día simulado.
+    segundos_por_dia: f64,
+    /// Días como máximo por llamada a `avanzar`.
+    pub maximo_dias: u32,
+    acumulado: f64,
+}
+
+impl RelojSimulacion {
+    pub fn new(segundos_por_dia: f64) -> Self {
+        Self { segundos_por_dia: segundos_por_dia.max(f64::EPSILON), maximo_dias: MAXIMO_DIAS_POR_DEFECTO, acumulado: 0.0 }
+    }
+
+    pub fn segundos_por_dia(&self) -> f64 {
+        self.segundos_por_dia
+    }
+
+    /// Cambia la duración de un día conservando la fracción ya transcurrida del siguiente,
+    /// para que cambiar de velocidad no adelante ni retrase el próximo paso.
+    pub fn cambiar_velocidad(&mut self, segundos_por_dia: f64) {
+        let segundos_por_dia = segundos_por_dia.max(f64::EPSILON);
+        if segundos_por_dia != self.segundos_por_dia {
+            self.acumulado = self.fraccion() * segundos_por_dia;
+            self.segundos_por_dia = segundos_por_dia;
+        }
+    }
+
+    /// Suma el tiempo real transcurrido y devuelve cuántos días hay que simular ahora.
+    pub fn avanzar(&mut self, segundos: f64) -> u32 {
+        self.acumulado += segundos.max(0.0);
+        let dias = (self.acumulado / self.segundos_por_dia).floor();
+        if dias > self.maximo_dias as f64 {
+            // Demasiado retraso para recuperarlo: se simula el máximo y se descarta el resto.
+            self.acumulado = 0.0;
+            return self.maximo_dias;
+        }
+        self.acumulado -= dias * self.segundos_por_dia;
+        dias as u32
+    }
+
+    /// Fracción (de 0 a 1) del día siguiente que ya ha transcurrido.
+    pub fn fraccion(&self) -> f64 {
+        (self.acumulado / self.segundos_por_dia).clamp(0.0, 1.0)
+    }
+
+    /// Descarta el tiempo acumulado (p. ej. al pausar o al volver de una suspensión).
+    pub fn reiniciar(&mut self) {
+        self.acumulado = 0.0;
+    }
+}
+
+/// Posiciones del día anterior, para dibujar a las presas y al depredador a medio camino de
+/// las del día actual según la fracción del reloj, en lugar de a saltos de un día. Se anotan
+/// antes de simular cada día; si desde entonces la simulación no avanzó exactamente uno (una
+/// carga, un retroceso, otra corrida), se dibujan las posiciones actuales.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Interpolacion {
+    dia: u32,
+    presas: HashMap<u64, Posicion>,
+    depredador: Option<Posicion>,
+    fraccion: f32,
+}
+
+impl Interpolacion {
+    /// Anota las posiciones de ahora, antes de simular el día siguiente.
+    pub fn anotar(&mut self, sim: &Simulacion) {
+        self.dia = sim.dia;
+        self.presas = sim.presas.iter().map(|p| (p.id(), sim.posicion_presa(p.id()))).collect();
+        self.depredador = Some(sim.posicion_depredador());
+    }
+
+    /// Descarta las posiciones anotadas (p. ej. tras mover a mano al depredador).
+    pub fn olvidar(&mut self) {
+        self.presas.clear();
+        self.depredador = None;
+    }
+
+    /// Fija cuánto se avanza desde las posiciones anotadas hacia las actuales (de 0 a 1; ver
+    /// `RelojSimulacion::fraccion`).
+    pub fn fijar_fraccion(&mut self, fraccion: f64) {
+        self.fraccion = fraccion.clamp(0.0, 1.0) as f32;
+    }
+
+    fn entre(&self, sim: &Simulacion, anterior: Option<Posicion>, actual: Posicion) -> Posicion {
+        match anterior {
+            Some(anterior) if sim.dia == self.dia + 1 => Posicion::new(
+                anterior.x + (actual.x - anterior.x) * self.fraccion,
+                anterior.y + (actual.y - anterior.y) * self.fraccion,
+            ),
+            _ => actual,
+        }
+    }
+
+    /// Dónde dibujar una presa; las recién nacidas, en su posición actual.
+    pub fn presa(&self, sim: &Simulacion, id: u64) -> Posicion {
+        self.entre(sim, self.presas.get(&id).copied(), sim.posicion_presa(id))
+    }
+
+    /// Dónde dibujar al depredador de referencia.
+    pub fn depredador(&self, sim: &Simulacion) -> Posicion {
+        self.entre(sim, self.depredador, sim.posicion_depredador())
+    }
+}
+
+/// Segundos sobre los que se promedia el ritmo medido.
+const VENTANA_MEDICION: f64 = 0.5;
+
+/// Mide el ritmo conseguido en días simulados por segundo de tiempo real.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MedidorRitmo {
+    dias: u32,
+    segundos: f64,
+    ritmo: f64,
+}
+
+impl MedidorRitmo {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Anota los días simulados en un fotograma y lo que duró; el ritmo se actualiza cada
+    /// medio segundo para que la cifra se pueda leer.
+    pub fn anotar(&mut self, dias: u32, segundos: f64) {
+        self.dias += dias;
+        self.segundos += segundos.max(0.0);
+        if self.segundos >= VENTANA_MEDICION {
+            self.ritmo = self.dias as f64 / self.segundos;
+            self.dias = 0;
+            self.segundos = 0.0;
+        }
+    }
+
+    /// Días por segundo en la última medición.
+    pub fn ritmo(&self) -> f64 {
+        self.ritmo
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn avanzar_convierte_el_tiempo_en_dias_y_guarda_el_resto() {
+        let mut reloj = RelojSimulacion::new(0.1);
+        assert_eq!(reloj.avanzar(0.05), 0);
+        assert!((reloj.fraccion() - 0.5).abs() < 1e-9);
+        assert_eq!(reloj.avanzar(0.26), 3);
+        assert!((reloj.fraccion() - 0.1).abs() < 1e-9);
+        assert_eq!(reloj.avanzar(-1.0), 0);
+    }
+
+    #[test]
+    fn cambiar_velocidad_conserva_la_fraccion() {
+        let mut reloj = RelojSimulacion::new(0.1);
+        reloj.avanzar(0.025);
+        reloj.cambiar_velocidad(0.5);
+        assert!((reloj.fraccion() - 0.25).abs() < 1e-9);
+        assert_eq!(reloj.avanzar(0.374), 0);
+        assert_eq!(reloj.avanzar(0.002), 1);
+    }
+
+    #[test]
+    fn un_retraso_excesivo_se_recorta_al_maximo() {
+        let mut reloj = RelojSimulacion::new(0.1);
+        assert_eq!(reloj.avanzar(5.0), MAXIMO_DIAS_POR_DEFECTO);
+        assert_eq!(reloj.fraccion(), 0.0);
+        reloj.maximo_dias = 100;
+        assert_eq!(reloj.avanzar(5.0), 50);
+    }
+
+    #[test]
+    fn reiniciar_descarta_lo_acumulado() {
+        let mut reloj = RelojSimulacion::new(0.1);
+        reloj.avanzar(0.09);
+        reloj.reiniciar();
+        assert_eq!(reloj.fraccion(), 0.0);
+        assert_eq!(reloj.avanzar(0.05), 0);
+    }
+
+    #[test]
+    fn la_interpolacion_va_del_dia_anterior_al_actual() {
+        let mut sim = Simulacion::con_semilla(9);
+        let mut interpolacion = Interpolacion::default();
+        interpolacion.anotar(&sim);
+        let id = sim.presas[0].id();
+        let antes = sim.posicion_presa(id);
+        sim.avanzar_dia();
+        let despues = sim.posicion_presa(id);
+        interpolacion.fijar_fraccion(0.0);
+        assert_eq!(interpolacion.presa(&sim, id), antes);
+        interpolacion.fijar_fraccion(1.0);
+        assert_eq!(interpolacion.presa(&sim, id), despues);
+        interpolacion.fijar_fraccion(0.5);
+        let medio = interpolacion.presa(&sim, id);
+        assert!((medio.x - (antes.x + despues.x) / 2.0).abs() < 1e-3);
+        // Si la simulación avanza sin anotar, se dibuja donde está.
+        sim.avanzar_dia();
+        assert_eq!(interpolacion.presa(&sim, id), sim.posicion_presa(id));
+    }
+}
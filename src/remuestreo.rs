@@ -0,0 +1,161 @@
+// src/remuestreo.rs
+
+// Resumen de lotes de corridas con intervalos de confianza bootstrap. De cada corrida se
+// extrae su desenlace (picos de población, qué especies se extinguieron y cuándo, cuánto vivió
+// el depredador) y, para cada resultado clave del lote, se da la media, el mínimo y el máximo
+// junto con un intervalo de percentiles obtenido remuestreando las corridas con reemplazo. Así
+// la tabla dice también cuánto se puede fiar uno de la media, que con pocas corridas suele ser poco.
+// El generador del remuestreo usa una semilla fija: el mismo lote da siempre el mismo intervalo.
+
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+
+/// Remuestreos por intervalo.
+pub const REMUESTREOS: u32 = 2000;
+/// Nivel de confianza de los intervalos.
+pub const NIVEL_CONFIANZA: f64 = 0.95;
+const SEMILLA_REMUESTREO: u64 = 0x5EED_B007;
+
+/// Columnas de la tabla de resumen que siguen a las que identifican el grupo.
+pub const ENCABEZADO_CSV: &str = "variable,n,media,ic95_inferior,ic95_superior,minimo,maximo";
+
+/// Resultados clave del resumen, en el orden en que se escriben.
+pub const VARIABLES: &[&str] = &[
+    "pico_conejos", "pico_cabras", "pico_presas",
+    "extincion_conejos", "extincion_cabras", "extincion_presas",
+    "dia_extincion_conejos", "dia_extincion_cabras", "dia_extincion_presas",
+    "muerte_depredador", "vida_depredador",
+];
+
+/// Lo que interesa de una corrida para resumir el lote.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Desenlace {
+    pub pico_conejos: u32,
+    pub pico_cabras: u32,
+    /// Máximo de presas vivas a la vez (no la suma de los dos picos).
+    pub pico_presas: u32,
+    pub conejos_finales: u32,
+    pub cabras_finales: u32,
+    /// Día desde el que no queda ninguna presa de cada clase; `None` si al final quedaba alguna.
+    pub extincion_conejos: Option<u32>,
+    pub extincion_cabras: Option<u32>,
+    pub extincion_presas: Option<u32>,
+    /// Día en que empezó la corrida y último día observado.
+    pub dia_inicial: u32,
+    pub ultimo_dia: u32,
+    /// Si el depredador murió (la corrida termina ese día).
+    pub depredador_muerto: bool,
+}
+
+impl Desenlace {
+    /// Desenlace vacío de una corrida que empieza el día indicado.
+    pub fn desde(dia_inicial: u32) -> Self {
+        Self { dia_inicial, ultimo_dia: dia_inicial, ..Self::default() }
+    }
+
+    /// Anota las poblaciones de un día.
+    pub fn observar(&mut self, dia: u32, conejos: u32, cabras: u32) {
+        self.pico_conejos = self.pico_conejos.max(conejos);
+        self.pico_cabras = self.pico_cabras.max(cabras);
+        self.pico_presas = self.pico_presas.max(conejos + cabras);
+        self.conejos_finales = conejos;
+        self.cabras_finales = cabras;
+        let extincion = |desde: Option<u32>, quedan: u32| if quedan == 0 { desde.or(Some(dia)) } else { None };
+        self.extincion_conejos = extincion(self.extincion_conejos, conejos);
+        self.extincion_cabras = extincion(self.extincion_cabras, cabras);
+        self.extincion_presas = extincion(self.extincion_presas, conejos + cabras);
+        self.ultimo_dia = dia;
+    }
+
+    /// Si al final de la corrida seguían vivos el depredador y alguna presa.
+    pub fn coexisten(&self) -> bool {
+        !self.depredador_muerto && self.conejos_finales + self.cabras_finales > 0
+    }
+
+    /// Días que vivió el depredador desde que empezó la corrida. Si llegó vivo al final es la
+    /// duración de la corrida, así que con depredadores longevos la media se queda corta.
+    pub fn vida_depredador(&self) -> u32 {
+        self.ultimo_dia.saturating_sub(self.dia_inicial)
+    }
+
+    /// Valor de cada una de `VARIABLES`, o `None` si no aplica a esta corrida (el día de
+    /// extinción de una especie que sobrevivió). Las extinciones y la muerte del depredador
+    /// valen 1 o 0, así que su media es una probabilidad.
+    fn valores(&self) -> [Option<f64>; 11] {
+        let indicador = |ocurrio: bool| Some(if ocurrio { 1.0 } else { 0.0 });
+        let dia = |extincion: Option<u32>| extincion.map(|d| d as f64);
+        [
+            Some(self.pico_conejos as f64),
+            Some(self.pico_cabras as f64),
+            Some(self.pico_presas as f64),
+            indicador(self.conejos_finales == 0),
+            indicador(self.cabras_finales == 0),
+            indicador(self.conejos_finales + self.cabras_finales == 0),
+            dia(self.extincion_conejos),
+            dia(self.extincion_cabras),
+            dia(self.extincion_presas),
+            indicador(self.depredador_muerto),
+            Some(self.vida_depredador() as f64),
+        ]
+    }
+}
+
+/// Media de una muestra con su intervalo de confianza y sus extremos.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Intervalo {
+    pub n: usize,
+    pub media: f64,
+    pub inferior: f64,
+    pub superior: f64,
+    pub minimo: f64,
+    pub maximo: f64,
+}
+
+/// Intervalo bootstrap de percentiles para la media de `valores`; `None` si no hay valores.
+pub fn intervalo_media(valores: &[f64], rng: &mut ChaCha8Rng) -> Option<Intervalo> {
+    if valores.is_empty() {
+        return None;
+    }
+    let n = valores.len();
+    let mut medias: Vec<f64> = (0..REMUESTREOS)
+        .map(|_| (0..n).map(|_| valores[rng.gen_range(0..n)]).sum::<f64>() / n as f64)
+        .collect();
+    medias.sort_by(f64::total_cmp);
+    let cola = (1.0 - NIVEL_CONFIANZA) / 2.0;
+    let percentil = |p: f64| medias[((p * (medias.len() - 1) as f64).round() as usize).min(medias.len() - 1)];
+    Some(Intervalo {
+        n,
+        media: valores.iter().sum::<f64>() / n as f64,
+        inferior: percentil(cola),
+        superior: percentil(1.0 - cola),
+        minimo: valores.iter().copied().fold(f64::INFINITY, f64::min),
+        maximo: valores.iter().copied().fold(f64::NEG_INFINITY, f64::max),
+    })
+}
+
+/// Intervalos de cada resultado clave de un lote, en el orden de `VARIABLES`. Cada uno se
+/// calcula solo con las corridas en que el resultado aplica.
+pub fn resumir(desenlaces: &[Desenlace]) -> Vec<(&'static str, Option<Intervalo>)> {
+    let mut rng = ChaCha8Rng::seed_from_u64(SEMILLA_REMUESTREO);
+    let valores: Vec<[Option<f64>; 11]> = desenlaces.iter().map(Desenlace::valores).collect();
+    VARIABLES.iter().enumerate()
+        .map(|(i, &variable)| {
+            let columna: Vec<f64> = valores.iter().filter_map(|v| v[i]).collect();
+            (variable, intervalo_media(&columna, &mut rng))
+        })
+        .collect()
+}
+
+/// Filas CSV del resumen de un lote, cada una precedida de `prefijo` (las celdas que
+/// identifican el grupo, ya separadas por comas y terminadas en coma, o vacío).
+pub fn filas_csv(prefijo: &str, desenlaces: &[Desenlace]) -> String {
+    resumir(desenlaces).into_iter()
+        .map(|(variable, intervalo)| match intervalo {
+            Some(i) => format!(
+                "{}{},{},{:.4},{:.4},{:.4},{:.4},{:.4}\n",
+                prefijo, variable, i.n, i.media, i.inferior, i.superior, i.minimo, i.maximo
+            ),
+            None => format!("{}{},0,,,,,\n", prefijo, variable),
+        })
+        .collect()
+}
@@ -0,0 +1,153 @@
+// src/repeticion.rs
+
+// Repeticiones: el resumen de una corrida, día a día, guardado en un archivo compacto para
+// volver a verla después. A diferencia de una instantánea no guarda las presas una a una ni
+// el estado del generador, solo lo que se ve (poblaciones, depredador, balance del día y
+// sucesos), así que la reproducción no vuelve a simular nada: no depende del determinismo ni
+// de la versión del modelo con que se grabó, y se puede saltar a cualquier día al instante.
+//
+// El archivo tiene el mismo esquema que las instantáneas binarias: una firma propia, la
+// versión del formato y el contenido en bincode comprimido con zstd.
+
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::ascii::{barra, ANCHO_BARRA};
+use crate::eventos::Evento;
+use crate::instantanea::{comprimir, datos_invalidos, descomprimir};
+use crate::parametros::Parametros;
+use crate::simulacion::Simulacion;
+
+/// Versión del formato de las repeticiones.
+pub const VERSION_REPETICION: u32 = 1;
+
+/// Firma con la que empiezan los archivos de repetición.
+pub const FIRMA_REPETICION: &[u8; 4] = b"SPDR";
+
+/// Lo que se vio de la simulación al terminar un día.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Fotograma {
+    pub dia: u32,
+    pub conejos: u32,
+    pub cabras: u32,
+    pub reserva_kg: f64,
+    pub depredador_vivo: bool,
+    /// "optimo", "minimo", "peligro" o "muerto" (ver `Simulacion::estado_depredador`).
+    pub estado_depredador: String,
+    pub nacimientos: u32,
+    pub muertes: u32,
+    pub cazas: u32,
+    pub eventos: Vec<Evento>,
+}
+
+impl Fotograma {
+    pub fn de(sim: &Simulacion) -> Self {
+        let (conejos, cabras) = sim.contar_especies();
+        let estadisticas = &sim.estadisticas_dia;
+        Self {
+            dia: sim.dia,
+            conejos: conejos as u32,
+            cabras: cabras as u32,
+            reserva_kg: sim.depredador.reserva_comida_kg,
+            depredador_vivo: sim.depredador.vivo,
+            estado_depredador: sim.estado_depredador().to_string(),
+            nacimientos: estadisticas.nacimientos,
+            muertes: estadisticas.muertes,
+            cazas: estadisticas.cazas,
+            // Los nacimientos y muertes de cada presa inflarían el archivo sin verse en la repetición.
+            eventos: sim.eventos_dia.iter().filter(|e| !e.es_demografico()).cloned().collect(),
+        }
+    }
+}
+
+/// Corrida grabada: los datos para identificarla y un fotograma por día.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Repeticion {
+    pub semilla: u64,
+    pub parametros: Parametros,
+    pub fotogramas: Vec<Fotograma>,
+}
+
+impl Repeticion {
+    /// Empieza a grabar con el estado inicial de `sim` como primer fotograma.
+    pub fn new(sim: &Simulacion) -> Self {
+        Self { semilla: sim.semilla(), parametros: sim.parametros.clone(), fotogramas: vec![Fotograma::de(sim)] }
+    }
+
+    /// Añade el fotograma del día que acaba de terminar.
+    pub fn grabar(&mut self, sim: &Simulacion) {
+        self.fotogramas.push(Fotograma::de(sim));
+    }
+
+    pub fn guardar(&self, ruta: impl AsRef<Path>) -> io::Result<()> {
+        fs::write(ruta, self.a_binario()?)
+    }
+
+    pub fn cargar(ruta: impl AsRef<Path>) -> io::Result<Self> {
+        Self::desde_bytes(&fs::read(ruta)?)
+    }
+
+    /// Codifica la repetición: firma, versión (u32 little endian) y bincode comprimido con zstd.
+    pub fn a_binario(&self) -> io::Result<Vec<u8>> {
+        let datos = bincode::serialize(self).map_err(io::Error::other)?;
+        let mut bytes = Vec::with_capacity(datos.len() / 4);
+        bytes.extend_from_slice(FIRMA_REPETICION);
+        bytes.extend_from_slice(&VERSION_REPETICION.to_le_bytes());
+        bytes.extend(comprimir(&datos)?);
+        Ok(bytes)
+    }
+
+    pub fn desde_bytes(bytes: &[u8]) -> io::Result<Self> {
+        let resto = bytes.strip_prefix(FIRMA_REPETICION).ok_or_else(|| datos_invalidos("no es un archivo de repetición"))?;
+        let (version, comprimido) = resto.split_at_checked(4).ok_or_else(|| datos_invalidos("repetición truncada"))?;
+        match u32::from_le_bytes(version.try_into().unwrap_or_default()) {
+            VERSION_REPETICION => bincode::deserialize(&descomprimir(comprimido)?).map_err(datos_invalidos),
+            otra => Err(datos_invalidos(format!("versión de repetición no soportada: {}", otra))),
+        }
+    }
+
+    /// Índice del fotograma del día `dia`, o del último anterior si la corrida terminó antes.
+    pub fn indice_del_dia(&self, dia: u32) -> usize {
+        self.fotogramas.partition_point(|f| f.dia <= dia).saturating_sub(1)
+    }
+
+    /// Dibuja el fotograma `indice` en texto, como `Simulacion::render_ascii`, con las barras
+    /// escaladas a los picos de toda la corrida (así no saltan al avanzar) y una línea de
+    /// tiempo con la posición actual.
+    pub fn render_ascii(&self, indice: usize) -> String {
+        let Some(fotograma) = self.fotogramas.get(indice) else {
+            return String::new();
+        };
+        let mayor = self.fotogramas.iter().map(|f| f.conejos.max(f.cabras)).max().unwrap_or(0) as f64;
+        let reserva_maxima = self.fotogramas.iter().map(|f| f.reserva_kg).fold(self.parametros.depredador_reserva_inicial_kg, f64::max);
+        let ultimo = self.fotogramas.last().map_or(0, |f| f.dia);
+
+        let posicion = if ultimo > 0 { (fotograma.dia as usize * (ANCHO_BARRA - 1)) / ultimo as usize } else { 0 };
+        let mut linea_tiempo = ".".repeat(ANCHO_BARRA);
+        linea_tiempo.replace_range(posicion..posicion + 1, "|");
+
+        let mut texto = format!("Dia {} de {} (semilla {})\n", fotograma.dia, ultimo, self.semilla);
+        texto.push_str(&format!("{:<10} {:>7} [{}]\n", "Tiempo", indice, linea_tiempo));
+        texto.push_str(&format!("{:<10} {:>7} {}\n", "Conejos", fotograma.conejos, barra(fotograma.conejos as f64, mayor, ANCHO_BARRA)));
+        texto.push_str(&format!("{:<10} {:>7} {}\n", "Cabras", fotograma.cabras, barra(fotograma.cabras as f64, mayor, ANCHO_BARRA)));
+        texto.push_str(&format!(
+            "{:<10} {:>7} {} {:.1} kg ({})\n",
+            "Depredador",
+            if fotograma.depredador_vivo { "vivo" } else { "muerto" },
+            barra(fotograma.reserva_kg, reserva_maxima, ANCHO_BARRA),
+            fotograma.reserva_kg,
+            fotograma.estado_depredador
+        ));
+        texto.push_str(&format!(
+            "Hoy: {} nacimientos, {} muertes, {} cazas\n",
+            fotograma.nacimientos, fotograma.muertes, fotograma.cazas
+        ));
+        for evento in &fotograma.eventos {
+            texto.push_str(&format!("! {}\n", evento.descripcion()));
+        }
+        texto
+    }
+}
@@ -0,0 +1,161 @@
+// src/reproductor.rs
+
+// Reproductor de corridas grabadas (parte del binario, no de la biblioteca).
+// Con `--reproducir` el visualizador no abre una simulación sino una repetición (ver
+// `repeticion`) grabada con `simulador run --replay` o con F6 en el propio visualizador: la
+// curva de población de toda la corrida con una marca en el día que se está viendo, las cifras
+// de ese día y sus sucesos. No se vuelve a simular nada, así que se puede ir a cualquier día al
+// instante, hacia delante o hacia atrás.
+//
+// Uso:
+//   simulador_ecosistema_presa_depredador --reproducir CORRIDA.replay
+// Espacio reproduce o pausa; las flechas retroceden o avanzan un día (con Mayús, un mes); Inicio
+// y Fin van al primer y último día; un clic o arrastre en la línea de tiempo salta a ese día.
+
+use std::path::PathBuf;
+
+use macroquad::prelude::*;
+
+use simulador_ecosistema_presa_depredador::idioma::{Idioma, Texto};
+use simulador_ecosistema_presa_depredador::repeticion::Repeticion;
+
+use crate::tablero::dibujar_curva;
+
+/// Días por segundo al reproducir, el ritmo normal del visualizador.
+const DIAS_POR_SEGUNDO: f32 = 10.0;
+/// Días que saltan las flechas con Mayús.
+const SALTO_LARGO: usize = 30;
+/// Sucesos del día que se listan como mucho.
+const MAXIMO_SUCESOS: usize = 5;
+const MARGEN: f32 = 12.0;
+const ALTO_TEXTO: f32 = 20.0;
+const ALTO_LINEA_TIEMPO: f32 = 16.0;
+
+/// Opciones del reproductor.
+#[derive(Debug, Clone)]
+pub struct OpcionesReproductor {
+    pub ruta: PathBuf,
+}
+
+impl OpcionesReproductor {
+    /// Lee las opciones de la línea de comandos; `None` si no se pidió reproducir.
+    pub fn desde_args(args: impl Iterator<Item = String>) -> Result<Option<Self>, String> {
+        let mut ruta = None;
+        let mut args = args;
+        while let Some(arg) = args.next() {
+            if arg == "--reproducir" {
+                ruta = Some(PathBuf::from(args.next().ok_or("falta el valor de --reproducir")?));
+            }
+        }
+        Ok(ruta.map(|ruta| Self { ruta }))
+    }
+}
+
+/// Carga la repetición y la muestra hasta que se cierre la ventana.
+pub async fn mostrar(opciones: &OpcionesReproductor, idioma: Idioma) -> Result<(), String> {
+    let repeticion = Repeticion::cargar(&opciones.ruta).map_err(|e| format!("no se pudo leer {}: {}", opciones.ruta.display(), e))?;
+    if repeticion.fotogramas.is_empty() {
+        return Err(format!("{}: la repetición no tiene ningún día", opciones.ruta.display()));
+    }
+    let poblaciones: Vec<(u32, u32)> = repeticion.fotogramas.iter().map(|f| (f.conejos, f.cabras)).collect();
+    let mayor = poblaciones.iter().map(|&(conejos, cabras)| conejos.max(cabras)).max().unwrap_or(0).max(1) as f32;
+    let ultimo = repeticion.fotogramas.len() - 1;
+
+    let mut indice = 0;
+    let mut reproduciendo = false;
+    let mut acumulado = 0.0;
+    loop {
+        let linea_tiempo = Rect::new(MARGEN, screen_height() - MARGEN - ALTO_TEXTO - ALTO_LINEA_TIEMPO, screen_width() - 2.0 * MARGEN, ALTO_LINEA_TIEMPO);
+
+        // Controles: cualquier salto a mano detiene la reproducción.
+        let salto = if is_key_down(KeyCode::LeftShift) || is_key_down(KeyCode::RightShift) { SALTO_LARGO } else { 1 };
+        let pedido = if is_key_pressed(KeyCode::Right) {
+            Some((indice + salto).min(ultimo))
+        } else if is_key_pressed(KeyCode::Left) {
+            Some(indice.saturating_sub(salto))
+        } else if is_key_pressed(KeyCode::Home) {
+            Some(0)
+        } else if is_key_pressed(KeyCode::End) {
+            Some(ultimo)
+        } else if is_mouse_button_down(MouseButton::Left) && linea_tiempo.contains(mouse_position().into()) {
+            let fraccion = ((mouse_position().0 - linea_tiempo.x) / linea_tiempo.w).clamp(0.0, 1.0);
+            Some((fraccion * ultimo as f32).round() as usize)
+        } else {
+            None
+        };
+        if let Some(pedido) = pedido {
+            indice = pedido;
+            reproduciendo = false;
+        }
+        if is_key_pressed(KeyCode::Space) {
+            reproduciendo = !reproduciendo;
+            // Al final de la corrida, reproducir vuelve a empezar.
+            if reproduciendo && indice == ultimo {
+                indice = 0;
+            }
+            acumulado = 0.0;
+        }
+        if reproduciendo {
+            acumulado += get_frame_time() * DIAS_POR_SEGUNDO;
+            let dias = acumulado.floor();
+            acumulado -= dias;
+            indice = (indice + dias as usize).min(ultimo);
+            reproduciendo = indice < ultimo;
+        }
+
+        clear_background(WHITE);
+        let fotograma = &repeticion.fotogramas[indice];
+        let mut y = MARGEN + ALTO_TEXTO;
+        let cabecera = format!(
+            "{} {} / {} ({} {}){}",
+            idioma.texto(Texto::Dia), fotograma.dia, repeticion.fotogramas[ultimo].dia,
+            idioma.texto(Texto::Semilla), repeticion.semilla,
+            if reproduciendo { String::new() } else { format!(" - {}", idioma.texto(Texto::Pausa)) }
+        );
+        draw_text(&cabecera, MARGEN, y, ALTO_TEXTO, BLACK);
+        y += ALTO_TEXTO;
+        let estado = idioma.texto(if fotograma.depredador_vivo { Texto::DepredadorVivo } else { Texto::DepredadorSinVida });
+        let poblacion = format!(
+            "{} {}, {} {}, {} ({:.1} kg, {})",
+            fotograma.conejos, idioma.texto(Texto::Conejos), fotograma.cabras, idioma.texto(Texto::Cabras),
+            estado, fotograma.reserva_kg, fotograma.estado_depredador
+        );
+        draw_text(&poblacion, MARGEN, y, ALTO_TEXTO, if fotograma.depredador_vivo { DARKGRAY } else { RED });
+        y += ALTO_TEXTO;
+        let balance = format!(
+            "{}: {}, {}: {}, {}: {}",
+            idioma.texto(Texto::Nacimientos), fotograma.nacimientos,
+            idioma.texto(Texto::Muertes), fotograma.muertes,
+            idioma.texto(Texto::Cazas), fotograma.cazas
+        );
+        draw_text(&balance, MARGEN, y, ALTO_TEXTO, DARKGRAY);
+
+        // Curva de toda la corrida con la marca del día actual.
+        let alto_sucesos = (MAXIMO_SUCESOS + 1) as f32 * ALTO_TEXTO;
+        let grafico = Rect::new(MARGEN, y + MARGEN, screen_width() - 2.0 * MARGEN, (linea_tiempo.y - y - 2.0 * MARGEN - alto_sucesos).max(1.0));
+        draw_rectangle(grafico.x, grafico.y, grafico.w, grafico.h, Color::new(0.96, 0.96, 0.94, 1.0));
+        dibujar_curva(grafico, &poblaciones, mayor, |&(conejos, _)| conejos, BROWN);
+        dibujar_curva(grafico, &poblaciones, mayor, |&(_, cabras)| cabras, DARKBLUE);
+        let fraccion = if ultimo > 0 { indice as f32 / ultimo as f32 } else { 0.0 };
+        let x = grafico.x + grafico.w * fraccion;
+        draw_line(x, grafico.y, x, grafico.y + grafico.h, 1.0, RED);
+
+        // Sucesos del día.
+        let mut y = grafico.y + grafico.h + ALTO_TEXTO;
+        for evento in fotograma.eventos.iter().take(MAXIMO_SUCESOS) {
+            draw_text(&format!("! {}", evento.descripcion()), MARGEN, y, ALTO_TEXTO, BLACK);
+            y += ALTO_TEXTO;
+        }
+        if fotograma.eventos.len() > MAXIMO_SUCESOS {
+            draw_text(&format!("(+{})", fotograma.eventos.len() - MAXIMO_SUCESOS), MARGEN, y, ALTO_TEXTO, GRAY);
+        }
+
+        // Línea de tiempo y ayuda de los controles.
+        draw_rectangle(linea_tiempo.x, linea_tiempo.y, linea_tiempo.w, linea_tiempo.h, LIGHTGRAY);
+        draw_rectangle(linea_tiempo.x, linea_tiempo.y, linea_tiempo.w * fraccion, linea_tiempo.h, GRAY);
+        draw_circle(linea_tiempo.x + linea_tiempo.w * fraccion, linea_tiempo.y + linea_tiempo.h / 2.0, linea_tiempo.h * 0.6, RED);
+        draw_text(idioma.texto(Texto::AyudaReproductor), MARGEN, screen_height() - MARGEN, ALTO_TEXTO * 0.8, DARKGRAY);
+
+        next_frame().await;
+    }
+}
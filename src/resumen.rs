@@ -0,0 +1,107 @@
+// src/resumen.rs
+
+// Resumen del estado en frases completas, pensado para lectores de pantalla y para pegarlo en
+// un chat al pedir ayuda. A diferencia de `render_ascii` no usa barras ni columnas alineadas,
+// que un lector de pantalla lee como ruido: cada línea es una frase y van siempre en el mismo
+// orden (día, poblaciones, tendencia, depredador, último día y sucesos recientes).
+
+use crate::eventos::Evento;
+use crate::simulacion::Simulacion;
+
+/// Días hacia atrás con los que se calcula la tendencia.
+pub const DIAS_TENDENCIA: u32 = 30;
+/// Sucesos recientes que se incluyen, los últimos.
+const MAXIMO_SUCESOS: usize = 10;
+/// Cambio relativo por debajo del cual se considera que una población se mantiene.
+const CAMBIO_ESTABLE: f64 = 0.05;
+
+/// Cómo cambió una población entre `antes` y `ahora`, p. ej. "suben de 250 a 312 (+25 %)".
+fn tendencia(antes: usize, ahora: usize) -> String {
+    if antes == 0 {
+        return if ahora == 0 { "siguen sin individuos".to_string() } else { format!("reaparecen con {}", ahora) };
+    }
+    if ahora == 0 {
+        return format!("se extinguen (había {})", antes);
+    }
+    let cambio = (ahora as f64 - antes as f64) / antes as f64;
+    if cambio.abs() < CAMBIO_ESTABLE {
+        format!("se mantienen estables ({} a {})", antes, ahora)
+    } else if cambio > 0.0 {
+        format!("suben de {} a {} (+{:.0} %)", antes, ahora, cambio * 100.0)
+    } else {
+        format!("bajan de {} a {} ({:.0} %)", antes, ahora, cambio * 100.0)
+    }
+}
+
+impl Simulacion {
+    /// Resumen del estado en texto plano, una frase por línea. `registro` son los sucesos
+    /// recientes con su día (p. ej. el registro de eventos de la interfaz); si está vacío se
+    /// usan los del último día simulado.
+    ///
+    /// ```text
+    /// Resumen de la simulación, día 120 (semilla 42).
+    /// Poblaciones: 312 conejos y 20 cabras, 332 presas en total.
+    /// Tendencia de los últimos 30 días: los conejos suben de 250 a 312 (+25 %); las cabras se mantienen estables (20 a 20).
+    /// Depredador: vivo, con 300.0 kg de reserva, por encima de su consumo óptimo de 10.0 kg al día.
+    /// Último día: 12 nacimientos, 3 muertes y 1 cazas.
+    /// Sucesos recientes:
+    /// - Día 118: Extinción de Cabra
+    /// ```
+    pub fn resumen_texto(&self, registro: &[(u32, String)]) -> String {
+        let (conejos, cabras) = self.contar_especies();
+        let mut texto = format!("Resumen de la simulación, día {} (semilla {}).\n", self.dia, self.semilla());
+        texto.push_str(&format!("Poblaciones: {} conejos y {} cabras, {} presas en total.\n", conejos, cabras, conejos + cabras));
+
+        let recientes = self.poblaciones_recientes();
+        let desde = self.dia.saturating_sub(DIAS_TENDENCIA);
+        // La instantánea más reciente de hace al menos `DIAS_TENDENCIA` días o, al principio de
+        // la corrida, la más antigua que haya.
+        let referencia = recientes.iter().rev().find(|(dia, _, _)| *dia <= desde)
+            .or_else(|| recientes.first())
+            .filter(|(dia, _, _)| *dia < self.dia);
+        match referencia {
+            Some(&(dia, conejos_antes, cabras_antes)) => texto.push_str(&format!(
+                "Tendencia de los últimos {} días: los conejos {}; las cabras {}.\n",
+                self.dia - dia, tendencia(conejos_antes, conejos), tendencia(cabras_antes, cabras)
+            )),
+            None => texto.push_str("Tendencia: todavía no hay días anteriores con los que comparar.\n"),
+        }
+
+        let reserva = self.depredador.reserva_comida_kg;
+        let (minimo, optimo) = self.parametros.consumo_depredador_kg();
+        let estado = match self.estado_depredador() {
+            "muerto" => "muerto".to_string(),
+            "optimo" => format!("vivo, con {:.1} kg de reserva, por encima de su consumo óptimo de {:.1} kg al día", reserva, optimo),
+            "minimo" => format!("vivo, con {:.1} kg de reserva, entre su consumo mínimo de {:.1} kg y el óptimo de {:.1} kg al día", reserva, minimo, optimo),
+            _ => format!("en peligro, con {:.1} kg de reserva, por debajo de su consumo mínimo de {:.1} kg al día", reserva, minimo),
+        };
+        texto.push_str(&format!("Depredador: {}.\n", estado));
+
+        let estadisticas = &self.estadisticas_dia;
+        texto.push_str(&format!(
+            "Último día: {} nacimientos, {} muertes y {} cazas.\n",
+            estadisticas.nacimientos, estadisticas.muertes, estadisticas.cazas
+        ));
+
+        let del_dia: Vec<(u32, String)>;
+        let sucesos = if registro.is_empty() {
+            // Las cazas, los nacimientos y las muertes ya cuentan en la línea anterior.
+            del_dia = self.eventos_dia.iter()
+                .filter(|e| !matches!(e, Evento::Caza { .. }) && !e.es_demografico())
+                .map(|e| (self.dia, e.descripcion()))
+                .collect();
+            &del_dia[..]
+        } else {
+            registro
+        };
+        if sucesos.is_empty() {
+            texto.push_str("Sucesos recientes: ninguno.\n");
+        } else {
+            texto.push_str("Sucesos recientes:\n");
+            for (dia, descripcion) in &sucesos[sucesos.len().saturating_sub(MAXIMO_SUCESOS)..] {
+                texto.push_str(&format!("- Día {}: {}\n", dia, descripcion));
+            }
+        }
+        texto
+    }
+}
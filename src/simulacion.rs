@@ -4,46 +4,335 @@
 // Orquesta las interacciones entre las entidades y gestiona el paso del tiempo.
 // Es independiente de la visualización.
 
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::fmt;
+use std::path::Path;
+
+use crate::alarmas::Alarma;
+use crate::bus::{Bus, Fase, Suceso};
+use crate::censo;
+use crate::clima::{self, Estacion};
+use crate::energia::{BalanceEnergia, FlujosKg, MetricasTroficas};
+use crate::enfermedad::{RecuentoSir, Salud};
 use crate::entidades::*;
-use rand::thread_rng;
+use crate::espacio::{self, Posicion};
+use crate::eventos::{Evento, TipoEvento};
+use crate::parametros::{CadenaTrofica, Enfermedad, Parametros};
+use crate::pastizal::Pastizal;
+use crate::recarga;
+use crate::instantanea::{EstadoPresa, Instantanea, VERSION_INSTANTANEA};
+use rand_chacha::ChaCha8Rng;
+use rand::{Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+
+/// Cada cuántos días se retiene por defecto una instantánea para poder retroceder.
+pub const INTERVALO_INSTANTANEAS: u32 = 10;
+/// Días hacia atrás que cubren por defecto las instantáneas retenidas.
+pub const HORIZONTE_INSTANTANEAS_DIAS: u32 = 500;
+/// Memoria que pueden ocupar por defecto las instantáneas retenidas (32 MiB).
+pub const PRESUPUESTO_INSTANTANEAS_BYTES: usize = 32 << 20;
+
+/// Qué instantáneas se retienen para poder retroceder (ver `Simulacion::retroceder_a`). Cada
+/// una copia la población entera, así que se descartan las más antiguas en cuanto quedan fuera
+/// del horizonte o no caben en el presupuesto de memoria. Se retiene una cada `intervalo_dias`
+/// días y otra tras cada intervención, pero nunca más de una por día.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Retencion {
+    /// Cada cuántos días se retiene una instantánea; con 0 no se retiene ninguna.
+    pub intervalo_dias: u32,
+    pub horizonte_dias: u32,
+    pub presupuesto_bytes: usize,
+}
+
+impl Retencion {
+    /// Sin instantáneas: para las corridas por lotes, que nunca retroceden.
+    pub const DESACTIVADA: Retencion = Retencion { intervalo_dias: 0, horizonte_dias: 0, presupuesto_bytes: 0 };
+
+    pub fn activa(&self) -> bool {
+        self.intervalo_dias > 0
+    }
+}
+
+impl Default for Retencion {
+    fn default() -> Self {
+        Self {
+            intervalo_dias: INTERVALO_INSTANTANEAS,
+            horizonte_dias: HORIZONTE_INSTANTANEAS_DIAS,
+            presupuesto_bytes: PRESUPUESTO_INSTANTANEAS_BYTES,
+        }
+    }
+}
+
+// Subflujos aleatorios (ver `SUBFLUJOS_ALEATORIOS`): uno por tipo de decisión y uno por presa.
+const SUBFLUJO_CAZA: u64 = 1;
+const SUBFLUJO_BROTES: u64 = 2;
+const SUBFLUJO_DENSIDAD: u64 = 3;
+const SUBFLUJO_MOVIMIENTO: u64 = 4;
+const SUBFLUJO_MANADA: u64 = 5;
+const SUBFLUJO_ENFERMEDAD: u64 = 6;
+/// Se mezcla con la semilla para que los flujos de las presas no coincidan con los de las decisiones.
+const SAL_PRESAS: u64 = 0x9E37_79B9_7F4A_7C15;
+
+/// Generador de un subflujo para el día indicado: el flujo `flujo` de la semilla, avanzado
+/// hasta una posición propia del día, de modo que no depende de lo que se sorteó antes.
+fn subflujo(semilla: u64, flujo: u64, dia: u32) -> ChaCha8Rng {
+    let mut rng = ChaCha8Rng::seed_from_u64(semilla);
+    rng.set_stream(flujo);
+    rng.set_word_pos((dia as u128) << 32);
+    rng
+}
+
+/// Contadores de lo ocurrido durante el último día simulado.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct EstadisticasDia {
+    pub nacimientos: u32,
+    /// Muertes de presas sin contar las cazadas.
+    pub muertes: u32,
+    pub cazas: u32,
+    /// Intentos de caza en que la presa escapó (solo con `EXITO_CAZA`).
+    pub cazas_fallidas: u32,
+    /// Presas que se contagiaron y presas infectadas al final del día (solo con `ENFERMEDAD`).
+    pub contagios: u32,
+    pub infectadas: u32,
+    /// Todas las muertes del día, también las cazas, según su causa.
+    pub muertes_por_causa: MuertesPorCausa,
+}
+
+/// Muertes de presas separadas por causa (ver `CausaMuerte`).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct MuertesPorCausa {
+    pub vejez: u32,
+    pub enfermedad: u32,
+    pub hambre: u32,
+    pub caza: u32,
+    pub sobrepoblacion: u32,
+    pub mortalidad_clase: u32,
+}
+
+impl MuertesPorCausa {
+    pub fn de(&self, causa: CausaMuerte) -> u32 {
+        match causa {
+            CausaMuerte::Vejez => self.vejez,
+            CausaMuerte::Enfermedad => self.enfermedad,
+            CausaMuerte::Hambre => self.hambre,
+            CausaMuerte::Caza => self.caza,
+            CausaMuerte::Sobrepoblacion => self.sobrepoblacion,
+            CausaMuerte::MortalidadClase => self.mortalidad_clase,
+        }
+    }
+
+    pub(crate) fn anotar(&mut self, causa: CausaMuerte, cantidad: u32) {
+        let contador = match causa {
+            CausaMuerte::Vejez => &mut self.vejez,
+            CausaMuerte::Enfermedad => &mut self.enfermedad,
+            CausaMuerte::Hambre => &mut self.hambre,
+            CausaMuerte::Caza => &mut self.caza,
+            CausaMuerte::Sobrepoblacion => &mut self.sobrepoblacion,
+            CausaMuerte::MortalidadClase => &mut self.mortalidad_clase,
+        };
+        *contador += cantidad;
+    }
+
+    pub fn sumar(&mut self, otras: &MuertesPorCausa) {
+        for causa in CausaMuerte::TODAS {
+            self.anotar(causa, otras.de(causa));
+        }
+    }
+
+    pub fn total(&self) -> u32 {
+        CausaMuerte::TODAS.into_iter().map(|causa| self.de(causa)).sum()
+    }
+
+    /// Fracción de las muertes que se debe a la causa indicada (`None` si no hubo ninguna).
+    pub fn fraccion(&self, causa: CausaMuerte) -> Option<f64> {
+        match self.total() {
+            0 => None,
+            total => Some(self.de(causa) as f64 / total as f64),
+        }
+    }
+}
+
+impl fmt::Display for MuertesPorCausa {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for causa in CausaMuerte::TODAS {
+            let porcentaje = self.fraccion(causa).map_or("-".to_string(), |v| format!("{:.1} %", v * 100.0));
+            writeln!(f, "  {:<24} {:>8} {:>8}", format!("{:?}:", causa), self.de(causa), porcentaje)?;
+        }
+        write!(f, "  {:<24} {:>8}", "Total:", self.total())
+    }
+}
+
+/// Segundos que tardó cada fase del último día simulado, para el panel de depuración.
+/// Solo se mide si se dio un reloj con `Simulacion::perfilar`; si no, todo queda en 0.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct PerfilDia {
+    pub caza: f64,
+    /// Pastoreo y envejecimiento de las presas.
+    pub envejecimiento: f64,
+    pub reproduccion: f64,
+    /// Crías, brotes, muertes, tope de densidad, eventos e instantáneas.
+    pub limpieza: f64,
+}
+
+impl PerfilDia {
+    pub fn total(&self) -> f64 {
+        self.caza + self.envejecimiento + self.reproduccion + self.limpieza
+    }
+}
+
+/// Lo que las fases de un día se pasan entre sí.
+struct Jornada {
+    poblacion_previa: (usize, usize),
+    peligro_previo: bool,
+    nuevas_crias: Vec<Box<dyn Presa>>,
+    reloj: Option<fn() -> f64>,
+    perfil: PerfilDia,
+    /// Parámetros con los factores de la estación aplicados (ver `clima`).
+    parametros: Parametros,
+}
+
+impl Jornada {
+    fn ahora(&self) -> f64 {
+        self.reloj.map_or(0.0, |r| r())
+    }
+}
 
 /// Contiene el estado completo de la simulación en un momento dado.
 pub struct Simulacion {
     pub dia: u32,
     pub presas: Vec<Box<dyn Presa>>,
-    pub depredador: Depredador,
-    next_id: u32, // Un contador para asegurar que cada nueva presa tenga un ID único.
+    pub depredador: Depredador, // El depredador de referencia: con la manada, el más antiguo que sigue vivo.
+    pub manada: Vec<Depredador>, // Los demás depredadores, del más antiguo al más joven (solo con la manada).
+    pub parametros: Parametros,
+    pub estadisticas_dia: EstadisticasDia,
+    pub eventos_dia: Vec<Evento>, // Sucesos notables del último día simulado.
+    bus: Bus, // Sucesos internos que emitieron las fases del último día (ver `bus`).
+    pub balance_dia: BalanceEnergia, // Flujos de energía del último día simulado.
+    pub balance_corrida: BalanceEnergia, // Flujos acumulados desde que empezó la corrida.
+    pub muertes_corrida: MuertesPorCausa, // Muertes por causa desde que empezó la corrida.
+    pub metricas_dia: MetricasTroficas, // Presión de depredación y de pastoreo del último día.
+    pub perfil_dia: PerfilDia, // Tiempo de cada fase del último día (solo con un reloj de perfilado).
+    reloj_perfil: Option<fn() -> f64>, // Reloj en segundos con que se mide `perfil_dia`.
+    semilla: u64, // La semilla con la que se creó la corrida, para poder reproducirla.
+    next_id: u64, // Un contador para asegurar que cada nueva presa tenga un ID único (ver `asignar_id`).
+    rng: ChaCha8Rng,  // Generador propio de la simulación: con la misma semilla, la misma historia.
+    instantaneas: VecDeque<Instantanea>, // Estados recientes desde los que se puede retroceder.
+    retencion: Retencion, // Qué instantáneas se retienen y cuánta memoria pueden ocupar.
+    alarmas: Vec<Alarma>,
+    alarmas_cumplidas: Vec<bool>, // Si la condición de cada alarma se cumplía al final del último día.
+    dias_brote: [u32; 2], // Días que le quedan al brote de cada especie (conejos, cabras).
+    pastizal: Pastizal, // Pasto de cada celda; solo cuenta con `CADENA_TROFICA.activa`.
+    celdas_presas: HashMap<u64, u32>, // Celda del pastizal en que está cada presa.
+    posiciones_presas: HashMap<u64, Posicion>, // Punto del terreno en que está cada presa (ver `espacio`).
+    salud_presas: HashMap<u64, Salud>, // Presas infectadas o recuperadas; las que faltan son susceptibles (ver `enfermedad`).
+    olas: [bool; 2], // Si cada especie (conejos, cabras) está en plena ola epidémica.
+}
+
+impl Default for Simulacion {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl Simulacion {
     /// Crea una nueva instancia de la simulación con las poblaciones iniciales.
+    /// La semilla se sortea al azar, por lo que cada corrida es distinta (pero queda registrada).
     pub fn new() -> Self {
-        let mut rng = thread_rng();
+        Self::con_semilla(rand::random())
+    }
+
+    /// Crea una simulación reproducible: dos instancias con la misma semilla evolucionan igual.
+    pub fn con_semilla(semilla: u64) -> Self {
+        Self::con_parametros(Parametros::default(), semilla)
+    }
+
+    /// Crea una simulación reproducible con parámetros propios en lugar de los valores por defecto.
+    pub fn con_parametros(parametros: Parametros, semilla: u64) -> Self {
+        Self::construir(parametros, semilla, None)
+    }
+
+    /// Crea la simulación con la población inicial de los parámetros o, si se da, con un censo.
+    fn construir(parametros: Parametros, semilla: u64, censo: Option<Vec<EstadoPresa>>) -> Self {
+        let mut rng = ChaCha8Rng::seed_from_u64(semilla);
         let mut presas: Vec<Box<dyn Presa>> = Vec::new();
         let mut current_id = 0;
 
-        // Poblar el mundo con conejos iniciales.
-        for _ in 0..N_CONEJOS_INICIAL {
-            presas.push(Box::new(Conejo::new(current_id, &mut rng)));
-            current_id += 1;
+        if let Some(censo) = censo {
+            // Poblar el mundo con los individuos del censo, renumerados en orden.
+            for mut estado in censo {
+                estado.id = current_id;
+                presas.push(estado.a_presa());
+                current_id += 1;
+            }
+        } else {
+            // Poblar el mundo con conejos iniciales.
+            for _ in 0..parametros.n_conejos_inicial {
+                presas.push(Box::new(PresaGenerica::new(Especie::Conejo, current_id, &parametros, &mut rng)));
+                current_id += 1;
+            }
+            // Poblar el mundo con cabras iniciales.
+            for _ in 0..parametros.n_cabras_inicial {
+                presas.push(Box::new(PresaGenerica::new(Especie::Cabra, current_id, &parametros, &mut rng)));
+                current_id += 1;
+            }
         }
-        // Poblar el mundo con cabras iniciales.
-        for _ in 0..N_CABRAS_INICIAL {
-            presas.push(Box::new(Cabra::new(current_id, &mut rng)));
-            current_id += 1;
+
+        // Con la manada, todos los depredadores empiezan adultos, alternando hembras y machos.
+        let mut depredador = Depredador::new(parametros.depredador_reserva_inicial_kg);
+        let mut manada = Vec::new();
+        if parametros.manada.activa {
+            manada = (0..parametros.manada.n_inicial.max(1))
+                .map(|i| Depredador {
+                    edad_dias: parametros.manada.edad_reproductiva_dias,
+                    ..Depredador::cria(parametros.depredador_reserva_inicial_kg, if i.is_multiple_of(2) { Sexo::Hembra } else { Sexo::Macho }, None)
+                })
+                .collect();
+            depredador = manada.remove(0);
         }
 
-        Self {
+        let pastizal = Pastizal::new(&parametros.cadena_trofica);
+        let mut sim = Self {
             dia: 0,
             presas,
-            depredador: Depredador::new(DEPREDADOR_RESERVA_INICIAL_KG),
+            depredador,
+            manada,
+            parametros,
+            estadisticas_dia: EstadisticasDia::default(),
+            eventos_dia: Vec::new(),
+            bus: Bus::default(),
+            balance_dia: BalanceEnergia::default(),
+            balance_corrida: BalanceEnergia::default(),
+            muertes_corrida: MuertesPorCausa::default(),
+            metricas_dia: MetricasTroficas::default(),
+            perfil_dia: PerfilDia::default(),
+            reloj_perfil: None,
+            semilla,
             next_id: current_id,
-        }
+            rng,
+            instantaneas: VecDeque::new(),
+            retencion: Retencion::default(),
+            alarmas: Vec::new(),
+            alarmas_cumplidas: Vec::new(),
+            dias_brote: [0, 0],
+            pastizal,
+            celdas_presas: HashMap::new(),
+            posiciones_presas: HashMap::new(),
+            salud_presas: HashMap::new(),
+            olas: [false, false],
+        };
+        sim.retener_instantanea();
+        sim
+    }
+
+    /// Devuelve la semilla de la corrida.
+    pub fn semilla(&self) -> u64 {
+        self.semilla
     }
 
-    /// Avanza la simulación un día, ejecutando todas las fases en orden.
+    /// Avanza la simulación un día, ejecutando todas las fases en orden (ver `Fase::ORDEN`).
+    /// Cada fase emite lo que pasó al bus del día; al final, las estadísticas, el balance
+    /// energético y los eventos notables se calculan a partir de esos sucesos.
     pub fn avanzar_dia(&mut self) {
-        // ===== CAMBIO CLAVE =====
         // La simulación ahora solo se detiene si el depredador muere.
         // Continuará incluso si no hay presas.
         if !self.depredador.vivo {
@@ -51,31 +340,696 @@ impl Simulacion {
         }
 
         self.dia += 1;
-        let mut rng = thread_rng();
-        let mut nuevas_crias: Vec<Box<dyn Presa>> = Vec::new();
+        self.bus.vaciar();
+        let poblacion_previa = self.contar_especies();
+        // El amanecer va con la primera fase, para que los sucesos queden en el orden de las fases.
+        self.bus.emitir(Fase::ORDEN[0], Suceso::Amanecer {
+            conejos: poblacion_previa.0,
+            cabras: poblacion_previa.1,
+            biomasa_kg: self.presas.iter().map(|p| p.peso()).sum(),
+        });
+        let mut jornada = Jornada {
+            poblacion_previa,
+            peligro_previo: self.depredador.en_peligro(&self.parametros),
+            nuevas_crias: Vec::new(),
+            reloj: self.reloj_perfil,
+            perfil: PerfilDia::default(),
+            parametros: clima::parametros_del_dia(&self.parametros, self.dia),
+        };
+
+        for fase in Fase::ORDEN {
+            let marca = jornada.ahora();
+            match fase {
+                Fase::Manada => self.fase_manada(),
+                Fase::Depredador => self.fase_depredador(),
+                Fase::Presas => self.fase_presas(&mut jornada),
+                Fase::Censo => self.fase_censo(&mut jornada),
+                Fase::Transiciones => self.fase_transiciones(&jornada),
+            }
+            let duracion = jornada.ahora() - marca;
+            match fase {
+                Fase::Manada | Fase::Depredador => jornada.perfil.caza += duracion,
+                Fase::Presas => jornada.perfil.envejecimiento = duracion - jornada.perfil.reproduccion,
+                Fase::Censo | Fase::Transiciones => jornada.perfil.limpieza += duracion,
+            }
+        }
+
+        // Los oyentes del bus: estadísticas, contabilidad energética y eventos notables.
+        let marca = jornada.ahora();
+        self.estadisticas_dia = EstadisticasDia::default();
+        self.bus.repartir(&mut self.estadisticas_dia);
+        let mut flujos = FlujosKg::default();
+        self.bus.repartir(&mut flujos);
+        self.balance_dia = BalanceEnergia::desde_kg(&flujos, &self.parametros.energia);
+        self.balance_corrida.sumar(&self.balance_dia);
+        self.muertes_corrida.sumar(&self.estadisticas_dia.muertes_por_causa);
+        self.metricas_dia = MetricasTroficas::desde_kg(&flujos);
+        self.eventos_dia.clear();
+        self.bus.repartir(&mut self.eventos_dia);
+
+        if self.retencion.activa() && self.dia.is_multiple_of(self.retencion.intervalo_dias) {
+            self.retener_instantanea();
+        }
+        jornada.perfil.limpieza += jornada.ahora() - marca;
+        self.perfil_dia = jornada.perfil;
+    }
+
+    /// Sucesos que emitieron las fases del último día simulado, en orden.
+    pub fn sucesos_dia(&self) -> &[(Fase, Suceso)] {
+        self.bus.sucesos()
+    }
+
+    /// Fase 1: en el modo de manada, los depredadores envejecen, los que llegan a su edad
+    /// máxima mueren y cada hembra adulta con reserva de sobra puede parir si queda algún macho
+    /// adulto. La madre cede parte de su reserva a cada cría, que nace en su mismo punto.
+    fn fase_manada(&mut self) {
+        let manada = self.parametros.manada;
+        if !manada.activa {
+            return;
+        }
+        let mut rng = subflujo(self.semilla, SUBFLUJO_MANADA, self.dia);
+        let mut muertes_vejez = 0;
+        for depredador in std::iter::once(&mut self.depredador).chain(&mut self.manada).filter(|d| d.vivo) {
+            depredador.edad_dias += 1;
+            if depredador.edad_dias >= manada.edad_maxima_dias {
+                depredador.vivo = false;
+                muertes_vejez += 1;
+            }
+        }
+        let adulto = |d: &Depredador| d.vivo && d.edad_dias >= manada.edad_reproductiva_dias;
+        let hay_macho = self.depredadores().any(|d| adulto(d) && d.sexo == Sexo::Macho);
+        let mut crias = Vec::new();
+        for madre in std::iter::once(&mut self.depredador).chain(&mut self.manada) {
+            if !hay_macho || !adulto(madre) || madre.sexo != Sexo::Hembra || madre.reserva_comida_kg < manada.reserva_reproduccion_kg {
+                continue;
+            }
+            if !rng.gen_bool(manada.tasa_reproduccion_diaria) {
+                continue;
+            }
+            for _ in 0..rng.gen_range(manada.crias_por_parto.min..=manada.crias_por_parto.max) {
+                if madre.reserva_comida_kg < manada.reserva_cria_kg {
+                    break;
+                }
+                madre.reserva_comida_kg -= manada.reserva_cria_kg;
+                let sexo = if rng.gen_bool(self.parametros.probabilidad_nacer_macho) { Sexo::Macho } else { Sexo::Hembra };
+                crias.push(Depredador::cria(manada.reserva_cria_kg, sexo, madre.posicion));
+            }
+        }
+        let nacidas = crias.len() as u32;
+        self.manada.extend(crias);
+        self.relevar_depredador();
+        self.bus.emitir(Fase::Manada, Suceso::Manada { crias: nacidas, muertes_vejez });
+    }
 
-        // --- FASE 1: DEPREDADOR ---
-        // El depredador consume su reserva y, si está vivo, intenta cazar.
-        self.depredador.consumir_reserva();
-        if self.depredador.vivo {
+    /// Fase 2: cada depredador come de su despensa, consume su reserva y, si está vivo, intenta
+    /// cazar; con la manada, del más antiguo al más joven. Los que mueren dejan la manada.
+    fn fase_depredador(&mut self) {
+        let espacio = self.parametros.espacio;
+        let mut propio = self.parametros.subflujos_aleatorios.then(|| subflujo(self.semilla, SUBFLUJO_CAZA, self.dia));
+        let rng = propio.as_mut().unwrap_or(&mut self.rng);
+        let (mut asimilado_kg, mut podrido_kg, mut metabolizado_kg) = (0.0, 0.0, 0.0);
+        let mut total = ResultadoCaza::default();
+        let mut intentos = 0;
+        let mut escapadas = 0;
+        let mut cazas = Vec::new();
+        let antes = self.presas.len();
+        for indice in 0..=self.manada.len() {
+            let depredador = if indice == 0 { &mut self.depredador } else { &mut self.manada[indice - 1] };
+            let (asimilado, podrido) = depredador.atender_despensa(&self.parametros);
+            asimilado_kg += asimilado;
+            podrido_kg += podrido;
+            metabolizado_kg += depredador.consumir_reserva(&self.parametros);
             // Solo intentará cazar si todavía hay presas.
-            if !self.presas.is_empty() {
-                self.depredador.cazar(&mut self.presas, &mut rng);
+            if !depredador.vivo || self.presas.is_empty() {
+                continue;
             }
+            intentos += 1;
+            // En el modo espacial solo alcanza a las presas que tiene cerca; fuera de él, a todas.
+            let origen = depredador.posicion.unwrap_or_else(|| Posicion::centro(espacio.lado_m));
+            let posiciones = &self.posiciones_presas;
+            let distancia = |p: &dyn Presa| {
+                if !espacio.activo {
+                    return Some(0.0);
+                }
+                let metros = posicion_de(posiciones, p.id(), espacio.lado_m).distancia(origen);
+                (metros <= espacio.radio_caza_m).then_some(metros)
+            };
+            let caza = depredador.cazar(&mut self.presas, &self.parametros, rng, distancia);
+            total.cazable_kg = total.cazable_kg.max(caza.cazable_kg);
+            total.presa_kg += caza.presa_kg;
+            total.asimilado_kg += caza.asimilado_kg;
+            total.gasto_kg += caza.gasto_kg;
+            escapadas += caza.escapadas;
+            if espacio.activo {
+                // Se queda donde cayó la última presa o, si no cazó, se acerca a la más próxima.
+                let destino = match caza.cazadas.last() {
+                    Some(cazada) => Some(posicion_de(posiciones, cazada.id, espacio.lado_m)),
+                    None => self.presas.iter()
+                        .map(|p| posicion_de(posiciones, p.id(), espacio.lado_m))
+                        .min_by(|a, b| a.distancia(origen).total_cmp(&b.distancia(origen)))
+                        .map(|destino| origen.hacia(destino, espacio.paso_depredador_m)),
+                };
+                depredador.posicion = destino.or(depredador.posicion);
+            }
+            cazas.extend(caza.cazadas);
+        }
+        self.bus.emitir(Fase::Depredador, Suceso::Despensa { asimilado_kg, podrido_kg, metabolizado_kg });
+        if intentos > 0 {
+            let cazadas = (antes - self.presas.len()) as u32;
+            self.bus.emitir(Fase::Depredador, Suceso::Caza {
+                cazadas,
+                escapadas,
+                cazable_kg: total.cazable_kg,
+                presa_kg: total.presa_kg,
+                asimilado_kg: total.asimilado_kg,
+                gasto_kg: total.gasto_kg,
+            });
+            self.bus.emitir(Fase::Depredador, Suceso::Muertes { causa: CausaMuerte::Caza, cantidad: cazadas });
+        }
+        for cazada in cazas {
+            let celda = self.celda_presa(cazada.id);
+            self.bus.emitir(Fase::Depredador, Suceso::Notable(Evento::Caza {
+                id: cazada.id,
+                especie: cazada.especie,
+                edad_dias: cazada.edad_dias,
+                peso_kg: cazada.peso_kg,
+                celda,
+                posicion: self.posicion_presa(cazada.id),
+            }));
         }
+        self.relevar_depredador();
+    }
 
-        // --- FASE 2: PRESAS ---
-        // Cada presa envejece y tiene la oportunidad de reproducirse.
-        for presa in &mut self.presas {
-            presa.envejecer();
-            nuevas_crias.extend(presa.reproducirse(&mut rng, &mut self.next_id));
+    /// Saca de la manada a los depredadores muertos y, si murió el de referencia, pone en su
+    /// lugar al más antiguo de los que siguen vivos. Sin manada no hace nada.
+    fn relevar_depredador(&mut self) {
+        self.manada.retain(|d| d.vivo);
+        if !self.depredador.vivo && !self.manada.is_empty() {
+            self.depredador = self.manada.remove(0);
+        }
+    }
+
+    /// Fase 3: en el modo de cadena trófica, el pasto crece y las presas comen lo que encuentran.
+    /// Después, cada presa envejece y tiene la oportunidad de reproducirse.
+    /// Con el efecto Allee, en una especie escasa solo una parte de las presas encuentra pareja.
+    fn fase_presas(&mut self, jornada: &mut Jornada) {
+        let saciedades = self.pastar(jornada.parametros.cadena_trofica);
+        self.mover_presas();
+        let encuentro = |poblacion: usize| match self.parametros.umbral_allee {
+            0 => 1.0,
+            umbral => (poblacion as f64 / umbral as f64).min(1.0),
+        };
+        let (encuentro_conejos, encuentro_cabras) = (encuentro(jornada.poblacion_previa.0), encuentro(jornada.poblacion_previa.1));
+        // Una hembra solo pare si hay un macho adulto de su especie; en el modo espacial, además,
+        // tiene que estar a su alcance. Cuentan los machos que empiezan el día vivos, con la edad
+        // que cumplen hoy.
+        let espacio = self.parametros.espacio;
+        let machos: Vec<(Especie, Posicion)> = self.presas.iter()
+            .filter(|p| p.esta_viva() && p.sexo() == Sexo::Macho && p.edad() + 1 >= p.especie().rasgos(&self.parametros).edad_reproductiva_dias)
+            .map(|p| (p.especie(), posicion_de(&self.posiciones_presas, p.id(), espacio.lado_m)))
+            .collect();
+        let subflujos = self.parametros.subflujos_aleatorios;
+        let mut crecimiento_kg = 0.0;
+        for (presa, &saciedad) in self.presas.iter_mut().zip(&saciedades) {
+            // Con subflujos, cada presa sortea con su propio generador (también el sexo de sus crías).
+            let mut propio = subflujos.then(|| subflujo(self.semilla ^ SAL_PRESAS, presa.id(), self.dia));
+            let rng = propio.as_mut().unwrap_or(&mut self.rng);
+            let peso_previo = presa.peso();
+            presa.envejecer(&jornada.parametros, saciedad, rng);
+            crecimiento_kg += presa.peso() - peso_previo;
+            let encuentro = match presa.especie() {
+                Especie::Conejo => encuentro_conejos,
+                Especie::Cabra => encuentro_cabras,
+            };
+            // Una presa mal alimentada concibe en proporción a lo que comió; la que ya está preñada
+            // sigue adelante con la gestación. Sin efecto Allee ni hambre no se consume el generador,
+            // para no alterar corridas ya guardadas.
+            let fertilidad = encuentro * saciedad;
+            if presa.gestacion().is_none() && fertilidad < 1.0 && !rng.gen_bool(fertilidad) {
+                continue;
+            }
+            let inicio_reproduccion = jornada.ahora();
+            let con_macho = presa.sexo() == Sexo::Hembra && {
+                let posicion = posicion_de(&self.posiciones_presas, presa.id(), espacio.lado_m);
+                machos.iter().any(|&(especie, macho)| {
+                    especie == presa.especie() && (!espacio.activo || macho.distancia(posicion) <= espacio.radio_apareamiento_m)
+                })
+            };
+            let crias = presa.reproducirse(&jornada.parametros, con_macho, rng, &mut self.next_id);
+            // Las crías nacen en la celda y en el punto de su madre.
+            if let Some(&celda) = self.celdas_presas.get(&presa.id()) {
+                self.celdas_presas.extend(crias.iter().map(|cria| (cria.id(), celda)));
+            }
+            if let Some(&posicion) = self.posiciones_presas.get(&presa.id()) {
+                self.posiciones_presas.extend(crias.iter().map(|cria| (cria.id(), posicion)));
+            }
+            if !crias.is_empty() {
+                let crias = crias.iter().map(|cria| cria.id()).collect();
+                self.bus.emitir(Fase::Presas, Suceso::Parto { madre: presa.id(), especie: presa.especie(), crias });
+            }
+            jornada.nuevas_crias.extend(crias);
+            jornada.perfil.reproduccion += jornada.ahora() - inicio_reproduccion;
         }
+        self.bus.emitir(Fase::Presas, Suceso::Crecimiento { kg: crecimiento_kg });
+    }
 
-        // --- FASE 3: CENSO Y LIMPIANZA ---
-        // Se añaden las nuevas crías a la población.
-        self.presas.extend(nuevas_crias);
-        // Se eliminan de la lista todas las presas que han muerto en este día.
+    /// Fase 4: entran las crías, se retiran las presas muertas (también las de los brotes) y,
+    /// por encima del tope de población, las que sobran.
+    fn fase_censo(&mut self, jornada: &mut Jornada) {
+        let crias = std::mem::take(&mut jornada.nuevas_crias);
+        self.bus.emitir(Fase::Censo, Suceso::Nacimientos { crias: crias.len() as u32, kg: crias.iter().map(|p| p.peso()).sum() });
+        self.presas.extend(crias);
+        let biomasa_previa: f64 = self.presas.iter().map(|p| p.peso()).sum();
+        // Las que murieron al envejecer se cuentan antes de los brotes, que pueden llevarse alguna.
+        let mut muertes = MuertesPorCausa::default();
+        for presa in &self.presas {
+            if let Some(causa) = presa.causa_muerte() {
+                muertes.anotar(causa, 1);
+                self.bus.emitir(Fase::Censo, Suceso::Notable(muerte_presa(presa.as_ref(), causa)));
+            }
+        }
+        for causa in CausaMuerte::TODAS {
+            if muertes.de(causa) > 0 {
+                self.bus.emitir(Fase::Censo, Suceso::Muertes { causa, cantidad: muertes.de(causa) });
+            }
+        }
+        self.propagar_brotes(jornada.poblacion_previa);
+        self.propagar_enfermedad(jornada.parametros.enfermedad);
         self.presas.retain(|p| p.esta_viva());
+        // Válvula de seguridad: por encima del tope, la densidad mata al azar a las que sobran.
+        let tope = self.parametros.poblacion_maxima as usize;
+        if self.presas.len() > tope {
+            let poblacion = self.presas.len() as u32;
+            let mut propio = self.parametros.subflujos_aleatorios.then(|| subflujo(self.semilla, SUBFLUJO_DENSIDAD, self.dia));
+            let rng = propio.as_mut().unwrap_or(&mut self.rng);
+            while self.presas.len() > tope {
+                let indice = rng.gen_range(0..self.presas.len());
+                self.presas.swap_remove(indice);
+            }
+            let muertes = poblacion - tope as u32;
+            self.bus.emitir(Fase::Censo, Suceso::Muertes { causa: CausaMuerte::Sobrepoblacion, cantidad: muertes });
+            self.bus.emitir(Fase::Censo, Suceso::Notable(Evento::MortalidadEmergencia { poblacion, tope: tope as u32, muertes }));
+        }
+        self.bus.emitir(Fase::Censo, Suceso::Retiradas { kg: biomasa_previa - self.presas.iter().map(|p| p.peso()).sum::<f64>() });
+    }
+
+    /// Fase 5: se comparan el inicio y el final del día para anunciar las transiciones importantes.
+    fn fase_transiciones(&mut self, jornada: &Jornada) {
+        let (conejos, cabras) = self.contar_especies();
+        if jornada.poblacion_previa.0 > 0 && conejos == 0 {
+            self.bus.emitir(Fase::Transiciones, Suceso::Notable(Evento::Extincion { especie: Especie::Conejo }));
+        }
+        if jornada.poblacion_previa.1 > 0 && cabras == 0 {
+            self.bus.emitir(Fase::Transiciones, Suceso::Notable(Evento::Extincion { especie: Especie::Cabra }));
+        }
+        if !self.depredador.vivo {
+            self.bus.emitir(Fase::Transiciones, Suceso::Notable(Evento::MuerteDepredador));
+        } else if !jornada.peligro_previo && self.depredador.en_peligro(&self.parametros) {
+            let reserva_kg = self.depredador.reserva_comida_kg;
+            self.bus.emitir(Fase::Transiciones, Suceso::Notable(Evento::DepredadorEnPeligro { reserva_kg }));
+        }
+        // Las alarmas se anuncian el día en que su condición empieza a cumplirse.
+        let cumplidas: Vec<bool> = self.alarmas.iter().map(|a| a.cumplida(self)).collect();
+        for (i, cumplida) in cumplidas.into_iter().enumerate() {
+            if cumplida && !self.alarmas_cumplidas[i] {
+                let alarma = self.alarmas[i].to_string();
+                self.bus.emitir(Fase::Transiciones, Suceso::Notable(Evento::AlarmaDisparada { alarma }));
+            }
+            self.alarmas_cumplidas[i] = cumplida;
+        }
+    }
+
+    /// Activa (con un reloj en segundos) o desactiva la medición del tiempo de cada fase en
+    /// `perfil_dia`. El reloj lo pone quien llama porque `std::time` no funciona en la web.
+    pub fn perfilar(&mut self, reloj: Option<fn() -> f64>) {
+        self.reloj_perfil = reloj;
+        if reloj.is_none() {
+            self.perfil_dia = PerfilDia::default();
+        }
+    }
+
+    /// Estimación de la memoria que ocupa el estado (bytes): presas, manada, mapas de celdas,
+    /// de posiciones y de salud, pastizal e instantáneas retenidas. No cuenta lo que reserva el asignador.
+    pub fn memoria_estimada(&self) -> usize {
+        let presas: usize = self.presas.iter().map(|p| std::mem::size_of_val(&**p)).sum::<usize>()
+            + self.presas.capacity() * std::mem::size_of::<Box<dyn Presa>>();
+        let celdas = self.celdas_presas.capacity() * (std::mem::size_of::<(u64, u32)>() + 1)
+            + self.posiciones_presas.capacity() * (std::mem::size_of::<(u64, Posicion)>() + 1)
+            + self.salud_presas.capacity() * (std::mem::size_of::<(u64, Salud)>() + 1)
+            + self.manada.capacity() * std::mem::size_of::<Depredador>();
+        let pastizal = self.pastizal.celdas.capacity() * std::mem::size_of::<f64>();
+        std::mem::size_of::<Self>() + presas + celdas + pastizal + self.memoria_instantaneas()
+    }
+
+    /// Estimación de la memoria que ocupan las instantáneas retenidas (bytes).
+    pub fn memoria_instantaneas(&self) -> usize {
+        self.instantaneas.iter().map(Instantanea::memoria_estimada).sum()
+    }
+
+    /// Pasto disponible en todo el terreno (kg); 0 fuera del modo de cadena trófica.
+    pub fn pasto_kg(&self) -> f64 {
+        if self.parametros.cadena_trofica.activa { self.pastizal.total() } else { 0.0 }
+    }
+
+    /// La cuadrícula de pasto, para dibujarla.
+    pub fn pastizal(&self) -> &Pastizal {
+        &self.pastizal
+    }
+
+    /// Celda del pastizal en que está una presa; `None` fuera del modo de cadena trófica.
+    pub fn celda_presa(&self, id: u64) -> Option<u32> {
+        if !self.parametros.cadena_trofica.activa {
+            return None;
+        }
+        Some(self.celdas_presas.get(&id).copied().unwrap_or_else(|| self.pastizal.celda_inicial(id)))
+    }
+
+    /// Punto del terreno en que está una presa (ver `espacio`). Las presas se mueven siempre,
+    /// aunque el modo espacial esté desactivado: así la interfaz las dibuja donde están.
+    pub fn posicion_presa(&self, id: u64) -> Posicion {
+        posicion_de(&self.posiciones_presas, id, self.parametros.espacio.lado_m)
+    }
+
+    /// Punto del terreno en que está el depredador de referencia; empieza en el centro.
+    pub fn posicion_depredador(&self) -> Posicion {
+        self.depredador.posicion.unwrap_or_else(|| Posicion::centro(self.parametros.espacio.lado_m))
+    }
+
+    /// Los depredadores vivos: el de referencia y, con la manada, los demás.
+    pub fn depredadores(&self) -> impl Iterator<Item = &Depredador> {
+        std::iter::once(&self.depredador).chain(&self.manada).filter(|d| d.vivo)
+    }
+
+    /// Cada presa da un paso al azar sin salir del terreno ni, con la cadena trófica, de su
+    /// celda: si su rebaño se mudó, entra en la nueva por el borde más cercano. Sortea con su
+    /// propio subflujo, así que no altera el resto de la corrida.
+    fn mover_presas(&mut self) {
+        let espacio = self.parametros.espacio;
+        let mut rng = subflujo(self.semilla, SUBFLUJO_MOVIMIENTO, self.dia);
+        let posiciones = self.presas.iter()
+            .map(|p| {
+                let limites = match self.celda_presa(p.id()) {
+                    Some(celda) => espacio::limites_celda(&self.pastizal, celda, espacio.lado_m),
+                    None => espacio::limites_terreno(espacio.lado_m),
+                };
+                (p.id(), self.posicion_presa(p.id()).paso_aleatorio(espacio.paso_presa_m, &mut rng).limitar(limites))
+            })
+            .collect();
+        self.posiciones_presas = posiciones;
+    }
+
+    /// Hace rebrotar el pastizal y deja que cada rebaño (las presas de una celda) coma de su
+    /// celda lo que hay por encima del refugio (ver `CadenaTrofica`); los que se quedan con
+    /// hambre se mudan a la celda vecina con más pasto. Devuelve la saciedad de cada presa, en
+    /// el orden de `presas`: la fracción de su ración que encontró. Fuera del modo es 1.
+    fn pastar(&mut self, cadena: CadenaTrofica) -> Vec<f64> {
+        if !cadena.activa {
+            return vec![1.0; self.presas.len()];
+        }
+        self.pastizal.ajustar(&cadena);
+        self.pastizal.crecer(&cadena);
+        // Cada presa sigue en su celda y las recién llegadas se dispersan; se olvidan las que ya no están.
+        let n_celdas = self.pastizal.n_celdas();
+        let celdas: HashMap<u64, u32> = self.presas.iter()
+            .map(|p| {
+                let celda = self.celdas_presas.get(&p.id()).copied().filter(|&c| (c as usize) < n_celdas);
+                (p.id(), celda.unwrap_or_else(|| self.pastizal.celda_inicial(p.id())))
+            })
+            .collect();
+        let mut raciones = vec![0.0; n_celdas];
+        for presa in &self.presas {
+            raciones[celdas[&presa.id()] as usize] += presa.peso() * cadena.consumo_relativo;
+        }
+        let disponible_kg = self.pastizal.total();
+        let mut pastado_kg = 0.0;
+        let saciedad_celdas: Vec<f64> = raciones.iter().enumerate()
+            .map(|(celda, &racion)| {
+                if racion <= 0.0 {
+                    return 1.0;
+                }
+                let comido = self.pastizal.pastar(celda as u32, racion, &cadena);
+                pastado_kg += comido;
+                comido / racion
+            })
+            .collect();
+        let destinos: Vec<u32> = (0..n_celdas as u32)
+            .map(|celda| if saciedad_celdas[celda as usize] < 1.0 { self.pastizal.mejor_vecina(celda) } else { celda })
+            .collect();
+        let racion_kg = raciones.iter().sum();
+        self.bus.emitir(Fase::Presas, Suceso::Pastoreo { disponible_kg, racion_kg, pastado_kg });
+        let saciedades = self.presas.iter().map(|p| saciedad_celdas[celdas[&p.id()] as usize]).collect();
+        self.celdas_presas = celdas.into_iter().map(|(id, celda)| (id, destinos[celda as usize])).collect();
+        saciedades
+    }
+
+    /// Inicia, aplica y agota los brotes de enfermedad por hacinamiento de cada especie
+    /// (ver `Epidemias`), a partir de la población con la que empezó el día.
+    fn propagar_brotes(&mut self, poblacion: (usize, usize)) {
+        let epidemias = self.parametros.epidemias;
+        if epidemias.umbral_densidad == 0 {
+            self.dias_brote = [0, 0];
+            return;
+        }
+        let mut propio = self.parametros.subflujos_aleatorios.then(|| subflujo(self.semilla, SUBFLUJO_BROTES, self.dia));
+        let rng = propio.as_mut().unwrap_or(&mut self.rng);
+        for (i, (especie, cantidad)) in [(Especie::Conejo, poblacion.0), (Especie::Cabra, poblacion.1)].into_iter().enumerate() {
+            let hacinamiento = cantidad as f64 / epidemias.umbral_densidad as f64;
+            if self.dias_brote[i] == 0 {
+                let estalla = hacinamiento >= 1.0 && rng.gen_bool((epidemias.probabilidad_brote * hacinamiento).min(1.0));
+                if !estalla || epidemias.duracion_dias == 0 {
+                    continue;
+                }
+                self.dias_brote[i] = epidemias.duracion_dias;
+                self.bus.emitir(Fase::Censo, Suceso::Notable(Evento::BroteEpidemia { especie }));
+            }
+            // Cuanto más hacinada está la especie, más se contagia; al ralear, el brote remite.
+            let mortalidad = (epidemias.mortalidad * hacinamiento).min(1.0);
+            let mut contagiadas = Vec::new();
+            self.presas.retain(|p| {
+                let muere = p.especie() == especie && rng.gen_bool(mortalidad);
+                // Las que ya habían muerto hoy se contaron con su propia causa.
+                if muere && p.esta_viva() {
+                    contagiadas.push(muerte_presa(p.as_ref(), CausaMuerte::Enfermedad));
+                }
+                !muere
+            });
+            if !contagiadas.is_empty() {
+                self.bus.emitir(Fase::Censo, Suceso::Muertes { causa: CausaMuerte::Enfermedad, cantidad: contagiadas.len() as u32 });
+            }
+            for muerte in contagiadas {
+                self.bus.emitir(Fase::Censo, Suceso::Notable(muerte));
+            }
+            self.dias_brote[i] -= 1;
+        }
+    }
+
+    /// Un día de la enfermedad contagiosa (ver `enfermedad`): se contagian las susceptibles,
+    /// mueren o se recuperan las infectadas y pierden la inmunidad las recuperadas, según las
+    /// infectadas de su especie al empezar. A una especie sin infectadas puede llegarle un caso.
+    /// Sortea con su propio subflujo, así que no altera el resto de la corrida.
+    fn propagar_enfermedad(&mut self, enfermedad: Enfermedad) {
+        if !enfermedad.activa {
+            self.salud_presas.clear();
+            self.olas = [false, false];
+            return;
+        }
+        let mut rng = subflujo(self.semilla, SUBFLUJO_ENFERMEDAD, self.dia);
+        let indice = |especie: Especie| match especie {
+            Especie::Conejo => 0,
+            Especie::Cabra => 1,
+        };
+        let mut recuentos = [RecuentoSir::default(); 2];
+        for presa in self.presas.iter().filter(|p| p.esta_viva()) {
+            recuentos[indice(presa.especie())].anotar(self.salud_presa(presa.id()));
+        }
+        // Llegada de un caso a las especies sin infectadas.
+        let mut introducidas = [None, None];
+        for (i, recuento) in recuentos.iter().enumerate() {
+            if recuento.infectadas == 0 && recuento.susceptibles > 0 && rng.gen_bool(enfermedad.introduccion) {
+                introducidas[i] = Some(rng.gen_range(0..recuento.susceptibles));
+            }
+        }
+        let contagio = recuentos.map(|r| r.probabilidad_contagio(&enfermedad));
+        let mut susceptibles_vistas = [0; 2];
+        let mut salud = HashMap::with_capacity(self.salud_presas.len());
+        let mut muertas = HashSet::new();
+        let mut contagios = 0;
+        for presa in self.presas.iter().filter(|p| p.esta_viva()) {
+            let i = indice(presa.especie());
+            let nueva = match self.salud_presa(presa.id()) {
+                Salud::Susceptible => {
+                    let introducida = introducidas[i] == Some(susceptibles_vistas[i]);
+                    susceptibles_vistas[i] += 1;
+                    if introducida || rng.gen_bool(contagio[i]) {
+                        contagios += 1;
+                        Salud::Infectada
+                    } else {
+                        Salud::Susceptible
+                    }
+                }
+                Salud::Infectada if rng.gen_bool(enfermedad.mortalidad) => {
+                    muertas.insert(presa.id());
+                    continue;
+                }
+                Salud::Infectada if rng.gen_bool(enfermedad.recuperacion) => Salud::Recuperada,
+                Salud::Recuperada if rng.gen_bool(enfermedad.perdida_inmunidad) => Salud::Susceptible,
+                actual => actual,
+            };
+            if nueva != Salud::Susceptible {
+                salud.insert(presa.id(), nueva);
+            }
+        }
+        self.salud_presas = salud;
+        if !muertas.is_empty() {
+            self.bus.emitir(Fase::Censo, Suceso::Muertes { causa: CausaMuerte::Enfermedad, cantidad: muertas.len() as u32 });
+            for presa in self.presas.iter().filter(|p| muertas.contains(&p.id())) {
+                self.bus.emitir(Fase::Censo, Suceso::Notable(muerte_presa(presa.as_ref(), CausaMuerte::Enfermedad)));
+            }
+            self.presas.retain(|p| !muertas.contains(&p.id()));
+        }
+        // Las olas se anuncian el día en que la fracción infectada alcanza el umbral.
+        let mut infectadas = 0;
+        for especie in [Especie::Conejo, Especie::Cabra] {
+            let recuento = self.recuento_sir(especie);
+            infectadas += recuento.infectadas;
+            let en_ola = recuento.en_ola(&enfermedad, self.olas[indice(especie)]);
+            if en_ola && !self.olas[indice(especie)] {
+                self.bus.emitir(Fase::Censo, Suceso::Notable(Evento::OlaEpidemica { especie, infectadas: recuento.infectadas }));
+            }
+            self.olas[indice(especie)] = en_ola;
+        }
+        self.bus.emitir(Fase::Censo, Suceso::Contagios { contagios, infectadas });
+    }
+
+    /// Estado de una presa frente a la enfermedad contagiosa; susceptible fuera de ese modo.
+    pub fn salud_presa(&self, id: u64) -> Salud {
+        self.salud_presas.get(&id).copied().unwrap_or_default()
+    }
+
+    /// Presas vivas de una especie en cada estado de la enfermedad contagiosa.
+    pub fn recuento_sir(&self, especie: Especie) -> RecuentoSir {
+        let mut recuento = RecuentoSir::default();
+        for presa in self.presas.iter().filter(|p| p.esta_viva() && p.especie() == especie) {
+            recuento.anotar(self.salud_presa(presa.id()));
+        }
+        recuento
+    }
+
+    /// Estación del día actual; `None` sin `CLIMA.activo`.
+    pub fn estacion(&self) -> Option<Estacion> {
+        self.parametros.clima.activo.then(|| Estacion::del_dia(self.dia, &self.parametros.clima))
+    }
+
+    /// Sustituye las alarmas que se comprueban al final de cada día (ver `alarmas`).
+    pub fn definir_alarmas(&mut self, alarmas: Vec<Alarma>) {
+        self.alarmas_cumplidas = vec![false; alarmas.len()];
+        self.alarmas = alarmas;
+    }
+
+    pub fn alarmas(&self) -> &[Alarma] {
+        &self.alarmas
+    }
+
+    /// Avanza día a día hasta que ocurra un evento del tipo indicado, como mucho `maximo_dias`.
+    /// Devuelve el evento, que queda entre los `eventos_dia` del día en que se detuvo, o `None`
+    /// si no ocurrió en ese plazo o el depredador murió antes (y la simulación ya no avanza).
+    pub fn ejecutar_hasta_evento(&mut self, tipo: TipoEvento, maximo_dias: u32) -> Option<Evento> {
+        for _ in 0..maximo_dias {
+            if !self.depredador.vivo {
+                return None;
+            }
+            self.avanzar_dia();
+            if let Some(evento) = self.eventos_dia.iter().find(|e| e.tipo() == Some(tipo)) {
+                return Some(evento.clone());
+            }
+        }
+        None
+    }
+
+    /// Añade presas recién nacidas de la especie indicada y devuelve sus ids; si se agotan
+    /// los ids (ver `asignar_id`), solo las que se pudieron numerar.
+    pub fn generar_presas(&mut self, especie: Especie, cantidad: u32) -> Vec<u64> {
+        let mut ids = Vec::with_capacity(cantidad as usize);
+        for _ in 0..cantidad {
+            let Some(id) = asignar_id(&mut self.next_id) else {
+                break;
+            };
+            self.presas.push(Box::new(PresaGenerica::new(especie, id, &self.parametros, &mut self.rng)));
+            ids.push(id);
+        }
+        self.retener_instantanea();
+        ids
+    }
+
+    /// Añade a mano una presa recién nacida (p. ej. desde el modo de edición) y lo anuncia
+    /// como evento del día en curso. Devuelve su id, o `None` si ya no quedan ids.
+    pub fn agregar_presa(&mut self, especie: Especie) -> Option<u64> {
+        let id = *self.generar_presas(especie, 1).first()?;
+        self.eventos_dia.push(Evento::PresaAgregada { id, especie });
+        Some(id)
+    }
+
+    /// Retira a mano una presa y lo anuncia como evento del día en curso, junto con la
+    /// extinción si era la última de su especie. Devuelve su especie, o `None` si no existe.
+    pub fn retirar_presa(&mut self, id: u64) -> Option<Especie> {
+        let indice = self.presas.iter().position(|p| p.id() == id)?;
+        let presa = self.presas.remove(indice);
+        let especie = presa.especie();
+        self.eventos_dia.push(Evento::PresaRetirada { id, especie, peso_kg: presa.peso() });
+        if !self.presas.iter().any(|p| p.especie() == especie) {
+            self.eventos_dia.push(Evento::Extincion { especie });
+        }
+        self.retener_instantanea();
+        Some(especie)
+    }
+
+    /// Pone a mano una presa en un punto del terreno (p. ej. la recién añadida, donde se hizo
+    /// clic); con la cadena trófica pasa a pastar en la celda de ese punto. No hace nada si la
+    /// presa no existe.
+    pub fn colocar_presa(&mut self, id: u64, posicion: Posicion) {
+        if !self.presas.iter().any(|p| p.id() == id) {
+            return;
+        }
+        let lado = self.parametros.espacio.lado_m;
+        let posicion = posicion.limitar(espacio::limites_terreno(lado));
+        self.posiciones_presas.insert(id, posicion);
+        if self.parametros.cadena_trofica.activa {
+            self.celdas_presas.insert(id, espacio::celda_en(&self.pastizal, posicion, lado));
+        }
+        self.retener_instantanea();
+    }
+
+    /// Traslada a mano al depredador al punto del terreno indicado (en metros) y lo anuncia
+    /// como evento del día en curso. En el modo espacial, desde ahí cazará al día siguiente.
+    pub fn reubicar_depredador(&mut self, x: f32, y: f32) {
+        let posicion = Posicion::new(x, y).limitar(espacio::limites_terreno(self.parametros.espacio.lado_m));
+        self.depredador.posicion = Some(posicion);
+        self.eventos_dia.push(Evento::DepredadorReubicado { x: posicion.x, y: posicion.y });
+        self.retener_instantanea();
+    }
+
+    /// Cambia en caliente un parámetro por su nombre, como una recarga del archivo: se valida
+    /// el resultado, queda un evento `ParametroCambiado` y se retiene una instantánea. Con
+    /// `especie`, el nombre va sin el prefijo de la especie (`TASA_REPRODUCCION_DIARIA` con
+    /// `Cabra` es `CABRA_TASA_REPRODUCCION_DIARIA`). Solo se admiten los parámetros de
+    /// `recarga::PARAMETROS_RECARGABLES`; los demás exigen empezar otra corrida.
+    pub fn establecer_parametro(&mut self, especie: Option<Especie>, nombre: &str, valor: serde_json::Value) -> Result<(), String> {
+        let nombre = match especie {
+            Some(Especie::Conejo) => format!("CONEJO_{}", nombre),
+            Some(Especie::Cabra) => format!("CABRA_{}", nombre),
+            None => nombre.to_string(),
+        };
+        let mut nuevos = self.parametros.clone();
+        nuevos.establecer(&nombre, valor)?;
+        let (actual, propuesto) = (self.parametros.a_arbol(), nuevos.a_arbol());
+        if let (Some(actual), Some(propuesto)) = (actual.as_object(), propuesto.as_object()) {
+            let fijo = propuesto.iter()
+                .find(|(clave, valor)| actual.get(*clave) != Some(valor) && !recarga::PARAMETROS_RECARGABLES.contains(&clave.as_str()));
+            if let Some((clave, _)) = fijo {
+                return Err(format!("{} no se puede cambiar a mitad de una corrida", clave));
+            }
+        }
+        recarga::aplicar_recarga(self, &nuevos).map(|_| ())
     }
 
     /// Devuelve el número de conejos y cabras actualmente en la simulación.
@@ -90,4 +1044,611 @@ impl Simulacion {
         }
         (conejos, cabras)
     }
-}
\ No newline at end of file
+
+    /// Presas vivas de cada clase de edad (en el orden de `ClaseEdad::TODAS`), como (conejos, cabras).
+    pub fn contar_clases(&self) -> [(usize, usize); 4] {
+        let mut cuentas = [(0, 0); 4];
+        for presa in self.presas.iter().filter(|p| p.esta_viva()) {
+            let clase = presa.especie().clase_edad(presa.edad(), &self.parametros);
+            let cuenta = &mut cuentas[clase as usize];
+            match presa.especie() {
+                Especie::Conejo => cuenta.0 += 1,
+                Especie::Cabra => cuenta.1 += 1,
+            }
+        }
+        cuentas
+    }
+
+    /// Estado del depredador como etiqueta ASCII: "optimo", "minimo", "peligro" o "muerto".
+    pub fn estado_depredador(&self) -> &'static str {
+        let reserva = self.depredador.reserva_comida_kg;
+        if !self.depredador.vivo {
+            "muerto"
+        } else if reserva >= self.parametros.consumo_depredador_kg().1 {
+            "optimo"
+        } else if reserva >= self.parametros.consumo_depredador_kg().0 {
+            "minimo"
+        } else {
+            "peligro"
+        }
+    }
+
+    /// Captura el estado completo (incluido el generador aleatorio) en una instantánea.
+    pub fn instantanea(&self) -> Instantanea {
+        Instantanea {
+            version: VERSION_INSTANTANEA,
+            semilla: self.semilla,
+            dia: self.dia,
+            next_id: self.next_id,
+            depredador: self.depredador.clone(),
+            manada: self.manada.clone(),
+            parametros: self.parametros.clone(),
+            presas: self.presas.iter().map(|p| EstadoPresa::desde_presa(p.as_ref())).collect(),
+            rng: self.rng.clone(),
+            dias_brote: self.dias_brote,
+            pastizal: self.pastizal.clone(),
+            celdas_presas: self.celdas_presas.iter().map(|(&id, &celda)| (id, celda)).collect(),
+            posiciones_presas: self.posiciones_presas.iter().map(|(&id, &posicion)| (id, posicion)).collect(),
+            salud_presas: self.salud_presas.iter().map(|(&id, &salud)| (id, salud)).collect(),
+            olas: self.olas,
+            balance_corrida: self.balance_corrida,
+            muertes_corrida: self.muertes_corrida,
+            alarmas: self.alarmas.clone(),
+            alarmas_cumplidas: self.alarmas_cumplidas.clone(),
+            retencion: self.retencion,
+        }
+    }
+
+    /// Reconstruye una simulación a partir de una instantánea; continúa exactamente donde quedó.
+    pub fn desde_instantanea(mut instantanea: Instantanea) -> Self {
+        // Un JSON editado a mano puede no traer el estado de todas las alarmas.
+        instantanea.alarmas_cumplidas.resize(instantanea.alarmas.len(), false);
+        let mut sim = Self {
+            dia: instantanea.dia,
+            presas: instantanea.presas.iter().map(EstadoPresa::a_presa).collect(),
+            depredador: instantanea.depredador,
+            manada: instantanea.manada,
+            parametros: instantanea.parametros,
+            estadisticas_dia: EstadisticasDia::default(),
+            eventos_dia: Vec::new(),
+            bus: Bus::default(),
+            balance_dia: BalanceEnergia::default(),
+            balance_corrida: instantanea.balance_corrida,
+            muertes_corrida: instantanea.muertes_corrida,
+            metricas_dia: MetricasTroficas::default(),
+            perfil_dia: PerfilDia::default(),
+            reloj_perfil: None,
+            semilla: instantanea.semilla,
+            next_id: instantanea.next_id,
+            rng: instantanea.rng,
+            instantaneas: VecDeque::new(),
+            retencion: instantanea.retencion,
+            alarmas: instantanea.alarmas,
+            alarmas_cumplidas: instantanea.alarmas_cumplidas,
+            dias_brote: instantanea.dias_brote,
+            pastizal: instantanea.pastizal,
+            celdas_presas: instantanea.celdas_presas.into_iter().collect(),
+            posiciones_presas: instantanea.posiciones_presas.into_iter().collect(),
+            salud_presas: instantanea.salud_presas.into_iter().collect(),
+            olas: instantanea.olas,
+        };
+        sim.retener_instantanea();
+        sim
+    }
+
+    /// Guarda el estado completo en una instantánea binaria, para reanudar la corrida más tarde.
+    pub fn guardar(&self, ruta: impl AsRef<Path>) -> Result<(), String> {
+        let ruta = ruta.as_ref();
+        self.instantanea().guardar(ruta).map_err(|e| format!("no se pudo escribir {}: {}", ruta.display(), e))
+    }
+
+    /// Reanuda una corrida guardada con `guardar` (o cualquier instantánea, binaria o JSON),
+    /// con sus totales, sus alarmas y su retención; las instantáneas retenidas no se guardan.
+    pub fn cargar(ruta: impl AsRef<Path>) -> Result<Self, String> {
+        let ruta = ruta.as_ref();
+        let instantanea = Instantanea::cargar(ruta).map_err(|e| format!("no se pudo leer {}: {}", ruta.display(), e))?;
+        Ok(Self::desde_instantanea(instantanea))
+    }
+
+    /// Guarda el estado actual entre las instantáneas desde las que se puede retroceder.
+    /// Se hace sola cada `Retencion::intervalo_dias` días y tras cada intervención (presas
+    /// añadidas o retiradas, recargas de parámetros); quien cambie los campos públicos
+    /// directamente debe llamarla después para que el retroceso reproduzca ese cambio. Sin
+    /// retención no hace nada.
+    pub fn retener_instantanea(&mut self) {
+        if !self.retencion.activa() {
+            return;
+        }
+        let instantanea = self.instantanea();
+        // Una intervención sustituye a la instantánea ya tomada ese mismo día, así que varias
+        // intervenciones no desplazan a las periódicas.
+        if self.instantaneas.back().is_some_and(|i| i.dia == self.dia) {
+            self.instantaneas.pop_back();
+        }
+        self.instantaneas.push_back(instantanea);
+        self.recortar_instantaneas();
+    }
+
+    /// Cambia qué instantáneas se retienen y descarta las que ya no entran.
+    pub fn retener(&mut self, retencion: Retencion) {
+        self.retencion = retencion;
+        self.recortar_instantaneas();
+    }
+
+    pub fn retencion(&self) -> Retencion {
+        self.retencion
+    }
+
+    /// Descarta las instantáneas más antiguas hasta que las que quedan estén dentro del
+    /// horizonte y quepan en el presupuesto.
+    fn recortar_instantaneas(&mut self) {
+        let retencion = self.retencion;
+        if !retencion.activa() {
+            self.instantaneas.clear();
+            return;
+        }
+        let desde = self.dia.saturating_sub(retencion.horizonte_dias);
+        let mut bytes = self.memoria_instantaneas();
+        while let Some(primera) = self.instantaneas.front() {
+            if primera.dia >= desde && bytes <= retencion.presupuesto_bytes {
+                break;
+            }
+            bytes -= primera.memoria_estimada();
+            self.instantaneas.pop_front();
+        }
+    }
+
+    /// Primer día al que todavía se puede retroceder.
+    pub fn dia_mas_antiguo_retenido(&self) -> u32 {
+        self.instantaneas.front().map_or(self.dia, |i| i.dia)
+    }
+
+    /// Conejos y cabras de cada instantánea retenida para retroceder (ver `Retencion`), de la más antigua a la última,
+    /// como (día, conejos, cabras): la historia reciente sin tener que guardarla aparte.
+    pub fn poblaciones_recientes(&self) -> Vec<(u32, usize, usize)> {
+        self.instantaneas.iter()
+            .map(|instantanea| {
+                let conejos = instantanea.presas.iter().filter(|p| p.especie == Especie::Conejo).count();
+                (instantanea.dia, conejos, instantanea.presas.len() - conejos)
+            })
+            .collect()
+    }
+
+    /// Crea una simulación nueva en el estado del día indicado, sin tocar esta: parte de la
+    /// instantánea retenida más cercana y repite los días que faltan. Como el generador
+    /// aleatorio va en la instantánea, el resultado es idéntico a como estaba ese día, y
+    /// desde ahí se pueden explorar futuros alternativos.
+    pub fn bifurcar_desde(&self, dia: u32) -> Result<Simulacion, String> {
+        if dia > self.dia {
+            return Err(format!("el día {} todavía no se ha simulado (día actual: {})", dia, self.dia));
+        }
+        let base = self.instantaneas.iter().rev().find(|i| i.dia <= dia).ok_or_else(|| {
+            format!("el día {} ya no está retenido (el más antiguo es el {})", dia, self.dia_mas_antiguo_retenido())
+        })?;
+        let mut sim = Simulacion::desde_instantanea(base.clone());
+        sim.retener(self.retencion);
+        sim.alarmas_cumplidas = self.alarmas.iter().map(|a| a.cumplida(&sim)).collect();
+        sim.alarmas = self.alarmas.clone();
+        while sim.dia < dia && sim.depredador.vivo {
+            sim.avanzar_dia();
+        }
+        Ok(sim)
+    }
+
+    /// Vuelve al estado del día indicado, descartando lo ocurrido después. Las instantáneas
+    /// anteriores se conservan, así que se puede retroceder varias veces seguidas.
+    pub fn retroceder_a(&mut self, dia: u32) -> Result<(), String> {
+        let mut pasado = self.bifurcar_desde(dia)?;
+        self.instantaneas.retain(|i| i.dia <= dia);
+        pasado.instantaneas = std::mem::take(&mut self.instantaneas);
+        *self = pasado;
+        Ok(())
+    }
+
+    /// Calcula una huella (hash estable) del estado completo del día actual: contadores,
+    /// generador, depredadores, brotes, pasto y cada presa con su posición, celda y salud.
+    /// Usa FNV-1a sobre los valores en bits, así que es comparable entre compilaciones distintas.
+    pub fn huella(&self) -> u64 {
+        let mut h = Fnv1a::new();
+        h.escribir(self.dia as u64);
+        h.escribir(self.next_id);
+        let posicion_rng = self.rng.get_word_pos();
+        h.escribir(posicion_rng as u64);
+        h.escribir((posicion_rng >> 64) as u64);
+        for depredador in std::iter::once(&self.depredador).chain(&self.manada) {
+            escribir_depredador(&mut h, depredador);
+        }
+        for (dias, ola) in self.dias_brote.iter().zip(self.olas) {
+            h.escribir(*dias as u64);
+            h.escribir(ola as u64);
+        }
+        for pasto in &self.pastizal.celdas {
+            h.escribir(pasto.to_bits());
+        }
+        for (id, huella) in self.huellas_presas() {
+            h.escribir(id);
+            h.escribir(huella);
+        }
+        h.terminar()
+    }
+
+    /// Devuelve la huella individual de cada presa, en el orden interno de la población.
+    /// Permite señalar exactamente qué entidad fue la primera en divergir.
+    pub fn huellas_presas(&self) -> Vec<(u64, u64)> {
+        self.presas.iter().map(|p| (p.id(), self.huella_presa(p.as_ref()))).collect()
+    }
+
+    /// Huella de una presa a partir de todos sus datos observables y de lo que la simulación
+    /// guarda de ella aparte: posición, celda del pastizal y salud.
+    fn huella_presa(&self, presa: &dyn Presa) -> u64 {
+        let mut h = Fnv1a::new();
+        h.escribir(presa.id());
+        h.escribir(presa.especie() as u64);
+        h.escribir(presa.sexo() as u64);
+        h.escribir(presa.edad() as u64);
+        h.escribir(presa.peso().to_bits());
+        h.escribir(presa.esta_viva() as u64);
+        if let Some(gestacion) = presa.gestacion() {
+            h.escribir(gestacion.dias_restantes as u64);
+            h.escribir(gestacion.crias as u64);
+        }
+        escribir_posicion(&mut h, self.posiciones_presas.get(&presa.id()).copied());
+        h.escribir(self.celdas_presas.get(&presa.id()).map_or(u64::MAX, |&celda| celda as u64));
+        h.escribir(self.salud_presa(presa.id()) as u64);
+        h.terminar()
+    }
+}
+
+/// Punto de una presa según el mapa de posiciones o, si todavía no tiene, el que le toca por su id.
+fn posicion_de(posiciones: &HashMap<u64, Posicion>, id: u64, lado: f32) -> Posicion {
+    posiciones.get(&id).copied().unwrap_or_else(|| Posicion::inicial(id, lado))
+}
+
+/// Evento de la muerte natural de una presa.
+fn muerte_presa(presa: &dyn Presa, causa: CausaMuerte) -> Evento {
+    Evento::MuertePresa { id: presa.id(), especie: presa.especie(), edad_dias: presa.edad(), causa }
+}
+
+/// Añade a la huella todo el estado de un depredador.
+fn escribir_depredador(h: &mut Fnv1a, depredador: &Depredador) {
+    h.escribir(depredador.reserva_comida_kg.to_bits());
+    h.escribir(depredador.vivo as u64);
+    h.escribir(depredador.despensa_kg.to_bits());
+    h.escribir(depredador.edad_dias as u64);
+    h.escribir(depredador.sexo as u64);
+    escribir_posicion(h, depredador.posicion);
+}
+
+/// Añade a la huella una posición opcional, distinguiendo la ausencia de cualquier punto.
+fn escribir_posicion(h: &mut Fnv1a, posicion: Option<Posicion>) {
+    match posicion {
+        Some(p) => {
+            h.escribir(1);
+            h.escribir(p.x.to_bits() as u64);
+            h.escribir(p.y.to_bits() as u64);
+        }
+        None => h.escribir(0),
+    }
+}
+
+/// Hash FNV-1a de 64 bits. Se implementa a mano porque el `DefaultHasher` de la
+/// biblioteca estándar no garantiza el mismo resultado entre versiones de Rust.
+pub(crate) struct Fnv1a(u64);
+
+impl Fnv1a {
+    pub(crate) fn new() -> Self { Fnv1a(0xcbf2_9ce4_8422_2325) }
+
+    pub(crate) fn escribir(&mut self, valor: u64) {
+        for byte in valor.to_le_bytes() {
+            self.0 ^= byte as u64;
+            self.0 = self.0.wrapping_mul(0x0000_0100_0000_01b3);
+        }
+    }
+
+    pub(crate) fn terminar(&self) -> u64 { self.0 }
+}
+
+/// Población de partida para un arranque en caliente: las presas vivas y la reserva del
+/// depredador con que terminó otra corrida, para empezar ya cerca del equilibrio en lugar de
+/// repetir en cada corrida el transitorio inicial. Se conservan también los parámetros de
+/// aquella corrida por si la nueva no indica otros.
+#[derive(Debug, Clone)]
+pub struct ArranqueCaliente {
+    pub presas: Vec<EstadoPresa>,
+    pub reserva_depredador_kg: f64,
+    pub parametros: Parametros,
+}
+
+impl ArranqueCaliente {
+    pub fn desde_instantanea(instantanea: Instantanea) -> Self {
+        Self {
+            presas: instantanea.presas.into_iter().filter(|p| p.vivo).collect(),
+            reserva_depredador_kg: instantanea.depredador.reserva_comida_kg,
+            parametros: instantanea.parametros,
+        }
+    }
+
+    /// Lee la instantánea final de otra corrida (binaria o JSON).
+    pub fn cargar(ruta: impl AsRef<Path>) -> Result<Self, String> {
+        let ruta = ruta.as_ref();
+        let instantanea = Instantanea::cargar(ruta).map_err(|e| format!("no se pudo leer {}: {}", ruta.display(), e))?;
+        Ok(Self::desde_instantanea(instantanea))
+    }
+}
+
+/// Construye simulaciones a medida: parámetros, semilla y población inicial. Los métodos se
+/// pueden encadenar en cualquier orden: lo que se fija con `conejos`, `cabras` o
+/// `reserva_depredador` prevalece sobre los `parametros`; p. ej.
+/// `SimulacionBuilder::new().conejos(100).cabras(10).reserva_depredador(500.0).semilla(42).construir()`.
+#[derive(Default)]
+pub struct SimulacionBuilder {
+    parametros: Parametros,
+    semilla: Option<u64>,
+    censo: Option<Vec<EstadoPresa>>,
+    n_conejos: Option<u32>,
+    n_cabras: Option<u32>,
+    /// Reserva inicial de cada depredador; la fija un arranque en caliente o `reserva_depredador`.
+    reserva_depredador_kg: Option<f64>,
+    retencion: Option<Retencion>,
+}
+
+impl SimulacionBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Parte de un censo en CSV (columnas species, sex, age, weight) en lugar de una
+    /// población sintética de recién nacidos.
+    pub fn desde_censo(ruta: impl AsRef<Path>) -> Result<Self, String> {
+        Ok(Self::new().censo(censo::leer_censo(ruta)?))
+    }
+
+    /// Usa una población inicial ya leída; sustituye a `N_CONEJOS_INICIAL`/`N_CABRAS_INICIAL`.
+    pub fn censo(mut self, presas: Vec<EstadoPresa>) -> Self {
+        self.censo = Some(presas);
+        self
+    }
+
+    /// Parte de la población y la reserva del depredador de un arranque en caliente (la de
+    /// cada depredador de la manada). La corrida empieza en el día 0 con los parámetros que se
+    /// indiquen aparte.
+    pub fn arranque(mut self, arranque: &ArranqueCaliente) -> Self {
+        self.censo = Some(arranque.presas.clone());
+        self.reserva_depredador_kg = Some(arranque.reserva_depredador_kg);
+        self
+    }
+
+    pub fn parametros(mut self, parametros: Parametros) -> Self {
+        self.parametros = parametros;
+        self
+    }
+
+    pub fn semilla(mut self, semilla: u64) -> Self {
+        self.semilla = Some(semilla);
+        self
+    }
+
+    /// Conejos recién nacidos de la población inicial (`N_CONEJOS_INICIAL`); sin efecto con un censo.
+    pub fn conejos(mut self, cantidad: u32) -> Self {
+        self.n_conejos = Some(cantidad);
+        self
+    }
+
+    /// Cabras recién nacidas de la población inicial (`N_CABRAS_INICIAL`); sin efecto con un censo.
+    pub fn cabras(mut self, cantidad: u32) -> Self {
+        self.n_cabras = Some(cantidad);
+        self
+    }
+
+    /// Reserva inicial de cada depredador (`DEPREDADOR_RESERVA_INICIAL_KG`), en kg; sustituye a
+    /// la de un arranque en caliente.
+    pub fn reserva_depredador(mut self, kg: f64) -> Self {
+        self.reserva_depredador_kg = Some(kg);
+        self
+    }
+
+    /// Qué instantáneas se retienen para retroceder; por defecto, `Retencion::default()`.
+    pub fn retencion(mut self, retencion: Retencion) -> Self {
+        self.retencion = Some(retencion);
+        self
+    }
+
+    /// Crea la simulación; sin semilla explícita se sortea una.
+    pub fn construir(self) -> Simulacion {
+        let semilla = self.semilla.unwrap_or_else(rand::random);
+        let mut parametros = self.parametros;
+        parametros.n_conejos_inicial = self.n_conejos.unwrap_or(parametros.n_conejos_inicial);
+        parametros.n_cabras_inicial = self.n_cabras.unwrap_or(parametros.n_cabras_inicial);
+        parametros.depredador_reserva_inicial_kg = self.reserva_depredador_kg.unwrap_or(parametros.depredador_reserva_inicial_kg);
+        let mut sim = Simulacion::construir(parametros, semilla, self.censo);
+        if let Some(retencion) = self.retencion {
+            sim.retener(retencion);
+        }
+        sim
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bus::Oyente;
+
+    fn huellas(semilla: u64, dias: u32) -> Vec<u64> {
+        let mut sim = Simulacion::con_semilla(semilla);
+        (0..dias).map(|_| { sim.avanzar_dia(); sim.huella() }).collect()
+    }
+
+    #[test]
+    fn la_huella_solo_coincide_con_la_misma_semilla() {
+        assert_eq!(huellas(42, 30), huellas(42, 30));
+        assert_ne!(huellas(42, 30)[0], huellas(43, 30)[0]);
+    }
+
+    #[test]
+    fn los_ids_agotados_no_se_repiten_ni_desbordan() {
+        let mut sim = Simulacion::con_semilla(1);
+        sim.next_id = u64::MAX - 2;
+        let ids = sim.generar_presas(Especie::Conejo, 5);
+        assert_eq!(ids, vec![u64::MAX - 2, u64::MAX - 1]);
+        assert_eq!(sim.agregar_presa(Especie::Cabra), None);
+        let mut todos: Vec<u64> = sim.presas.iter().map(|p| p.id()).collect();
+        let total = todos.len();
+        todos.sort_unstable();
+        todos.dedup();
+        assert_eq!(todos.len(), total);
+        // La corrida sigue avanzando con el contador agotado.
+        for _ in 0..30 {
+            sim.avanzar_dia();
+        }
+        assert_eq!(sim.next_id, u64::MAX);
+    }
+
+    #[test]
+    fn las_intervenciones_no_desplazan_a_las_instantaneas_periodicas() {
+        let mut sim = Simulacion::con_semilla(3);
+        for _ in 0..200 {
+            sim.avanzar_dia();
+            let id = sim.agregar_presa(Especie::Conejo).unwrap();
+            sim.colocar_presa(id, Posicion::new(1.0, 1.0));
+            sim.retirar_presa(id);
+        }
+        // Una por día intervenido, hasta el horizonte: el día 0 sigue retenido.
+        assert_eq!(sim.dia_mas_antiguo_retenido(), 0);
+        assert_eq!(sim.instantaneas.len(), 201);
+        assert!(sim.retroceder_a(5).is_ok());
+        assert_eq!(sim.dia, 5);
+    }
+
+    #[test]
+    fn la_retencion_respeta_su_presupuesto_y_se_puede_desactivar() {
+        let mut sim = Simulacion::con_semilla(4);
+        let una = sim.memoria_instantaneas();
+        sim.retener(Retencion { presupuesto_bytes: una * 3, ..Retencion::default() });
+        for _ in 0..100 {
+            sim.avanzar_dia();
+        }
+        assert!(sim.memoria_instantaneas() <= una * 3);
+        assert!(!sim.instantaneas.is_empty());
+        sim.retener(Retencion::DESACTIVADA);
+        sim.avanzar_dia();
+        assert_eq!(sim.memoria_instantaneas(), 0);
+        assert!(sim.retroceder_a(sim.dia - 1).is_err());
+    }
+
+    #[test]
+    fn guardar_y_cargar_conserva_los_totales_las_alarmas_y_la_retencion() {
+        let mut original = Simulacion::con_semilla(6);
+        original.retener(Retencion { intervalo_dias: 7, ..Retencion::default() });
+        original.definir_alarmas(vec![Alarma::desde_texto("poblacion > 1").unwrap()]);
+        for _ in 0..60 {
+            original.avanzar_dia();
+        }
+        assert!(original.muertes_corrida.total() > 0);
+        let ruta = std::env::temp_dir().join(format!("spd_guardar_y_cargar_{}.spd", std::process::id()));
+        original.guardar(&ruta).unwrap();
+        let cargada = Simulacion::cargar(&ruta);
+        std::fs::remove_file(&ruta).unwrap();
+        let mut cargada = cargada.unwrap();
+        assert_eq!(cargada.huella(), original.huella());
+        assert_eq!(cargada.balance_corrida, original.balance_corrida);
+        assert_eq!(cargada.muertes_corrida, original.muertes_corrida);
+        assert_eq!(cargada.alarmas(), original.alarmas());
+        assert_eq!(cargada.alarmas_cumplidas, original.alarmas_cumplidas);
+        assert_eq!(cargada.retencion(), original.retencion());
+        for _ in 0..30 {
+            original.avanzar_dia();
+            cargada.avanzar_dia();
+        }
+        assert_eq!(cargada.huella(), original.huella());
+        assert_eq!(cargada.balance_corrida, original.balance_corrida);
+        assert_eq!(cargada.muertes_corrida, original.muertes_corrida);
+    }
+
+    /// Anota la fase y el tipo de cada suceso que recibe.
+    #[derive(Default)]
+    struct Grabadora(Vec<(Fase, String)>);
+
+    impl Oyente for Grabadora {
+        fn recibir(&mut self, fase: Fase, suceso: &Suceso) {
+            let tipo = format!("{:?}", suceso);
+            self.0.push((fase, tipo[..tipo.find([' ', '(']).unwrap_or(tipo.len())].to_string()));
+        }
+    }
+
+    #[test]
+    fn los_oyentes_reciben_los_sucesos_en_el_orden_de_las_fases() {
+        let mut parametros = Parametros::default();
+        parametros.manada.activa = true;
+        parametros.cadena_trofica.activa = true;
+        let mut sim = Simulacion::con_parametros(parametros, 5);
+        for _ in 0..3 {
+            sim.avanzar_dia();
+        }
+        let mut grabadora = Grabadora::default();
+        sim.bus.repartir(&mut grabadora);
+        assert_eq!(grabadora.0.len(), sim.sucesos_dia().len());
+
+        let posicion = |fase: Fase| Fase::ORDEN.iter().position(|&f| f == fase).unwrap();
+        assert!(grabadora.0.windows(2).all(|par| posicion(par[0].0) <= posicion(par[1].0)), "{:?}", grabadora.0);
+        let tipos: Vec<&str> = grabadora.0.iter().map(|(_, tipo)| tipo.as_str()).collect();
+        let esperados = [
+            (Fase::Manada, "Amanecer"),
+            (Fase::Manada, "Manada"),
+            (Fase::Depredador, "Despensa"),
+            (Fase::Presas, "Pastoreo"),
+            (Fase::Presas, "Crecimiento"),
+            (Fase::Censo, "Nacimientos"),
+            (Fase::Censo, "Retiradas"),
+        ];
+        let mut resto = grabadora.0.iter();
+        for esperado in esperados {
+            assert!(resto.any(|(fase, tipo)| (*fase, tipo.as_str()) == esperado), "falta {:?} en su sitio: {:?}", esperado, tipos);
+        }
+    }
+
+    #[test]
+    fn la_huella_cubre_el_estado_que_se_guarda_aparte_de_las_presas() {
+        let sim = Simulacion::con_semilla(2);
+        let id = sim.presas[0].id();
+        let base = sim.huella();
+        let cambios: [fn(&mut Simulacion, u64); 5] = [
+            |sim, id| { sim.posiciones_presas.insert(id, Posicion { x: 1.0, y: 2.0 }); },
+            |sim, id| { sim.salud_presas.insert(id, Salud::Infectada); },
+            |sim, _| sim.olas[1] = true,
+            |sim, _| sim.dias_brote[0] = 3,
+            |sim, _| sim.depredador.despensa_kg = 1.5,
+        ];
+        for (i, cambiar) in cambios.into_iter().enumerate() {
+            let mut otra = Simulacion::con_semilla(2);
+            cambiar(&mut otra, id);
+            assert_ne!(otra.huella(), base, "cambio {}", i);
+        }
+    }
+
+    #[test]
+    fn la_reserva_del_arranque_llega_a_toda_la_manada() {
+        let mut parametros = Parametros::default();
+        parametros.manada.activa = true;
+        parametros.manada.n_inicial = 3;
+        let arranque = ArranqueCaliente { presas: Vec::new(), reserva_depredador_kg: 123.0, parametros: Parametros::default() };
+        let reservas = |sim: &Simulacion| std::iter::once(&sim.depredador).chain(&sim.manada).map(|d| d.reserva_comida_kg).collect::<Vec<_>>();
+
+        let sim = SimulacionBuilder::new().arranque(&arranque).parametros(parametros.clone()).semilla(1).construir();
+        assert_eq!(reservas(&sim), vec![123.0; 3]);
+        let sim = SimulacionBuilder::new().arranque(&arranque).parametros(parametros).reserva_depredador(50.0).semilla(1).construir();
+        assert_eq!(reservas(&sim), vec![50.0; 3]);
+    }
+
+    #[test]
+    fn por_defecto_el_pasto_limita_a_las_presas() {
+        let parametros = Parametros { depredador_reserva_inicial_kg: 1e9, ..Parametros::default() };
+        let mut sim = Simulacion::con_parametros(parametros, 7);
+        let mut pico = 0;
+        for _ in 0..400 {
+            sim.avanzar_dia();
+            pico = pico.max(sim.presas.len());
+        }
+        assert!(pico < 10_000, "pico de {} presas", pico);
+        assert!(sim.muertes_corrida.hambre > 0);
+    }
+}
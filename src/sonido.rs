@@ -0,0 +1,207 @@
+// src/sonido.rs
+
+// Sonido del visualizador (parte del binario, no de la biblioteca), con el audio de macroquad.
+//   - Un golpe corto cada día en que el depredador caza.
+//   - Un aviso doble cuando una especie cae por debajo del umbral de alerta, o cuando el
+//     depredador entra en peligro o muere.
+//   - Un fondo en bucle distinto para cada estación del año.
+// Los sonidos se sintetizan al arrancar como WAV en memoria, así que no hay archivos que
+// distribuir ni en escritorio ni en la web.
+
+use std::f32::consts::TAU;
+
+use macroquad::audio::{load_sound_from_bytes, play_sound, stop_sound, PlaySoundParams, Sound};
+
+use simulador_ecosistema_presa_depredador::configuracion::AjustesAudio;
+use simulador_ecosistema_presa_depredador::eventos::Evento;
+use simulador_ecosistema_presa_depredador::simulacion::Simulacion;
+
+/// Frecuencia de muestreo de los sonidos sintetizados.
+const MUESTRAS_POR_SEGUNDO: u32 = 22_050;
+/// Duración del bucle de fondo. Todas sus frecuencias son enteras, así que cada
+/// onda completa un número exacto de ciclos y el bucle no chasquea al repetirse.
+const SEGUNDOS_AMBIENTE: f32 = 4.0;
+/// El fondo suena más bajo que los efectos.
+const VOLUMEN_AMBIENTE: f32 = 0.35;
+
+/// Estaciones de 91 días (el año de la simulación tiene 365), empezando en primavera.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Estacion {
+    Primavera,
+    Verano,
+    Otono,
+    Invierno,
+}
+
+impl Estacion {
+    fn del_dia(dia: u32) -> Self {
+        match (dia % 365) * 4 / 365 {
+            0 => Estacion::Primavera,
+            1 => Estacion::Verano,
+            2 => Estacion::Otono,
+            _ => Estacion::Invierno,
+        }
+    }
+}
+
+/// Sonidos cargados y estado necesario para decidir cuándo suenan.
+pub struct Sonido {
+    activado: bool,
+    volumen: f32,
+    umbral_alerta: u32,
+    caza: Sound,
+    alerta: Sound,
+    /// Un fondo por estación, en el orden de `Estacion`.
+    ambientes: [Sound; 4],
+    estacion_sonando: Option<Estacion>,
+    /// Conejos y cabras del día anterior, para detectar el cruce del umbral.
+    poblacion_anterior: (usize, usize),
+}
+
+impl Sonido {
+    /// Sintetiza y carga los sonidos; el fondo empieza a sonar si está activado.
+    pub async fn cargar(ajustes: &AjustesAudio, sim: &Simulacion) -> Result<Self, String> {
+        let cargar = |muestras: Vec<f32>| async move {
+            load_sound_from_bytes(&wav(&muestras)).await.map_err(|e| format!("no se pudo cargar un sonido: {}", e))
+        };
+        let mut sonido = Self {
+            activado: false,
+            volumen: ajustes.volumen.clamp(0.0, 1.0),
+            umbral_alerta: ajustes.umbral_alerta,
+            caza: cargar(sintetizar_caza()).await?,
+            alerta: cargar(sintetizar_alerta()).await?,
+            ambientes: [
+                cargar(sintetizar_ambiente(&[262, 330, 392], 0.0)).await?,
+                cargar(sintetizar_ambiente(&[294, 370, 440, 554], 0.0)).await?,
+                cargar(sintetizar_ambiente(&[220, 262, 330], 0.15)).await?,
+                cargar(sintetizar_ambiente(&[110, 165], 0.5)).await?,
+            ],
+            estacion_sonando: None,
+            poblacion_anterior: sim.contar_especies(),
+        };
+        sonido.alternar(ajustes.activado, sim);
+        Ok(sonido)
+    }
+
+    pub fn activado(&self) -> bool {
+        self.activado
+    }
+
+    /// Activa o silencia todo el sonido.
+    pub fn alternar(&mut self, activado: bool, sim: &Simulacion) {
+        self.activado = activado;
+        if activado {
+            self.actualizar_ambiente(sim);
+        } else if let Some(estacion) = self.estacion_sonando.take() {
+            stop_sound(&self.ambientes[estacion as usize]);
+        }
+    }
+
+    /// Reproduce los sonidos del día que la simulación acaba de completar.
+    pub fn dia_avanzado(&mut self, sim: &Simulacion) {
+        let (conejos, cabras) = sim.contar_especies();
+        let (conejos_antes, cabras_antes) = std::mem::replace(&mut self.poblacion_anterior, (conejos, cabras));
+        if !self.activado {
+            return;
+        }
+
+        if sim.estadisticas_dia.cazas > 0 {
+            self.efecto(&self.caza);
+        }
+
+        let umbral = self.umbral_alerta as usize;
+        let cruza_umbral = |antes: usize, ahora: usize| antes >= umbral && ahora < umbral;
+        let alerta_depredador = sim.eventos_dia.iter()
+            .any(|e| matches!(e, Evento::DepredadorEnPeligro { .. } | Evento::MuerteDepredador));
+        if cruza_umbral(conejos_antes, conejos) || cruza_umbral(cabras_antes, cabras) || alerta_depredador {
+            self.efecto(&self.alerta);
+        }
+
+        self.actualizar_ambiente(sim);
+    }
+
+    fn efecto(&self, sonido: &Sound) {
+        play_sound(sonido, PlaySoundParams { looped: false, volume: self.volumen });
+    }
+
+    /// Cambia el fondo si la estación ha cambiado.
+    fn actualizar_ambiente(&mut self, sim: &Simulacion) {
+        let estacion = Estacion::del_dia(sim.dia);
+        if self.estacion_sonando == Some(estacion) {
+            return;
+        }
+        if let Some(anterior) = self.estacion_sonando {
+            stop_sound(&self.ambientes[anterior as usize]);
+        }
+        let ambiente = &self.ambientes[estacion as usize];
+        play_sound(ambiente, PlaySoundParams { looped: true, volume: self.volumen * VOLUMEN_AMBIENTE });
+        self.estacion_sonando = Some(estacion);
+    }
+}
+
+// --- Síntesis ---
+
+fn n_muestras(segundos: f32) -> usize {
+    (segundos * MUESTRAS_POR_SEGUNDO as f32) as usize
+}
+
+/// Golpe grave con caída de tono y de volumen.
+fn sintetizar_caza() -> Vec<f32> {
+    let mut fase = 0.0;
+    (0..n_muestras(0.25))
+        .map(|i| {
+            let t = i as f32 / MUESTRAS_POR_SEGUNDO as f32;
+            fase += TAU * (220.0 - 440.0 * t) / MUESTRAS_POR_SEGUNDO as f32;
+            fase.sin() * (-t * 18.0).exp()
+        })
+        .collect()
+}
+
+/// Dos pitidos agudos seguidos.
+fn sintetizar_alerta() -> Vec<f32> {
+    (0..n_muestras(0.36))
+        .map(|i| {
+            let t = i as f32 / MUESTRAS_POR_SEGUNDO as f32;
+            let en_pitido = t % 0.18 < 0.12;
+            if en_pitido { 0.3 * (TAU * 880.0 * t).sin().signum() } else { 0.0 }
+        })
+        .collect()
+}
+
+/// Acorde sostenido con un vaivén lento de volumen y, opcionalmente, ruido de viento.
+fn sintetizar_ambiente(frecuencias: &[u32], ruido: f32) -> Vec<f32> {
+    // Generador congruencial: el ruido es el mismo en cada arranque.
+    let mut estado: u32 = 0x2545_F491;
+    (0..n_muestras(SEGUNDOS_AMBIENTE))
+        .map(|i| {
+            let t = i as f32 / MUESTRAS_POR_SEGUNDO as f32;
+            let acorde: f32 = frecuencias.iter().map(|&f| (TAU * f as f32 * t).sin()).sum::<f32>() / frecuencias.len() as f32;
+            let vaiven = 0.75 + 0.25 * (TAU * 0.5 * t).sin();
+            estado = estado.wrapping_mul(1_664_525).wrapping_add(1_013_904_223);
+            let blanco = (estado >> 8) as f32 / (1u32 << 24) as f32 * 2.0 - 1.0;
+            0.5 * ((1.0 - ruido) * acorde * vaiven + ruido * blanco * vaiven)
+        })
+        .collect()
+}
+
+/// Codifica muestras en [-1, 1] como WAV PCM de 16 bits, mono.
+fn wav(muestras: &[f32]) -> Vec<u8> {
+    let datos = (muestras.len() * 2) as u32;
+    let mut bytes = Vec::with_capacity(44 + datos as usize);
+    bytes.extend_from_slice(b"RIFF");
+    bytes.extend_from_slice(&(36 + datos).to_le_bytes());
+    bytes.extend_from_slice(b"WAVEfmt ");
+    bytes.extend_from_slice(&16u32.to_le_bytes());
+    bytes.extend_from_slice(&1u16.to_le_bytes()); // PCM
+    bytes.extend_from_slice(&1u16.to_le_bytes()); // mono
+    bytes.extend_from_slice(&MUESTRAS_POR_SEGUNDO.to_le_bytes());
+    bytes.extend_from_slice(&(MUESTRAS_POR_SEGUNDO * 2).to_le_bytes());
+    bytes.extend_from_slice(&2u16.to_le_bytes());
+    bytes.extend_from_slice(&16u16.to_le_bytes());
+    bytes.extend_from_slice(b"data");
+    bytes.extend_from_slice(&datos.to_le_bytes());
+    for muestra in muestras {
+        bytes.extend_from_slice(&((muestra.clamp(-1.0, 1.0) * i16::MAX as f32) as i16).to_le_bytes());
+    }
+    bytes
+}
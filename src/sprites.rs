@@ -0,0 +1,68 @@
+// src/sprites.rs
+
+// Imágenes de las presas y del depredador (parte del binario, no de la biblioteca).
+// Se cargan al arrancar de una carpeta (`sprites` o la que diga el escenario) con los nombres
+// `conejo.png`, `cabra.png` y `depredador.png`; con `load_texture` de macroquad funciona igual
+// en escritorio, en la web (se piden al servidor) y en Android (recursos del APK).
+// Cada imagen que falte o no se pueda leer se sustituye por el círculo de siempre, así que la
+// carpeta es opcional y se puede rellenar solo en parte.
+// Las presas se escalan con el mismo radio que los círculos, es decir, según su peso.
+
+use macroquad::prelude::*;
+
+use simulador_ecosistema_presa_depredador::entidades::Especie;
+
+/// Carpeta de la que se cargan las imágenes si el escenario no indica otra.
+pub const CARPETA_SPRITES: &str = "sprites";
+
+/// Imágenes cargadas; `None` en las que no se encontraron.
+#[derive(Default)]
+pub struct Sprites {
+    conejo: Option<Texture2D>,
+    cabra: Option<Texture2D>,
+    depredador: Option<Texture2D>,
+}
+
+impl Sprites {
+    /// Carga las imágenes de `carpeta`. Las que faltan no son un error: se dibujan círculos.
+    pub async fn cargar(carpeta: &str) -> Self {
+        let cargar = |nombre: &str| {
+            let ruta = format!("{}/{}.png", carpeta.trim_end_matches('/'), nombre);
+            async move {
+                let textura = load_texture(&ruta).await.ok()?;
+                textura.set_filter(FilterMode::Linear);
+                Some(textura)
+            }
+        };
+        Self {
+            conejo: cargar("conejo").await,
+            cabra: cargar("cabra").await,
+            depredador: cargar("depredador").await,
+        }
+    }
+
+    pub fn presa(&self, especie: Especie) -> Option<&Texture2D> {
+        match especie {
+            Especie::Conejo => self.conejo.as_ref(),
+            Especie::Cabra => self.cabra.as_ref(),
+        }
+    }
+
+    pub fn depredador(&self) -> Option<&Texture2D> {
+        self.depredador.as_ref()
+    }
+}
+
+/// Dibuja una imagen centrada en `centro`, encajada en el cuadrado que ocuparía un círculo de
+/// radio `radio` (sin deformarla) y teñida con `tinte` (`WHITE` la deja como está).
+pub fn dibujar(textura: &Texture2D, centro: Vec2, radio: f32, tinte: Color) {
+    let escala = 2.0 * radio / textura.width().max(textura.height()).max(1.0);
+    let tamano = vec2(textura.width() * escala, textura.height() * escala);
+    draw_texture_ex(
+        textura,
+        centro.x - tamano.x / 2.0,
+        centro.y - tamano.y / 2.0,
+        tinte,
+        DrawTextureParams { dest_size: Some(tamano), ..Default::default() },
+    );
+}
@@ -0,0 +1,79 @@
+// src/superposicion.rs
+
+// Archivo de superposición para transmisiones en directo (p. ej. una fuente de texto de OBS).
+// Tras cada día se reescribe un archivo pequeño con el día, las poblaciones y el estado del
+// depredador: en JSON si la ruta termina en `.json` (para overlays en HTML), o en texto plano.
+// Se escribe en un temporal y se renombra, así que quien lo lea nunca ve un archivo a medias.
+
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+use serde::Serialize;
+
+use crate::simulacion::Simulacion;
+
+/// Cifras que muestra la superposición.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct EstadoSuperposicion {
+    pub dia: u32,
+    pub semilla: u64,
+    pub conejos: usize,
+    pub cabras: usize,
+    pub depredador_vivo: bool,
+    pub reserva_kg: f64,
+    /// "optimo", "minimo", "peligro" o "muerto".
+    pub estado_depredador: &'static str,
+}
+
+impl EstadoSuperposicion {
+    pub fn de(sim: &Simulacion) -> Self {
+        let (conejos, cabras) = sim.contar_especies();
+        Self {
+            dia: sim.dia,
+            semilla: sim.semilla(),
+            conejos,
+            cabras,
+            depredador_vivo: sim.depredador.vivo,
+            reserva_kg: sim.depredador.reserva_comida_kg,
+            estado_depredador: sim.estado_depredador(),
+        }
+    }
+
+    /// Texto de una línea por dato, listo para una fuente de texto.
+    pub fn a_texto(&self) -> String {
+        format!(
+            "Día {}\nConejos: {}\nCabras: {}\nDepredador: {:.1} kg ({})\n",
+            self.dia, self.conejos, self.cabras, self.reserva_kg, self.estado_depredador
+        )
+    }
+}
+
+/// Escritor del archivo de superposición.
+pub struct Superposicion {
+    ruta: PathBuf,
+    temporal: PathBuf,
+    json: bool,
+}
+
+impl Superposicion {
+    pub fn new(ruta: impl Into<PathBuf>) -> Self {
+        let ruta = ruta.into();
+        let json = ruta.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("json"));
+        let mut temporal = ruta.clone().into_os_string();
+        temporal.push(".tmp");
+        Self { ruta, temporal: temporal.into(), json }
+    }
+
+    /// Reescribe el archivo con el estado actual de la simulación.
+    pub fn escribir(&self, sim: &Simulacion) -> io::Result<()> {
+        let estado = EstadoSuperposicion::de(sim);
+        let contenido = if self.json {
+            serde_json::to_string(&estado).map_err(io::Error::other)?
+        } else {
+            estado.a_texto()
+        };
+        fs::write(&self.temporal, contenido)?;
+        fs::rename(&self.temporal, &self.ruta)
+    }
+}
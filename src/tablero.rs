@@ -0,0 +1,206 @@
+// src/tablero.rs
+
+// Tablero de escenarios (parte del binario, no de la biblioteca).
+// Con `--tablero` el visualizador no abre una simulación sino una cuadrícula de 2 a 9 casillas,
+// cada una con la curva de población de una corrida, para recorrer de un vistazo un barrido de
+// parámetros. Las corridas se definen como en `simulador experiment` (una por combinación de
+// valores de `--variar`) y cada una avanza en su propio hilo tan rápido como puede; también se
+// pueden cargar corridas ya grabadas con `simulador run --replay` (o con F6 en el visualizador),
+// que aparecen completas.
+// Todas las corridas nuevas usan la misma semilla, para que las diferencias entre casillas se
+// deban a los parámetros y no al azar.
+//
+// Uso:
+//   simulador_ecosistema_presa_depredador --tablero [--variar NOMBRE=v1,v2|inicio:paso:fin]... [--cargar CORRIDA.replay]... [--dias N] [--semilla N]
+
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver};
+use std::thread;
+
+use macroquad::prelude::*;
+
+use simulador_ecosistema_presa_depredador::experimento::{Experimento, Variacion};
+use simulador_ecosistema_presa_depredador::idioma::{Idioma, Texto};
+use simulador_ecosistema_presa_depredador::parametros::Parametros;
+use simulador_ecosistema_presa_depredador::repeticion::Repeticion;
+use simulador_ecosistema_presa_depredador::simulacion::{Retencion, SimulacionBuilder};
+
+/// Casillas que caben en la cuadrícula (3x3).
+const MAXIMO_CASILLAS: usize = 9;
+/// Separación entre casillas y margen interior, en píxeles.
+const MARGEN: f32 = 8.0;
+/// Alto de las líneas de texto de cada casilla.
+const ALTO_TEXTO: f32 = 18.0;
+
+/// Opciones del tablero.
+#[derive(Debug, Clone)]
+pub struct OpcionesTablero {
+    pub variaciones: Vec<Variacion>,
+    pub cargar: Vec<PathBuf>,
+    pub dias: u32,
+    pub semilla: Option<u64>,
+}
+
+impl OpcionesTablero {
+    /// Lee las opciones de la línea de comandos; `None` si no se pidió el tablero.
+    pub fn desde_args(args: impl Iterator<Item = String>) -> Result<Option<Self>, String> {
+        let mut pedido = false;
+        let mut opciones = Self { variaciones: Vec::new(), cargar: Vec::new(), dias: 3650, semilla: None };
+        let mut args = args;
+        while let Some(arg) = args.next() {
+            let mut valor = || args.next().ok_or(format!("falta el valor de {}", arg));
+            match arg.as_str() {
+                "--tablero" => pedido = true,
+                "--variar" => opciones.variaciones.push(Variacion::desde_texto(&valor()?)?),
+                "--cargar" => opciones.cargar.push(PathBuf::from(valor()?)),
+                "--dias" => opciones.dias = valor()?.parse().map_err(|_| "número de días inválido")?,
+                "--semilla" => opciones.semilla = Some(valor()?.parse().map_err(|_| "semilla inválida")?),
+                // El resto de flags (p. ej. `--alarma`) son del visualizador.
+                _ => {}
+            }
+        }
+        Ok(pedido.then_some(opciones))
+    }
+}
+
+/// Lo que se sabe de una corrida: su población día a día y si el depredador sigue vivo.
+struct Casilla {
+    etiqueta: String,
+    /// Conejos y cabras al final de cada día, desde el día 0.
+    poblaciones: Vec<(u32, u32)>,
+    depredador_vivo: bool,
+    /// Días que llegan del hilo que corre la simulación; `None` en las corridas cargadas.
+    receptor: Option<Receiver<(u32, u32, bool)>>,
+}
+
+impl Casilla {
+    /// Lanza la corrida en su propio hilo.
+    fn correr(etiqueta: String, parametros: Parametros, semilla: u64, dias: u32) -> Self {
+        let (emisor, receptor) = mpsc::channel();
+        thread::spawn(move || {
+            let mut sim = SimulacionBuilder::new().parametros(parametros).semilla(semilla).retencion(Retencion::DESACTIVADA).construir();
+            let (conejos, cabras) = sim.contar_especies();
+            let _ = emisor.send((conejos as u32, cabras as u32, true));
+            while sim.dia < dias && sim.depredador.vivo {
+                sim.avanzar_dia();
+                let (conejos, cabras) = sim.contar_especies();
+                // Si se cierra la ventana, el receptor desaparece y el hilo termina.
+                if emisor.send((conejos as u32, cabras as u32, sim.depredador.vivo)).is_err() {
+                    return;
+                }
+            }
+        });
+        Self { etiqueta, poblaciones: Vec::new(), depredador_vivo: true, receptor: Some(receptor) }
+    }
+
+    fn cargada(ruta: &Path, repeticion: &Repeticion) -> Self {
+        let nombre = ruta.file_name().map_or_else(|| ruta.display().to_string(), |n| n.to_string_lossy().into_owned());
+        Self {
+            etiqueta: format!("{} ({})", nombre, repeticion.semilla),
+            poblaciones: repeticion.fotogramas.iter().map(|f| (f.conejos, f.cabras)).collect(),
+            depredador_vivo: repeticion.fotogramas.last().is_none_or(|f| f.depredador_vivo),
+            receptor: None,
+        }
+    }
+
+    /// Recoge los días que el hilo haya terminado desde el último fotograma.
+    fn recibir(&mut self) {
+        if let Some(receptor) = &self.receptor {
+            for (conejos, cabras, vivo) in receptor.try_iter() {
+                self.poblaciones.push((conejos, cabras));
+                self.depredador_vivo = vivo;
+            }
+        }
+    }
+
+    fn dibujar(&self, area: Rect, idioma: Idioma) {
+        draw_rectangle(area.x, area.y, area.w, area.h, Color::new(0.96, 0.96, 0.94, 1.0));
+        draw_rectangle_lines(area.x, area.y, area.w, area.h, 1.0, GRAY);
+        draw_text(&self.etiqueta, area.x + MARGEN, area.y + ALTO_TEXTO, ALTO_TEXTO, BLACK);
+        let dia = self.poblaciones.len().saturating_sub(1);
+        let (conejos, cabras) = self.poblaciones.last().copied().unwrap_or_default();
+        let estado = idioma.texto(if self.depredador_vivo { Texto::DepredadorVivo } else { Texto::DepredadorSinVida });
+        let resumen = format!(
+            "{} {}: {} {}, {} {}, {}",
+            idioma.texto(Texto::Dia), dia, conejos, idioma.texto(Texto::Conejos), cabras, idioma.texto(Texto::Cabras), estado
+        );
+        draw_text(&resumen, area.x + MARGEN, area.y + 2.0 * ALTO_TEXTO, ALTO_TEXTO, if self.depredador_vivo { DARKGRAY } else { RED });
+
+        let grafico = Rect::new(area.x + MARGEN, area.y + 2.5 * ALTO_TEXTO, area.w - 2.0 * MARGEN, area.h - 2.5 * ALTO_TEXTO - MARGEN);
+        let mayor = self.poblaciones.iter().map(|&(conejos, cabras)| conejos.max(cabras)).max().unwrap_or(0).max(1) as f32;
+        dibujar_curva(grafico, &self.poblaciones, mayor, |&(conejos, _)| conejos, BROWN);
+        dibujar_curva(grafico, &self.poblaciones, mayor, |&(_, cabras)| cabras, DARKBLUE);
+    }
+}
+
+/// Curva de población en `area`: el eje x son los días de la corrida y el y va de 0 a `mayor`.
+/// Con más días que píxeles se dibuja un punto por columna.
+pub fn dibujar_curva(area: Rect, poblaciones: &[(u32, u32)], mayor: f32, valor: impl Fn(&(u32, u32)) -> u32, color: Color) {
+    if poblaciones.len() < 2 {
+        return;
+    }
+    let paso = (poblaciones.len() as f32 / area.w).ceil().max(1.0) as usize;
+    let ultimo = (poblaciones.len() - 1) as f32;
+    let punto = |i: usize| {
+        let x = area.x + area.w * i as f32 / ultimo;
+        let y = area.y + area.h * (1.0 - valor(&poblaciones[i]) as f32 / mayor);
+        vec2(x, y)
+    };
+    let mut anterior = punto(0);
+    for i in (paso..poblaciones.len()).step_by(paso).chain([poblaciones.len() - 1]) {
+        let actual = punto(i);
+        draw_line(anterior.x, anterior.y, actual.x, actual.y, 1.5, color);
+        anterior = actual;
+    }
+}
+
+/// Etiqueta de una combinación de valores, p. ej. `CONEJO_TASA=0.4, CABRA_TASA=0.2`.
+fn etiqueta_combinacion(variaciones: &[Variacion], combinacion: &[serde_json::Value]) -> String {
+    variaciones.iter().zip(combinacion).map(|(v, valor)| format!("{}={}", v.nombre, valor)).collect::<Vec<_>>().join(", ")
+}
+
+/// Prepara las casillas y las muestra hasta que se cierre la ventana.
+pub async fn mostrar(opciones: &OpcionesTablero, base: Parametros, semilla_escenario: Option<u64>, idioma: Idioma) -> Result<(), String> {
+    let experimento = Experimento {
+        nombre: String::new(),
+        base,
+        variaciones: opciones.variaciones.clone(),
+        repeticiones: 1,
+        dias: opciones.dias,
+        semilla_base: 0,
+        numeros_comunes: false,
+        arranque: None,
+    };
+    let combinaciones = if opciones.variaciones.is_empty() { Vec::new() } else { experimento.combinaciones() };
+    let total = combinaciones.len() + opciones.cargar.len();
+    if !(2..=MAXIMO_CASILLAS).contains(&total) {
+        return Err(format!("el tablero admite de 2 a {} corridas y se pidieron {}", MAXIMO_CASILLAS, total));
+    }
+
+    let mut casillas = Vec::with_capacity(total);
+    for ruta in &opciones.cargar {
+        let repeticion = Repeticion::cargar(ruta).map_err(|e| format!("no se pudo leer {}: {}", ruta.display(), e))?;
+        casillas.push(Casilla::cargada(ruta, &repeticion));
+    }
+    // Sin semilla pedida se sortea una, la misma para todas las corridas nuevas.
+    let semilla = opciones.semilla.or(semilla_escenario).unwrap_or_else(::rand::random);
+    for combinacion in &combinaciones {
+        let parametros = experimento.parametros_de(combinacion)?;
+        casillas.push(Casilla::correr(etiqueta_combinacion(&opciones.variaciones, combinacion), parametros, semilla, opciones.dias));
+    }
+
+    let columnas = (total as f32).sqrt().ceil() as usize;
+    let filas = total.div_ceil(columnas);
+    loop {
+        clear_background(WHITE);
+        let ancho = (screen_width() - MARGEN) / columnas as f32;
+        let alto = (screen_height() - MARGEN) / filas as f32;
+        for (i, casilla) in casillas.iter_mut().enumerate() {
+            casilla.recibir();
+            let (columna, fila) = (i % columnas, i / columnas);
+            let area = Rect::new(MARGEN + columna as f32 * ancho, MARGEN + fila as f32 * alto, ancho - MARGEN, alto - MARGEN);
+            casilla.dibujar(area, idioma);
+        }
+        next_frame().await;
+    }
+}
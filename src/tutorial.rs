@@ -0,0 +1,152 @@
+// src/tutorial.rs
+
+// Tutorial guiado opcional del visualizador (parte del binario, no de la biblioteca).
+// El guion es una lista de pasos: cada uno espera a que ocurra algo en la simulación
+// (un día concreto, el depredador en peligro, una extinción...), entonces pausa, explica lo
+// que está pasando y resalta la parte de la pantalla de la que habla. Se activa con
+// `tutorial = true` en el escenario o con `--tutorial`.
+
+use egui_macroquad::egui;
+
+use simulador_ecosistema_presa_depredador::idioma::{Idioma, Texto};
+use simulador_ecosistema_presa_depredador::simulacion::Simulacion;
+
+use crate::paneles::Paneles;
+
+/// Lo que hace avanzar el guion hasta un paso.
+#[derive(Debug, Clone, Copy)]
+enum Disparador {
+    /// Nada más empezar.
+    Inicio,
+    /// Al llegar a un día.
+    Dia(u32),
+    /// La reserva del depredador baja del consumo mínimo diario.
+    DepredadorEnPeligro,
+    /// Se extingue alguna especie de presa.
+    Extincion,
+    /// Muere el depredador.
+    MuerteDepredador,
+}
+
+/// Parte de la pantalla que resalta un paso.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Resaltado {
+    /// El círculo del depredador en la escena.
+    Depredador,
+    /// Una ventana, por el texto de su título (se abre si estaba cerrada).
+    Ventana(Texto),
+}
+
+struct Paso {
+    disparador: Disparador,
+    texto: Texto,
+    resaltado: Option<Resaltado>,
+    /// Añade al texto el consumo mínimo diario del depredador.
+    con_consumo_minimo: bool,
+}
+
+/// El guion del tutorial. Cada paso se muestra una sola vez, cuando se cumple su disparador;
+/// si se cumplen varios a la vez, primero el que va antes en la lista.
+const GUION: &[Paso] = &[
+    Paso { disparador: Disparador::Inicio, texto: Texto::TutorialBienvenida, resaltado: Some(Resaltado::Depredador), con_consumo_minimo: false },
+    Paso { disparador: Disparador::Dia(3), texto: Texto::TutorialReserva, resaltado: Some(Resaltado::Ventana(Texto::VentanaEstadisticas)), con_consumo_minimo: true },
+    Paso { disparador: Disparador::Dia(20), texto: Texto::TutorialParametros, resaltado: Some(Resaltado::Ventana(Texto::VentanaParametros)), con_consumo_minimo: false },
+    Paso { disparador: Disparador::DepredadorEnPeligro, texto: Texto::TutorialPeligro, resaltado: Some(Resaltado::Depredador), con_consumo_minimo: true },
+    Paso { disparador: Disparador::Extincion, texto: Texto::TutorialExtincion, resaltado: Some(Resaltado::Ventana(Texto::VentanaEventos)), con_consumo_minimo: false },
+    Paso { disparador: Disparador::MuerteDepredador, texto: Texto::TutorialFin, resaltado: None, con_consumo_minimo: false },
+];
+
+/// Avance por el guion.
+pub struct Tutorial {
+    /// Pasos ya mostrados.
+    vistos: Vec<bool>,
+    /// Índice del paso que está en pantalla esperando a que se pulse "Continuar".
+    paso: usize,
+    mostrando: bool,
+}
+
+impl Tutorial {
+    pub fn new() -> Self {
+        Self { vistos: vec![false; GUION.len()], paso: 0, mostrando: false }
+    }
+
+    /// Parte de la pantalla que hay que resaltar ahora.
+    pub fn resaltado(&self) -> Option<Resaltado> {
+        if !self.mostrando {
+            return None;
+        }
+        GUION[self.paso].resaltado
+    }
+
+    /// Comprueba si se cumple el disparador de algún paso pendiente; si es así, lo muestra
+    /// y devuelve `true` para que se pause la simulación.
+    pub fn revisar(&mut self, sim: &Simulacion) -> bool {
+        if self.mostrando {
+            return false;
+        }
+        let pendiente = GUION.iter().enumerate().find(|(i, paso)| !self.vistos[*i] && cumplido(paso.disparador, sim));
+        let Some((indice, _)) = pendiente else {
+            return false;
+        };
+        self.paso = indice;
+        self.mostrando = true;
+        true
+    }
+
+    /// Dibuja el paso actual y su resaltado. Devuelve `true` cuando se pulsa "Continuar".
+    pub fn mostrar(&mut self, ctx: &egui::Context, sim: &Simulacion, paneles: &mut Paneles, idioma: Idioma) -> bool {
+        if !self.mostrando {
+            return false;
+        }
+        let paso = &GUION[self.paso];
+        let mut texto = idioma.texto(paso.texto).to_string();
+        if paso.con_consumo_minimo {
+            texto.push_str(&format!(" ({:.1} kg)", sim.parametros.consumo_depredador_kg().0));
+        }
+
+        if let Some(Resaltado::Ventana(titulo)) = paso.resaltado {
+            match titulo {
+                Texto::VentanaEstadisticas => paneles.estadisticas = true,
+                Texto::VentanaParametros => paneles.parametros = true,
+                Texto::VentanaEventos => paneles.eventos = true,
+                _ => {}
+            }
+            if let Some(rect) = ctx.memory(|m| m.area_rect(egui::Id::new(idioma.texto(titulo)))) {
+                let capa = egui::LayerId::new(egui::Order::Foreground, egui::Id::new("resaltado_tutorial"));
+                ctx.layer_painter(capa).rect_stroke(
+                    rect.expand(4.0),
+                    6.0,
+                    egui::Stroke::new(3.0, egui::Color32::YELLOW),
+                    egui::StrokeKind::Outside,
+                );
+            }
+        }
+
+        let mut continuar = false;
+        egui::Window::new(idioma.texto(Texto::Tutorial))
+            .anchor(egui::Align2::CENTER_BOTTOM, [0.0, -20.0])
+            .collapsible(false)
+            .resizable(false)
+            .show(ctx, |ui| {
+                ui.set_max_width(420.0);
+                ui.label(texto);
+                continuar = ui.button(idioma.texto(Texto::Continuar)).clicked();
+            });
+        if continuar {
+            self.mostrando = false;
+            self.vistos[self.paso] = true;
+        }
+        continuar
+    }
+}
+
+fn cumplido(disparador: Disparador, sim: &Simulacion) -> bool {
+    let (conejos, cabras) = sim.contar_especies();
+    match disparador {
+        Disparador::Inicio => true,
+        Disparador::Dia(dia) => sim.dia >= dia,
+        Disparador::DepredadorEnPeligro => sim.depredador.vivo && sim.depredador.en_peligro(&sim.parametros),
+        Disparador::Extincion => conejos == 0 || cabras == 0,
+        Disparador::MuerteDepredador => !sim.depredador.vivo,
+    }
+}
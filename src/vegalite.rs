@@ -0,0 +1,131 @@
+// src/vegalite.rs
+
+// Exportación de una corrida como especificación Vega-Lite: los datos van incrustados en
+// el propio JSON junto con dos vistas (curvas de población a lo largo del tiempo y plano de
+// fases presa-depredador), más una tercera con los tres niveles de la cadena trófica cuando
+// la corrida tiene pasto, así que el archivo se puede abrir en el editor de Vega, en una
+// vista previa de VS Code o incrustar en una página sin escribir código de gráficos.
+
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use serde::Serialize;
+use serde_json::{json, Value};
+
+/// Versión del esquema de Vega-Lite que se declara en las especificaciones.
+pub const ESQUEMA_VEGA_LITE: &str = "https://vega.github.io/schema/vega-lite/v5.json";
+
+/// Curvas de conejos y cabras por día.
+pub fn especificacion_poblaciones() -> Value {
+    json!({
+        "title": "Poblaciones",
+        "width": 600,
+        "height": 250,
+        "transform": [{ "fold": ["conejos", "cabras"], "as": ["especie", "individuos"] }],
+        "mark": "line",
+        "encoding": {
+            "x": { "field": "dia", "type": "quantitative", "title": "Día" },
+            "y": { "field": "individuos", "type": "quantitative", "title": "Individuos" },
+            "color": { "field": "especie", "type": "nominal", "title": "Especie" }
+        }
+    })
+}
+
+/// Plano de fases: presas totales frente a la reserva del depredador, recorrido en orden de días.
+pub fn especificacion_fases() -> Value {
+    json!({
+        "title": "Plano de fases",
+        "width": 600,
+        "height": 250,
+        "transform": [{ "calculate": "datum.conejos + datum.cabras", "as": "presas" }],
+        "mark": { "type": "line", "point": { "size": 8 } },
+        "encoding": {
+            "x": { "field": "presas", "type": "quantitative", "title": "Presas vivas" },
+            "y": { "field": "reserva_depredador_kg", "type": "quantitative", "title": "Reserva del depredador (kg)" },
+            "order": { "field": "dia", "type": "quantitative" },
+            "color": { "field": "dia", "type": "quantitative", "title": "Día" }
+        }
+    })
+}
+
+/// Pasto, presas y reserva del depredador juntos. Cada nivel se escala a su propio máximo,
+/// porque sus magnitudes no se parecen: lo que se compara es el desfase entre las oscilaciones.
+pub fn especificacion_cadena_trofica() -> Value {
+    json!({
+        "title": "Cadena trófica",
+        "width": 600,
+        "height": 250,
+        "transform": [
+            { "calculate": "datum.conejos + datum.cabras", "as": "presas" },
+            { "fold": ["pasto_kg", "presas", "reserva_depredador_kg"], "as": ["nivel", "valor"] },
+            { "joinaggregate": [{ "op": "max", "field": "valor", "as": "maximo" }], "groupby": ["nivel"] },
+            { "calculate": "datum.maximo > 0 ? datum.valor / datum.maximo : 0", "as": "relativo" }
+        ],
+        "mark": "line",
+        "encoding": {
+            "x": { "field": "dia", "type": "quantitative", "title": "Día" },
+            "y": { "field": "relativo", "type": "quantitative", "title": "Fracción del máximo" },
+            "color": {
+                "field": "nivel",
+                "type": "nominal",
+                "title": "Nivel",
+                "sort": ["pasto_kg", "presas", "reserva_depredador_kg"],
+                "scale": { "range": ["#4c9a2a", "#8c6d31", "#b22222"] }
+            }
+        }
+    })
+}
+
+/// Especificación completa con los datos de la corrida incrustados y las vistas apiladas;
+/// la de la cadena trófica solo si la corrida tuvo pasto. Las filas pueden ser las del
+/// registro diario o las del esqueleto determinista: basta con que tengan las mismas columnas.
+pub fn tablero<T: Serialize>(titulo: &str, filas: &[T]) -> Value {
+    let filas = serde_json::to_value(filas).unwrap_or_default();
+    let mut vistas = vec![especificacion_poblaciones(), especificacion_fases()];
+    let con_pasto = filas.as_array().is_some_and(|filas| filas.iter().any(|f| f["pasto_kg"].as_f64().is_some_and(|p| p > 0.0)));
+    if con_pasto {
+        vistas.push(especificacion_cadena_trofica());
+    }
+    json!({
+        "$schema": ESQUEMA_VEGA_LITE,
+        "title": titulo,
+        "data": { "values": filas },
+        "vconcat": vistas
+    })
+}
+
+/// Página HTML autónoma que dibuja la especificación con vega-embed.
+pub fn pagina_html(especificacion: &Value) -> String {
+    format!(
+        r##"<!DOCTYPE html>
+<html>
+<head>
+  <meta charset="utf-8">
+  <script src="https://cdn.jsdelivr.net/npm/vega@5"></script>
+  <script src="https://cdn.jsdelivr.net/npm/vega-lite@5"></script>
+  <script src="https://cdn.jsdelivr.net/npm/vega-embed@6"></script>
+</head>
+<body>
+  <div id="grafico"></div>
+  <script>vegaEmbed("#grafico", {});</script>
+</body>
+</html>
+"##,
+        especificacion
+    )
+}
+
+/// Guarda el tablero de una corrida: como página HTML si la ruta termina en `.html`,
+/// si no, como la especificación JSON.
+pub fn guardar<T: Serialize>(ruta: impl AsRef<Path>, titulo: &str, filas: &[T]) -> io::Result<()> {
+    let ruta = ruta.as_ref();
+    let especificacion = tablero(titulo, filas);
+    let es_html = ruta.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("html"));
+    let contenido = if es_html {
+        pagina_html(&especificacion)
+    } else {
+        serde_json::to_string_pretty(&especificacion).map_err(io::Error::other)?
+    };
+    fs::write(ruta, contenido)
+}
@@ -0,0 +1,108 @@
+// src/volcado.rs
+
+// Volcado de fotogramas para montar vídeos sin conexión (parte del binario, no de la biblioteca).
+// Con `--fotogramas DIR` el visualizador no espera al reloj: avanza un día por fotograma, dibuja
+// la escena (sin ventanas ni entrada) a la resolución pedida y guarda cada fotograma, o uno cada
+// N días, como PNG numerado. Al terminar la corrida se cierra.
+//
+// Uso:
+//   simulador_ecosistema_presa_depredador --fotogramas DIR [--fotogramas-cada N] [--resolucion 1920x1080] [--dias N]
+//   ffmpeg -framerate 30 -i DIR/fotograma_%06d.png -pix_fmt yuv420p video.mp4
+
+use std::fs;
+use std::path::PathBuf;
+
+use macroquad::prelude::*;
+
+use simulador_ecosistema_presa_depredador::idioma::{Idioma, Texto};
+use simulador_ecosistema_presa_depredador::reloj::Interpolacion;
+use simulador_ecosistema_presa_depredador::simulacion::Simulacion;
+
+use crate::control::Camara;
+use crate::sprites::{self, Sprites};
+
+/// Opciones del volcado de fotogramas.
+#[derive(Debug, Clone)]
+pub struct OpcionesVolcado {
+    pub carpeta: PathBuf,
+    /// Se guarda un fotograma cada tantos días simulados.
+    pub cada: u32,
+    pub ancho: u32,
+    pub alto: u32,
+    pub dias: u32,
+}
+
+impl OpcionesVolcado {
+    /// Lee las opciones de la línea de comandos; `None` si no se pidió volcado.
+    pub fn desde_args(args: impl Iterator<Item = String>) -> Result<Option<Self>, String> {
+        let mut carpeta = None;
+        let mut opciones = Self { carpeta: PathBuf::new(), cada: 1, ancho: 1280, alto: 720, dias: 3650 };
+        let mut args = args;
+        while let Some(arg) = args.next() {
+            let mut valor = || args.next().ok_or(format!("falta el valor de {}", arg));
+            match arg.as_str() {
+                "--fotogramas" => carpeta = Some(PathBuf::from(valor()?)),
+                "--fotogramas-cada" => {
+                    opciones.cada = valor()?.parse().map_err(|_| "intervalo de --fotogramas-cada inválido")?;
+                    if opciones.cada == 0 {
+                        return Err("--fotogramas-cada debe ser mayor que 0".to_string());
+                    }
+                }
+                "--resolucion" => (opciones.ancho, opciones.alto) = resolucion(&valor()?)?,
+                "--dias" => opciones.dias = valor()?.parse().map_err(|_| "número de días inválido")?,
+                // El resto de flags (p. ej. `--enlace`) son del visualizador.
+                _ => {}
+            }
+        }
+        Ok(carpeta.map(|carpeta| Self { carpeta, ..opciones }))
+    }
+}
+
+/// Interpreta una resolución `ANCHOxALTO`.
+fn resolucion(texto: &str) -> Result<(u32, u32), String> {
+    let invalida = || format!("resolución inválida: {} (use ANCHOxALTO, p. ej. 1920x1080)", texto);
+    let (ancho, alto) = texto.split_once(['x', 'X']).ok_or_else(invalida)?;
+    let ancho: u32 = ancho.trim().parse().map_err(|_| invalida())?;
+    let alto: u32 = alto.trim().parse().map_err(|_| invalida())?;
+    if ancho == 0 || alto == 0 {
+        return Err(invalida());
+    }
+    Ok((ancho, alto))
+}
+
+/// Corre la simulación hasta `dias` (o la muerte del depredador) guardando los fotogramas.
+/// Devuelve cuántos se escribieron.
+pub async fn volcar(sim: &mut Simulacion, opciones: &OpcionesVolcado, idioma: Idioma) -> Result<u32, String> {
+    fs::create_dir_all(&opciones.carpeta)
+        .map_err(|e| format!("no se pudo crear {}: {}", opciones.carpeta.display(), e))?;
+
+    // Algunos gestores de ventanas aplican el tamaño pedido un fotograma después de abrirla.
+    next_frame().await;
+    if screen_width() as u32 != opciones.ancho || screen_height() as u32 != opciones.alto {
+        eprintln!(
+            "la ventana mide {}x{} en lugar de {}x{}; los fotogramas tendrán ese tamaño",
+            screen_width(), screen_height(), opciones.ancho, opciones.alto
+        );
+    }
+
+    let camara = Camara::new();
+    let carpeta_sprites = super::escenario_local().sprites.as_deref().unwrap_or(sprites::CARPETA_SPRITES);
+    let sprites = Sprites::cargar(carpeta_sprites).await;
+    let mut escritos = 0;
+    loop {
+        let ultimo = sim.dia >= opciones.dias || !sim.depredador.vivo;
+        if sim.dia.is_multiple_of(opciones.cada) || ultimo {
+            super::dibujar_simulacion(sim, &Interpolacion::default(), idioma, None, None, &camara, None, &sprites, &super::cazas::Cazas::default(), None);
+            draw_text(&format!("{} {}", idioma.texto(Texto::Dia), sim.dia), 10.0, 30.0, 30.0, BLACK);
+            let ruta = opciones.carpeta.join(format!("fotograma_{:06}.png", escritos));
+            let ruta = ruta.to_str().ok_or_else(|| format!("ruta no válida: {}", ruta.display()))?;
+            get_screen_data().export_png(ruta);
+            escritos += 1;
+        }
+        next_frame().await;
+        if ultimo {
+            return Ok(escritos);
+        }
+        sim.avanzar_dia();
+    }
+}
@@ -0,0 +1,27 @@
+// web/enlace.js
+//
+// Plugin de miniquad que da acceso al fragmento de la URL (`location.hash`) desde Rust,
+// para que los escenarios se puedan compartir como enlaces. Se carga después de
+// `mq_js_bundle.js` y antes de `load("...wasm")`.
+
+function enlace_registrar(importObject) {
+    const codificador = new TextEncoder();
+    const fragmento = () => codificador.encode(window.location.hash.replace(/^#/, ""));
+
+    importObject.env.enlace_longitud_fragmento = function () {
+        return fragmento().length;
+    };
+    importObject.env.enlace_leer_fragmento = function (destino, longitud) {
+        new Uint8Array(wasm_memory.buffer, destino, longitud).set(fragmento().subarray(0, longitud));
+    };
+    importObject.env.enlace_publicar_fragmento = function (origen, longitud) {
+        const texto = new TextDecoder().decode(new Uint8Array(wasm_memory.buffer, origen, longitud));
+        // replaceState no recarga la página ni llena el historial.
+        history.replaceState(null, "", "#" + texto);
+        if (navigator.clipboard) {
+            navigator.clipboard.writeText(window.location.href).catch(() => {});
+        }
+    };
+}
+
+miniquad_add_plugin({ register_plugin: enlace_registrar, name: "enlace", version: 1 });
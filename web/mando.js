@@ -0,0 +1,26 @@
+// web/mando.js
+//
+// Plugin de miniquad que da acceso a los mandos (API Gamepad del navegador) desde Rust.
+// Se usa el primer mando con distribución estándar. Se carga después de
+// `mq_js_bundle.js` y antes de `load("...wasm")`.
+
+function mando_registrar(importObject) {
+    const primero = () => Array.from(navigator.getGamepads ? navigator.getGamepads() : [])
+        .find((m) => m && m.connected && m.mapping === "standard");
+
+    importObject.env.mando_conectado = function () {
+        return primero() ? 1 : 0;
+    };
+    importObject.env.mando_botones = function () {
+        const mando = primero();
+        if (!mando) return 0;
+        // Máscara de bits con un bit por botón, en el orden de la distribución estándar.
+        return mando.buttons.reduce((mascara, boton, i) => (boton.pressed && i < 32 ? mascara | (1 << i) : mascara), 0) >>> 0;
+    };
+    importObject.env.mando_eje = function (indice) {
+        const mando = primero();
+        return mando && indice < mando.axes.length ? mando.axes[indice] : 0;
+    };
+}
+
+miniquad_add_plugin({ register_plugin: mando_registrar, name: "mando", version: 1 });